@@ -0,0 +1,64 @@
+"""Compare the throughput of `gb_io.load` with `read` and `readinto`.
+
+File-like objects implementing `readinto` are read without an extra copy
+of each chunk, while objects only implementing `read` are read through
+an intermediate `bytes` object.
+"""
+
+import argparse
+import io
+import os
+import timeit
+
+import gb_io
+
+DATA_FOLDER = os.path.join(os.path.dirname(__file__), os.pardir, "tests", "data")
+
+
+class ReadOnly(object):
+    """A binary file-like object only implementing `read`."""
+
+    def __init__(self, handle):
+        self.handle = handle
+
+    def read(self, n=-1):
+        return self.handle.read(n)
+
+
+def main():
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    parser.add_argument(
+        "-i",
+        "--input",
+        default=os.path.join(DATA_FOLDER, "biopython", "extra_keywords.gb"),
+        help="the GenBank file to read",
+    )
+    parser.add_argument(
+        "-c",
+        "--copies",
+        type=int,
+        default=50,
+        help="the number of copies of the input to concatenate",
+    )
+    parser.add_argument(
+        "-r",
+        "--repeat",
+        type=int,
+        default=5,
+        help="the number of timed runs, of which the best is reported",
+    )
+    args = parser.parse_args()
+
+    with open(args.input, "rb") as f:
+        data = f.read() * args.copies
+    size = len(data) / 2**20
+
+    for name, wrap in [("readinto", io.BytesIO), ("read", lambda d: ReadOnly(io.BytesIO(d)))]:
+        best = min(
+            timeit.repeat(lambda: gb_io.load(wrap(data)), number=1, repeat=args.repeat)
+        )
+        print("{:<8} {:8.3f} s {:8.1f} MiB/s".format(name, best, size / best))
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,69 @@
+"""Compare the throughput of `gb_io.load` with a varying number of threads.
+
+The input file is concatenated several times into a temporary file, so
+that it contains enough records to be split across the threads.
+"""
+
+import argparse
+import os
+import tempfile
+import timeit
+
+import gb_io
+
+DATA_FOLDER = os.path.join(os.path.dirname(__file__), os.pardir, "tests", "data")
+
+
+def main():
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    parser.add_argument(
+        "-i",
+        "--input",
+        default=os.path.join(DATA_FOLDER, "biopython", "extra_keywords.gb"),
+        help="the GenBank file to read",
+    )
+    parser.add_argument(
+        "-c",
+        "--copies",
+        type=int,
+        default=200,
+        help="the number of copies of the input to concatenate",
+    )
+    parser.add_argument(
+        "-j",
+        "--threads",
+        type=int,
+        nargs="+",
+        default=[1, 2, 4, 8],
+        help="the numbers of threads to benchmark",
+    )
+    parser.add_argument(
+        "-r",
+        "--repeat",
+        type=int,
+        default=5,
+        help="the number of timed runs, of which the best is reported",
+    )
+    args = parser.parse_args()
+
+    with open(args.input, "rb") as f:
+        data = f.read() * args.copies
+    size = len(data) / 2**20
+
+    with tempfile.TemporaryDirectory() as folder:
+        path = os.path.join(folder, "records.gb")
+        with open(path, "wb") as f:
+            f.write(data)
+        for threads in args.threads:
+            best = min(
+                timeit.repeat(
+                    lambda: gb_io.load(path, threads=threads),
+                    number=1,
+                    repeat=args.repeat,
+                )
+            )
+            print("{:>3} threads {:8.3f} s {:8.1f} MiB/s".format(threads, best, size / best))
+
+
+if __name__ == "__main__":
+    main()
@@ -9,9 +9,11 @@ __all__ = [
     "Record",
     "Source",
     "Feature",
+    "FeatureIterator",
     "Qualifier",
     "Location",
     "Range",
+    "Site",
     "Between",
     "Complement",
     "Join",
@@ -21,9 +23,22 @@ __all__ = [
     "External",
     "Reference",
     "RecordReader",
+    "RecordBatchReader",
+    "GrepReader",
+    "RecordIndex",
+    "Writer",
+    "FeatureKeys",
+    "QualifierKeys",
     "load",
+    "loads",
     "iter",
-    "dump"
+    "iter_batches",
+    "for_each",
+    "grep",
+    "index",
+    "parse_location",
+    "dump",
+    "dumps",
 ]
 
 # Small addition to the docstring: we want to show a link redirecting to the
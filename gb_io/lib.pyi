@@ -1,5 +1,6 @@
 import datetime
-from typing import Optional, Union, BinaryIO, List, Iterator, Iterable
+import io
+from typing import Any, Callable, Dict, Generic, Optional, Union, BinaryIO, TextIO, List, Iterator, Iterable, Mapping, Tuple, TypeVar, overload
 
 try:
     from typing import Literal
@@ -10,6 +11,169 @@ __author__: str
 __version__: str
 
 _STRAND = Literal["+", "-"]
+_ALPHABET = Literal["dna", "rna", "protein", "iupac"]
+_HIERARCHY_NODE = Tuple["Feature", List[Any]]
+_GREP_FIELD = Literal["definition", "features", "organism"]
+
+class FeatureKeys:
+    ASSEMBLY_GAP: str
+    C_REGION: str
+    CDS: str
+    CENTROMERE: str
+    D_LOOP: str
+    D_SEGMENT: str
+    EXON: str
+    GAP: str
+    GENE: str
+    IDNA: str
+    INTRON: str
+    J_SEGMENT: str
+    MAT_PEPTIDE: str
+    MISC_BINDING: str
+    MISC_DIFFERENCE: str
+    MISC_FEATURE: str
+    MISC_RECOMB: str
+    MISC_RNA: str
+    MISC_STRUCTURE: str
+    MOBILE_ELEMENT: str
+    MODIFIED_BASE: str
+    MRNA: str
+    NCRNA: str
+    N_REGION: str
+    OLD_SEQUENCE: str
+    OPERON: str
+    ORIT: str
+    POLYA_SITE: str
+    PRECURSOR_RNA: str
+    PRIM_TRANSCRIPT: str
+    PRIMER_BIND: str
+    PROPEPTIDE: str
+    PROTEIN_BIND: str
+    REGULATORY: str
+    REPEAT_REGION: str
+    REP_ORIGIN: str
+    RRNA: str
+    S_REGION: str
+    SIG_PEPTIDE: str
+    SOURCE: str
+    STEM_LOOP: str
+    STS: str
+    TELOMERE: str
+    TMRNA: str
+    TRANSIT_PEPTIDE: str
+    TRNA: str
+    UNSURE: str
+    V_REGION: str
+    V_SEGMENT: str
+    VARIATION: str
+    THREE_PRIME_UTR: str
+    FIVE_PRIME_UTR: str
+
+class QualifierKeys:
+    ALLELE: str
+    ALTITUDE: str
+    ANTICODON: str
+    ARTIFICIAL_LOCATION: str
+    BIO_MATERIAL: str
+    BOUND_MOIETY: str
+    CELL_LINE: str
+    CELL_TYPE: str
+    CHROMOSOME: str
+    CIRCULAR_RNA: str
+    CITATION: str
+    CLONE: str
+    CLONE_LIB: str
+    CODON_START: str
+    COLLECTED_BY: str
+    COLLECTION_DATE: str
+    COMPARE: str
+    COUNTRY: str
+    CULTIVAR: str
+    CULTURE_COLLECTION: str
+    DB_XREF: str
+    DEV_STAGE: str
+    DIRECTION: str
+    EC_NUMBER: str
+    ECOTYPE: str
+    ENVIRONMENTAL_SAMPLE: str
+    ESTIMATED_LENGTH: str
+    EXCEPTION: str
+    EXPERIMENT: str
+    FOCUS: str
+    FREQUENCY: str
+    FUNCTION: str
+    GAP_TYPE: str
+    GENE: str
+    GENE_SYNONYM: str
+    GEO_LOC_NAME: str
+    GERMLINE: str
+    HAPLOGROUP: str
+    HAPLOTYPE: str
+    HOST: str
+    IDENTIFIED_BY: str
+    INFERENCE: str
+    ISOLATE: str
+    ISOLATION_SOURCE: str
+    LAB_HOST: str
+    LAT_LON: str
+    LINKAGE_EVIDENCE: str
+    LOCUS_TAG: str
+    MACRONUCLEAR: str
+    MAP: str
+    MATING_TYPE: str
+    METAGENOME_SOURCE: str
+    MOBILE_ELEMENT_TYPE: str
+    MOD_BASE: str
+    MOL_TYPE: str
+    NCRNA_CLASS: str
+    NOTE: str
+    NUMBER: str
+    OLD_LOCUS_TAG: str
+    OPERON: str
+    ORGANELLE: str
+    ORGANISM: str
+    PARTIAL: str
+    PCR_CONDITIONS: str
+    PCR_PRIMERS: str
+    PHENOTYPE: str
+    PLASMID: str
+    POP_VARIANT: str
+    PRODUCT: str
+    PROTEIN_ID: str
+    PROVIRAL: str
+    PSEUDO: str
+    PSEUDOGENE: str
+    REARRANGED: str
+    RECOMBINATION_CLASS: str
+    REGULATORY_CLASS: str
+    REPLACE: str
+    RIBOSOMAL_SLIPPAGE: str
+    RPT_FAMILY: str
+    RPT_TYPE: str
+    RPT_UNIT_RANGE: str
+    RPT_UNIT_SEQ: str
+    SATELLITE: str
+    SEGMENT: str
+    SEROTYPE: str
+    SEROVAR: str
+    SEX: str
+    SPECIMEN_VOUCHER: str
+    STANDARD_NAME: str
+    STRAIN: str
+    SUB_CLONE: str
+    SUB_SPECIES: str
+    SUB_STRAIN: str
+    SUBMITTER_SEQID: str
+    TAG_PEPTIDE: str
+    TISSUE_LIB: str
+    TISSUE_TYPE: str
+    TRANS_SPLICING: str
+    TRANSGENIC: str
+    TRANSL_EXCEPT: str
+    TRANSL_TABLE: str
+    TRANSLATION: str
+    TYPE_MATERIAL: str
+    VARIETY: str
 
 class Record:
     name: Optional[str]
@@ -21,8 +185,25 @@ class Record:
     version: Optional[str]
     dblink: Optional[str]
     keywords: Optional[str]
+    origin_note: Optional[str]
+    wgs: List[str]
+    wgs_scafld: List[str]
+    comments: List[str]
+    structured_comments: Dict[str, Dict[str, str]]
+    primary: List[Tuple[str, str, str, bool]]
     circular: bool
     date: Optional[datetime.date]
+    gi: Optional[int]
+    source: Optional[Source]
+    contig: Optional[Location]
+    @property
+    def kind(self) -> Optional[Literal["nucleotide", "protein"]]: ...
+    @property
+    def accession_type(
+        self,
+    ) -> Optional[Literal["refseq", "insdc", "wgs", "unknown"]]: ...
+    @property
+    def date_string(self) -> Optional[str]: ...
     sequence: bytearray
     features: List[Feature]
     references: List[Reference]
@@ -39,6 +220,10 @@ class Record:
         version: Optional[str] = None,
         dblink: Optional[str] = None,
         keywords: Optional[str] = None,
+        origin_note: Optional[str] = None,
+        wgs: Iterable[str] = (),
+        wgs_scafld: Iterable[str] = (),
+        primary: Iterable[Tuple[str, str, str, bool]] = (),
         circular: bool = False,
         date: Optional[datetime.date] = None,
         source: Optional[Source] = None,
@@ -46,30 +231,172 @@ class Record:
         references: Optional[Iterable[Reference]] = None,
         features: Optional[Iterable[Feature]] = None,
     ): ...
+    @classmethod
+    def from_biopython(cls, seqrecord: Any) -> Record: ...
+    def to_biopython(self) -> Any: ...
+    def set_sequence_from_path(self, path: str) -> None: ...
+    def split_at(self, positions: Iterable[int]) -> List[Record]: ...
+    def reverse_complement(self) -> Record: ...
+    def set_origin(self, position: int) -> None: ...
+    def extract(self, location: Location) -> bytes: ...
+    @overload
+    def gene_sequences(
+        self,
+        kind: Optional[str] = "CDS",
+        key: str = "locus_tag",
+        *,
+        strict: bool = False,
+        duplicates: Literal["suffix"] = "suffix",
+    ) -> Dict[str, bytes]: ...
+    @overload
+    def gene_sequences(
+        self,
+        kind: Optional[str] = "CDS",
+        key: str = "locus_tag",
+        *,
+        strict: bool = False,
+        duplicates: Literal["list"],
+    ) -> Dict[str, List[bytes]]: ...
+    def iter_features(
+        self,
+        kind: Optional[str] = None,
+        in_range: Optional[Tuple[int, int]] = None,
+    ) -> FeatureIterator: ...
+    def has_feature(self, kind: str) -> bool: ...
+    def count_features(self, kind: Optional[str] = None) -> int: ...
+    def features_of_kind(self, kind: str) -> List[Feature]: ...
+    def rename_features_kind(self, old: str, new: str) -> int: ...
+    def gaps(self, *, min_length: int = 1) -> List[Tuple[int, int]]: ...
+    def ungapped_length(self) -> int: ...
+    def validate_sequence(
+        self, alphabet: _ALPHABET = "dna", *, strict: bool = False
+    ) -> Dict[str, List[int]]: ...
+    def features_in_range(
+        self, start: int, end: int, kind: Optional[str] = None
+    ) -> List[Feature]: ...
+    def overlapping_features(
+        self, *, same_strand: bool = False, kind: Optional[str] = None
+    ) -> List[Tuple[Feature, Feature]]: ...
+    def build_hierarchy(self) -> List[_HIERARCHY_NODE]: ...
+    def __contains__(self, item: Union[int, str, Feature]) -> bool: ...
+    def __len__(self) -> int: ...
+    def __bool__(self) -> bool: ...
+    def identity(self, checksum: Literal["crc64", "crc32"] = "crc64") -> str: ...
+    def identity_to(self, other: Record) -> Tuple[float, int]: ...
+    def gc_content(self) -> float: ...
+    def base_counts(self) -> Dict[str, int]: ...
+    def equals(
+        self,
+        other: Record,
+        *,
+        ignore_order: bool = False,
+        ignore: Optional[Iterable[str]] = None,
+    ) -> bool: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def to_genbank(
+        self,
+        *,
+        escape_locus: bool = False,
+        truncate_locus: bool = False,
+        wrap_width: int = 79,
+    ) -> str: ...
+    def to_fasta(self, *, line_width: int = 70) -> str: ...
+    def __copy__(self) -> Record: ...
+    def __deepcopy__(self, memo: Dict[int, Any]) -> Record: ...
 
 class Source:
     name: str
     organism: Optional[str]
-    def __init__(self, name: str, organism: Optional[str]): ...
+    mol_type: Optional[str]
+    organelle: Optional[str]
+    plasmid: Optional[str]
+    chromosome: Optional[str]
+    segment: Optional[str]
+    def __init__(
+        self,
+        name: str,
+        organism: Optional[str] = None,
+        *,
+        mol_type: Optional[str] = None,
+        organelle: Optional[str] = None,
+        plasmid: Optional[str] = None,
+        chromosome: Optional[str] = None,
+        segment: Optional[str] = None,
+    ): ...
     def __repr__(self) -> str: ...
 
 class Feature:
     kind: str
-    location: Location
     qualifiers: List[Qualifier]
     def __init__(
         self, kind: str, location: Location, qualifiers: Optional[List[Qualifier]]
     ): ...
     def __repr__(self) -> str: ...
+    def __len__(self) -> int: ...
+    def __bool__(self) -> bool: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    @property
+    def location(self) -> Location: ...
+    @location.setter
+    def location(
+        self, location: Union[Location, str, List[Location], Tuple[Location, ...]]
+    ) -> None: ...
+    @property
+    def strand(self) -> Optional[_STRAND]: ...
+    @strand.setter
+    def strand(self, strand: _STRAND) -> None: ...
+    @property
+    def gene_synonyms(self) -> List[str]: ...
+    @gene_synonyms.setter
+    def gene_synonyms(self, synonyms: List[str]) -> None: ...
+    def extract(self, record: Record) -> bytes: ...
+    def exons(self) -> List[Tuple[int, int, _STRAND]]: ...
+    def set_translation(
+        self, record: Record, table: int = 11, verify: bool = True
+    ) -> str: ...
+    def qualifiers_dict(self) -> Dict[str, List[Optional[str]]]: ...
+    def dedupe_qualifiers(self, keep_repeats: Optional[Iterable[str]] = None) -> int: ...
+    def __copy__(self) -> Feature: ...
+    def __deepcopy__(self, memo: Dict[int, Any]) -> Feature: ...
+
+class FeatureIterator(Iterator[Feature]):
+    def __iter__(self) -> FeatureIterator: ...
+    def __next__(self) -> Feature: ...
 
 class Qualifier:
     key: str
     value: Optional[str]
-    def __init__(self, key: str, value: Optional[str] = None): ...
+    quoted: Optional[bool]
+    def __init__(
+        self, key: str, value: Optional[str] = None, *, quoted: Optional[bool] = None
+    ): ...
     def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
 
 class Location:
-    pass
+    def intersects_range(self, start: int, end: int) -> bool: ...
+    def overlaps(self, other: Location, *, same_strand: bool = False) -> bool: ...
+    def contains(self, other: Location, *, same_strand: bool = False) -> bool: ...
+    def flip(self, length: int) -> Location: ...
+    def shift(self, offset: int) -> Location: ...
+    def simplify(self) -> Location: ...
+    def to_tuple(self) -> Tuple[Any, ...]: ...
+    @staticmethod
+    def from_tuple(data: Tuple[Any, ...]) -> Location: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __lt__(self, other: Location) -> bool: ...
+    def __le__(self, other: Location) -> bool: ...
+    def __gt__(self, other: Location) -> bool: ...
+    def __ge__(self, other: Location) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __len__(self) -> int: ...
+    def __bool__(self) -> bool: ...
+    def __str__(self) -> str: ...
 
 class Range(Location):
     start: int
@@ -83,6 +410,19 @@ class Range(Location):
     ): ...
     def __repr__(self) -> str: ...
 
+class Site(Range):
+    position: int
+    @property
+    def start(self) -> int: ...  # type: ignore[override]
+    @property
+    def end(self) -> int: ...  # type: ignore[override]
+    @property
+    def before(self) -> bool: ...  # type: ignore[override]
+    @property
+    def after(self) -> bool: ...  # type: ignore[override]
+    def __init__(self, position: int): ...
+    def __repr__(self) -> str: ...
+
 class Between(Location):
     start: int
     end: int
@@ -98,7 +438,7 @@ class Complement(Location):
     @property
     def end(self) -> int: ...
     @property
-    def strand(self) -> _STRAND: ...
+    def strand(self) -> Optional[_STRAND]: ...
     def __init__(self, location: Location): ...
     def __repr__(self) -> str: ...
 
@@ -108,28 +448,38 @@ class Join(Location):
     def start(self) -> int: ...
     @property
     def end(self) -> int: ...
-    def __init__(self, locations: List[Location]): ...
+    @property
+    def strand(self) -> Optional[_STRAND]: ...
+    def __init__(self, locations: List[Location], *, strict: bool = False): ...
     def __repr__(self) -> str: ...
 
 class Order(Location):
     locations: List[Location]
+    @property
+    def strand(self) -> Optional[_STRAND]: ...
     def __init__(self, locations: List[Location]): ...
     def __repr__(self) -> str: ...
 
 class Bond(Location):
     locations: List[Location]
+    @property
+    def strand(self) -> Optional[_STRAND]: ...
     def __init__(self, locations: List[Location]): ...
     def __repr__(self) -> str: ...
 
 class OneOf(Location):
     locations: List[Location]
+    @property
+    def strand(self) -> Optional[_STRAND]: ...
     def __init__(self, locations: List[Location]): ...
     def __repr__(self) -> str: ...
 
 class External(Location):
     accession: str
     location: Optional[Location]
-    def __init__(self, accession: str, location: Optional[Location] = None): ...
+    def __init__(
+        self, accession: str, location: Union[Location, str, None] = None
+    ): ...
     def __repr__(self) -> str: ...
 
 class Reference:
@@ -141,11 +491,202 @@ class Reference:
     pubmed: Optional[str]
     remark: Optional[str]
 
-def load(fh: Union[str, BinaryIO]) -> List[Record]: ...
-def iter(fh: Union[str, BinaryIO]) -> Iterator[Record]: ...
+_NORMALIZE_KINDS = Union[Literal["lower", "upper"], Dict[str, str]]
+_ERROR_POLICY = Literal["raise", "skip", "warn"]
+_FORMAT = Literal["genbank", "embl"]
+_T = TypeVar("_T")
+
+class RecordReader(Generic[_T], Iterator[_T]):
+    def __iter__(self) -> RecordReader[_T]: ...
+    def __next__(self) -> _T: ...
+    def tell(self) -> Optional[int]: ...
+
+class RecordIndex(Mapping[str, Record]):
+    def __getitem__(self, key: str) -> Record: ...
+    def __iter__(self) -> Iterator[str]: ...
+    def __len__(self) -> int: ...
+    def __contains__(self, key: object) -> bool: ...
+    def keys(self) -> List[str]: ...  # type: ignore[override]
+    @overload
+    def get(self, key: str) -> Optional[Record]: ...
+    @overload
+    def get(self, key: str, default: Union[Record, _T]) -> Union[Record, _T]: ...
+    def offset(self, key: str) -> int: ...
+
+class Writer:
+    def __init__(
+        self,
+        fh: Union[str, BinaryIO, TextIO],
+        *,
+        escape_locus: bool = False,
+        truncate_locus: bool = False,
+        compresslevel: int = 9,
+        emit_gi: bool = False,
+        unquoted_qualifiers: Optional[Iterable[str]] = None,
+        date: Union[datetime.date, str, None] = None,
+        simplify_locations: bool = False,
+        wrap_width: int = 79,
+        linearize_circular: bool = False,
+        wrap_definition: Union[bool, int, None] = None,
+        canonical_qualifier_order: Union[bool, Iterable[str], None] = None,
+        format: _FORMAT = "genbank",
+        line_width: int = 60,
+        validate: bool = False,
+    ) -> None: ...
+    def __enter__(self) -> Writer: ...
+    def __exit__(self, *args: object) -> bool: ...
+    @property
+    def closed(self) -> bool: ...
+    def write(self, record: Record) -> None: ...
+    def flush(self) -> None: ...
+    def close(self) -> None: ...
+
+def load(
+    fh: Union[str, BinaryIO],
+    *,
+    normalize_kinds: Optional[_NORMALIZE_KINDS] = None,
+    drop_qualifiers: Optional[Iterable[str]] = None,
+    threads: int = 1,
+    decompress: bool = True,
+    on_error: _ERROR_POLICY = "raise",
+) -> List[Record]: ...
+def loads(
+    data: Union[str, bytes],
+    *,
+    normalize_kinds: Optional[_NORMALIZE_KINDS] = None,
+    drop_qualifiers: Optional[Iterable[str]] = None,
+) -> List[Record]: ...
+@overload
+def iter(
+    fh: Union[str, BinaryIO],
+    *,
+    normalize_kinds: Optional[_NORMALIZE_KINDS] = None,
+    drop_qualifiers: Optional[Iterable[str]] = None,
+    reuse: bool = False,
+    prefetch: int = 0,
+    require_qualifiers: Optional[Mapping[str, Union[str, Iterable[str]]]] = None,
+    errors: _ERROR_POLICY = "raise",
+    decompress: bool = True,
+    on_error: _ERROR_POLICY = "raise",
+) -> RecordReader[Record]: ...
+@overload
+def iter(
+    fh: Union[str, BinaryIO],
+    *,
+    with_text: Literal[False],
+    normalize_kinds: Optional[_NORMALIZE_KINDS] = None,
+    drop_qualifiers: Optional[Iterable[str]] = None,
+    reuse: bool = False,
+    prefetch: int = 0,
+    require_qualifiers: Optional[Mapping[str, Union[str, Iterable[str]]]] = None,
+    errors: _ERROR_POLICY = "raise",
+    decompress: bool = True,
+    on_error: _ERROR_POLICY = "raise",
+) -> RecordReader[Record]: ...
+@overload
+def iter(
+    fh: Union[str, BinaryIO],
+    *,
+    with_text: Literal[True],
+    normalize_kinds: Optional[_NORMALIZE_KINDS] = None,
+    drop_qualifiers: Optional[Iterable[str]] = None,
+    reuse: bool = False,
+    require_qualifiers: Optional[Mapping[str, Union[str, Iterable[str]]]] = None,
+    errors: _ERROR_POLICY = "raise",
+    decompress: bool = True,
+    on_error: _ERROR_POLICY = "raise",
+) -> RecordReader[Tuple[bytes, Record]]: ...
+def iter_batches(
+    fh: Union[str, BinaryIO],
+    batch_size: int = 1000,
+    *,
+    normalize_kinds: Optional[_NORMALIZE_KINDS] = None,
+    release_gil: bool = True,
+) -> Iterator[List[Record]]: ...
+def for_each(
+    fh: Union[str, BinaryIO], callback: Callable[[Record], Optional[bool]]
+) -> int: ...
+def grep(
+    fh: Union[str, BinaryIO],
+    pattern: str,
+    *,
+    fields: Union[_GREP_FIELD, Iterable[_GREP_FIELD], None] = None,
+) -> Iterator[Tuple[Record, _GREP_FIELD]]: ...
+def index(
+    path: str,
+    *,
+    key: Literal["accession", "name"] = "accession",
+) -> RecordIndex: ...
+def parse_location(text: str) -> Location: ...
+@overload
+def dump(
+    records: Union[Record, Iterable[Record]],
+    fh: Union[str, BinaryIO],
+    escape_locus: bool = False,
+    truncate_locus: bool = False,
+    compresslevel: int = 9,
+    emit_gi: bool = False,
+    unquoted_qualifiers: Optional[Iterable[str]] = None,
+    date: Union[datetime.date, str, None] = None,
+    *,
+    return_count: Literal[False] = False,
+    simplify_locations: bool = False,
+    wrap_width: int = 79,
+    linearize_circular: bool = False,
+    wrap_definition: Union[bool, int, None] = None,
+    canonical_qualifier_order: Union[bool, Iterable[str], None] = None,
+    format: _FORMAT = "genbank",
+    line_width: int = 60,
+    validate: bool = False,
+) -> None: ...
+@overload
 def dump(
     records: Union[Record, Iterable[Record]],
     fh: Union[str, BinaryIO],
     escape_locus: bool = False,
     truncate_locus: bool = False,
-): ...
+    compresslevel: int = 9,
+    emit_gi: bool = False,
+    unquoted_qualifiers: Optional[Iterable[str]] = None,
+    date: Union[datetime.date, str, None] = None,
+    *,
+    return_count: Literal[True],
+    simplify_locations: bool = False,
+    wrap_width: int = 79,
+    linearize_circular: bool = False,
+    wrap_definition: Union[bool, int, None] = None,
+    canonical_qualifier_order: Union[bool, Iterable[str], None] = None,
+    format: _FORMAT = "genbank",
+    line_width: int = 60,
+    validate: bool = False,
+) -> int: ...
+@overload
+def dump(
+    records: Union[Record, Iterable[Record]],
+    fh: None = None,
+    escape_locus: bool = False,
+    truncate_locus: bool = False,
+    compresslevel: int = 9,
+    emit_gi: bool = False,
+    unquoted_qualifiers: Optional[Iterable[str]] = None,
+    date: Union[datetime.date, str, None] = None,
+    *,
+    simplify_locations: bool = False,
+    wrap_width: int = 79,
+    linearize_circular: bool = False,
+    wrap_definition: Union[bool, int, None] = None,
+    canonical_qualifier_order: Union[bool, Iterable[str], None] = None,
+    format: _FORMAT = "genbank",
+    line_width: int = 60,
+    validate: bool = False,
+) -> io.BytesIO: ...
+def dumps(
+    records: Union[Record, Iterable[Record]],
+    *,
+    escape_locus: bool = False,
+    truncate_locus: bool = False,
+    wrap_width: int = 79,
+    format: _FORMAT = "genbank",
+    line_width: int = 60,
+    validate: bool = False,
+) -> str: ...
@@ -0,0 +1,48 @@
+//! Classification of sequence accessions following the NCBI prefix rules.
+//!
+//! See the [NCBI accession prefix](https://www.ncbi.nlm.nih.gov/genbank/acc_prefix/)
+//! and [RefSeq prefix](https://www.ncbi.nlm.nih.gov/books/NBK21091/table/ch18.T.refseq_accession_numbers_and_mole/)
+//! tables for reference.
+
+/// The two-letter prefixes of RefSeq accessions.
+const REFSEQ_PREFIXES: &[&str] = &[
+    "AC", "AP", "NC", "NG", "NM", "NP", "NR", "NT", "NW", "NZ", "WP", "XM", "XP", "XR", "YP",
+];
+
+/// Count the leading ASCII letters and the following ASCII digits of a string.
+fn letters_and_digits(accession: &str) -> Option<(usize, usize)> {
+    let letters = accession
+        .bytes()
+        .take_while(u8::is_ascii_alphabetic)
+        .count();
+    let digits = accession.len() - letters;
+    accession.as_bytes()[letters..]
+        .iter()
+        .all(u8::is_ascii_digit)
+        .then_some((letters, digits))
+}
+
+/// Classify an accession as `refseq`, `wgs`, `insdc` or `unknown`.
+///
+/// Only the first accession of the string is considered, and its version
+/// suffix is ignored if any.
+pub fn classify(accession: &str) -> &'static str {
+    let accession = accession.split_whitespace().next().unwrap_or_default();
+    let accession = accession.split('.').next().unwrap_or_default();
+    if let Some((prefix, rest)) = accession.split_once('_') {
+        let known = REFSEQ_PREFIXES.contains(&prefix);
+        let valid = !rest.is_empty() && rest.bytes().all(|c| c.is_ascii_alphanumeric());
+        return if known && valid { "refseq" } else { "unknown" };
+    }
+    match letters_and_digits(accession) {
+        // nucleotide: 1 letter + 5 digits, 2 letters + 6 or 8 digits
+        Some((1, 5)) | Some((2, 6)) | Some((2, 8)) => "insdc",
+        // protein: 3 letters + 5 or 7 digits
+        Some((3, 5)) | Some((3, 7)) => "insdc",
+        // mass sequence for genome annotation: 5 letters + 7 digits
+        Some((5, 7)) => "insdc",
+        // WGS: 4 or 6 letters + 2-digit version + 6 or more digits
+        Some((4, n)) | Some((6, n)) if n >= 8 => "wgs",
+        _ => "unknown",
+    }
+}
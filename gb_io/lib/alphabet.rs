@@ -0,0 +1,123 @@
+//! Alphabets to validate the symbols of a sequence.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyString;
+
+/// The minimum length of a sequence made only of nucleotide symbols to be
+/// considered a nucleotide sequence.
+const MIN_NUCLEOTIDE_LENGTH: usize = 10;
+
+/// Check whether a sequence looks like a nucleotide sequence.
+///
+/// Nucleotide symbols are valid amino acid codes as well, so only sequences
+/// of at least `MIN_NUCLEOTIDE_LENGTH` symbols, all of them unambiguous
+/// nucleotides or `N`, are considered nucleotide sequences.
+pub fn is_nucleotide_like(sequence: &[u8]) -> bool {
+    sequence.len() >= MIN_NUCLEOTIDE_LENGTH
+        && sequence
+            .iter()
+            .all(|c| b"ACGTUN".contains(&c.to_ascii_uppercase()))
+}
+
+/// An alphabet of sequence symbols.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alphabet {
+    /// Unambiguous nucleotides of DNA, and `N` for unknown bases.
+    Dna,
+    /// Unambiguous nucleotides of RNA, and `N` for unknown bases.
+    Rna,
+    /// The 20 standard amino acids, ambiguity codes, rare amino acids
+    /// and the `*` stop symbol.
+    Protein,
+    /// The IUPAC nucleotide codes, including ambiguous bases.
+    Iupac,
+}
+
+impl Alphabet {
+    /// Get the name of the alphabet, as given from Python.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Alphabet::Dna => "dna",
+            Alphabet::Rna => "rna",
+            Alphabet::Protein => "protein",
+            Alphabet::Iupac => "iupac",
+        }
+    }
+
+    /// Get the uppercase symbols of the alphabet.
+    pub fn symbols(&self) -> &'static [u8] {
+        match self {
+            Alphabet::Dna => b"ACGTN",
+            Alphabet::Rna => b"ACGUN",
+            Alphabet::Protein => b"ACDEFGHIKLMNPQRSTVWYBZJXUO*",
+            Alphabet::Iupac => b"ACGTURYSWKMBDHVN",
+        }
+    }
+
+    /// Get the alphabet of the sequences of a molecule type, if known.
+    ///
+    /// Nucleotide molecule types, such as ``DNA`` or ``mRNA``, use the
+    /// IUPAC alphabet, so that records with ambiguous bases are valid.
+    pub fn for_molecule_type(molecule_type: &str) -> Option<Self> {
+        let molecule_type = molecule_type.to_ascii_uppercase();
+        if molecule_type == "PROTEIN" || molecule_type == "AA" {
+            Some(Alphabet::Protein)
+        } else if molecule_type.contains("DNA") || molecule_type.contains("RNA") {
+            Some(Alphabet::Iupac)
+        } else {
+            None
+        }
+    }
+
+    /// Build a lookup table of the symbols, in both cases.
+    fn table(&self) -> [bool; 256] {
+        let mut table = [false; 256];
+        for &c in self.symbols() {
+            table[c as usize] = true;
+            table[c.to_ascii_lowercase() as usize] = true;
+        }
+        table
+    }
+
+    /// Get the position of the first symbol not in the alphabet.
+    pub fn find_invalid(&self, sequence: &[u8]) -> Option<usize> {
+        let table = self.table();
+        sequence.iter().position(|&c| !table[c as usize])
+    }
+
+    /// Get the symbols not in the alphabet, with their positions.
+    ///
+    /// Symbols are reported in order of first occurrence, each with the
+    /// increasing positions where it occurs in the sequence.
+    pub fn invalid_symbols(&self, sequence: &[u8]) -> Vec<(u8, Vec<usize>)> {
+        let table = self.table();
+        let mut index: [Option<usize>; 256] = [None; 256];
+        let mut invalid: Vec<(u8, Vec<usize>)> = Vec::new();
+        for (i, &c) in sequence.iter().enumerate() {
+            if !table[c as usize] {
+                let j = *index[c as usize].get_or_insert_with(|| {
+                    invalid.push((c, Vec::new()));
+                    invalid.len() - 1
+                });
+                invalid[j].1.push(i);
+            }
+        }
+        invalid
+    }
+}
+
+impl<'py> FromPyObject<'py> for Alphabet {
+    fn extract(ob: &'py PyAny) -> PyResult<Self> {
+        match ob.downcast::<PyString>()?.to_str()? {
+            "dna" => Ok(Alphabet::Dna),
+            "rna" => Ok(Alphabet::Rna),
+            "protein" => Ok(Alphabet::Protein),
+            "iupac" => Ok(Alphabet::Iupac),
+            other => Err(PyValueError::new_err(format!(
+                "invalid alphabet: {:?}",
+                other
+            ))),
+        }
+    }
+}
@@ -0,0 +1,392 @@
+//! Conversion between Biopython objects and native `gb_io` objects.
+//!
+//! Biopython is not a dependency of this package. When reading Biopython
+//! objects, the `SeqRecord`, `SeqFeature` and `SimpleLocation` objects are
+//! only accessed through their attributes, and any object with the same
+//! attributes is accepted. When creating Biopython objects, the `Bio`
+//! modules are imported on demand.
+
+use gb_io::seq::After;
+use gb_io::seq::Before;
+use gb_io::seq::Date;
+use gb_io::seq::Feature;
+use gb_io::seq::Location;
+use gb_io::seq::Seq;
+use gb_io::FeatureKind;
+use gb_io::QualifierKey;
+use pyo3::exceptions::PyImportError;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use pyo3::types::PyList;
+use pyo3::types::PyString;
+
+use super::location;
+use super::writer::split_version;
+
+/// The placeholders used by Biopython for unknown record attributes.
+const UNKNOWN: &[&str] = &["<unknown id>", "<unknown name>", "<unknown description>"];
+
+/// Get a string attribute of a record, ignoring Biopython placeholders.
+pub fn record_attribute(record: &Bound<PyAny>, name: &str) -> PyResult<Option<String>> {
+    let value = record.getattr(name)?.extract::<Option<String>>()?;
+    Ok(value.filter(|value| !value.is_empty() && !UNKNOWN.contains(&value.as_str())))
+}
+
+/// Parse a date in the ``DD-MON-YYYY`` format of the ``LOCUS`` line.
+pub fn parse_date(text: &str) -> Option<Date> {
+    const MONTHS: &[&str] = &[
+        "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+    ];
+    let mut fields = text.trim().splitn(3, '-');
+    let day = fields.next()?.parse().ok()?;
+    let month = fields.next()?.to_ascii_uppercase();
+    let month = MONTHS.iter().position(|m| *m == month)? as u32 + 1;
+    let year = fields.next()?.parse().ok()?;
+    Date::from_ymd(year, month, day).ok()
+}
+
+/// Extract a simple location, with its strand and reference.
+fn extract_simple_location(object: &Bound<PyAny>) -> PyResult<Location> {
+    let start = object.getattr("start")?;
+    let end = object.getattr("end")?;
+    // fuzzy positions are `int` subclasses, only recognizable from their text
+    let before = start.str()?.to_str()?.starts_with('<');
+    let after = end.str()?.to_str()?.starts_with('>');
+    let (start, end) = (start.extract::<i64>()?, end.extract::<i64>()?);
+    let mut location = match start == end && start > 0 && !before && !after {
+        // Biopython reads `x^y` as an empty location after `x`
+        true => Location::Between(start - 1, start),
+        false => Location::Range((start, Before(before)), (end, After(after))),
+    };
+    if let Some(accession) = object.getattr("ref")?.extract::<Option<String>>()? {
+        if !location::is_valid_accession(&accession) {
+            return Err(PyValueError::new_err(format!(
+                "invalid accession: {:?}",
+                accession
+            )));
+        }
+        location = Location::External(accession, Some(Box::new(location)));
+    }
+    if object.getattr("strand")?.extract::<Option<i64>>()? == Some(-1) {
+        location = Location::Complement(Box::new(location));
+    }
+    Ok(location)
+}
+
+/// Extract a location, either simple or compound.
+///
+/// Biopython stores a complemented compound location, such as
+/// ``complement(join(1..10,20..30))``, as a compound location with its
+/// parts on the reverse strand in reverse order, so such locations are
+/// converted back to the complement of a compound location.
+pub fn extract_location(object: &Bound<PyAny>) -> PyResult<Location> {
+    if !object.hasattr("operator")? {
+        return extract_simple_location(object);
+    }
+    let mut parts = object
+        .getattr("parts")?
+        .iter()?
+        .map(|part| extract_simple_location(&part?))
+        .collect::<PyResult<Vec<_>>>()?;
+    let reverse = !parts.is_empty()
+        && parts
+            .iter()
+            .all(|part| matches!(part, Location::Complement(_)));
+    if reverse {
+        parts = parts
+            .into_iter()
+            .rev()
+            .map(|part| match part {
+                Location::Complement(inner) => *inner,
+                other => other,
+            })
+            .collect();
+    }
+    let location = match object.getattr("operator")?.extract::<String>()?.as_str() {
+        "join" => Location::Join(parts),
+        "order" => Location::Order(parts),
+        "bond" => Location::Bond(parts),
+        other => {
+            return Err(PyValueError::new_err(format!(
+                "unsupported location operator: {:?}",
+                other
+            )))
+        }
+    };
+    if reverse {
+        Ok(Location::Complement(Box::new(location)))
+    } else {
+        Ok(location)
+    }
+}
+
+/// Extract a feature, with its location and qualifiers.
+///
+/// Qualifier values are stored in lists, with an empty string for
+/// qualifiers without a value such as ``/pseudo``.
+pub fn extract_feature(object: &Bound<PyAny>) -> PyResult<Feature> {
+    let kind = object.getattr("type")?.extract::<String>()?;
+    let location = object.getattr("location")?;
+    if location.is_none() {
+        return Err(PyValueError::new_err(format!(
+            "feature without location: {:?}",
+            kind
+        )));
+    }
+    let mut qualifiers = Vec::new();
+    for item in object
+        .getattr("qualifiers")?
+        .call_method0("items")?
+        .iter()?
+    {
+        let (key, values) = item?.extract::<(String, Bound<PyAny>)>()?;
+        let values = match values.downcast::<PyString>() {
+            Ok(value) => vec![value.clone().into_any()],
+            Err(_) => values.iter()?.collect::<PyResult<Vec<_>>>()?,
+        };
+        for value in values {
+            let value = value.str()?.to_str()?.to_string();
+            let value = Some(value).filter(|value| !value.is_empty());
+            qualifiers.push((QualifierKey::from(key.as_str()), value));
+        }
+    }
+    Ok(Feature {
+        kind: FeatureKind::from(kind.as_str()),
+        location: extract_location(&location)?,
+        qualifiers,
+    })
+}
+
+/// The Biopython classes used to create records.
+struct Classes<'py> {
+    seq: Bound<'py, PyAny>,
+    seq_record: Bound<'py, PyAny>,
+    seq_feature: Bound<'py, PyAny>,
+    simple_location: Bound<'py, PyAny>,
+    compound_location: Bound<'py, PyAny>,
+    exact_position: Bound<'py, PyAny>,
+    before_position: Bound<'py, PyAny>,
+    after_position: Bound<'py, PyAny>,
+}
+
+impl<'py> Classes<'py> {
+    /// Import the Biopython classes, failing if Biopython is not installed.
+    fn import(py: Python<'py>) -> PyResult<Self> {
+        let import = |module: &str| {
+            py.import_bound(module).map_err(|err| {
+                if err.is_instance_of::<PyImportError>(py) {
+                    let msg = "Biopython is required to create a `SeqRecord`, \
+                        install it with `pip install biopython`";
+                    let error = PyImportError::new_err(msg);
+                    error.set_cause(py, Some(err));
+                    error
+                } else {
+                    err
+                }
+            })
+        };
+        let seq = import("Bio.Seq")?;
+        let seq_record = import("Bio.SeqRecord")?;
+        let seq_feature = import("Bio.SeqFeature")?;
+        Ok(Self {
+            seq: seq.getattr("Seq")?,
+            seq_record: seq_record.getattr("SeqRecord")?,
+            seq_feature: seq_feature.getattr("SeqFeature")?,
+            // `SimpleLocation` replaced `FeatureLocation` in Biopython 1.80
+            simple_location: seq_feature.getattr("SimpleLocation").map_err(|err| {
+                let msg = "Biopython 1.80 or later is required to create a `SeqRecord`, \
+                    upgrade it with `pip install -U biopython`";
+                let error = PyImportError::new_err(msg);
+                error.set_cause(py, Some(err));
+                error
+            })?,
+            compound_location: seq_feature.getattr("CompoundLocation")?,
+            exact_position: seq_feature.getattr("ExactPosition")?,
+            before_position: seq_feature.getattr("BeforePosition")?,
+            after_position: seq_feature.getattr("AfterPosition")?,
+        })
+    }
+
+    /// Create a simple location on the given strand.
+    fn simple_location(
+        &self,
+        start: Bound<'py, PyAny>,
+        end: Bound<'py, PyAny>,
+        reverse: bool,
+        reference: Option<&str>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let py = start.py();
+        let kwargs = PyDict::new_bound(py);
+        kwargs.set_item("strand", if reverse { -1 } else { 1 })?;
+        kwargs.set_item("ref", reference)?;
+        self.simple_location.call((start, end), Some(&kwargs))
+    }
+
+    /// Collect the simple parts of a location, in Biopython order.
+    ///
+    /// Returns the operator of the outermost compound location, if any.
+    fn collect_parts(
+        &self,
+        location: &Location,
+        reverse: bool,
+        reference: Option<&str>,
+        parts: &mut Vec<Bound<'py, PyAny>>,
+    ) -> PyResult<Option<&'static str>> {
+        match location {
+            Location::Range((start, Before(before)), (end, After(after))) => {
+                let start = match before {
+                    true => self.before_position.call1((*start,))?,
+                    false => self.exact_position.call1((*start,))?,
+                };
+                let end = match after {
+                    true => self.after_position.call1((*end,))?,
+                    false => self.exact_position.call1((*end,))?,
+                };
+                parts.push(self.simple_location(start, end, reverse, reference)?);
+                Ok(None)
+            }
+            Location::Between(start, _) => {
+                // Biopython reads `x^y` as an empty location after `x`
+                let position = self.exact_position.call1((*start + 1,))?;
+                parts.push(self.simple_location(position.clone(), position, reverse, reference)?);
+                Ok(None)
+            }
+            Location::Complement(inner) => self.collect_parts(inner, !reverse, reference, parts),
+            Location::Join(locations) | Location::Order(locations) | Location::Bond(locations) => {
+                // parts on the reverse strand are listed from the end
+                let ordered: Box<dyn Iterator<Item = &Location>> = match reverse {
+                    true => Box::new(locations.iter().rev()),
+                    false => Box::new(locations.iter()),
+                };
+                for part in ordered {
+                    self.collect_parts(part, reverse, reference, parts)?;
+                }
+                Ok(Some(match location {
+                    Location::Order(_) => "order",
+                    Location::Bond(_) => "bond",
+                    _ => "join",
+                }))
+            }
+            Location::External(accession, Some(inner)) => {
+                self.collect_parts(inner, reverse, Some(accession), parts)
+            }
+            other => Err(PyValueError::new_err(format!(
+                "cannot convert location to Biopython: {}",
+                other.to_gb_format()
+            ))),
+        }
+    }
+
+    /// Create a simple or compound location.
+    fn location(&self, py: Python<'py>, location: &Location) -> PyResult<Bound<'py, PyAny>> {
+        let mut parts = Vec::new();
+        let operator = self.collect_parts(location, false, None, &mut parts)?;
+        match parts.len() {
+            0 => Err(PyValueError::new_err(format!(
+                "cannot convert empty location to Biopython: {}",
+                location.to_gb_format()
+            ))),
+            1 => Ok(parts.remove(0)),
+            _ => self
+                .compound_location
+                .call1((PyList::new_bound(py, parts), operator.unwrap_or("join"))),
+        }
+    }
+
+    /// Create a feature, with its location and qualifiers.
+    fn feature(&self, py: Python<'py>, feature: &Feature) -> PyResult<Bound<'py, PyAny>> {
+        let qualifiers = PyDict::new_bound(py);
+        for (key, value) in &feature.qualifiers {
+            // Biopython joins the lines of the values, and stores
+            // qualifiers without a value as empty strings
+            let value = value.as_deref().unwrap_or_default().replace('\n', " ");
+            match qualifiers.get_item(key.as_ref())? {
+                Some(values) => values.downcast::<PyList>()?.append(value)?,
+                None => qualifiers.set_item(key.as_ref(), PyList::new_bound(py, [value]))?,
+            }
+        }
+        let kwargs = PyDict::new_bound(py);
+        kwargs.set_item("type", feature.kind.as_ref())?;
+        kwargs.set_item("qualifiers", qualifiers)?;
+        self.seq_feature
+            .call((self.location(py, &feature.location)?,), Some(&kwargs))
+    }
+}
+
+/// Create a Biopython `SeqRecord` from a native record.
+///
+/// The annotations are set like in the records read by `Bio.SeqIO`, so
+/// that the record can be written back with `Bio.SeqIO.write`.
+pub fn to_seq_record(py: Python, seq: &Seq) -> PyResult<PyObject> {
+    let classes = Classes::import(py)?;
+
+    let sequence = match (seq.seq.is_empty(), seq.len) {
+        // Biopython stores sequences without contents as undefined
+        (true, Some(length)) if length > 0 => classes.seq.call1((py.None(), length))?,
+        _ => classes
+            .seq
+            .call1((String::from_utf8_lossy(&seq.seq).into_owned(),))?,
+    };
+
+    let annotations = PyDict::new_bound(py);
+    if let Some(molecule_type) = &seq.molecule_type {
+        annotations.set_item("molecule_type", molecule_type)?;
+    }
+    annotations.set_item("topology", seq.topology.to_string())?;
+    annotations.set_item("data_file_division", &seq.division)?;
+    if let Some(date) = &seq.date {
+        annotations.set_item("date", date.to_string())?;
+    }
+    if let Some(accession) = &seq.accession {
+        annotations.set_item(
+            "accessions",
+            accession.split_whitespace().collect::<Vec<_>>(),
+        )?;
+    }
+    let version = seq
+        .version
+        .as_deref()
+        .map(|version| split_version(version).0);
+    if let Some((_, number)) = version.and_then(|version| version.rsplit_once('.')) {
+        if let Ok(number) = number.parse::<u32>() {
+            annotations.set_item("sequence_version", number)?;
+        }
+    }
+
+    let features = seq
+        .features
+        .iter()
+        .map(|feature| classes.feature(py, feature))
+        .collect::<PyResult<Vec<_>>>()?;
+
+    let id = version
+        .or_else(|| {
+            seq.accession
+                .as_deref()
+                .and_then(|a| a.split_whitespace().next())
+        })
+        .or(seq.name.as_deref())
+        .unwrap_or("<unknown id>");
+    // Biopython removes the final period of the definition line
+    let description = match &seq.definition {
+        Some(definition) => {
+            let definition = definition.replace('\n', " ");
+            definition
+                .strip_suffix('.')
+                .unwrap_or(&definition)
+                .to_string()
+        }
+        None => String::from("<unknown description>"),
+    };
+    let kwargs = PyDict::new_bound(py);
+    kwargs.set_item("id", id)?;
+    kwargs.set_item("name", seq.name.as_deref().unwrap_or("<unknown name>"))?;
+    kwargs.set_item("description", description)?;
+    kwargs.set_item("annotations", annotations)?;
+    kwargs.set_item("features", PyList::new_bound(py, features))?;
+    Ok(classes
+        .seq_record
+        .call((sequence,), Some(&kwargs))?
+        .unbind())
+}
@@ -0,0 +1,38 @@
+//! Checksums of sequences.
+
+/// The lookup table for the CRC-64 checksum, computed at compile-time.
+const CRC64_TABLE: [u64; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u64;
+        let mut j = 0;
+        while j < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xD800_0000_0000_0000
+            } else {
+                crc >> 1
+            };
+            j += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Compute the CRC-64 checksum of a sequence.
+///
+/// This is the ISO 3309 variant used by SwissProt and `Bio.SeqUtils.CheckSum`.
+pub fn crc64(sequence: &[u8]) -> u64 {
+    sequence.iter().fold(0, |crc, &c| {
+        CRC64_TABLE[((crc ^ c as u64) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+/// Compute the CRC-32 checksum of a sequence, as computed by `zlib.crc32`.
+pub fn crc32(sequence: &[u8]) -> u32 {
+    let mut crc = flate2::Crc::new();
+    crc.update(sequence);
+    crc.sum()
+}
@@ -8,6 +8,11 @@ use pyo3::types::PyList;
 use pyo3::types::PyString;
 use pyo3::PyTypeInfo;
 
+/// A cache of Python strings interned with `sys.intern`.
+///
+/// Strings are interned at the Python level, so that equal feature kinds
+/// and qualifier keys share the same object even when converted with
+/// different interners.
 #[derive(Debug, Default)]
 pub struct PyInterner {
     cache: RwLock<HashMap<String, Py<PyString>>>,
@@ -25,7 +30,7 @@ impl PyInterner {
             return pystring.clone();
         }
         let mut cache = self.cache.write().expect("failed to acquire cache");
-        let pystring = Py::from(PyString::new_bound(py, key));
+        let pystring = Py::from(PyString::intern_bound(py, key));
         cache.insert(key.into(), pystring.clone());
         pystring
     }
@@ -127,9 +132,10 @@ pub enum Coa<T: Convert> {
 }
 
 impl<T: Convert + Temporary> Coa<T> {
+    #[allow(clippy::wrong_self_convention)]
     pub fn to_shared(&mut self, py: Python) -> PyResult<Py<<T as Convert>::Output>> {
         match self {
-            Coa::Shared(pyref) => return Ok(pyref.clone_ref(py)),
+            Coa::Shared(pyref) => Ok(pyref.clone_ref(py)),
             Coa::Owned(value) => {
                 let pyref = std::mem::replace(value, Temporary::temporary()).convert(py)?;
                 *self = Coa::Shared(pyref.clone_ref(py));
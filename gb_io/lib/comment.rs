@@ -0,0 +1,113 @@
+//! Parsing and formatting of structured comments.
+//!
+//! Structured comments are blocks of key-value pairs embedded in the
+//! ``COMMENT`` field of NCBI records, delimited by ``##Name-START##`` and
+//! ``##Name-END##`` lines, with keys and values separated by ``::``:
+//!
+//! ```text
+//! ##Genome-Assembly-Data-START##
+//! Assembly Method       :: SPAdes v. 3.9
+//! Sequencing Technology :: Illumina
+//! ##Genome-Assembly-Data-END##
+//! ```
+
+use std::borrow::Cow;
+
+/// The separator between the keys and the values of a block.
+const DELIMITER: &str = "::";
+
+/// A structured comment block, with its name and its entries in order.
+pub type Block = (String, Vec<(String, String)>);
+
+/// Get the name of the block started by a line, if any.
+fn block_start(line: &str) -> Option<&str> {
+    line.trim()
+        .strip_prefix("##")?
+        .strip_suffix("-START##")
+        .filter(|name| !name.is_empty())
+}
+
+/// Check whether a line ends the block with the given name.
+fn is_block_end(line: &str, name: &str) -> bool {
+    line.trim()
+        .strip_prefix("##")
+        .and_then(|line| line.strip_suffix("-END##"))
+        .is_some_and(|end| end == name)
+}
+
+/// Parse the structured comment blocks of a comment.
+///
+/// Lines without a delimiter continue the value of the previous entry,
+/// like in the Biopython parser. A block without an end line extends to
+/// the end of the comment.
+pub fn parse(comment: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut current: Option<Block> = None;
+    for line in comment.lines() {
+        match &mut current {
+            None => {
+                if let Some(name) = block_start(line) {
+                    current = Some((name.to_string(), Vec::new()));
+                }
+            }
+            Some((name, _)) if is_block_end(line, name) => {
+                blocks.extend(current.take());
+            }
+            Some((_, entries)) => match line.split_once(DELIMITER) {
+                Some((key, value)) => {
+                    entries.push((key.trim().to_string(), value.trim().to_string()));
+                }
+                None => {
+                    if let Some((_, value)) = entries.last_mut() {
+                        value.push(' ');
+                        value.push_str(line.trim());
+                    }
+                }
+            },
+        }
+    }
+    blocks.extend(current);
+    blocks
+}
+
+/// Replace the structured comment blocks of a comment.
+///
+/// Each block is replaced in place with the text returned by `replacement`
+/// for its name, or removed if it returns `None`. The blank lines left at
+/// the end of the comment are removed as well, so that the comment can be
+/// extended with new blocks.
+pub fn replace<F>(comment: &str, mut replacement: F) -> String
+where
+    F: FnMut(&str) -> Option<String>,
+{
+    let mut lines = Vec::new();
+    let mut current: Option<&str> = None;
+    for line in comment.lines() {
+        match current {
+            None => match block_start(line) {
+                Some(name) => {
+                    lines.extend(replacement(name).map(Cow::Owned));
+                    current = Some(name);
+                }
+                None => lines.push(Cow::Borrowed(line)),
+            },
+            Some(name) if is_block_end(line, name) => current = None,
+            Some(_) => (),
+        }
+    }
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+/// Format a structured comment block, with the delimiters aligned.
+pub fn format(name: &str, entries: &[(String, String)]) -> String {
+    let width = entries.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+    let mut text = format!("##{}-START##\n", name);
+    for (key, value) in entries {
+        text.push_str(&format!("{:<width$} {} {}\n", key, DELIMITER, value));
+    }
+    text.push_str(&format!("##{}-END##", name));
+    text
+}
@@ -0,0 +1,302 @@
+//! Serialization of records in the EMBL flat file format.
+//!
+//! The EMBL format stores the same information as the GenBank format, with
+//! two-letter line codes instead of keywords. The feature table is shared
+//! with the GenBank format except for the `FT` prefix of its lines, so it
+//! is written by the `SeqWriter`, and only the header and the sequence of
+//! the records are formatted here.
+
+use std::io::Error as IoError;
+use std::io::Write;
+
+use gb_io::seq::Date;
+use gb_io::seq::Reference;
+use gb_io::seq::Seq;
+use gb_io::seq::Topology;
+
+use super::writer::split_version;
+use super::writer::wrap_text;
+
+/// The prefix of the first line of each feature.
+pub const FEATURE_PREFIX: &str = "FT   ";
+/// The indent of the qualifier lines and of the wrapped location lines.
+pub const QUALIFIER_INDENT: &str = "FT                   ";
+/// The header of the feature table.
+pub const FEATURE_HEADER: &[u8] = b"FH   Key             Location/Qualifiers\nFH\n";
+
+/// The data classes of EMBL, which are GenBank divisions as well.
+const DATA_CLASSES: &[&str] = &["CON", "EST", "GSS", "HTC", "HTG", "PAT", "STS", "TSA"];
+
+/// The taxonomic divisions of EMBL.
+const TAXONOMIC_DIVISIONS: &[&str] = &[
+    "ENV", "FUN", "HUM", "INV", "MAM", "MUS", "PHG", "PLN", "PRO", "ROD", "SYN", "TGN", "UNC",
+    "VRL", "VRT",
+];
+
+/// Get the data class and the taxonomic division of a GenBank division.
+fn classify(division: &str) -> (&str, &str) {
+    if DATA_CLASSES.contains(&division) {
+        (division, "UNC")
+    } else if TAXONOMIC_DIVISIONS.contains(&division) {
+        ("STD", division)
+    } else if division == "BCT" {
+        ("STD", "PRO")
+    } else {
+        ("STD", "UNC")
+    }
+}
+
+/// Generate the `ID` line of the record.
+fn id_line(record: &Seq, topology: &Topology) -> String {
+    let accession = record
+        .accession
+        .as_deref()
+        .and_then(|accession| accession.split_whitespace().next())
+        .or(record.name.as_deref())
+        .unwrap_or("XXX");
+    let version = record
+        .version
+        .as_deref()
+        .map(|version| split_version(version).0)
+        .and_then(|version| version.rsplit_once('.'))
+        .map(|(_, number)| number)
+        .filter(|number| !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()))
+        .unwrap_or("1");
+    let molecule_type = match record.molecule_type.as_deref() {
+        None | Some("") => "unassigned DNA",
+        Some(molecule_type) => molecule_type,
+    };
+    let (data_class, division) = classify(&record.division);
+    format!(
+        "ID   {}; SV {}; {}; {}; {}; {}; {} BP.\n",
+        accession,
+        version,
+        topology,
+        molecule_type,
+        data_class,
+        division,
+        record.len()
+    )
+}
+
+/// Write a field with the given line code, followed by a separator line.
+fn write_field<W: Write>(
+    mut file: W,
+    width: Option<usize>,
+    field: &str,
+    code: &str,
+) -> Result<(), IoError> {
+    write_lines(&mut file, width, field, code)?;
+    writeln!(file, "XX")
+}
+
+/// Write the lines of a field with the given line code.
+fn write_lines<W: Write>(
+    mut file: W,
+    width: Option<usize>,
+    field: &str,
+    code: &str,
+) -> Result<(), IoError> {
+    let prefix = format!("{:<5}", code);
+    match width {
+        Some(width) => wrap_text(file, field, width, &prefix, &prefix, false),
+        None => {
+            for line in field.split('\n') {
+                writeln!(file, "{}{}", prefix, line)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Convert GenBank authors to the EMBL format.
+///
+/// GenBank lists authors as ``Smith,J.A., Doe,J. and Roe,R.``, while EMBL
+/// lists them as ``Smith J.A., Doe J., Roe R.;``.
+fn format_authors(authors: &str) -> String {
+    let authors = authors.replace('\n', " ");
+    let mut names = authors.split(", ").collect::<Vec<_>>();
+    if let Some(last) = names.pop() {
+        names.extend(last.split(" and "));
+    }
+    let names = names
+        .into_iter()
+        .map(|name| name.trim().replacen(',', " ", 1))
+        .collect::<Vec<_>>();
+    format!("{};", names.join(", "))
+}
+
+/// Convert the description of a GenBank reference to its number and the
+/// sequence positions it spans, in the EMBL format.
+///
+/// A description such as ``1  (bases 1 to 100; 200 to 300)`` gives the
+/// positions ``1-100, 200-300``. References without positions are assumed
+/// to span the whole sequence.
+fn format_positions(description: &str, length: i64) -> (&str, Option<String>) {
+    let (number, rest) = description
+        .trim()
+        .split_once(char::is_whitespace)
+        .unwrap_or((description.trim(), ""));
+    let spans = rest
+        .trim()
+        .strip_prefix('(')
+        .and_then(|rest| rest.strip_suffix(')'))
+        .and_then(|rest| {
+            rest.strip_prefix("bases")
+                .or_else(|| rest.strip_prefix("residues"))
+        })
+        .map(|spans| {
+            spans
+                .split(';')
+                .map(|span| span.trim().replacen(" to ", "-", 1))
+                .collect::<Vec<_>>()
+                .join(", ")
+        });
+    let spans = spans.or_else(|| Some(format!("1-{}", length)).filter(|_| length > 0));
+    (number, spans)
+}
+
+/// Write a reference block.
+fn write_reference<W: Write>(
+    mut file: W,
+    width: Option<usize>,
+    reference: &Reference,
+    length: i64,
+) -> Result<(), IoError> {
+    let (number, positions) = format_positions(&reference.description, length);
+    writeln!(file, "RN   [{}]", number)?;
+    if let Some(positions) = positions {
+        write_lines(&mut file, width, &positions, "RP")?;
+    }
+    if let Some(pubmed) = &reference.pubmed {
+        writeln!(file, "RX   PUBMED; {}.", pubmed.trim())?;
+    }
+    if let Some(consortium) = &reference.consortium {
+        write_lines(&mut file, width, &format!("{};", consortium), "RG")?;
+    }
+    match &reference.authors {
+        Some(authors) => write_lines(&mut file, width, &format_authors(authors), "RA")?,
+        None => writeln!(file, "RA   ;")?,
+    }
+    match reference.title.as_str() {
+        "" | "." => writeln!(file, "RT   ;")?,
+        title => write_lines(&mut file, width, &format!("\"{}\";", title), "RT")?,
+    }
+    if let Some(remark) = &reference.remark {
+        write_lines(&mut file, width, remark, "RC")?;
+    }
+    if let Some(journal) = &reference.journal {
+        let journal = match journal.ends_with('.') {
+            true => journal.clone(),
+            false => format!("{}.", journal),
+        };
+        write_lines(&mut file, width, &journal, "RL")?;
+    }
+    writeln!(file, "XX")
+}
+
+/// Write the header of a record, up to the feature table.
+///
+/// Fields without an EMBL equivalent, such as the `PRIMARY` table of TPA
+/// records, are not written.
+pub fn write_header<W: Write>(
+    mut file: W,
+    record: &Seq,
+    topology: &Topology,
+    date: Option<&Date>,
+    width: Option<usize>,
+) -> Result<(), IoError> {
+    file.write_all(id_line(record, topology).as_bytes())?;
+    writeln!(file, "XX")?;
+
+    if let Some(accession) = &record.accession {
+        let accessions = accession
+            .split_whitespace()
+            .map(|accession| format!("{};", accession))
+            .collect::<Vec<_>>();
+        write_field(&mut file, width, &accessions.join(" "), "AC")?;
+    }
+    if let Some(dblink) = &record.dblink {
+        for line in dblink.lines() {
+            if let Some((database, identifier)) = line.split_once(':') {
+                writeln!(file, "DR   {}; {}.", database.trim(), identifier.trim())?;
+            }
+        }
+        writeln!(file, "XX")?;
+    }
+    if let Some(date) = date {
+        writeln!(file, "DT   {}", date)?;
+        writeln!(file, "XX")?;
+    }
+    if let Some(definition) = &record.definition {
+        write_field(&mut file, width, definition, "DE")?;
+    }
+    if let Some(keywords) = &record.keywords {
+        write_field(&mut file, width, keywords, "KW")?;
+    }
+    if let Some(source) = &record.source {
+        write_lines(&mut file, width, &source.source, "OS")?;
+        let lineage = source
+            .organism
+            .as_deref()
+            .and_then(|organism| organism.split_once('\n'))
+            .map(|(_, lineage)| lineage.replace('\n', " "));
+        if let Some(lineage) = lineage {
+            write_lines(&mut file, width, &lineage, "OC")?;
+        }
+        writeln!(file, "XX")?;
+    }
+    for reference in &record.references {
+        write_reference(&mut file, width, reference, record.len())?;
+    }
+    for comment in &record.comments {
+        write_field(&mut file, width, comment, "CC")?;
+    }
+
+    Ok(())
+}
+
+/// Write the `SQ` block of a record.
+///
+/// The sequence is written in lines of `width` characters, in blocks of
+/// 10, followed by the position of the last character of the line.
+pub fn write_sequence<W: Write>(mut file: W, seq: &[u8], width: usize) -> Result<(), IoError> {
+    let mut counts = [0; 4];
+    for b in seq {
+        match b.to_ascii_lowercase() {
+            b'a' => counts[0] += 1,
+            b'c' => counts[1] += 1,
+            b'g' => counts[2] += 1,
+            b't' => counts[3] += 1,
+            _ => (),
+        }
+    }
+    writeln!(
+        file,
+        "SQ   Sequence {} BP; {} A; {} C; {} G; {} T; {} other;",
+        seq.len(),
+        counts[0],
+        counts[1],
+        counts[2],
+        counts[3],
+        seq.len() - counts.iter().sum::<usize>()
+    )?;
+
+    // the positions are aligned after the blocks of a full line
+    let blocks_width = 4 + width + width.div_ceil(10);
+    let mut line = Vec::with_capacity(blocks_width + 11);
+    for (i, chunk) in seq.chunks(width).enumerate() {
+        line.clear();
+        line.extend_from_slice(b"    ");
+        for block in chunk.chunks(10) {
+            line.push(b' ');
+            line.extend_from_slice(block);
+        }
+        let position = format!("{:>10}", i * width + chunk.len());
+        line.resize(blocks_width, b' ');
+        line.extend_from_slice(position.as_bytes());
+        line.push(b'\n');
+        file.write_all(&line)?;
+    }
+    Ok(())
+}
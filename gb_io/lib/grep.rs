@@ -0,0 +1,83 @@
+//! Regular expression searches in the text fields of records.
+
+use gb_io::seq::Seq;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyString;
+use regex::Regex;
+
+/// A text field of a record that can be searched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    /// The `DEFINITION` line of the record.
+    Definition,
+    /// The values of the qualifiers of the record features.
+    Features,
+    /// The `ORGANISM` line of the record source.
+    Organism,
+}
+
+impl Field {
+    /// Get the name of the field, as given from Python.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Field::Definition => "definition",
+            Field::Features => "features",
+            Field::Organism => "organism",
+        }
+    }
+
+    /// Check whether the field of a record matches a regular expression.
+    fn is_match(&self, regex: &Regex, seq: &Seq) -> bool {
+        match self {
+            Field::Definition => seq.definition.as_deref().is_some_and(|d| regex.is_match(d)),
+            Field::Features => seq.features.iter().any(|feature| {
+                feature
+                    .qualifiers
+                    .iter()
+                    .filter_map(|(_, value)| value.as_deref())
+                    .any(|value| regex.is_match(value))
+            }),
+            Field::Organism => seq
+                .source
+                .as_ref()
+                .and_then(|source| source.organism.as_deref())
+                .is_some_and(|organism| regex.is_match(organism)),
+        }
+    }
+}
+
+impl<'py> FromPyObject<'py> for Field {
+    fn extract(ob: &'py PyAny) -> PyResult<Self> {
+        match ob.downcast::<PyString>()?.to_str()? {
+            "definition" => Ok(Field::Definition),
+            "features" => Ok(Field::Features),
+            "organism" => Ok(Field::Organism),
+            other => Err(PyValueError::new_err(format!("invalid field: {:?}", other))),
+        }
+    }
+}
+
+/// A regular expression to search in some fields of records.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    regex: Regex,
+    fields: Vec<Field>,
+}
+
+impl Pattern {
+    /// Compile a pattern searching the given fields, in order.
+    pub fn new(pattern: &str, fields: Vec<Field>) -> PyResult<Self> {
+        let regex = Regex::new(pattern)
+            .map_err(|e| PyValueError::new_err(format!("invalid pattern: {}", e)))?;
+        Ok(Self { regex, fields })
+    }
+
+    /// Get the first field of the record matching the pattern, if any.
+    pub fn find(&self, seq: &Seq) -> Option<Field> {
+        self.fields
+            .iter()
+            .copied()
+            .find(|field| field.is_match(&self.regex, seq))
+    }
+}
@@ -0,0 +1,135 @@
+//! Heuristics to link the features of a record into a gene hierarchy.
+
+use std::collections::HashMap;
+
+use gb_io::seq::Location;
+
+use super::location;
+
+/// The kinds of features describing a transcript of a gene.
+const TRANSCRIPT_KINDS: &[&str] = &[
+    "mRNA",
+    "ncRNA",
+    "rRNA",
+    "tRNA",
+    "tmRNA",
+    "misc_RNA",
+    "precursor_RNA",
+    "prim_transcript",
+];
+
+/// The kinds of features describing a part of a transcript.
+const PART_KINDS: &[&str] = &[
+    "CDS",
+    "exon",
+    "intron",
+    "5'UTR",
+    "3'UTR",
+    "sig_peptide",
+    "mat_peptide",
+    "transit_peptide",
+    "propeptide",
+];
+
+/// A feature to be placed in the hierarchy.
+#[derive(Debug, Clone)]
+pub struct Node {
+    pub kind: String,
+    pub location: Location,
+    pub locus_tag: Option<String>,
+    pub gene: Option<String>,
+}
+
+impl Node {
+    /// Get the level of the feature in the hierarchy, if any.
+    ///
+    /// Genes are at the top level, followed by transcripts, and then by
+    /// the parts of transcripts.
+    fn level(&self) -> Option<u8> {
+        let kind = self.kind.as_str();
+        if kind == "gene" {
+            Some(0)
+        } else if TRANSCRIPT_KINDS.contains(&kind) {
+            Some(1)
+        } else if PART_KINDS.contains(&kind) {
+            Some(2)
+        } else {
+            None
+        }
+    }
+
+    /// Check whether two features share an identifier.
+    ///
+    /// The `/locus_tag` qualifiers are compared if both features have one,
+    /// and the `/gene` qualifiers otherwise.
+    fn shares_identifier(&self, other: &Node) -> bool {
+        match (&self.locus_tag, &other.locus_tag) {
+            (Some(a), Some(b)) => a == b,
+            _ => matches!((&self.gene, &other.gene), (Some(a), Some(b)) if a == b),
+        }
+    }
+}
+
+/// Find the parent of each feature in the gene hierarchy.
+///
+/// A feature can be the child of a feature at a higher level which shares
+/// an identifier with it, and which overlaps it on the same strand. When
+/// several features qualify, the closest level is preferred, then a parent
+/// whose parts contain all the parts of the child, then the parent with
+/// the shortest span, and finally the first parent in record order.
+pub fn parents(nodes: &[Node]) -> Vec<Option<usize>> {
+    let levels = nodes.iter().map(Node::level).collect::<Vec<_>>();
+
+    // index the potential parents by identifier to avoid a quadratic scan
+    let mut by_locus_tag: HashMap<&str, Vec<usize>> = HashMap::new();
+    let mut by_gene: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, node) in nodes.iter().enumerate() {
+        if matches!(levels[i], Some(level) if level < 2) {
+            if let Some(locus_tag) = &node.locus_tag {
+                by_locus_tag.entry(locus_tag).or_default().push(i);
+            }
+            if let Some(gene) = &node.gene {
+                by_gene.entry(gene).or_default().push(i);
+            }
+        }
+    }
+
+    let mut parents = Vec::with_capacity(nodes.len());
+    for (j, child) in nodes.iter().enumerate() {
+        let level = match levels[j] {
+            Some(level) if level > 0 => level,
+            _ => {
+                parents.push(None);
+                continue;
+            }
+        };
+        let mut candidates: Vec<usize> = Vec::new();
+        if let Some(indices) = child.locus_tag.as_deref().and_then(|t| by_locus_tag.get(t)) {
+            candidates.extend(indices);
+        }
+        if let Some(indices) = child.gene.as_deref().and_then(|g| by_gene.get(g)) {
+            candidates.extend(indices);
+        }
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        let parent = candidates
+            .into_iter()
+            .filter(|&i| {
+                let parent = &nodes[i];
+                matches!(levels[i], Some(l) if l < level)
+                    && parent.shares_identifier(child)
+                    && location::same_strand(&parent.location, &child.location)
+                    && location::overlaps(&parent.location, &child.location)
+            })
+            .min_by_key(|&i| {
+                let parent = &nodes[i];
+                let contained = location::contains(&parent.location, &child.location);
+                let length = location::span(&parent.location).map_or(i64::MAX, |(s, e)| e - s);
+                (std::cmp::Reverse(levels[i]), !contained, length, i)
+            });
+        parents.push(parent);
+    }
+
+    parents
+}
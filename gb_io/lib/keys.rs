@@ -0,0 +1,208 @@
+//! Standard keys of the INSDC feature table.
+//!
+//! See the *INSDC Feature Table Definition Document*, sections 7.2 and 7.3.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use pyo3::types::PyString;
+use pyo3::types::PyTuple;
+
+/// The feature keys defined by the INSDC.
+pub const FEATURE_KEYS: &[&str] = &[
+    "assembly_gap",
+    "C_region",
+    "CDS",
+    "centromere",
+    "D-loop",
+    "D_segment",
+    "exon",
+    "gap",
+    "gene",
+    "iDNA",
+    "intron",
+    "J_segment",
+    "mat_peptide",
+    "misc_binding",
+    "misc_difference",
+    "misc_feature",
+    "misc_recomb",
+    "misc_RNA",
+    "misc_structure",
+    "mobile_element",
+    "modified_base",
+    "mRNA",
+    "ncRNA",
+    "N_region",
+    "old_sequence",
+    "operon",
+    "oriT",
+    "polyA_site",
+    "precursor_RNA",
+    "prim_transcript",
+    "primer_bind",
+    "propeptide",
+    "protein_bind",
+    "regulatory",
+    "repeat_region",
+    "rep_origin",
+    "rRNA",
+    "S_region",
+    "sig_peptide",
+    "source",
+    "stem_loop",
+    "STS",
+    "telomere",
+    "tmRNA",
+    "transit_peptide",
+    "tRNA",
+    "unsure",
+    "V_region",
+    "V_segment",
+    "variation",
+    "3'UTR",
+    "5'UTR",
+];
+
+/// The qualifier keys defined by the INSDC.
+pub const QUALIFIER_KEYS: &[&str] = &[
+    "allele",
+    "altitude",
+    "anticodon",
+    "artificial_location",
+    "bio_material",
+    "bound_moiety",
+    "cell_line",
+    "cell_type",
+    "chromosome",
+    "circular_RNA",
+    "citation",
+    "clone",
+    "clone_lib",
+    "codon_start",
+    "collected_by",
+    "collection_date",
+    "compare",
+    "country",
+    "cultivar",
+    "culture_collection",
+    "db_xref",
+    "dev_stage",
+    "direction",
+    "EC_number",
+    "ecotype",
+    "environmental_sample",
+    "estimated_length",
+    "exception",
+    "experiment",
+    "focus",
+    "frequency",
+    "function",
+    "gap_type",
+    "gene",
+    "gene_synonym",
+    "geo_loc_name",
+    "germline",
+    "haplogroup",
+    "haplotype",
+    "host",
+    "identified_by",
+    "inference",
+    "isolate",
+    "isolation_source",
+    "lab_host",
+    "lat_lon",
+    "linkage_evidence",
+    "locus_tag",
+    "macronuclear",
+    "map",
+    "mating_type",
+    "metagenome_source",
+    "mobile_element_type",
+    "mod_base",
+    "mol_type",
+    "ncRNA_class",
+    "note",
+    "number",
+    "old_locus_tag",
+    "operon",
+    "organelle",
+    "organism",
+    "partial",
+    "PCR_conditions",
+    "PCR_primers",
+    "phenotype",
+    "plasmid",
+    "pop_variant",
+    "product",
+    "protein_id",
+    "proviral",
+    "pseudo",
+    "pseudogene",
+    "rearranged",
+    "recombination_class",
+    "regulatory_class",
+    "replace",
+    "ribosomal_slippage",
+    "rpt_family",
+    "rpt_type",
+    "rpt_unit_range",
+    "rpt_unit_seq",
+    "satellite",
+    "segment",
+    "serotype",
+    "serovar",
+    "sex",
+    "specimen_voucher",
+    "standard_name",
+    "strain",
+    "sub_clone",
+    "sub_species",
+    "sub_strain",
+    "submitter_seqid",
+    "tag_peptide",
+    "tissue_lib",
+    "tissue_type",
+    "trans_splicing",
+    "transgenic",
+    "transl_except",
+    "transl_table",
+    "translation",
+    "type_material",
+    "variety",
+];
+
+/// Get the name of the Python constant for a key.
+///
+/// Keys are uppercased, and characters that are not valid in Python
+/// identifiers are replaced with underscores, so that for instance
+/// `D-loop` becomes `D_LOOP`. The `3'UTR` and `5'UTR` keys become
+/// `THREE_PRIME_UTR` and `FIVE_PRIME_UTR`.
+pub fn constant_name(key: &str) -> String {
+    let key = key
+        .replace("3'", "THREE_PRIME_")
+        .replace("5'", "FIVE_PRIME_");
+    key.chars()
+        .map(|c| match c {
+            c if c.is_ascii_alphanumeric() => c.to_ascii_uppercase(),
+            _ => '_',
+        })
+        .collect()
+}
+
+/// Create a namespace class exposing the given keys as `str` constants.
+pub fn namespace<'py>(
+    py: Python<'py>,
+    name: &str,
+    doc: &str,
+    keys: &[&str],
+) -> PyResult<Bound<'py, PyAny>> {
+    let attributes = PyDict::new_bound(py);
+    attributes.set_item("__module__", "gb_io")?;
+    attributes.set_item("__doc__", doc)?;
+    attributes.set_item("__slots__", PyTuple::empty_bound(py))?;
+    for key in keys {
+        attributes.set_item(constant_name(key), PyString::intern_bound(py, key))?;
+    }
+    py.get_type_bound::<pyo3::types::PyType>()
+        .call1((name, PyTuple::empty_bound(py), attributes))
+}
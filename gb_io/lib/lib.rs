@@ -1,47 +1,104 @@
+extern crate flate2;
 extern crate gb_io;
 extern crate libc;
 extern crate pyo3;
 extern crate pyo3_built;
+extern crate regex;
 
+mod accession;
+mod alphabet;
+mod biopython;
 mod built;
+mod checksum;
 mod coa;
+mod comment;
+mod embl;
+mod grep;
+mod hierarchy;
+mod keys;
+mod location;
 mod pyfile;
 mod reader;
+mod translation;
+mod writer;
 
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::io::BufRead;
 use std::io::Read;
 use std::io::Write;
 use std::ops::DerefMut;
 
-use gb_io::reader::GbParserError;
 use gb_io::reader::SeqReader;
 use gb_io::seq::After;
 use gb_io::seq::Before;
 use gb_io::seq::Location as SeqLocation;
 use gb_io::seq::Topology;
-use gb_io::writer::SeqWriter;
+use pyo3::basic::CompareOp;
+use pyo3::exceptions::PyDeprecationWarning;
 use pyo3::exceptions::PyIOError;
+use pyo3::exceptions::PyIndexError;
 use pyo3::exceptions::PyNotImplementedError;
 use pyo3::exceptions::PyOSError;
+use pyo3::exceptions::PyOverflowError;
 use pyo3::exceptions::PyTypeError;
+use pyo3::exceptions::PyUserWarning;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use pyo3::types::PyBool;
 use pyo3::types::PyByteArray;
+use pyo3::types::PyBytes;
 use pyo3::types::PyDate;
 use pyo3::types::PyDateAccess;
+use pyo3::types::PyDict;
 use pyo3::types::PyIterator;
 use pyo3::types::PyList;
+use pyo3::types::PyMapping;
 use pyo3::types::PyString;
 use pyo3::types::PyTuple;
+use pyo3::types::PyType;
+use pyo3::PyTraverseError;
+use pyo3::PyVisit;
 use pyo3_built::pyo3_built;
 
+use self::alphabet::Alphabet;
 use self::coa::Coa;
 use self::coa::Convert;
 use self::coa::Extract;
 use self::coa::PyInterner;
 use self::coa::Temporary;
+use self::grep::Field;
+use self::grep::Pattern;
+use self::pyfile::PyFileGILWrite;
 use self::pyfile::PyFileRead;
 use self::pyfile::PyFileWrite;
+use self::reader::convert_io_error;
+use self::reader::convert_parser_error;
+use self::reader::Decoder;
+use self::reader::ErrorPolicy;
+use self::reader::Extras;
+use self::reader::ExtrasScanner;
+use self::reader::GrepReader;
+use self::reader::Handle;
+use self::reader::IndexKey;
+use self::reader::KindNormalization;
+use self::reader::PrimarySpan;
+use self::reader::QualifierFilter;
+use self::reader::QualifierRequirements;
+use self::reader::RecordBatchReader;
+use self::reader::RecordExtras;
+use self::reader::RecordIndex;
 use self::reader::RecordReader;
+use self::reader::RecordSplitter;
+use self::reader::CHUNK_SIZE;
+use self::translation::GeneticCode;
+use self::writer::Encoder;
+use self::writer::Format;
+use self::writer::InvalidFeature;
+use self::writer::InvalidSequence;
+use self::writer::SeqWriter;
 
 // ---------------------------------------------------------------------------
 
@@ -76,12 +133,61 @@ pub struct Record {
     /// `str` or `None`: Word or phrase describing the sequence.
     #[pyo3(get, set)]
     keywords: Option<String>,
+    /// `str` or `None`: The annotation following the ``ORIGIN`` keyword.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyo3(get, set)]
+    origin_note: Option<String>,
+    /// `list` of `str`: The accession ranges of the ``WGS`` lines.
+    ///
+    /// WGS master records list the accessions of the contigs of the
+    /// assembly in ranges, e.g. ``AAAA02000001-AAAA02050231``. A new list
+    /// is returned on every access, so assign a list to modify it.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyo3(get, set)]
+    wgs: Vec<String>,
+    /// `list` of `str`: The accession ranges of the ``WGS_SCAFLD`` lines.
+    ///
+    /// WGS master records list the accessions of the scaffolds of the
+    /// assembly in ranges, e.g. ``CM000126-CM000137``. A new list is
+    /// returned on every access, so assign a list to modify it.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyo3(get, set)]
+    wgs_scafld: Vec<String>,
+    /// `list` of `tuple`: The rows of the ``PRIMARY`` table.
+    ///
+    /// Third-party annotation (TPA) records list the spans of the primary
+    /// records they were assembled from in a ``PRIMARY`` table. Each row
+    /// is a ``(tpa_span, primary_identifier, primary_span, complement)``
+    /// tuple, where spans are given verbatim, e.g. ``"1-426"``, and
+    /// ``complement`` is `True` for primary spans on the reverse strand.
+    /// A new list is returned on every access, so assign a list to modify
+    /// it.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyo3(get, set)]
+    primary: Vec<PrimarySpan>,
+    /// `list` of `str`: The ``COMMENT`` blocks of the record.
+    ///
+    /// Each element is the text of a ``COMMENT`` block, with its lines
+    /// separated by newlines. A new list is returned on every access, so
+    /// assign a list to modify it.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyo3(get, set)]
+    comments: Vec<String>,
 
     topology: Topology,
     date: Option<Coa<gb_io::seq::Date>>,
     source: Option<Coa<gb_io::seq::Source>>,
     references: Coa<Vec<gb_io::seq::Reference>>,
-    comments: Vec<String>,
     sequence: Coa<Vec<u8>>,
     contig: Option<Coa<gb_io::seq::Location>>,
     features: Coa<Vec<gb_io::seq::Feature>>,
@@ -99,6 +205,10 @@ impl Default for Record {
             version: None,
             dblink: None,
             keywords: None,
+            origin_note: None,
+            wgs: Vec::new(),
+            wgs_scafld: Vec::new(),
+            primary: Vec::new(),
             topology: Topology::Linear,
             date: None,
             source: None,
@@ -127,6 +237,10 @@ impl Record {
         version = None,
         dblink = None,
         keywords = None,
+        origin_note = None,
+        wgs = Vec::new(),
+        wgs_scafld = Vec::new(),
+        primary = Vec::new(),
         circular = false,
         date = None,
         source = None,
@@ -134,6 +248,7 @@ impl Record {
         references = None,
         features = None,
     ))]
+    #[allow(clippy::too_many_arguments)]
     fn __new__<'py>(
         sequence: &Bound<'py, PyAny>,
         name: Option<String>,
@@ -145,30 +260,39 @@ impl Record {
         version: Option<String>,
         dblink: Option<String>,
         keywords: Option<String>,
+        origin_note: Option<String>,
+        wgs: Vec<String>,
+        wgs_scafld: Vec<String>,
+        primary: Vec<PrimarySpan>,
         circular: bool,
         date: Option<Bound<'py, PyDate>>,
-        source: Option<Py<Source>>,
+        source: Option<Bound<'py, Source>>,
         contig: Option<Py<Location>>,
         references: Option<Bound<'py, PyAny>>,
         features: Option<Bound<'py, PyAny>>,
     ) -> PyResult<PyClassInitializer<Self>> {
         let py = sequence.py();
-        let mut record = Record::default();
-        record.name = name;
-        record.length = length;
-        record.molecule_type = molecule_type;
-        record.division = division;
-        record.definition = definition;
-        record.accession = accession;
-        record.version = version;
-        record.dblink = dblink;
-        record.keywords = keywords;
-        record.date = date.map(Py::from).map(Coa::Shared);
-        record.source = source.map(|source| Coa::Shared(source.clone_ref(py)));
-        record.contig = contig.map(|contig| Coa::Shared(contig.clone_ref(py)));
-        record.sequence = PyByteArray::from_bound(sequence)
-            .map(Py::from)
-            .map(Coa::Shared)?;
+        let mut record = Record {
+            name,
+            length,
+            molecule_type,
+            division,
+            definition,
+            accession,
+            version,
+            dblink,
+            keywords,
+            origin_note,
+            wgs,
+            wgs_scafld,
+            primary,
+            date: date.map(Py::from).map(Coa::Shared),
+            contig: contig.map(|contig| Coa::Shared(contig.clone_ref(py))),
+            sequence: PyByteArray::from_bound(sequence)
+                .map(Py::from)
+                .map(Coa::Shared)?,
+            ..Default::default()
+        };
 
         if circular {
             record.topology = Topology::Circular;
@@ -192,7 +316,133 @@ impl Record {
             record.references = Coa::Shared(Py::from(reference_list));
         }
 
-        Ok(PyClassInitializer::from(record))
+        // the source is attached once the features are set, so that its
+        // qualifiers are moved to the source feature of the record
+        let record = Bound::new(py, record)?;
+        Record::set_source(record.clone(), source)?;
+        Ok(PyClassInitializer::from(record.unbind()))
+    }
+
+    /// Create a new record from a Biopython `~Bio.SeqRecord.SeqRecord`.
+    ///
+    /// The identifier, name, description, sequence and features of the
+    /// record are copied, as well as the ``molecule_type``, ``topology``,
+    /// ``date`` and ``accessions`` annotations. Biopython is not imported,
+    /// so any object with the same attributes can be given.
+    ///
+    /// Arguments:
+    ///     seqrecord (`~Bio.SeqRecord.SeqRecord`): The record to convert.
+    ///
+    /// Returns:
+    ///     `Record`: A new record with the data of the Biopython record.
+    ///     The identifier of the record, such as ``"AY048670.1"``, is used
+    ///     as the version of the record.
+    ///
+    /// Raises:
+    ///     `ValueError`: When the date of the record is invalid, or when a
+    ///         feature has no location or an unsupported location.
+    ///
+    /// Note:
+    ///     Biopython removes the final period of the definition line when
+    ///     reading a GenBank record, so it is restored in the description
+    ///     of the new record. Records with an undefined sequence, such as
+    ///     CON records, get an empty sequence and the length of the
+    ///     undefined sequence.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[classmethod]
+    fn from_biopython(_cls: &Bound<'_, PyType>, seqrecord: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let py = seqrecord.py();
+        let annotations = seqrecord.getattr("annotations")?;
+        let annotation = |key: &str| annotations.call_method1("get", (key,));
+
+        let mut record = Record {
+            name: biopython::record_attribute(seqrecord, "name")?,
+            definition: biopython::record_attribute(seqrecord, "description")?.map(|description| {
+                match description.ends_with('.') {
+                    true => description,
+                    false => format!("{}.", description),
+                }
+            }),
+            version: biopython::record_attribute(seqrecord, "id")?.filter(|id| id.contains('.')),
+            molecule_type: annotation("molecule_type")?.extract()?,
+            ..Default::default()
+        };
+
+        let accessions = annotation("accessions")?.extract::<Option<Vec<String>>>()?;
+        record.accession = match accessions {
+            Some(accessions) if !accessions.is_empty() => Some(accessions.join(" ")),
+            _ => {
+                biopython::record_attribute(seqrecord, "id")?.map(|id| match id.rsplit_once('.') {
+                    Some((accession, _)) => accession.to_string(),
+                    None => id,
+                })
+            }
+        };
+        if annotation("topology")?
+            .extract::<Option<String>>()?
+            .as_deref()
+            == Some("circular")
+        {
+            record.topology = Topology::Circular;
+        }
+        let date = annotation("date")?;
+        if let Ok(date) = date.downcast::<PyDate>() {
+            record.date = Some(Coa::Shared(date.clone().unbind()));
+        } else if let Some(text) = date.extract::<Option<String>>()? {
+            let date = biopython::parse_date(&text)
+                .ok_or_else(|| PyValueError::new_err(format!("invalid record date: {:?}", text)))?;
+            record.date = Some(Coa::Owned(date));
+        }
+
+        let seq = seqrecord.getattr("seq")?;
+        if !seq.is_none() {
+            match seq.str() {
+                Ok(sequence) => {
+                    record.sequence = Coa::Owned(sequence.to_str()?.as_bytes().to_vec());
+                }
+                Err(err) if err.is_instance_of::<PyValueError>(py) => {
+                    record.length = Some(seq.len()?);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        record.features = Coa::Owned(
+            seqrecord
+                .getattr("features")?
+                .iter()?
+                .map(|feature| biopython::extract_feature(&feature?))
+                .collect::<PyResult<Vec<_>>>()?,
+        );
+
+        Ok(record)
+    }
+
+    /// Create a Biopython `~Bio.SeqRecord.SeqRecord` from this record.
+    ///
+    /// The identifier, name, description, sequence, features and
+    /// annotations of the Biopython record are set like in the records
+    /// read by `Bio.SeqIO.parse`. Locations on the reverse strand, such
+    /// as `Complement` locations, are given a strand of ``-1``.
+    ///
+    /// Returns:
+    ///     `~Bio.SeqRecord.SeqRecord`: A new Biopython record with the
+    ///     data of this record.
+    ///
+    /// Raises:
+    ///     `ImportError`: When Biopython is not installed, or is older
+    ///         than version 1.80, which added ``SimpleLocation``.
+    ///     `ValueError`: When a feature has a location which cannot be
+    ///         represented with Biopython, such as a `OneOf` location.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    fn to_biopython(slf: Bound<'_, Self>) -> PyResult<PyObject> {
+        let py = slf.py();
+        let seq: gb_io::seq::Seq = Extract::extract(py, slf.unbind())?;
+        biopython::to_seq_record(py, &seq)
     }
 
     /// `bool`: Whether the record describes a circular molecule.
@@ -214,10 +464,15 @@ impl Record {
     }
 
     /// `~datetime.date` or `None`: The date this record was submitted.
+    ///
+    /// If the date in the ``LOCUS`` line is not a valid calendar date,
+    /// this property is `None`, but the original date can still be
+    /// accessed with the `Record.date_string` property.
     #[getter]
     fn get_date(mut slf: PyRefMut<'_, Self>) -> PyResult<PyObject> {
         let py = slf.py();
         match &mut slf.deref_mut().date {
+            Some(Coa::Owned(date)) if !is_valid_date(date) => Ok(py.None()),
             Some(date) => Ok(date.to_shared(py)?.to_object(py)),
             None => Ok(py.None()),
         }
@@ -233,6 +488,294 @@ impl Record {
         Ok(())
     }
 
+    /// `str` or `None`: The date of the record, as written in the ``LOCUS`` line.
+    ///
+    /// .. versionadded:: 0.4.0
+    #[getter]
+    fn get_date_string(slf: PyRef<'_, Self>) -> PyResult<Option<String>> {
+        let py = slf.py();
+        match &slf.date {
+            Some(Coa::Owned(date)) => Ok(Some(date.to_string())),
+            Some(Coa::Shared(date)) => Ok(Some(
+                Extract::extract(py, date.clone_ref(py))
+                    .map(|date: gb_io::seq::Date| date.to_string())?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// `dict`: The structured comments of the record, by section name.
+    ///
+    /// Structured comments are the blocks of key-value pairs delimited by
+    /// lines such as ``##Genome-Assembly-Data-START##`` in the comments of
+    /// NCBI records. Each section is given as a `dict` mapping keys to
+    /// values, like in the ``structured_comment`` annotation of Biopython
+    /// records. The raw text of the blocks is kept in `Record.comments`.
+    ///
+    /// A new `dict` is returned on every access, so assign a `dict` to
+    /// modify the structured comments. The blocks of `Record.comments`
+    /// are then replaced in place by the sections with the same name, or
+    /// removed if the `dict` has no such section. The other sections are
+    /// added to the last comment of the record, so that they are written
+    /// to the ``COMMENT`` field by `gb_io.dump`.
+    ///
+    /// Example:
+    ///     >>> record = gb_io.Record(b"ATGC")
+    ///     >>> record.comments = ["Some comment."]
+    ///     >>> record.structured_comments = {
+    ///     ...     "Assembly-Data": {"Assembly Method": "SPAdes v. 3.9"},
+    ///     ... }
+    ///     >>> print(record.comments[0])
+    ///     Some comment.
+    ///     <BLANKLINE>
+    ///     ##Assembly-Data-START##
+    ///     Assembly Method :: SPAdes v. 3.9
+    ///     ##Assembly-Data-END##
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[getter]
+    fn get_structured_comments(slf: PyRef<'_, Self>) -> PyResult<Py<PyDict>> {
+        let py = slf.py();
+        let sections = PyDict::new_bound(py);
+        for (name, entries) in slf.comments.iter().flat_map(|c| comment::parse(c)) {
+            let section = PyDict::new_bound(py);
+            for (key, value) in entries {
+                section.set_item(key, value)?;
+            }
+            sections.set_item(name, section)?;
+        }
+        Ok(sections.unbind())
+    }
+
+    #[setter]
+    fn set_structured_comments(
+        mut slf: PyRefMut<'_, Self>,
+        sections: Bound<'_, PyAny>,
+    ) -> PyResult<()> {
+        let check = |text: &str| match text.contains('\n') {
+            false => Ok(()),
+            true => Err(PyValueError::new_err(format!(
+                "invalid structured comment: {:?}",
+                text
+            ))),
+        };
+        // the formatted blocks, by name, taken once written in place
+        let mut blocks = Vec::new();
+        for item in sections.downcast::<PyMapping>()?.items()?.iter()? {
+            let (name, section) = item?.extract::<(String, Bound<PyAny>)>()?;
+            check(&name)?;
+            if name.is_empty() {
+                return Err(PyValueError::new_err("empty structured comment name"));
+            }
+            let mut text = Vec::new();
+            for entry in section.downcast::<PyMapping>()?.items()?.iter()? {
+                let (key, value) = entry?.extract::<(String, String)>()?;
+                check(&key)?;
+                check(&value)?;
+                text.push((key, value));
+            }
+            let block = comment::format(&name, &text);
+            blocks.push((name, Some(block)));
+        }
+
+        let mut comments = slf
+            .comments
+            .iter()
+            .map(|c| {
+                comment::replace(c, |name| {
+                    blocks
+                        .iter_mut()
+                        .find(|(n, _)| n == name)
+                        .and_then(|(_, block)| block.take())
+                })
+            })
+            .filter(|c| !c.trim().is_empty())
+            .collect::<Vec<_>>();
+        let blocks = blocks
+            .into_iter()
+            .filter_map(|(_, block)| block)
+            .collect::<Vec<_>>();
+        if !blocks.is_empty() {
+            match comments.last_mut() {
+                Some(last) => {
+                    last.push_str("\n\n");
+                    last.push_str(&blocks.join("\n"));
+                }
+                None => comments.push(blocks.join("\n")),
+            }
+        }
+        slf.comments = comments;
+        Ok(())
+    }
+
+    /// `int` or `None`: The GI number of the record, if any.
+    ///
+    /// The GI number is stored on the ``VERSION`` line of legacy records,
+    /// and is only written by `gb_io.dump` when ``emit_gi=True``.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[getter]
+    fn get_gi(slf: PyRef<'_, Self>) -> Option<u64> {
+        slf.version
+            .as_deref()
+            .and_then(|version| writer::split_version(version).1)
+            .and_then(|gi| gi.parse().ok())
+    }
+
+    #[setter]
+    fn set_gi(mut slf: PyRefMut<'_, Self>, gi: Option<u64>) -> PyResult<()> {
+        let accession = match &slf.version {
+            Some(version) => writer::split_version(version).0.to_string(),
+            None if gi.is_none() => return Ok(()),
+            None => {
+                return Err(PyValueError::new_err(
+                    "cannot set the GI of a record without a version",
+                ))
+            }
+        };
+        slf.version = Some(match gi {
+            Some(gi) => format!("{}  GI:{}", accession, gi),
+            None => accession,
+        });
+        Ok(())
+    }
+
+    /// `str` or `None`: The kind of sequence stored in the record.
+    ///
+    /// Either ``"nucleotide"`` or ``"protein"``. The kind is inferred from
+    /// the molecule type when available (e.g. ``DNA`` or ``mRNA``), or
+    /// otherwise from the symbols of the sequence, and is `None` when
+    /// the record has neither a molecule type nor a sequence.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[getter]
+    fn get_kind(slf: PyRef<'_, Self>) -> PyResult<Option<&'static str>> {
+        let py = slf.py();
+        if let Some(molecule_type) = &slf.molecule_type {
+            let molecule_type = molecule_type.to_ascii_uppercase();
+            if molecule_type.contains("DNA") || molecule_type.contains("RNA") {
+                return Ok(Some("nucleotide"));
+            } else if molecule_type == "AA" || molecule_type.contains("PROTEIN") {
+                return Ok(Some("protein"));
+            }
+        }
+        let is_protein =
+            |sequence: &[u8]| sequence.iter().any(|c| b"EFIJLOPQZefijlopqz*".contains(c));
+        let kind = match &slf.sequence {
+            Coa::Owned(sequence) if sequence.is_empty() => None,
+            Coa::Owned(sequence) => Some(is_protein(sequence)),
+            Coa::Shared(sequence) => {
+                // SAFETY: the bytearray is not mutated while we hold the GIL.
+                let sequence = unsafe { sequence.bind(py).as_bytes() };
+                (!sequence.is_empty()).then(|| is_protein(sequence))
+            }
+        };
+        Ok(kind.map(|protein| if protein { "protein" } else { "nucleotide" }))
+    }
+
+    /// `Source` or `None`: The source of the record.
+    ///
+    /// The ``/mol_type``, ``/organelle``, ``/plasmid``, ``/chromosome``
+    /// and ``/segment`` attributes of the returned `Source` are views
+    /// over the qualifiers of the first ``source`` feature of the
+    /// record. See `Source` for more details.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[getter]
+    fn get_source(slf: Bound<'_, Self>) -> PyResult<Option<Py<Source>>> {
+        let py = slf.py();
+        let source = match &mut slf.borrow_mut().source {
+            Some(Coa::Shared(source)) => return Ok(Some(source.clone_ref(py))),
+            Some(source) => source.to_shared(py)?,
+            None => return Ok(None),
+        };
+        // a parsed source has no values of its own, it only views the
+        // source feature of the record
+        source.bind(py).borrow_mut().record = Some(slf.unbind());
+        Ok(Some(source))
+    }
+
+    #[setter]
+    fn set_source(slf: Bound<'_, Self>, source: Option<Bound<'_, Source>>) -> PyResult<()> {
+        let py = slf.py();
+        let source = match source {
+            // a source attached to another record is copied, so that it
+            // keeps viewing the features of its own record
+            Some(source) if source.borrow().is_attached_elsewhere(&slf) => {
+                Some(Bound::new(py, Source::detached(&source)?)?)
+            }
+            source => source,
+        };
+        let previous = std::mem::replace(
+            &mut slf.borrow_mut().source,
+            source.as_ref().map(|s| Coa::Shared(s.clone().unbind())),
+        );
+        if let Some(Coa::Shared(previous)) = previous {
+            Source::detach(previous.bind(py), &slf)?;
+        }
+        if let Some(source) = source {
+            Source::attach(&source, &slf)?;
+        }
+        Ok(())
+    }
+
+    /// `Location` or `None`: The location of the ``CONTIG`` line.
+    ///
+    /// CON-division records are built from the sequences of other
+    /// records, which are listed as a join of `External` locations.
+    ///
+    /// Raises:
+    ///     `NotImplementedError`: When the contig contains gaps, which
+    ///         cannot be represented as `Location` objects yet. The contig
+    ///         is kept unchanged, and is still written by `gb_io.dump`.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[getter]
+    fn get_contig(mut slf: PyRefMut<'_, Self>) -> PyResult<Option<Py<Location>>> {
+        let py = slf.py();
+        match &slf.contig {
+            None => Ok(None),
+            Some(Coa::Shared(contig)) => Ok(Some(contig.clone_ref(py))),
+            Some(Coa::Owned(contig)) => {
+                // convert a copy so that the contig is not lost if the
+                // conversion fails, e.g. because of gaps
+                let contig = contig.clone().convert(py)?;
+                slf.contig = Some(Coa::Shared(contig.clone_ref(py)));
+                Ok(Some(contig))
+            }
+        }
+    }
+
+    #[setter]
+    fn set_contig(mut slf: PyRefMut<'_, Self>, contig: Option<Py<Location>>) {
+        slf.contig = contig.map(Coa::Shared);
+    }
+
+    /// `str` or `None`: The type of the record accession.
+    ///
+    /// The type is derived from the prefix of the accession, following
+    /// the NCBI conventions, and is one of:
+    ///
+    /// - ``"refseq"`` for RefSeq accessions (e.g. ``NC_005816``).
+    /// - ``"wgs"`` for primary accessions of whole genome shotgun
+    ///   sequencing projects (e.g. ``AAAA01000001``).
+    /// - ``"insdc"`` for other primary INSDC accessions (e.g. ``AY048670``).
+    /// - ``"unknown"`` for accessions not matching any of these patterns.
+    ///
+    /// The property is `None` when the record has no accession.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[getter]
+    fn get_accession_type(slf: PyRef<'_, Self>) -> Option<&'static str> {
+        slf.accession.as_deref().map(accession::classify)
+    }
+
     /// `bytes`: The sequence of the record in lowercase, as raw ASCII.
     #[getter]
     fn get_sequence(mut slf: PyRefMut<'_, Self>) -> PyResult<Py<PyByteArray>> {
@@ -245,6 +788,217 @@ impl Record {
         slf.sequence = Coa::Shared(sequence);
     }
 
+    /// Load the sequence of the record from a file.
+    ///
+    /// The file may contain either a raw sequence, or a single sequence
+    /// in FASTA format. Whitespace and line breaks are ignored, and the
+    /// sequence is read directly without creating an intermediate Python
+    /// object, which makes this method suitable for large sequences.
+    ///
+    /// Arguments:
+    ///     path (`str`): The path to the file containing the sequence.
+    ///
+    /// Raises:
+    ///     `OSError`: When the file could not be read.
+    ///     `ValueError`: When the file contains more than one FASTA
+    ///         record, or characters that are not sequence symbols.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    fn set_sequence_from_path(mut slf: PyRefMut<'_, Self>, path: &str) -> PyResult<()> {
+        let convert_error = |err: std::io::Error| match err.raw_os_error() {
+            Some(code) => PyOSError::new_err((code, err.to_string())),
+            None => PyOSError::new_err(err.to_string()),
+        };
+        let file = std::fs::File::open(path).map_err(convert_error)?;
+        let mut reader = std::io::BufReader::new(file);
+
+        let mut sequence = Vec::new();
+        let mut line = Vec::new();
+        let mut lineno = 0;
+        while reader.read_until(b'\n', &mut line).map_err(convert_error)? > 0 {
+            lineno += 1;
+            if line.starts_with(b">") {
+                if lineno > 1 {
+                    return Err(PyValueError::new_err(format!(
+                        "unexpected FASTA header on line {}",
+                        lineno
+                    )));
+                }
+            } else {
+                for &c in line.iter().filter(|c| !c.is_ascii_whitespace()) {
+                    if !c.is_ascii_alphabetic() && c != b'*' && c != b'-' {
+                        return Err(PyValueError::new_err(format!(
+                            "invalid sequence character on line {}: {:?}",
+                            lineno, c as char
+                        )));
+                    }
+                    sequence.push(c);
+                }
+            }
+            line.clear();
+        }
+
+        if slf.length.is_some() {
+            slf.length = Some(sequence.len());
+        }
+        slf.sequence = Coa::Owned(sequence);
+        Ok(())
+    }
+
+    /// Get the runs of unknown bases in the record sequence.
+    ///
+    /// Arguments:
+    ///     min_length (`int`): The minimum length of the runs to report.
+    ///
+    /// Returns:
+    ///     `list` of `tuple`: The maximal runs of ``N`` or ``n`` symbols
+    ///     in the sequence, as zero-based ``(start, end)`` ranges with an
+    ///     exclusive end, in increasing order.
+    ///
+    /// Example:
+    ///     Get the gaps of a sequence, ignoring single unknown bases::
+    ///
+    ///         >>> record = gb_io.Record(b"ATGNNNCnGTNNNN")
+    ///         >>> record.gaps()
+    ///         [(3, 6), (7, 8), (10, 14)]
+    ///         >>> record.gaps(min_length=2)
+    ///         [(3, 6), (10, 14)]
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyo3(signature = (*, min_length = 1))]
+    fn gaps(slf: PyRef<'_, Self>, min_length: usize) -> Vec<(usize, usize)> {
+        let py = slf.py();
+        let gaps = |sequence: &[u8]| {
+            let mut gaps = Vec::new();
+            let mut start = None;
+            for (i, c) in sequence.iter().chain(Some(&b'\0')).enumerate() {
+                match (start, c.eq_ignore_ascii_case(&b'N')) {
+                    (None, true) => start = Some(i),
+                    (Some(s), false) => {
+                        if i - s >= min_length {
+                            gaps.push((s, i));
+                        }
+                        start = None;
+                    }
+                    _ => (),
+                }
+            }
+            gaps
+        };
+        match &slf.sequence {
+            Coa::Owned(sequence) => gaps(sequence),
+            Coa::Shared(sequence) => {
+                // SAFETY: the bytearray is not mutated while we hold the GIL.
+                gaps(unsafe { sequence.bind(py).as_bytes() })
+            }
+        }
+    }
+
+    /// Get the number of known bases in the record sequence.
+    ///
+    /// Returns:
+    ///     `int`: The length of the sequence, excluding ``N`` or ``n``
+    ///     symbols.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    fn ungapped_length(slf: PyRef<'_, Self>) -> usize {
+        let py = slf.py();
+        let count = |sequence: &[u8]| {
+            sequence
+                .iter()
+                .filter(|c| !c.eq_ignore_ascii_case(&b'N'))
+                .count()
+        };
+        match &slf.sequence {
+            Coa::Owned(sequence) => count(sequence),
+            Coa::Shared(sequence) => {
+                // SAFETY: the bytearray is not mutated while we hold the GIL.
+                count(unsafe { sequence.bind(py).as_bytes() })
+            }
+        }
+    }
+
+    /// Check that the record sequence only contains symbols of an alphabet.
+    ///
+    /// Symbols are compared case-insensitively. The ``dna`` and ``rna``
+    /// alphabets only allow unambiguous nucleotides and ``N`` for unknown
+    /// bases, while ``iupac`` allows all the IUPAC nucleotide codes. The
+    /// ``protein`` alphabet allows the standard amino acids, the ``B``,
+    /// ``Z``, ``J`` and ``X`` ambiguity codes, ``U`` and ``O`` for
+    /// selenocysteine and pyrrolysine, and ``*`` for stop codons.
+    ///
+    /// Arguments:
+    ///     alphabet (`str`): The alphabet to check the sequence against,
+    ///         either ``dna``, ``rna``, ``protein`` or ``iupac``.
+    ///     strict (`bool`): Set to `True` to raise an error on the first
+    ///         invalid symbol instead of reporting all of them.
+    ///
+    /// Returns:
+    ///     `dict`: A dictionary mapping each invalid symbol to the list
+    ///     of its zero-based positions in the sequence, in order of first
+    ///     occurrence. The dictionary is empty for a valid sequence.
+    ///
+    /// Raises:
+    ///     `ValueError`: When the alphabet is unknown, or when ``strict``
+    ///         is `True` and the sequence contains an invalid symbol.
+    ///
+    /// Example:
+    ///     Find the symbols that are not nucleotides in a sequence::
+    ///
+    ///         >>> record = gb_io.Record(b"ATGCNxTG-A-")
+    ///         >>> record.validate_sequence()
+    ///         {'x': [5], '-': [8, 10]}
+    ///         >>> record.validate_sequence("iupac", strict=True)
+    ///         Traceback (most recent call last):
+    ///         ...
+    ///         ValueError: invalid symbol 'x' at position 5 for the 'iupac' alphabet
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyo3(signature = (alphabet = Alphabet::Dna, *, strict = false))]
+    fn validate_sequence<'py>(
+        slf: PyRef<'py, Self>,
+        alphabet: Alphabet,
+        strict: bool,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let py = slf.py();
+        let validate = |sequence: &[u8]| {
+            if strict {
+                match alphabet.find_invalid(sequence) {
+                    Some(i) => Err((sequence[i], i)),
+                    None => Ok(Vec::new()),
+                }
+            } else {
+                Ok(alphabet.invalid_symbols(sequence))
+            }
+        };
+        let result = match &slf.sequence {
+            Coa::Owned(sequence) => validate(sequence),
+            Coa::Shared(sequence) => {
+                // SAFETY: the bytearray is not mutated while we hold the GIL,
+                //         and no Python object is created before the result
+                //         is computed.
+                validate(unsafe { sequence.bind(py).as_bytes() })
+            }
+        };
+        let invalid = result.map_err(|(c, i)| {
+            PyValueError::new_err(format!(
+                "invalid symbol {:?} at position {} for the '{}' alphabet",
+                char::from(c),
+                i,
+                alphabet.name()
+            ))
+        })?;
+        let dict = PyDict::new_bound(py);
+        for (c, positions) in invalid {
+            dict.set_item(char::from(c), positions)?;
+        }
+        Ok(dict)
+    }
+
     /// `list`: A list of `Feature` within the record.
     #[getter]
     fn get_features(mut slf: PyRefMut<'_, Self>) -> PyResult<Py<PyList>> {
@@ -268,52 +1022,1597 @@ impl Record {
     fn set_references(mut slf: PyRefMut<'_, Self>, references: Py<PyList>) {
         slf.references = Coa::Shared(references);
     }
-}
 
-impl Convert for gb_io::seq::Seq {
-    type Output = Record;
-    fn convert_with(self, py: Python, _interner: &mut PyInterner) -> PyResult<Py<Self::Output>> {
-        Py::new(
-            py,
-            Record {
-                name: self.name,
-                topology: self.topology,
-                date: self.date.map(Coa::Owned),
-                length: self.len,
-                molecule_type: self.molecule_type,
-                division: self.division,
-                definition: self.definition,
-                accession: self.accession,
-                version: self.version,
-                source: self.source.map(Coa::Owned),
-                dblink: self.dblink,
-                keywords: self.keywords,
-                references: self.references.into(),
-                comments: self.comments,
-                sequence: Coa::Owned(self.seq),
-                contig: self.contig.map(Coa::Owned),
-                features: self.features.into(),
-            },
-        )
+    /// Get the features of the record intersecting the given range.
+    ///
+    /// Arguments:
+    ///     start (`int`): The start of the range (zero-based, inclusive).
+    ///     end (`int`): The end of the range (zero-based, exclusive).
+    ///     kind (`str`, optional): The kind of features to retain, or
+    ///         `None` to retain features of any kind.
+    ///
+    /// Returns:
+    ///     `list` of `Feature`: The features whose location intersects
+    ///     the range, in the order they appear in the record.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyo3(signature = (start, end, kind = None))]
+    fn features_in_range(
+        mut slf: PyRefMut<'_, Self>,
+        start: i64,
+        end: i64,
+        kind: Option<&str>,
+    ) -> PyResult<Py<PyList>> {
+        let py = slf.py();
+        let features = slf.features.to_shared(py)?;
+        let selected = PyList::empty_bound(py);
+        for object in features.bind(py).iter() {
+            let feature = object.downcast::<Feature>()?.borrow();
+            if let Some(kind) = kind {
+                if !feature.has_kind(py, kind)? {
+                    continue;
+                }
+            }
+            if feature.intersects_range(py, start, end)? {
+                selected.append(&object)?;
+            }
+        }
+        Ok(selected.unbind())
     }
-}
 
-impl Extract for gb_io::seq::Seq {
-    fn extract(py: Python, object: Py<<Self as Convert>::Output>) -> PyResult<Self> {
-        let record = object.bind(py).borrow();
-        Ok(gb_io::seq::Seq {
-            name: record.name.clone(),
-            topology: record.topology.clone(),
-            len: record.length.clone(),
-            molecule_type: record.molecule_type.clone(),
-            division: record.division.clone(),
-            definition: record.definition.clone(),
-            accession: record.accession.clone(),
-            version: record.version.clone(),
-            dblink: record.dblink.clone(),
-            keywords: record.keywords.clone(),
-            comments: record.comments.clone(),
-            seq: record.sequence.to_owned_native(py)?,
+    /// Get all the pairs of overlapping features of the record.
+    ///
+    /// Overlaps are computed with a sweep-line over the feature spans,
+    /// and compound locations are compared part by part, so that two
+    /// interleaved but disjoint joins do not overlap.
+    ///
+    /// Arguments:
+    ///     same_strand (`bool`): Pass `True` to only report overlapping
+    ///         features on the same strand.
+    ///     kind (`str`, optional): The kind of features to compare, or
+    ///         `None` to compare features of any kind.
+    ///
+    /// Returns:
+    ///     `list` of `tuple`: The pairs of overlapping features, as
+    ///     ``(Feature, Feature)`` tuples in the order the features appear
+    ///     in the record. External locations never overlap.
+    ///
+    /// Caution:
+    ///     Features spanning the origin of a circular record are only
+    ///     handled when written as a ``join`` of two locations, such as
+    ///     ``join(4500..5000,1..200)``. Coordinates are never wrapped, so
+    ///     a range extending beyond the sequence length will not overlap
+    ///     the features at the start of the record.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyo3(signature = (*, same_strand = false, kind = None))]
+    fn overlapping_features(
+        mut slf: PyRefMut<'_, Self>,
+        same_strand: bool,
+        kind: Option<&str>,
+    ) -> PyResult<Vec<(Py<Feature>, Py<Feature>)>> {
+        let py = slf.py();
+        let mut features = Vec::new();
+        let mut locations = Vec::new();
+        for object in slf.features.to_shared(py)?.bind(py).iter() {
+            let feature = object.downcast::<Feature>()?;
+            if let Some(kind) = kind {
+                if !feature.borrow().has_kind(py, kind)? {
+                    continue;
+                }
+            }
+            locations.push(feature.borrow().native_location(py)?);
+            features.push(feature.clone().unbind());
+        }
+        let locations = locations.iter().collect::<Vec<_>>();
+        Ok(location::overlapping_pairs(&locations, same_strand)
+            .into_iter()
+            .map(|(i, j)| (features[i].clone_ref(py), features[j].clone_ref(py)))
+            .collect())
+    }
+
+    /// Group the features of the record into a gene hierarchy.
+    ///
+    /// GenBank features are flat, so the hierarchy is inferred with the
+    /// following heuristics:
+    ///
+    /// - ``gene`` features are at the top level, followed by transcripts
+    ///   (``mRNA``, ``ncRNA``, ``rRNA``, ``tRNA``, ``tmRNA``, ``misc_RNA``,
+    ///   ``precursor_RNA`` and ``prim_transcript``), and then by parts of
+    ///   transcripts (``CDS``, ``exon``, ``intron``, ``5'UTR``, ``3'UTR``
+    ///   and the peptide features). Features of other kinds are always
+    ///   at the top of the hierarchy, without children.
+    /// - A feature can only be the child of a feature at a higher level,
+    ///   which overlaps it on the same strand, and with the same
+    ///   ``/locus_tag`` qualifier, or the same ``/gene`` qualifier if any
+    ///   of the two features has no ``/locus_tag``.
+    /// - When several parents qualify, a transcript is preferred over a
+    ///   gene, then a parent whose parts contain all the parts of the
+    ///   child (e.g. a ``CDS`` inside the exons of an ``mRNA``), then the
+    ///   parent with the shortest span, and finally the first one in the
+    ///   record. A ``CDS`` is therefore attached to its ``gene`` when the
+    ///   record has no ``mRNA`` features.
+    ///
+    /// Returns:
+    ///     `list` of `tuple`: The roots of the hierarchy, as
+    ///     ``(Feature, children)`` tuples where ``children`` is a list of
+    ///     tuples of the same form. Every feature appears exactly once in
+    ///     the hierarchy, and siblings are in the order of the record.
+    ///
+    /// Example:
+    ///     Get the CDS of each gene of a record::
+    ///
+    ///         >>> record = gb_io.load("tests/data/biopython/NC_005816.gb")[0]
+    ///         >>> hierarchy = record.build_hierarchy()
+    ///         >>> gene, children = hierarchy[2]
+    ///         >>> gene.kind, [child.kind for child, _ in children]
+    ///         ('gene', ['CDS'])
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    fn build_hierarchy(mut slf: PyRefMut<'_, Self>) -> PyResult<Vec<PyObject>> {
+        let py = slf.py();
+        let features = slf.features.to_shared(py)?.into_bound(py);
+        let mut nodes = Vec::with_capacity(features.len());
+        for object in features.iter() {
+            let feature = object.downcast::<Feature>()?.borrow();
+            let kind = match &feature.kind {
+                Coa::Owned(kind) => kind.to_string(),
+                Coa::Shared(kind) => kind.bind(py).to_str()?.to_string(),
+            };
+            nodes.push(hierarchy::Node {
+                kind,
+                location: feature.native_location(py)?,
+                locus_tag: feature.qualifier_value(py, "locus_tag")?,
+                gene: feature.qualifier_value(py, "gene")?,
+            });
+        }
+
+        let mut roots = Vec::new();
+        let mut children = vec![Vec::new(); nodes.len()];
+        for (i, parent) in hierarchy::parents(&nodes).into_iter().enumerate() {
+            match parent {
+                Some(parent) => children[parent].push(i),
+                None => roots.push(i),
+            }
+        }
+
+        fn build(
+            py: Python,
+            features: &Bound<PyList>,
+            children: &[Vec<usize>],
+            i: usize,
+        ) -> PyResult<PyObject> {
+            let nodes = children[i]
+                .iter()
+                .map(|&j| build(py, features, children, j))
+                .collect::<PyResult<Vec<_>>>()?;
+            Ok((features.get_item(i)?, nodes).into_py(py))
+        }
+        roots
+            .into_iter()
+            .map(|i| build(py, &features, &children, i))
+            .collect()
+    }
+
+    /// Split the record into fragments at the given positions.
+    ///
+    /// Features are clipped to each fragment and their locations are
+    /// rebased on the fragment start. Features spanning a split position
+    /// are kept in all the fragments they overlap, with their clipped
+    /// ends marked as partial using the ``before`` and ``after`` flags.
+    /// Features located on another record, or on a gap, cannot be placed
+    /// in any fragment and are dropped, as well as such parts of compound
+    /// locations. Other record attributes are copied to each fragment, and
+    /// the fragments are always linear.
+    ///
+    /// Arguments:
+    ///     positions (iterable of `int`): The zero-based positions where
+    ///         to split the record, in increasing order.
+    ///
+    /// Returns:
+    ///     `list` of `Record`: The fragments of the record, with one more
+    ///     fragment than the number of split positions.
+    ///
+    /// Raises:
+    ///     `ValueError`: When the positions are not strictly increasing,
+    ///         or not strictly within the sequence.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    fn split_at(slf: Bound<'_, Self>, positions: Vec<i64>) -> PyResult<Vec<Record>> {
+        let py = slf.py();
+        let seq: gb_io::seq::Seq = Extract::extract(py, slf.unbind())?;
+
+        let length = seq.seq.len() as i64;
+        let mut bounds = Vec::with_capacity(positions.len() + 2);
+        bounds.push(0);
+        for position in positions {
+            if position <= bounds[bounds.len() - 1] || position >= length {
+                return Err(PyValueError::new_err(format!(
+                    "invalid split position: {}",
+                    position
+                )));
+            }
+            bounds.push(position);
+        }
+        bounds.push(length);
+
+        let fragments = bounds
+            .windows(2)
+            .map(|window| {
+                let (start, end) = (window[0], window[1]);
+                let features = seq
+                    .features
+                    .iter()
+                    .filter_map(|feature| {
+                        location::clip(&feature.location, start, end).map(|location| {
+                            gb_io::seq::Feature {
+                                kind: feature.kind.clone(),
+                                location: location::shift(&location, -start),
+                                qualifiers: feature.qualifiers.clone(),
+                            }
+                        })
+                    })
+                    .collect();
+                Record::from(gb_io::seq::Seq {
+                    name: seq.name.clone(),
+                    topology: Topology::Linear,
+                    date: seq.date.clone(),
+                    len: None,
+                    molecule_type: seq.molecule_type.clone(),
+                    division: seq.division.clone(),
+                    definition: seq.definition.clone(),
+                    accession: seq.accession.clone(),
+                    version: seq.version.clone(),
+                    source: seq.source.clone(),
+                    dblink: seq.dblink.clone(),
+                    keywords: seq.keywords.clone(),
+                    references: seq.references.clone(),
+                    comments: seq.comments.clone(),
+                    seq: seq.seq[start as usize..end as usize].to_vec(),
+                    contig: None,
+                    features,
+                })
+            })
+            .collect();
+        Ok(fragments)
+    }
+
+    /// Get the reverse complement of the record.
+    ///
+    /// The sequence is reversed and complemented, including the IUPAC
+    /// ambiguity codes, and the locations of the features are mirrored
+    /// onto the reverse strand, as done by `Location.flip`. Features are
+    /// kept in the same order, and other record attributes, including the
+    /// topology, are copied to the new record.
+    ///
+    /// Returns:
+    ///     `Record`: A new record for the reverse strand of the record.
+    ///
+    /// Raises:
+    ///     `ValueError`: When the record has no sequence.
+    ///
+    /// Example:
+    ///     >>> feature = gb_io.Feature("CDS", gb_io.Range(0, 6))
+    ///     >>> record = gb_io.Record(b"ATGAAArtn", features=[feature])
+    ///     >>> rc = record.reverse_complement()
+    ///     >>> rc.sequence
+    ///     bytearray(b'nayTTTCAT')
+    ///     >>> rc.features[0].location
+    ///     Complement(Range(3, 9))
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    fn reverse_complement(slf: Bound<'_, Self>) -> PyResult<Record> {
+        let py = slf.py();
+        let extras = RecordExtras::from(&*slf.borrow());
+        let mut seq: gb_io::seq::Seq = Extract::extract(py, slf.unbind())?;
+        if seq.seq.is_empty() {
+            return Err(PyValueError::new_err(
+                "cannot reverse complement a record without sequence",
+            ));
+        }
+
+        let length = seq.seq.len() as i64;
+        seq.seq = translation::reverse_complement(&seq.seq);
+        for feature in seq.features.iter_mut() {
+            feature.location = location::flip(&feature.location, length);
+        }
+        seq.contig = seq.contig.map(|contig| location::flip(&contig, length));
+
+        let mut record = Record::from(seq);
+        extras.apply(&mut record);
+        Ok(record)
+    }
+
+    /// Move the origin of a circular record to the given position.
+    ///
+    /// The sequence is rotated in place so that ``position`` becomes the
+    /// first coordinate, and the locations of the features are remapped
+    /// accordingly. Features spanning the new origin are split into a
+    /// join of two parts, one at the end and one at the start of the
+    /// sequence. Features covering the whole sequence, such as the
+    /// ``source`` feature, are left unchanged.
+    ///
+    /// Arguments:
+    ///     position (`int`): The zero-based coordinate of the new origin,
+    ///         e.g. the start of the *dnaA* gene of a bacterial chromosome.
+    ///
+    /// Raises:
+    ///     `ValueError`: When the record is not circular, or when the
+    ///         position is not within the sequence.
+    ///
+    /// Example:
+    ///     >>> feature = gb_io.Feature("CDS", gb_io.Range(6, 12))
+    ///     >>> record = gb_io.Record(b"CCCTAAATGAAA", circular=True, features=[feature])
+    ///     >>> record.set_origin(9)
+    ///     >>> record.sequence
+    ///     bytearray(b'AAACCCTAAATG')
+    ///     >>> record.features[0].location
+    ///     Join([Range(9, 12), Range(0, 3)])
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    fn set_origin(mut slf: PyRefMut<'_, Self>, position: i64) -> PyResult<()> {
+        let py = slf.py();
+        if !matches!(slf.topology, Topology::Circular) {
+            return Err(PyValueError::new_err(
+                "cannot move the origin of a linear record",
+            ));
+        }
+
+        let length = match &slf.sequence {
+            Coa::Owned(sequence) => sequence.len(),
+            Coa::Shared(sequence) => sequence.bind(py).len(),
+        } as i64;
+        if position < 0 || position >= length {
+            return Err(PyValueError::new_err(format!(
+                "invalid origin position: {}",
+                position
+            )));
+        }
+
+        // compute the new locations before modifying the record, so that
+        // it is left unchanged if a location fails to be converted
+        let rotated = match &slf.features {
+            Coa::Owned(_) => Vec::new(),
+            Coa::Shared(features) => {
+                let mut seen = HashSet::new();
+                let mut rotated = Vec::new();
+                for object in features.bind(py).iter() {
+                    let feature = object.downcast_into::<Feature>()?;
+                    // a feature listed several times must only be rotated once
+                    if seen.insert(feature.as_ptr()) {
+                        let native = feature.borrow().native_location(py)?;
+                        rotated.push((feature, location::rotate(&native, position, length)));
+                    }
+                }
+                rotated
+            }
+        };
+        let contig = match &slf.contig {
+            Some(Coa::Owned(contig)) => Some(location::rotate(contig, position, length)),
+            Some(Coa::Shared(contig)) => {
+                let native: SeqLocation = Extract::extract(py, contig.clone_ref(py))?;
+                Some(location::rotate(&native, position, length))
+            }
+            None => None,
+        };
+
+        match &mut slf.sequence {
+            Coa::Owned(sequence) => sequence.rotate_left(position as usize),
+            // SAFETY: the bytearray is not resized, and no reference to its
+            //         contents is held while we hold the GIL.
+            Coa::Shared(sequence) => {
+                unsafe { sequence.bind(py).as_bytes_mut() }.rotate_left(position as usize)
+            }
+        }
+        if let Coa::Owned(features) = &mut slf.features {
+            for feature in features.iter_mut() {
+                feature.location = location::rotate(&feature.location, position, length);
+            }
+        }
+        for (feature, location) in rotated {
+            feature.borrow_mut().location = Coa::Owned(location);
+        }
+        slf.contig = contig.map(Coa::Owned);
+        Ok(())
+    }
+
+    /// Extract the sequence covered by a location from the record.
+    ///
+    /// Parts of compound locations are concatenated in the order they
+    /// are given, and complemented parts are reverse-complemented, as
+    /// with `Feature.extract`. For circular records, ranges extending
+    /// past the end of the sequence wrap around the origin.
+    ///
+    /// Arguments:
+    ///     location (`Location`): The location to extract.
+    ///
+    /// Returns:
+    ///     `bytes`: The sequence covered by the location.
+    ///
+    /// Raises:
+    ///     `IndexError`: When the location is out of the bounds of the
+    ///         record sequence.
+    ///     `ValueError`: When the location cannot be extracted from the
+    ///         record, e.g. because it refers to another record.
+    ///
+    /// Example:
+    ///     >>> record = gb_io.Record(b"ATGAAACCCTAA", circular=True)
+    ///     >>> record.extract(gb_io.Complement(gb_io.Range(0, 6)))
+    ///     b'TTTCAT'
+    ///     >>> record.extract(gb_io.Range(9, 15))
+    ///     b'TAAATG'
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    fn extract<'py>(
+        slf: PyRef<'py, Self>,
+        location: Py<Location>,
+    ) -> PyResult<Bound<'py, PyBytes>> {
+        let py = slf.py();
+        let location: SeqLocation = Extract::extract(py, location)?;
+        let extracted = slf.extract_location(py, location)?;
+        Ok(PyBytes::new_bound(py, &extracted))
+    }
+
+    /// Extract the sequences of the features of the record by identifier.
+    ///
+    /// Arguments:
+    ///     kind (`str`, optional): The kind of features to extract, or
+    ///         `None` to extract features of any kind.
+    ///     key (`str`): The key of the qualifier giving the identifier
+    ///         of each feature, e.g. ``locus_tag``, ``gene`` or
+    ///         ``protein_id``.
+    ///     strict (`bool`): Pass `True` to raise an error for features
+    ///         without the qualifier, instead of skipping them.
+    ///     duplicates (`str`): How to handle several features with the
+    ///         same identifier, either ``suffix`` to store the sequences
+    ///         of the following features under the identifier suffixed
+    ///         with their occurrence, e.g. ``rop_2``, or ``list`` to map
+    ///         every identifier to a list of sequences.
+    ///
+    /// Returns:
+    ///     `dict`: A dictionary mapping the identifier of each feature to
+    ///     its sequence as `bytes`, or to a `list` of `bytes` with
+    ///     ``duplicates="list"``, in feature order. Sequences are
+    ///     extracted as with `Record.extract`.
+    ///
+    /// Raises:
+    ///     `ValueError`: When a feature has no identifier and ``strict``
+    ///         is `True`, when a feature location cannot be extracted, or
+    ///         when ``duplicates`` is invalid.
+    ///     `IndexError`: When a feature location is out of bounds.
+    ///
+    /// Example:
+    ///     >>> record = gb_io.Record(b"ATGAAATAGATGCCCTAA", features=[
+    ///     ...     gb_io.Feature("CDS", gb_io.Range(0, 9), [gb_io.Qualifier("locus_tag", "a")]),
+    ///     ...     gb_io.Feature("CDS", gb_io.Range(9, 18), [gb_io.Qualifier("locus_tag", "b")]),
+    ///     ... ])
+    ///     >>> record.gene_sequences()
+    ///     {'a': b'ATGAAATAG', 'b': b'ATGCCCTAA'}
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyo3(
+        signature = (kind = Some("CDS"), key = "locus_tag", *, strict = false, duplicates = "suffix"),
+        text_signature = "($self, kind=\"CDS\", key=\"locus_tag\", *, strict=False, duplicates=\"suffix\")"
+    )]
+    fn gene_sequences<'py>(
+        slf: PyRef<'py, Self>,
+        kind: Option<&str>,
+        key: &str,
+        strict: bool,
+        duplicates: &str,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let py = slf.py();
+        let as_list = match duplicates {
+            "suffix" => false,
+            "list" => true,
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "invalid duplicates policy: {:?}",
+                    other
+                )))
+            }
+        };
+
+        // collect the identifier and location of the selected features
+        let mut selected = Vec::new();
+        match &slf.features {
+            Coa::Owned(features) => {
+                for (index, feature) in features.iter().enumerate() {
+                    if kind.is_none_or(|kind| feature.kind.as_ref() == kind) {
+                        let value = feature
+                            .qualifiers
+                            .iter()
+                            .find(|(k, _)| k.as_ref() == key)
+                            .and_then(|(_, value)| value.clone());
+                        selected.push((index, value, feature.location.clone()));
+                    }
+                }
+            }
+            Coa::Shared(features) => {
+                for (index, object) in features.bind(py).iter().enumerate() {
+                    let feature = object.downcast::<Feature>()?.borrow();
+                    if let Some(kind) = kind {
+                        if !feature.has_kind(py, kind)? {
+                            continue;
+                        }
+                    }
+                    let value = feature.qualifier_value(py, key)?;
+                    selected.push((index, value, feature.native_location(py)?));
+                }
+            }
+        }
+
+        let sequences = PyDict::new_bound(py);
+        for (index, value, location) in selected {
+            let id = match value {
+                Some(id) => id,
+                None if strict => {
+                    return Err(PyValueError::new_err(format!(
+                        "feature {} has no /{} qualifier",
+                        index, key
+                    )))
+                }
+                None => continue,
+            };
+            let sequence = PyBytes::new_bound(py, &slf.extract_location(py, location)?);
+            if as_list {
+                match sequences.get_item(&id)? {
+                    Some(list) => list.downcast::<PyList>()?.append(sequence)?,
+                    None => sequences.set_item(id, PyList::new_bound(py, [sequence]))?,
+                }
+            } else if sequences.contains(&id)? {
+                let mut n = 2;
+                while sequences.contains(format!("{}_{}", id, n))? {
+                    n += 1;
+                }
+                sequences.set_item(format!("{}_{}", id, n), sequence)?;
+            } else {
+                sequences.set_item(id, sequence)?;
+            }
+        }
+        Ok(sequences)
+    }
+
+    /// Iterate lazily over the features of the record.
+    ///
+    /// Arguments:
+    ///     kind (`str`, optional): The kind of features to yield, or
+    ///         `None` to yield features of any kind.
+    ///     in_range (`tuple` of `int`, optional): A ``(start, end)`` range
+    ///         that the features must intersect, or `None` to yield
+    ///         features at any location.
+    ///
+    /// Returns:
+    ///     `FeatureIterator`: An iterator over the matching features. The
+    ///     number of features is fixed when the iterator is created, so
+    ///     features added to `Record.features` afterwards are not yielded.
+    ///
+    /// Note:
+    ///     If `Record.features` was not accessed yet, the features are
+    ///     only created as Python objects for the matches, which are
+    ///     copies: modifying them does not modify the record.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyo3(signature = (kind = None, in_range = None))]
+    fn iter_features(
+        slf: Bound<'_, Self>,
+        kind: Option<String>,
+        in_range: Option<(i64, i64)>,
+    ) -> PyResult<FeatureIterator> {
+        let py = slf.py();
+        let length = match &slf.borrow().features {
+            Coa::Owned(features) => features.len(),
+            Coa::Shared(features) => features.bind(py).len(),
+        };
+        Ok(FeatureIterator {
+            record: slf.unbind(),
+            index: 0,
+            length,
+            kind,
+            in_range,
+        })
+    }
+
+    /// Check whether the record contains a feature of the given kind.
+    ///
+    /// Arguments:
+    ///     kind (`str`): The kind of feature to look for.
+    ///
+    /// Returns:
+    ///     `bool`: `True` if the record has at least one feature of the
+    ///     given kind, `False` otherwise.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    fn has_feature(slf: PyRef<'_, Self>, kind: &str) -> PyResult<bool> {
+        let py = slf.py();
+        match &slf.features {
+            Coa::Owned(features) => Ok(features.iter().any(|f| f.kind.as_ref() == kind)),
+            Coa::Shared(features) => {
+                for object in features.bind(py).iter() {
+                    if object.downcast::<Feature>()?.borrow().has_kind(py, kind)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+        }
+    }
+
+    /// Count the features of the record.
+    ///
+    /// Arguments:
+    ///     kind (`str`, optional): The kind of features to count, or
+    ///         `None` to count features of any kind.
+    ///
+    /// Returns:
+    ///     `int`: The number of features of the given kind in the record.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyo3(signature = (kind = None))]
+    fn count_features(slf: PyRef<'_, Self>, kind: Option<&str>) -> PyResult<usize> {
+        let py = slf.py();
+        match (&slf.features, kind) {
+            (Coa::Owned(features), None) => Ok(features.len()),
+            (Coa::Owned(features), Some(kind)) => {
+                Ok(features.iter().filter(|f| f.kind.as_ref() == kind).count())
+            }
+            (Coa::Shared(features), None) => Ok(features.bind(py).len()),
+            (Coa::Shared(features), Some(kind)) => {
+                let mut count = 0;
+                for object in features.bind(py).iter() {
+                    if object.downcast::<Feature>()?.borrow().has_kind(py, kind)? {
+                        count += 1;
+                    }
+                }
+                Ok(count)
+            }
+        }
+    }
+
+    /// Get the features of the record of a given kind.
+    ///
+    /// Only the kind of the features is compared, so the qualifiers of
+    /// features parsed from a file are not converted to Python objects.
+    ///
+    /// Arguments:
+    ///     kind (`str`): The kind of features to retain, e.g. ``"CDS"``.
+    ///
+    /// Returns:
+    ///     `list` of `Feature`: The features of the given kind, in the
+    ///     order they appear in the record.
+    ///
+    /// Example:
+    ///     >>> record = gb_io.Record(b"ATGCATGC")
+    ///     >>> record.features.append(gb_io.Feature("gene", gb_io.Range(0, 8)))
+    ///     >>> record.features.append(gb_io.Feature("CDS", gb_io.Range(0, 6)))
+    ///     >>> record.features_of_kind("CDS")
+    ///     [Feature(kind='CDS', location=Range(0, 6))]
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    fn features_of_kind(mut slf: PyRefMut<'_, Self>, kind: &str) -> PyResult<Py<PyList>> {
+        let py = slf.py();
+        let features = slf.features.to_shared(py)?;
+        let interned = PyString::intern_bound(py, kind);
+        let selected = PyList::empty_bound(py);
+        for object in features.bind(py).iter() {
+            let feature = object.downcast::<Feature>()?.borrow();
+            let matches = match &feature.kind {
+                Coa::Owned(k) => k.as_ref() == kind,
+                // parsed kinds are interned, so most matches are the same object
+                Coa::Shared(k) => k.bind(py).is(&interned) || feature.has_kind(py, kind)?,
+            };
+            if matches {
+                selected.append(&object)?;
+            }
+        }
+        Ok(selected.unbind())
+    }
+
+    /// Rename the kind of all the features of a given kind.
+    ///
+    /// Features are updated in place, so the change is visible from any
+    /// reference to the features of the record. The new kind is interned
+    /// like the kinds of parsed features.
+    ///
+    /// Arguments:
+    ///     old (`str`): The kind of features to rename, e.g. ``"mRNA"``.
+    ///     new (`str`): The new kind of the features, e.g. ``"transcript"``.
+    ///
+    /// Returns:
+    ///     `int`: The number of renamed features.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    fn rename_features_kind(mut slf: PyRefMut<'_, Self>, old: &str, new: &str) -> PyResult<usize> {
+        let py = slf.py();
+        let mut count = 0;
+        match &mut slf.features {
+            Coa::Owned(features) => {
+                for feature in features.iter_mut().filter(|f| f.kind.as_ref() == old) {
+                    feature.kind = gb_io::FeatureKind::from(new);
+                    count += 1;
+                }
+            }
+            Coa::Shared(features) => {
+                let kind = PyString::intern_bound(py, new).unbind();
+                for object in features.bind(py).iter() {
+                    let mut feature = object.downcast::<Feature>()?.borrow_mut();
+                    if feature.has_kind(py, old)? {
+                        feature.kind = Coa::Shared(kind.clone_ref(py));
+                        count += 1;
+                    }
+                }
+            }
+        }
+        Ok(count)
+    }
+
+    /// Check whether the record contains a coordinate or a feature.
+    ///
+    /// The behaviour depends on the type of the argument:
+    ///
+    /// - An `int` is treated as a zero-based coordinate, and is contained
+    ///   in the record if it lies within the bounds of the sequence. On
+    ///   circular records, coordinates wrap around, so any coordinate is
+    ///   contained in a record with a non-empty sequence. A `bool` is not
+    ///   accepted as a coordinate.
+    /// - A `Feature` is contained in the record if it is one of the
+    ///   features of `Record.features`, compared by identity: an equal
+    ///   feature which is not part of the record is not contained.
+    /// - A `str` is treated as a feature kind, as with `Record.has_feature`.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    fn __contains__(slf: Bound<'_, Self>, item: Bound<'_, PyAny>) -> PyResult<bool> {
+        let py = slf.py();
+        if item.is_instance_of::<PyBool>() {
+            Err(PyTypeError::new_err(
+                "expected int, str or Feature, found bool",
+            ))
+        } else if let Ok(position) = item.extract::<i64>() {
+            let slf = slf.borrow();
+            let length = match (&slf.length, &slf.sequence) {
+                (Some(length), _) => *length as i64,
+                (None, Coa::Owned(sequence)) => sequence.len() as i64,
+                (None, Coa::Shared(sequence)) => sequence.bind(py).len() as i64,
+            };
+            Ok(match slf.topology {
+                Topology::Circular => length > 0,
+                Topology::Linear => (0..length).contains(&position),
+            })
+        } else if item.is_instance_of::<Feature>() {
+            let features = slf.borrow_mut().features.to_shared(py)?;
+            let contains = features.bind(py).iter().any(|feature| feature.is(&item));
+            Ok(contains)
+        } else if let Ok(kind) = item.downcast::<PyString>() {
+            Record::has_feature(slf.borrow(), kind.to_str()?)
+        } else {
+            Err(PyTypeError::new_err(format!(
+                "expected int, str or Feature, found {}",
+                item.get_type().name()?
+            )))
+        }
+    }
+
+    /// Get the length of the record sequence.
+    ///
+    /// Records without a sequence, such as CON records which are built
+    /// from other records, report the length declared in their ``LOCUS``
+    /// line instead, if any.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    fn __len__(slf: PyRef<'_, Self>) -> usize {
+        slf.sequence_length(slf.py())
+    }
+
+    fn __bool__(&self) -> bool {
+        // records are always truthy, even without a sequence
+        true
+    }
+
+    /// Get a key identifying the record, for deduplication.
+    ///
+    /// The key combines the accession and version of the record with a
+    /// checksum of its sequence, computed after converting the sequence
+    /// to uppercase. Records with the same key can be considered to be
+    /// copies of the same sequence entry. Features, references and other
+    /// metadata are ignored.
+    ///
+    /// Arguments:
+    ///     checksum (`str`): The checksum algorithm to use, either
+    ///         ``"crc64"`` (as used by SwissProt) or ``"crc32"``.
+    ///
+    /// Returns:
+    ///     `str`: The identity key of the record, formatted as
+    ///     ``{accession}.{version}:{checksum}:{digest}``. The accession
+    ///     is taken from `Record.version` when available, and otherwise
+    ///     from `Record.accession` or `Record.name`.
+    ///
+    /// Raises:
+    ///     `ValueError`: When the checksum algorithm is not supported, or
+    ///         when the record has no version, accession or name, since
+    ///         its key would only depend on its sequence.
+    ///
+    /// Example:
+    ///     >>> record = gb_io.Record(b"acgtacgtacgt", version="X00001.1")
+    ///     >>> record.identity()
+    ///     'X00001.1:crc64:C4FBB762C4A87EBD'
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyo3(signature = (checksum = "crc64"))]
+    fn identity(slf: PyRef<'_, Self>, checksum: &str) -> PyResult<String> {
+        let py = slf.py();
+        let digest = |sequence: &[u8]| match checksum {
+            "crc64" => Ok(format!(
+                "{:016X}",
+                checksum::crc64(&sequence.to_ascii_uppercase())
+            )),
+            "crc32" => Ok(format!(
+                "{:08X}",
+                checksum::crc32(&sequence.to_ascii_uppercase())
+            )),
+            other => Err(PyValueError::new_err(format!(
+                "unsupported checksum: {:?}",
+                other
+            ))),
+        };
+        let digest = match &slf.sequence {
+            Coa::Owned(sequence) => digest(sequence)?,
+            Coa::Shared(sequence) => {
+                // SAFETY: the bytearray is not mutated while we hold the GIL.
+                digest(unsafe { sequence.bind(py).as_bytes() })?
+            }
+        };
+        let accession = Record::display_id(
+            slf.version.as_deref(),
+            slf.accession.as_deref(),
+            slf.name.as_deref(),
+        )
+        .filter(|accession| !accession.is_empty())
+        .ok_or_else(|| {
+            PyValueError::new_err("cannot get the identity of a record without an identifier")
+        })?;
+        Ok(format!("{}:{}:{}", accession, checksum, digest))
+    }
+
+    /// Compute the identity between the sequences of two records.
+    ///
+    /// The sequences are compared position by position, up to the length
+    /// of the shorter sequence, and symbols are compared regardless of
+    /// their case. This is not an alignment: an insertion or a deletion
+    /// shifts all the following positions, so this is only useful to
+    /// detect near-duplicates of sequences with the same coordinates.
+    ///
+    /// Arguments:
+    ///     other (`Record`): The record to compare to.
+    ///
+    /// Returns:
+    ///     `tuple` of `float` and `int`: The fraction of identical
+    ///     positions, between 0 and 1, and the number of positions
+    ///     compared. The fraction is 0 when either sequence is empty.
+    ///
+    /// Example:
+    ///     >>> r1 = gb_io.Record(b"ATGCATGC")
+    ///     >>> r2 = gb_io.Record(b"atgaATGCAA")
+    ///     >>> r1.identity_to(r2)
+    ///     (0.875, 8)
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    fn identity_to(slf: PyRef<'_, Self>, other: PyRef<'_, Record>) -> (f64, usize) {
+        let py = slf.py();
+        // SAFETY: the bytearrays are not mutated while we hold the GIL.
+        let s1 = match &slf.sequence {
+            Coa::Owned(sequence) => sequence.as_slice(),
+            Coa::Shared(sequence) => unsafe { sequence.bind(py).as_bytes() },
+        };
+        let s2 = match &other.sequence {
+            Coa::Owned(sequence) => sequence.as_slice(),
+            Coa::Shared(sequence) => unsafe { sequence.bind(py).as_bytes() },
+        };
+        let length = s1.len().min(s2.len());
+        if length == 0 {
+            return (0.0, 0);
+        }
+        let matches = s1
+            .iter()
+            .zip(s2)
+            .filter(|(x, y)| x.eq_ignore_ascii_case(y))
+            .count();
+        (matches as f64 / length as f64, length)
+    }
+
+    /// Compute the GC content of the record sequence.
+    ///
+    /// Returns:
+    ///     `float`: The fraction of ``G`` and ``C`` bases in the sequence,
+    ///     regardless of their case, between 0 and 1. Ambiguity codes such
+    ///     as ``N`` or ``S`` count as other bases. The GC content of an
+    ///     empty sequence is 0.
+    ///
+    /// Example:
+    ///     >>> gb_io.Record(b"ATGCgcNN").gc_content()
+    ///     0.5
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    fn gc_content(slf: PyRef<'_, Self>) -> f64 {
+        let py = slf.py();
+        // SAFETY: the bytearray is not mutated while we hold the GIL.
+        let sequence = match &slf.sequence {
+            Coa::Owned(sequence) => sequence.as_slice(),
+            Coa::Shared(sequence) => unsafe { sequence.bind(py).as_bytes() },
+        };
+        if sequence.is_empty() {
+            return 0.0;
+        }
+        let gc = sequence
+            .iter()
+            .filter(|b| matches!(b.to_ascii_uppercase(), b'G' | b'C'))
+            .count();
+        gc as f64 / sequence.len() as f64
+    }
+
+    /// Count the occurrences of each base in the record sequence.
+    ///
+    /// Returns:
+    ///     `dict` of `str` to `int`: The number of occurrences of each
+    ///     base, regardless of their case. The ``A``, ``C``, ``G`` and
+    ///     ``T`` keys are always present, followed by the other symbols
+    ///     of the sequence, such as ambiguity codes, in sorted order.
+    ///
+    /// Example:
+    ///     >>> gb_io.Record(b"ATGCgcNNr").base_counts()
+    ///     {'A': 1, 'C': 2, 'G': 2, 'T': 1, 'N': 2, 'R': 1}
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    fn base_counts<'py>(slf: PyRef<'py, Self>) -> PyResult<Bound<'py, PyDict>> {
+        let py = slf.py();
+        let mut counts = [0usize; 256];
+        // SAFETY: the bytearray is not mutated while we hold the GIL.
+        let sequence = match &slf.sequence {
+            Coa::Owned(sequence) => sequence.as_slice(),
+            Coa::Shared(sequence) => unsafe { sequence.bind(py).as_bytes() },
+        };
+        for b in sequence {
+            counts[b.to_ascii_uppercase() as usize] += 1;
+        }
+        let dict = PyDict::new_bound(py);
+        for b in b"ACGT" {
+            dict.set_item(char::from(*b), counts[*b as usize])?;
+        }
+        for (b, &count) in counts.iter().enumerate() {
+            if count > 0 && !b"ACGT".contains(&(b as u8)) {
+                dict.set_item(char::from(b as u8), count)?;
+            }
+        }
+        Ok(dict)
+    }
+
+    /// Compare the record to another record, ignoring some differences.
+    ///
+    /// Arguments:
+    ///     other (`Record`): The record to compare to.
+    ///     ignore_order (`bool`): Pass `True` to compare the features of
+    ///         both records as multisets, and the qualifiers of each
+    ///         feature regardless of their order.
+    ///     ignore (iterable of `str`): The names of the `Record` attributes
+    ///         to exclude from the comparison, e.g. ``{"date"}``.
+    ///
+    /// Returns:
+    ///     `bool`: `True` if both records are equal, `False` otherwise.
+    ///
+    /// Raises:
+    ///     `ValueError`: When ``ignore`` contains an unknown attribute name.
+    ///
+    /// Hint:
+    ///     Records can also be compared with the ``==`` operator, which
+    ///     compares all their attributes, including the features in order.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyo3(signature = (other, *, ignore_order = false, ignore = None))]
+    fn equals(
+        slf: Bound<'_, Self>,
+        other: Bound<'_, Record>,
+        ignore_order: bool,
+        ignore: Option<Bound<'_, PyAny>>,
+    ) -> PyResult<bool> {
+        let py = slf.py();
+        let mut a: gb_io::seq::Seq = Extract::extract(py, slf.unbind())?;
+        let mut b: gb_io::seq::Seq = Extract::extract(py, other.unbind())?;
+        if let Some(fields) = ignore {
+            for field in fields.iter()? {
+                let field = field?;
+                let field = field.downcast::<PyString>()?.to_str()?;
+                for seq in [&mut a, &mut b] {
+                    Record::clear_field(seq, field)?;
+                }
+            }
+        }
+        if ignore_order {
+            for seq in [&mut a, &mut b] {
+                Record::sort_features(&mut seq.features);
+            }
+        }
+        Ok(a == b)
+    }
+
+    /// Get the GenBank representation of the record.
+    ///
+    /// The record is formatted exactly as it would be written by
+    /// `gb_io.dump` with the same arguments.
+    ///
+    /// Arguments:
+    ///     escape_locus (`bool`): Pass `True` to escape any whitespace in
+    ///         the locus name with an underscore character.
+    ///     truncate_locus (`bool`): Pass `True` to trim the locus fields
+    ///          so that the locus line is no longer than 79 characters.
+    ///     wrap_width (`int`): The maximum width of the lines of wrapped
+    ///         fields, locations and qualifiers, of at least 40 characters.
+    ///
+    /// Returns:
+    ///     `str`: The text of the record in GenBank format, from its
+    ///     ``LOCUS`` line to its ``//`` terminator.
+    ///
+    /// Raises:
+    ///     `ValueError`: When a feature of the record cannot be written,
+    ///         or when ``wrap_width`` is too small.
+    ///
+    /// Example:
+    ///     Get the GenBank text of a short record::
+    ///
+    ///         >>> record = gb_io.Record(b"ATGC", name="TEST")
+    ///         >>> text = record.to_genbank()
+    ///         >>> text.splitlines()[0]
+    ///         'LOCUS       TEST                       4 bp            linear UNK 01-JAN-1970'
+    ///         >>> text == gb_io.dump(record).read().decode()
+    ///         True
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyo3(signature = (*, escape_locus = false, truncate_locus = false, wrap_width = writer::MAX_WIDTH))]
+    fn to_genbank(
+        slf: Bound<'_, Self>,
+        escape_locus: bool,
+        truncate_locus: bool,
+        wrap_width: usize,
+    ) -> PyResult<String> {
+        let py = slf.py();
+        let records = PyIterator::from_bound_object(&PyTuple::new_bound(py, [slf]))?;
+        write_string(
+            py,
+            records,
+            escape_locus,
+            truncate_locus,
+            wrap_width,
+            Format::GenBank,
+            writer::SEQUENCE_WIDTH,
+            false,
+        )
+    }
+
+    /// Get the FASTA representation of the record.
+    ///
+    /// The header contains the primary accession of the record, or its
+    /// name when it has no accession, followed by the definition of the
+    /// record, if any.
+    ///
+    /// Arguments:
+    ///     line_width (`int`): The maximum number of sequence characters
+    ///         on each line. Pass ``0`` to write the sequence on a single
+    ///         line.
+    ///
+    /// Returns:
+    ///     `str`: The record in FASTA format, with the sequence in
+    ///     uppercase and a trailing newline.
+    ///
+    /// Example:
+    ///     >>> record = gb_io.Record(b"acgt" * 5, accession="X56734", definition="Test.")
+    ///     >>> print(record.to_fasta(line_width=8), end="")
+    ///     >X56734 Test.
+    ///     ACGTACGT
+    ///     ACGTACGT
+    ///     ACGT
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyo3(signature = (*, line_width = 70))]
+    fn to_fasta(slf: PyRef<'_, Self>, line_width: usize) -> String {
+        let py = slf.py();
+        let sequence = match &slf.sequence {
+            Coa::Owned(sequence) => sequence.to_ascii_uppercase(),
+            Coa::Shared(sequence) => {
+                // SAFETY: the bytearray is not mutated while we hold the GIL.
+                unsafe { sequence.bind(py).as_bytes() }.to_ascii_uppercase()
+            }
+        };
+
+        let id = slf
+            .accession
+            .as_deref()
+            .and_then(|accession| accession.split_whitespace().next())
+            .or(slf.name.as_deref());
+        let definition = slf
+            .definition
+            .as_deref()
+            .map(|definition| definition.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|definition| !definition.is_empty());
+        let mut fasta = String::with_capacity(sequence.len() + sequence.len() / 60 + 80);
+        fasta.push('>');
+        match (id, definition) {
+            (Some(id), Some(definition)) => {
+                fasta.push_str(id);
+                fasta.push(' ');
+                fasta.push_str(&definition);
+            }
+            (Some(id), None) => fasta.push_str(id),
+            (None, Some(definition)) => fasta.push_str(&definition),
+            (None, None) => (),
+        }
+        fasta.push('\n');
+
+        let width = if line_width == 0 {
+            sequence.len().max(1)
+        } else {
+            line_width
+        };
+        for line in sequence.chunks(width) {
+            fasta.push_str(&String::from_utf8_lossy(line));
+            fasta.push('\n');
+        }
+        fasta
+    }
+
+    fn __richcmp__(
+        slf: Bound<'_, Self>,
+        other: Bound<'_, PyAny>,
+        op: CompareOp,
+    ) -> PyResult<PyObject> {
+        let py = slf.py();
+        let other = match other.extract::<Py<Record>>() {
+            Ok(other) if matches!(op, CompareOp::Eq | CompareOp::Ne) => other,
+            _ => return Ok(py.NotImplemented()),
+        };
+        let equal = slf.is(&other)
+            || RecordExtras::from(&*slf.borrow()) == RecordExtras::from(&*other.borrow(py))
+                && gb_io::seq::Seq::extract(py, slf.unbind())?
+                    == gb_io::seq::Seq::extract(py, other)?;
+        match op {
+            CompareOp::Eq => Ok(equal.into_py(py)),
+            _ => Ok((!equal).into_py(py)),
+        }
+    }
+
+    fn __traverse__(&self, visit: PyVisit<'_>) -> Result<(), PyTraverseError> {
+        // an attached source references its record, forming a cycle
+        if let Some(Coa::Shared(source)) = &self.source {
+            visit.call(source)?;
+        }
+        Ok(())
+    }
+
+    fn __copy__(slf: Bound<'_, Self>) -> PyResult<Self> {
+        // share the Python objects of the record with the copy, so that the
+        // copy is shallow whether or not the attributes were accessed before
+        let py = slf.py();
+        Record::get_source(slf.clone())?;
+        let mut record = slf.borrow_mut();
+        let record = record.deref_mut();
+        record.sequence.to_shared(py)?;
+        record.references.to_shared(py)?;
+        record.features.to_shared(py)?;
+        Ok(record.clone())
+    }
+
+    fn __deepcopy__(slf: Bound<'_, Self>, _memo: Bound<'_, PyAny>) -> PyResult<Self> {
+        let py = slf.py();
+        let extras = RecordExtras::from(&*slf.borrow());
+        let quoting = slf.borrow().quoting(py)?;
+        let seq: gb_io::seq::Seq = Extract::extract(py, slf.unbind())?;
+        let mut record = Record::from(seq);
+        extras.apply(&mut record);
+        record.set_quoting(py, &quoting)?;
+        Ok(record)
+    }
+
+    fn __getnewargs__(mut slf: PyRefMut<'_, Self>) -> PyResult<(Py<PyByteArray>,)> {
+        let py = slf.py();
+        Ok((slf.sequence.to_shared(py)?,))
+    }
+
+    fn __getstate__(slf: Bound<'_, Self>) -> PyResult<Bound<'_, PyDict>> {
+        let py = slf.py();
+        let state = PyDict::new_bound(py);
+        // the source is obtained through the getter to lift its qualifiers
+        state.set_item("source", Record::get_source(slf.clone())?)?;
+        let mut record = slf.borrow_mut();
+        let record = record.deref_mut();
+        state.set_item("name", &record.name)?;
+        state.set_item("length", record.length)?;
+        state.set_item("molecule_type", &record.molecule_type)?;
+        state.set_item("division", &record.division)?;
+        state.set_item("definition", &record.definition)?;
+        state.set_item("accession", &record.accession)?;
+        state.set_item("version", &record.version)?;
+        state.set_item("dblink", &record.dblink)?;
+        state.set_item("keywords", &record.keywords)?;
+        state.set_item("origin_note", &record.origin_note)?;
+        state.set_item("wgs", &record.wgs)?;
+        state.set_item("wgs_scafld", &record.wgs_scafld)?;
+        state.set_item("primary", &record.primary)?;
+        state.set_item("circular", matches!(record.topology, Topology::Circular))?;
+        // dates are stored as tuples so that invalid dates are preserved
+        let date = match &record.date {
+            Some(Coa::Owned(date)) => Some(date.clone()),
+            Some(Coa::Shared(date)) => Some(Extract::extract(py, date.clone_ref(py))?),
+            None => None,
+        };
+        state.set_item("date", date.map(|d| (d.year(), d.month(), d.day())))?;
+        state.set_item("comments", &record.comments)?;
+        // contigs may contain gaps, which have no Python class, so they
+        // are stored in GenBank format
+        let contig = match &record.contig {
+            Some(Coa::Owned(contig)) => Some(contig.to_gb_format()),
+            Some(Coa::Shared(contig)) => {
+                let contig: gb_io::seq::Location = Extract::extract(py, contig.clone_ref(py))?;
+                Some(contig.to_gb_format())
+            }
+            None => None,
+        };
+        state.set_item("contig", contig)?;
+        state.set_item("references", record.references.to_shared(py)?)?;
+        state.set_item("features", record.features.to_shared(py)?)?;
+        Ok(state)
+    }
+
+    fn __setstate__(slf: Bound<'_, Self>, state: Bound<'_, PyDict>) -> PyResult<()> {
+        let item = |key: &str| -> PyResult<Bound<'_, PyAny>> {
+            state
+                .get_item(key)?
+                .ok_or_else(|| PyValueError::new_err(format!("missing record state: {:?}", key)))
+        };
+        {
+            let mut slf = slf.borrow_mut();
+            slf.name = item("name")?.extract()?;
+            slf.length = item("length")?.extract()?;
+            slf.molecule_type = item("molecule_type")?.extract()?;
+            slf.division = item("division")?.extract()?;
+            slf.definition = item("definition")?.extract()?;
+            slf.accession = item("accession")?.extract()?;
+            slf.version = item("version")?.extract()?;
+            slf.dblink = item("dblink")?.extract()?;
+            slf.keywords = item("keywords")?.extract()?;
+            slf.origin_note = item("origin_note")?.extract()?;
+            slf.wgs = item("wgs")?.extract()?;
+            slf.wgs_scafld = item("wgs_scafld")?.extract()?;
+            slf.primary = item("primary")?.extract()?;
+            slf.topology = if item("circular")?.extract()? {
+                Topology::Circular
+            } else {
+                Topology::Linear
+            };
+            slf.date = match item("date")?.extract::<Option<(i32, u32, u32)>>()? {
+                Some((year, month, day)) => Some(Coa::Owned(
+                    gb_io::seq::Date::from_ymd(year, month, day)
+                        .map_err(|_| PyValueError::new_err("invalid record date"))?,
+                )),
+                None => None,
+            };
+            slf.comments = item("comments")?.extract()?;
+            slf.contig = match item("contig")?.extract::<Option<String>>()? {
+                Some(text) => Some(Coa::Owned(
+                    gb_io::seq::Location::from_gb_format(&text)
+                        .map_err(|_| PyValueError::new_err("invalid record contig"))?,
+                )),
+                None => None,
+            };
+            slf.references = Coa::Shared(item("references")?.extract()?);
+            slf.features = Coa::Shared(item("features")?.extract()?);
+        }
+        // the source is attached once the features are restored
+        Record::set_source(slf, item("source")?.extract()?)
+    }
+}
+
+impl Record {
+    /// Get the explicit quoting of the qualifiers of the record features.
+    ///
+    /// The quoting is keyed by the index of the feature and the index of
+    /// the qualifier, and tells whether the qualifier value is quoted.
+    fn quoting(&self, py: Python) -> PyResult<HashMap<(usize, usize), bool>> {
+        let mut quoting = HashMap::new();
+        if let Coa::Shared(features) = &self.features {
+            for (f, feature) in features.bind(py).iter().enumerate() {
+                let feature = feature.downcast::<Feature>()?.borrow();
+                quoting.extend(feature.quoting(py)?.into_iter().map(|(q, b)| ((f, q), b)));
+            }
+        }
+        Ok(quoting)
+    }
+
+    /// Set the explicit quoting of the qualifiers of the record features.
+    fn set_quoting(&mut self, py: Python, quoting: &HashMap<(usize, usize), bool>) -> PyResult<()> {
+        if !quoting.is_empty() {
+            let features = self.features.to_shared(py)?;
+            for (&(f, q), &quoted) in quoting {
+                let feature = features.bind(py).get_item(f)?;
+                feature
+                    .downcast::<Feature>()?
+                    .borrow_mut()
+                    .set_quoting(py, &[(q, quoted)])?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Extract the sequence covered by a location, wrapping circular records.
+    fn extract_location(&self, py: Python, mut location: SeqLocation) -> PyResult<Vec<u8>> {
+        let sequence = match &self.sequence {
+            Coa::Owned(sequence) => sequence.as_slice(),
+            // SAFETY: the bytearray is not mutated while we hold the GIL.
+            Coa::Shared(sequence) => unsafe { sequence.bind(py).as_bytes() },
+        };
+
+        let length = sequence.len() as i64;
+        if matches!(self.topology, Topology::Circular) && length > 0 {
+            location = location::wrap(&location, length);
+        }
+        if let Some((start, end)) = location::span(&location) {
+            if start < 0 || end > length {
+                return Err(PyIndexError::new_err(format!(
+                    "location out of bounds: {}",
+                    location
+                )));
+            }
+        }
+
+        location::extract(&location, sequence).map_err(PyValueError::new_err)
+    }
+
+    /// Get the identifier of a record from its version, accession or name.
+    fn display_id<'a>(
+        version: Option<&'a str>,
+        accession: Option<&'a str>,
+        name: Option<&'a str>,
+    ) -> Option<&'a str> {
+        version
+            .map(|version| writer::split_version(version).0)
+            .or_else(|| accession.and_then(|a| a.split_whitespace().next()))
+            .or(name)
+    }
+
+    /// Reset the field of a record matching the given attribute name.
+    fn clear_field(seq: &mut gb_io::seq::Seq, field: &str) -> PyResult<()> {
+        match field {
+            "name" => seq.name = None,
+            "length" => seq.len = None,
+            "molecule_type" => seq.molecule_type = None,
+            "division" => seq.division.clear(),
+            "definition" => seq.definition = None,
+            "accession" => seq.accession = None,
+            "version" => seq.version = None,
+            "dblink" => seq.dblink = None,
+            "keywords" => seq.keywords = None,
+            "circular" => seq.topology = Topology::Linear,
+            "date" => seq.date = None,
+            "source" => seq.source = None,
+            "contig" => seq.contig = None,
+            "sequence" => seq.seq.clear(),
+            "references" => seq.references.clear(),
+            "features" => seq.features.clear(),
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown record attribute: {:?}",
+                    other
+                )))
+            }
+        }
+        Ok(())
+    }
+
+    /// Sort features and their qualifiers into a canonical order.
+    fn sort_features(features: &mut [gb_io::seq::Feature]) {
+        for feature in features.iter_mut() {
+            feature
+                .qualifiers
+                .sort_by(|(k1, v1), (k2, v2)| (k1.as_ref(), v1).cmp(&(k2.as_ref(), v2)));
+        }
+        features.sort_by_cached_key(|feature| {
+            (
+                feature.kind.to_string(),
+                feature.location.to_gb_format(),
+                feature
+                    .qualifiers
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.clone()))
+                    .collect::<Vec<_>>(),
+            )
+        });
+    }
+
+    /// Get the length of the sequence, or the declared length if empty.
+    fn sequence_length(&self, py: Python) -> usize {
+        let length = match &self.sequence {
+            Coa::Owned(sequence) => sequence.len(),
+            Coa::Shared(sequence) => sequence.bind(py).len(),
+        };
+        match self.length {
+            Some(declared) if length == 0 => declared,
+            _ => length,
+        }
+    }
+
+    /// Set or remove a qualifier of the first ``source`` feature.
+    ///
+    /// A ``source`` feature spanning the whole record is created if the
+    /// record has none and a value is given.
+    fn set_source_qualifier(
+        &mut self,
+        py: Python,
+        key: &str,
+        value: Option<String>,
+    ) -> PyResult<()> {
+        let length = self.sequence_length(py) as i64;
+        let new_feature = |value: String| gb_io::seq::Feature {
+            kind: gb_io::FeatureKind::from("source"),
+            location: gb_io::seq::Location::simple_range(0, length),
+            qualifiers: vec![(gb_io::QualifierKey::from(key), Some(value))],
+        };
+        match &mut self.features {
+            Coa::Owned(features) => {
+                match features.iter_mut().find(|f| f.kind.as_ref() == "source") {
+                    Some(feature) => {
+                        let qualifiers = &mut feature.qualifiers;
+                        match value {
+                            Some(value) => {
+                                match qualifiers.iter_mut().find(|(k, _)| k.as_ref() == key) {
+                                    Some((_, v)) => *v = Some(value),
+                                    None => qualifiers
+                                        .push((gb_io::QualifierKey::from(key), Some(value))),
+                                }
+                            }
+                            None => qualifiers.retain(|(k, _)| k.as_ref() != key),
+                        }
+                    }
+                    None => {
+                        if let Some(value) = value {
+                            features.insert(0, new_feature(value));
+                        }
+                    }
+                }
+                Ok(())
+            }
+            Coa::Shared(features) => {
+                let list = features.bind(py);
+                for object in list.iter() {
+                    let mut feature = object.downcast::<Feature>()?.borrow_mut();
+                    if feature.has_kind(py, "source")? {
+                        return match value {
+                            Some(value) => feature.set_qualifier_value(py, key, value),
+                            None => feature.remove_qualifiers(py, key),
+                        };
+                    }
+                }
+                if let Some(value) = value {
+                    list.insert(0, new_feature(value).convert(py)?)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Get the lifted qualifiers of the first ``source`` feature, if any.
+    fn source_qualifiers(&self, py: Python) -> PyResult<[Option<String>; 5]> {
+        let mut values: [Option<String>; 5] = Default::default();
+        match &self.features {
+            Coa::Owned(features) => {
+                if let Some(feature) = features.iter().find(|f| f.kind.as_ref() == "source") {
+                    for (value, key) in values.iter_mut().zip(Source::QUALIFIERS) {
+                        *value = feature
+                            .qualifier_values(key.into())
+                            .next()
+                            .map(String::from);
+                    }
+                }
+            }
+            Coa::Shared(features) => {
+                for object in features.bind(py).iter() {
+                    let feature = object.downcast::<Feature>()?.borrow();
+                    if feature.has_kind(py, "source")? {
+                        for (value, key) in values.iter_mut().zip(Source::QUALIFIERS) {
+                            *value = feature.qualifier_value(py, key)?;
+                        }
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(values)
+    }
+}
+
+impl Convert for gb_io::seq::Seq {
+    type Output = Record;
+    fn convert_with(self, py: Python, _interner: &mut PyInterner) -> PyResult<Py<Self::Output>> {
+        Py::new(py, Record::from_parsed(py, self)?)
+    }
+}
+
+impl Record {
+    /// Create a record from a parsed sequence, warning about invalid dates.
+    fn from_parsed(py: Python, seq: gb_io::seq::Seq) -> PyResult<Self> {
+        if let Some(date) = seq.date.as_ref().filter(|date| !is_valid_date(date)) {
+            let msg = format!(
+                "invalid date in record {}: {}",
+                seq.name.as_deref().unwrap_or("<unnamed>"),
+                date
+            );
+            PyErr::warn_bound(py, &py.get_type_bound::<PyUserWarning>(), &msg, 1)?;
+        }
+        Ok(Record::from(seq))
+    }
+}
+
+impl From<gb_io::seq::Seq> for Record {
+    fn from(seq: gb_io::seq::Seq) -> Self {
+        Record {
+            name: seq.name,
+            topology: seq.topology,
+            date: seq.date.map(Coa::Owned),
+            length: seq.len,
+            molecule_type: seq.molecule_type,
+            division: seq.division,
+            definition: seq.definition,
+            accession: seq.accession,
+            version: seq.version,
+            source: seq.source.map(Coa::Owned),
+            dblink: seq.dblink,
+            keywords: seq.keywords,
+            origin_note: None,
+            wgs: Vec::new(),
+            wgs_scafld: Vec::new(),
+            primary: Vec::new(),
+            references: seq.references.into(),
+            comments: seq.comments,
+            sequence: Coa::Owned(seq.seq),
+            contig: seq.contig.map(Coa::Owned),
+            features: seq.features.into(),
+        }
+    }
+}
+
+impl Extract for gb_io::seq::Seq {
+    fn extract(py: Python, object: Py<<Self as Convert>::Output>) -> PyResult<Self> {
+        let record = object.bind(py).borrow();
+        let mut seq = gb_io::seq::Seq {
+            name: record.name.clone(),
+            topology: record.topology.clone(),
+            len: record.length,
+            molecule_type: record.molecule_type.clone(),
+            division: record.division.clone(),
+            definition: record.definition.clone(),
+            accession: record.accession.clone(),
+            version: record.version.clone(),
+            dblink: record.dblink.clone(),
+            keywords: record.keywords.clone(),
+            comments: record.comments.clone(),
+            seq: record.sequence.to_owned_native(py)?,
             references: record.references.to_owned_native(py)?,
             features: record.features.to_owned_native(py)?,
             date: record
@@ -331,40 +2630,292 @@ impl Extract for gb_io::seq::Seq {
                 .as_ref()
                 .map(|contig| contig.to_owned_class(py))
                 .transpose()?,
+        };
+        if let Some(Coa::Shared(source)) = &record.source {
+            source.bind(py).borrow().write_qualifiers(&mut seq);
+        }
+        Ok(seq)
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+/// The source of a GenBank record.
+///
+/// Besides the ``SOURCE`` and ``ORGANISM`` lines, a `Source` exposes the
+/// qualifiers of the ``source`` feature that describe the record as a
+/// whole: ``/mol_type``, ``/organelle``, ``/plasmid``, ``/chromosome``
+/// and ``/segment``. Once obtained from or assigned to `Record.source`,
+/// these attributes are views over the first ``source`` feature of the
+/// record: reading them gets the first qualifier with the same key,
+/// setting them replaces that qualifier (creating a ``source`` feature
+/// over the whole record if needed), and setting them to `None` removes
+/// all the qualifiers with that key. All other qualifiers are only
+/// available from the `Feature.qualifiers` of the source feature.
+///
+/// A source views the features of a single record: assigning a source
+/// already attached to another record assigns a copy of it instead. A
+/// source replaced in or removed from its record keeps the values it was
+/// viewing, and no longer views the record features.
+///
+/// .. versionchanged:: 0.4.0
+///    Added the ``mol_type``, ``organelle``, ``plasmid``, ``chromosome``
+///    and ``segment`` attributes.
+///
+#[pyclass(module = "gb_io")]
+#[derive(Debug, Default)]
+pub struct Source {
+    /// `str`: The name of the source organism.
+    #[pyo3(get, set)]
+    name: String,
+    /// `str` or `None`: The scientific classification of the source organism.
+    #[pyo3(get, set)]
+    organism: Option<String>,
+    /// The lifted qualifiers of a source not attached to a record.
+    values: [Option<String>; 5],
+    /// The record whose source feature is viewed by this source.
+    record: Option<Py<Record>>,
+}
+
+impl Source {
+    /// The keys of the source feature qualifiers lifted to attributes.
+    const QUALIFIERS: [&'static str; 5] =
+        ["mol_type", "organelle", "plasmid", "chromosome", "segment"];
+
+    /// Attach a source to a record, moving its values to the source feature.
+    fn attach(source: &Bound<'_, Source>, record: &Bound<'_, Record>) -> PyResult<()> {
+        let py = source.py();
+        let mut source = source.borrow_mut();
+        if source.record.is_some() {
+            return Ok(());
+        }
+        let values = std::mem::take(&mut source.values);
+        let mut record_ref = record.borrow_mut();
+        for (key, value) in Source::QUALIFIERS.into_iter().zip(values) {
+            if value.is_some() {
+                record_ref.set_source_qualifier(py, key, value)?;
+            }
+        }
+        source.record = Some(record.clone().unbind());
+        Ok(())
+    }
+
+    /// Detach a source from a record, copying the values it was viewing.
+    ///
+    /// Sources attached to another record, or to no record, are left as is.
+    fn detach(source: &Bound<'_, Source>, record: &Bound<'_, Record>) -> PyResult<()> {
+        let py = source.py();
+        let mut source = source.borrow_mut();
+        if !source.record.as_ref().is_some_and(|r| r.is(record)) {
+            return Ok(());
+        }
+        source.values = record.borrow().source_qualifiers(py)?;
+        source.record = None;
+        Ok(())
+    }
+
+    /// Check whether the source is attached to a record other than `record`.
+    fn is_attached_elsewhere(&self, record: &Bound<'_, Record>) -> bool {
+        self.record.as_ref().is_some_and(|r| !r.is(record))
+    }
+
+    /// Create a copy of a source that is not attached to any record.
+    fn detached(source: &Bound<'_, Source>) -> PyResult<Self> {
+        let source = source.borrow();
+        Ok(Source {
+            name: source.name.clone(),
+            organism: source.organism.clone(),
+            values: source.values(source.py())?,
+            record: None,
+        })
+    }
+
+    /// Get the values of the lifted qualifiers.
+    fn values(&self, py: Python) -> PyResult<[Option<String>; 5]> {
+        match &self.record {
+            Some(record) => record.bind(py).borrow().source_qualifiers(py),
+            None => Ok(self.values.clone()),
+        }
+    }
+
+    fn get_value(&self, py: Python, index: usize) -> PyResult<Option<String>> {
+        Ok(std::mem::take(&mut self.values(py)?[index]))
+    }
+
+    fn set_value(&mut self, py: Python, index: usize, value: Option<String>) -> PyResult<()> {
+        match &self.record {
+            Some(record) => record.bind(py).borrow_mut().set_source_qualifier(
+                py,
+                Source::QUALIFIERS[index],
+                value,
+            ),
+            None => {
+                self.values[index] = value;
+                Ok(())
+            }
+        }
+    }
+
+    /// Write the lifted qualifiers to the first source feature of a record.
+    ///
+    /// Only sources not attached to a record hold values of their own,
+    /// the values of attached sources are already in the record features.
+    fn write_qualifiers(&self, seq: &mut gb_io::seq::Seq) {
+        if self.values.iter().all(Option::is_none) {
+            return;
+        }
+        let index = match seq
+            .features
+            .iter()
+            .position(|f| f.kind.as_ref() == "source")
+        {
+            Some(index) => index,
+            None => {
+                let end = seq.len();
+                seq.features.insert(
+                    0,
+                    gb_io::seq::Feature {
+                        kind: gb_io::FeatureKind::from("source"),
+                        location: gb_io::seq::Location::simple_range(0, end),
+                        qualifiers: Vec::new(),
+                    },
+                );
+                0
+            }
+        };
+        let qualifiers = &mut seq.features[index].qualifiers;
+        for (key, value) in Source::QUALIFIERS.into_iter().zip(&self.values) {
+            if let Some(value) = value {
+                match qualifiers.iter_mut().find(|(k, _)| k.as_ref() == key) {
+                    Some((_, v)) => *v = Some(value.clone()),
+                    None => qualifiers.push((gb_io::QualifierKey::from(key), Some(value.clone()))),
+                }
+            }
+        }
+    }
+}
+
+#[pymethods]
+impl Source {
+    #[new]
+    #[pyo3(signature = (
+        name,
+        organism = None,
+        *,
+        mol_type = None,
+        organelle = None,
+        plasmid = None,
+        chromosome = None,
+        segment = None,
+    ))]
+    fn __new__(
+        name: String,
+        organism: Option<String>,
+        mol_type: Option<String>,
+        organelle: Option<String>,
+        plasmid: Option<String>,
+        chromosome: Option<String>,
+        segment: Option<String>,
+    ) -> PyClassInitializer<Self> {
+        PyClassInitializer::from(Self {
+            name,
+            organism,
+            values: [mol_type, organelle, plasmid, chromosome, segment],
+            record: None,
         })
     }
-}
 
-// ---------------------------------------------------------------------------
+    fn __repr__<'py>(slf: PyRef<'py, Self>) -> PyResult<Bound<'py, PyAny>> {
+        let py = slf.py();
+        let mut template = String::from("Source({!r}");
+        let mut args = vec![slf.name.to_object(py)];
+        if let Some(v) = &slf.organism {
+            template.push_str(", {!r}");
+            args.push(v.to_object(py));
+        }
+        for (key, value) in Source::QUALIFIERS.into_iter().zip(slf.values(py)?) {
+            if let Some(v) = value {
+                template.push_str(&format!(", {}={{!r}}", key));
+                args.push(v.to_object(py));
+            }
+        }
+        template.push(')');
+        PyString::new_bound(py, &template).call_method1("format", PyTuple::new_bound(py, args))
+    }
+
+    fn __getnewargs_ex__(slf: PyRef<'_, Self>) -> PyResult<(PyObject, Bound<'_, PyDict>)> {
+        let py = slf.py();
+        let kwargs = PyDict::new_bound(py);
+        for (key, value) in Source::QUALIFIERS.into_iter().zip(slf.values(py)?) {
+            kwargs.set_item(key, value)?;
+        }
+        Ok(((slf.name.clone(), slf.organism.clone()).into_py(py), kwargs))
+    }
+
+    fn __traverse__(&self, visit: PyVisit<'_>) -> Result<(), PyTraverseError> {
+        if let Some(record) = &self.record {
+            visit.call(record)?;
+        }
+        Ok(())
+    }
+
+    fn __clear__(&mut self) {
+        self.record = None;
+    }
+
+    /// `str` or `None`: The in vivo molecule type of the sequence.
+    #[getter]
+    fn get_mol_type(&self, py: Python) -> PyResult<Option<String>> {
+        self.get_value(py, 0)
+    }
+
+    #[setter]
+    fn set_mol_type(&mut self, py: Python, mol_type: Option<String>) -> PyResult<()> {
+        self.set_value(py, 0, mol_type)
+    }
+
+    /// `str` or `None`: The organelle the sequence was obtained from.
+    #[getter]
+    fn get_organelle(&self, py: Python) -> PyResult<Option<String>> {
+        self.get_value(py, 1)
+    }
+
+    #[setter]
+    fn set_organelle(&mut self, py: Python, organelle: Option<String>) -> PyResult<()> {
+        self.set_value(py, 1, organelle)
+    }
+
+    /// `str` or `None`: The name of the plasmid the sequence belongs to.
+    #[getter]
+    fn get_plasmid(&self, py: Python) -> PyResult<Option<String>> {
+        self.get_value(py, 2)
+    }
+
+    #[setter]
+    fn set_plasmid(&mut self, py: Python, plasmid: Option<String>) -> PyResult<()> {
+        self.set_value(py, 2, plasmid)
+    }
+
+    /// `str` or `None`: The name of the chromosome the sequence belongs to.
+    #[getter]
+    fn get_chromosome(&self, py: Python) -> PyResult<Option<String>> {
+        self.get_value(py, 3)
+    }
 
-/// The source of a GenBank record.
-#[pyclass(module = "gb_io")]
-#[derive(Debug, Default)]
-pub struct Source {
-    /// `str`: The name of the source organism.
-    #[pyo3(get, set)]
-    name: String,
-    /// `str` or `None`: The scientific classification of the source organism.
-    #[pyo3(get, set)]
-    organism: Option<String>,
-}
+    #[setter]
+    fn set_chromosome(&mut self, py: Python, chromosome: Option<String>) -> PyResult<()> {
+        self.set_value(py, 3, chromosome)
+    }
 
-#[pymethods]
-impl Source {
-    #[new]
-    #[pyo3(signature = (name, organism = None))]
-    fn __new__(name: String, organism: Option<String>) -> PyClassInitializer<Self> {
-        PyClassInitializer::from(Self { name, organism })
+    /// `str` or `None`: The name of the viral or phage segment.
+    #[getter]
+    fn get_segment(&self, py: Python) -> PyResult<Option<String>> {
+        self.get_value(py, 4)
     }
 
-    fn __repr__<'py>(slf: PyRef<'py, Self>) -> PyResult<Bound<'py, PyAny>> {
-        let py = slf.py();
-        let name = &slf.name;
-        if let Some(v) = &slf.organism {
-            PyString::new_bound(py, "Source({!r}, {!r})").call_method1("format", (name, v))
-        } else {
-            PyString::new_bound(py, "Source({!r})").call_method1("format", (name,))
-        }
+    #[setter]
+    fn set_segment(&mut self, py: Python, segment: Option<String>) -> PyResult<()> {
+        self.set_value(py, 4, segment)
     }
 }
 
@@ -385,6 +2936,7 @@ impl Convert for gb_io::seq::Source {
             Source {
                 name: self.source,
                 organism: self.organism,
+                ..Default::default()
             },
         )
     }
@@ -402,13 +2954,25 @@ impl Extract for gb_io::seq::Source {
 
 // ---------------------------------------------------------------------------
 
+/// Check whether a date parsed by `gb_io` is an actual calendar date.
+///
+/// The `gb_io` parser only checks that the day is between 1 and 31, so
+/// dates such as ``31-FEB-2020`` can be read but not converted to Python.
+fn is_valid_date(date: &gb_io::seq::Date) -> bool {
+    let leap = (date.year() % 4 == 0 && date.year() % 100 != 0) || date.year() % 400 == 0;
+    let days = match date.month() {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+    date.day() <= days
+}
+
 impl Convert for gb_io::seq::Date {
     type Output = PyDate;
     fn convert_with(self, py: Python, _interner: &mut PyInterner) -> PyResult<Py<Self::Output>> {
-        Ok(
-            PyDate::new_bound(py, self.year() as i32, self.month() as u8, self.day() as u8)?
-                .unbind(),
-        )
+        Ok(PyDate::new_bound(py, self.year(), self.month() as u8, self.day() as u8)?.unbind())
     }
 }
 
@@ -427,6 +2991,14 @@ impl Extract for gb_io::seq::Date {
 // ---------------------------------------------------------------------------
 
 /// A feature located somewhere in the record.
+///
+/// Calling `len` on a feature gives the length of its location, i.e. the
+/// number of bases it covers. For a feature located on a `Join`, such as
+/// a multi-exon ``CDS``, this is the spliced length, which excludes the
+/// introns, and not the length of the span of the feature.
+///
+/// .. versionadded:: 0.4.0
+///    Support for `len`.
 #[pyclass(module = "gb_io")]
 #[derive(Debug, Clone)]
 pub struct Feature {
@@ -468,6 +3040,35 @@ impl Feature {
         }
     }
 
+    fn __len__(slf: PyRef<'_, Self>) -> PyResult<usize> {
+        let location = slf.native_location(slf.py())?;
+        location::length(&location).map_err(PyValueError::new_err)
+    }
+
+    fn __bool__(&self) -> bool {
+        // features are always truthy, even with an empty location
+        true
+    }
+
+    fn __richcmp__(
+        slf: Bound<'_, Self>,
+        other: Bound<'_, PyAny>,
+        op: CompareOp,
+    ) -> PyResult<PyObject> {
+        let py = slf.py();
+        let other = match other.extract::<Py<Feature>>() {
+            Ok(other) if matches!(op, CompareOp::Eq | CompareOp::Ne) => other,
+            _ => return Ok(py.NotImplemented()),
+        };
+        let equal = slf.is(&other)
+            || gb_io::seq::Feature::extract(py, slf.unbind())?
+                == gb_io::seq::Feature::extract(py, other)?;
+        match op {
+            CompareOp::Eq => Ok(equal.into_py(py)),
+            _ => Ok((!equal).into_py(py)),
+        }
+    }
+
     /// `str`: The kind of feature.
     #[getter]
     fn get_kind<'py>(mut slf: PyRefMut<'py, Self>) -> PyResult<Py<PyString>> {
@@ -480,28 +3081,742 @@ impl Feature {
         slf.kind = Coa::Shared(kind.unbind());
     }
 
-    /// `Location`: The location of the feature in the record.
-    #[getter]
-    fn get_location<'py>(mut slf: PyRefMut<'py, Self>) -> PyResult<Py<Location>> {
-        let py = slf.py();
-        slf.location.to_shared(py)
+    /// `Location`: The location of the feature in the record.
+    ///
+    /// The location can also be assigned as a string in GenBank format
+    /// (e.g. ``"complement(1..100)"``), or as a `list` or `tuple` of
+    /// `Location` objects, which are implicitly wrapped in a `Join`.
+    ///
+    /// Example:
+    ///     Assign the exons of a feature without creating a `Join`::
+    ///
+    ///         >>> feature = gb_io.Feature("mRNA", gb_io.Range(0, 100))
+    ///         >>> feature.location = [gb_io.Range(0, 10), gb_io.Range(20, 30)]
+    ///         >>> feature.location
+    ///         Join([Range(0, 10), Range(20, 30)])
+    ///
+    /// .. versionchanged:: 0.4.0
+    ///    Support for assigning a string or a sequence of locations.
+    ///
+    #[getter]
+    fn get_location<'py>(mut slf: PyRefMut<'py, Self>) -> PyResult<Py<Location>> {
+        let py = slf.py();
+        slf.location.to_shared(py)
+    }
+
+    #[setter]
+    fn set_location<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        location: Bound<'py, PyAny>,
+    ) -> PyResult<()> {
+        let py = slf.py();
+        let location = if let Ok(text) = location.downcast::<PyString>() {
+            location::parse(text.to_str()?)
+                .map_err(PyValueError::new_err)?
+                .convert(py)?
+        } else if location.is_instance_of::<PyList>() || location.is_instance_of::<PyTuple>() {
+            let list = PyList::empty_bound(py);
+            for object in location.iter()? {
+                let object = object?;
+                if !object.is_instance_of::<Location>() {
+                    return Err(PyTypeError::new_err(format!(
+                        "expected Location, found {}",
+                        object.get_type().name()?
+                    )));
+                }
+                list.append(object)?;
+            }
+            if list.is_empty() {
+                return Err(PyValueError::new_err(
+                    "cannot join an empty list of locations",
+                ));
+            }
+            Py::new(
+                py,
+                PyClassInitializer::from(Location).add_subclass(Join {
+                    locations: list.unbind(),
+                }),
+            )?
+            .into_any()
+            .extract(py)?
+        } else {
+            location.extract::<Py<Location>>()?
+        };
+        slf.location = Coa::Shared(location);
+        Ok(())
+    }
+
+    /// `str` or `None`: The strand of the feature location.
+    ///
+    /// The strand is ``"+"`` for the direct strand, ``"-"`` for the
+    /// reverse strand, or `None` for locations without a well-defined
+    /// strand, such as joins of parts on both strands. Setting the
+    /// opposite strand unwraps a `Complement` location, or wraps the
+    /// location in a new `Complement` otherwise, so that setting the
+    /// strand twice gives back the original location. Setting the current
+    /// strand leaves the location unchanged.
+    ///
+    /// Raises:
+    ///     `ValueError`: When setting the strand of a location without
+    ///         a well-defined strand, or when the strand is invalid.
+    ///
+    /// Example:
+    ///     >>> feature = gb_io.Feature("CDS", gb_io.Range(0, 9))
+    ///     >>> feature.strand = "-"
+    ///     >>> feature.location
+    ///     Complement(Range(0, 9))
+    ///     >>> feature.strand = "+"
+    ///     >>> feature.location
+    ///     Range(0, 9)
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[getter]
+    fn get_strand(slf: PyRef<'_, Self>) -> PyResult<Option<Py<PyString>>> {
+        let py = slf.py();
+        let location = slf.native_location(py)?;
+        Ok(location::is_reverse(&location).map(|reverse| {
+            if reverse {
+                Strand::Reverse.into_py(py)
+            } else {
+                Strand::Direct.into_py(py)
+            }
+        }))
+    }
+
+    #[setter]
+    fn set_strand(mut slf: PyRefMut<'_, Self>, strand: Strand) -> PyResult<()> {
+        let py = slf.py();
+        let reverse = matches!(strand, Strand::Reverse);
+        match location::is_reverse(&slf.native_location(py)?) {
+            Some(current) if current == reverse => return Ok(()),
+            Some(_) => (),
+            None => {
+                return Err(PyValueError::new_err(
+                    "cannot set the strand of a location without a well-defined strand",
+                ))
+            }
+        }
+        match &mut slf.location {
+            Coa::Owned(location) => {
+                *location = match location.clone() {
+                    SeqLocation::Complement(inner) => *inner,
+                    other => SeqLocation::Complement(Box::new(other)),
+                };
+            }
+            Coa::Shared(location) => {
+                let flipped = match location.bind(py).downcast::<Complement>() {
+                    Ok(complement) => complement.borrow().location.clone_ref(py),
+                    Err(_) => Py::new(
+                        py,
+                        PyClassInitializer::from(Location).add_subclass(Complement {
+                            location: location.clone_ref(py),
+                        }),
+                    )?
+                    .into_any()
+                    .extract(py)?,
+                };
+                *location = flipped;
+            }
+        }
+        Ok(())
+    }
+
+    /// `list`: A list of `Qualifier` for this particular feature.
+    #[getter]
+    fn get_qualifiers<'py>(mut slf: PyRefMut<'py, Self>) -> PyResult<Py<PyList>> {
+        let py = slf.py();
+        slf.qualifiers.to_shared(py)
+    }
+
+    #[setter]
+    fn set_qualifiers<'py>(mut slf: PyRefMut<'py, Self>, qualifiers: Py<PyList>) {
+        slf.qualifiers = Coa::Shared(qualifiers.clone_ref(slf.py()));
+    }
+
+    /// `list` of `str`: The gene synonyms of the feature.
+    ///
+    /// Synonyms are read from all the ``/gene_synonym`` qualifiers of the
+    /// feature, and split on semicolons or slashes. Setting this property
+    /// replaces these qualifiers with a single ``/gene_synonym`` qualifier
+    /// listing all synonyms separated by semicolons.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[getter]
+    fn get_gene_synonyms(slf: PyRef<'_, Self>) -> PyResult<Vec<String>> {
+        let py = slf.py();
+        let mut values = Vec::new();
+        match &slf.qualifiers {
+            Coa::Owned(qualifiers) => {
+                for (key, value) in qualifiers {
+                    if key.as_ref() == "gene_synonym" {
+                        values.extend(value.clone());
+                    }
+                }
+            }
+            Coa::Shared(qualifiers) => {
+                for object in qualifiers.bind(py).iter() {
+                    let qualifier = object.downcast::<Qualifier>()?.borrow();
+                    if qualifier.has_key(py, "gene_synonym")? {
+                        values.extend(qualifier.value.clone());
+                    }
+                }
+            }
+        }
+        Ok(values
+            .iter()
+            .flat_map(|value| value.split([';', '/']))
+            .map(str::trim)
+            .filter(|synonym| !synonym.is_empty())
+            .map(String::from)
+            .collect())
+    }
+
+    #[setter]
+    fn set_gene_synonyms(mut slf: PyRefMut<'_, Self>, synonyms: Vec<String>) -> PyResult<()> {
+        let py = slf.py();
+        let value = (!synonyms.is_empty()).then(|| synonyms.join("; "));
+        match &mut slf.qualifiers {
+            Coa::Owned(qualifiers) => {
+                let index = qualifiers
+                    .iter()
+                    .position(|(key, _)| key.as_ref() == "gene_synonym")
+                    .unwrap_or(qualifiers.len());
+                qualifiers.retain(|(key, _)| key.as_ref() != "gene_synonym");
+                if let Some(value) = value {
+                    let key = gb_io::QualifierKey::from("gene_synonym");
+                    qualifiers.insert(index, (key, Some(value)));
+                }
+            }
+            Coa::Shared(qualifiers) => {
+                let list = qualifiers.bind(py);
+                let mut index = None;
+                let mut retained = Vec::with_capacity(list.len());
+                for object in list.iter() {
+                    if object
+                        .downcast::<Qualifier>()?
+                        .borrow()
+                        .has_key(py, "gene_synonym")?
+                    {
+                        index.get_or_insert(retained.len());
+                    } else {
+                        retained.push(object);
+                    }
+                }
+                if let Some(value) = value {
+                    let key = PyString::intern_bound(py, "gene_synonym");
+                    let qualifier = Py::new(py, Qualifier::__new__(key, Some(value), None))?;
+                    retained.insert(
+                        index.unwrap_or(retained.len()),
+                        qualifier.into_any().into_bound(py),
+                    );
+                }
+                list.set_slice(0, list.len(), &PyList::new_bound(py, retained))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Extract the sequence of the feature from a record.
+    ///
+    /// Parts of compound locations are concatenated in the order they
+    /// are given, even when they overlap, such as the parts of a coding
+    /// sequence with a ``/ribosomal_slippage``. Complemented parts are
+    /// reverse-complemented. For circular records, features spanning
+    /// the origin are extracted across it. This is equivalent to calling
+    /// `Record.extract` with the feature location.
+    ///
+    /// Arguments:
+    ///     record (`Record`): The record containing the feature.
+    ///
+    /// Returns:
+    ///     `bytes`: The sequence of the feature.
+    ///
+    /// Raises:
+    ///     `IndexError`: When the feature location is out of the bounds
+    ///         of the record sequence.
+    ///     `ValueError`: When the feature location cannot be extracted
+    ///         from the record, e.g. because it refers to another record.
+    ///
+    /// Example:
+    ///     >>> record = gb_io.Record(b"ATGAAACCCTAA", circular=True)
+    ///     >>> feature = gb_io.Feature("CDS", gb_io.Range(9, 15))
+    ///     >>> feature.extract(record)
+    ///     b'TAAATG'
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    fn extract<'py>(
+        slf: PyRef<'py, Self>,
+        record: Bound<'py, Record>,
+    ) -> PyResult<Bound<'py, PyBytes>> {
+        let py = slf.py();
+        let location = slf.native_location(py)?;
+        let sequence = record.borrow().extract_location(py, location)?;
+        Ok(PyBytes::new_bound(py, &sequence))
+    }
+
+    /// Get the coordinates of the parts of the feature location.
+    ///
+    /// Each simple part of a compound location, such as the exons of a
+    /// spliced ``mRNA`` feature, is reported with its strand. Parts are
+    /// reported in genomic order, so the exons of a feature on the reverse
+    /// strand are reported from the last one to the first one, but are not
+    /// sorted by coordinates, so the parts of a feature spanning the
+    /// origin of a circular record keep the order of its location. The result
+    /// can be converted to a `numpy` array directly, e.g. to plot a gene
+    /// model.
+    ///
+    /// Returns:
+    ///     `list` of `tuple`: A list of ``(start, end, strand)`` tuples,
+    ///     with zero-based, half-open coordinates and a strand of either
+    ///     ``"+"`` or ``"-"``. Parts referring to other records, and gaps,
+    ///     are skipped.
+    ///
+    /// Example:
+    ///     >>> location = gb_io.Complement(gb_io.Join([
+    ///     ...     gb_io.Range(0, 10), gb_io.Range(20, 30)
+    ///     ... ]))
+    ///     >>> gb_io.Feature("mRNA", location).exons()
+    ///     [(0, 10, '-'), (20, 30, '-')]
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    fn exons(slf: PyRef<'_, Self>) -> PyResult<Vec<(i64, i64, Py<PyString>)>> {
+        let py = slf.py();
+        let location = slf.native_location(py)?;
+        Ok(location::exons(&location)
+            .into_iter()
+            .map(|(start, end, reverse)| {
+                let strand = if reverse {
+                    Strand::Reverse
+                } else {
+                    Strand::Direct
+                };
+                (start, end, strand.into_py(py))
+            })
+            .collect())
+    }
+
+    /// Translate the feature and store the result as a qualifier.
+    ///
+    /// The sequence of the feature is extracted from the record, and
+    /// translated starting from the ``/codon_start`` frame, if any. The
+    /// first codon is translated as a methionine when it is an alternative
+    /// start codon, unless the 5' end of the feature is partial. Any
+    /// terminal stop codon is not included in the translation.
+    ///
+    /// Arguments:
+    ///     record (`Record`): The record containing the feature.
+    ///     table (`int`): The NCBI identifier of the genetic code to use.
+    ///     verify (`bool`): Pass `False` to overwrite an existing
+    ///         ``/translation`` qualifier even if it differs from the
+    ///         computed translation.
+    ///
+    /// Returns:
+    ///     `str`: The translation, which is also stored in the
+    ///     ``/translation`` qualifier of the feature.
+    ///
+    /// Raises:
+    ///     `ValueError`: When the genetic code is unknown, when the feature
+    ///         location cannot be extracted from the record, or when the
+    ///         translation differs from the existing one and ``verify`` is
+    ///         `True`.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyo3(signature = (record, table = 11, verify = true))]
+    fn set_translation(
+        mut slf: PyRefMut<'_, Self>,
+        record: Bound<'_, Record>,
+        table: u8,
+        verify: bool,
+    ) -> PyResult<String> {
+        let py = slf.py();
+        let code = GeneticCode::from_id(table)
+            .ok_or_else(|| PyValueError::new_err(format!("unknown genetic code: {}", table)))?;
+
+        // extract the nucleotide sequence of the feature
+        let location = slf.native_location(py)?;
+        let nucleotides = Feature::extract_sequence(&location, &record)?;
+
+        // translate the sequence in the right frame
+        let codon_start = match slf.qualifier_value(py, "codon_start")? {
+            None => 1,
+            Some(value) => match value.trim().parse::<usize>() {
+                Ok(n @ 1..=3) => n,
+                _ => {
+                    return Err(PyValueError::new_err(format!(
+                        "invalid codon_start: {:?}",
+                        value
+                    )))
+                }
+            },
+        };
+        let start = codon_start == 1 && !location::is_five_prime_partial(&location);
+        let frame = nucleotides.get(codon_start - 1..).unwrap_or_default();
+        let mut protein = code.translate(frame, start);
+        if protein.last() == Some(&b'*') {
+            protein.pop();
+        }
+        let translation = String::from_utf8(protein).expect("translation is ASCII");
+
+        // check the existing translation, if any
+        if verify {
+            if let Some(existing) = slf.qualifier_value(py, "translation")? {
+                if existing != translation {
+                    return Err(PyValueError::new_err(format!(
+                        "translation mismatch: expected {:?}, found {:?}",
+                        existing, translation
+                    )));
+                }
+            }
+        }
+
+        slf.set_qualifier_value(py, "translation", translation.clone())?;
+        Ok(translation)
+    }
+
+    /// Get the qualifiers of the feature grouped by key.
+    ///
+    /// Keys are listed in the order of their first occurrence, and the
+    /// values of the qualifiers sharing a key are listed in order. The
+    /// returned dictionary is a copy, so modifying it does not change
+    /// the qualifiers of the feature.
+    ///
+    /// Returns:
+    ///     `dict`: A dictionary mapping each qualifier key to a `list` of
+    ///     values, where qualifiers without a value are given as `None`.
+    ///
+    /// Example:
+    ///     >>> feature = gb_io.Feature("CDS", gb_io.Range(0, 10), [
+    ///     ...     gb_io.Qualifier("gene", "rop"),
+    ///     ...     gb_io.Qualifier("pseudo"),
+    ///     ...     gb_io.Qualifier("note", "first"),
+    ///     ...     gb_io.Qualifier("note", "second"),
+    ///     ... ])
+    ///     >>> feature.qualifiers_dict()
+    ///     {'gene': ['rop'], 'pseudo': [None], 'note': ['first', 'second']}
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    fn qualifiers_dict<'py>(slf: PyRef<'py, Self>) -> PyResult<Bound<'py, PyDict>> {
+        let py = slf.py();
+        let dict = PyDict::new_bound(py);
+        for (key, value) in slf.qualifiers.to_owned_native(py)? {
+            match dict.get_item(key.as_ref())? {
+                Some(values) => values.downcast::<PyList>()?.append(value)?,
+                None => dict.set_item(key.as_ref(), PyList::new_bound(py, [value]))?,
+            }
+        }
+        Ok(dict)
+    }
+
+    /// Remove the qualifiers duplicating a previous qualifier of the feature.
+    ///
+    /// Qualifiers are duplicates when both their key and their value are
+    /// equal. The first occurrence of each qualifier is kept, and the order
+    /// of the qualifiers is preserved. The `Feature.qualifiers` list is
+    /// modified in place.
+    ///
+    /// Arguments:
+    ///     keep_repeats (iterable of `str`, optional): The keys of the
+    ///         qualifiers which can be intentionally repeated with the
+    ///         same value, and which are never removed.
+    ///
+    /// Returns:
+    ///     `int`: The number of qualifiers removed.
+    ///
+    /// Raises:
+    ///     `TypeError`: When ``keep_repeats`` is a single `str` rather
+    ///         than an iterable of keys.
+    ///
+    /// Example:
+    ///     >>> feature = gb_io.Feature("gene", gb_io.Range(0, 10), [
+    ///     ...     gb_io.Qualifier("gene", "rop"),
+    ///     ...     gb_io.Qualifier("note", "repressor"),
+    ///     ...     gb_io.Qualifier("gene", "rop"),
+    ///     ... ])
+    ///     >>> feature.dedupe_qualifiers()
+    ///     1
+    ///     >>> [q.key for q in feature.qualifiers]
+    ///     ['gene', 'note']
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyo3(signature = (keep_repeats = None))]
+    fn dedupe_qualifiers(
+        mut slf: PyRefMut<'_, Self>,
+        keep_repeats: Option<Bound<'_, PyAny>>,
+    ) -> PyResult<usize> {
+        let py = slf.py();
+        let mut keep = HashSet::new();
+        if let Some(keys) = keep_repeats {
+            if keys.is_instance_of::<PyString>() {
+                return Err(PyTypeError::new_err(
+                    "expected iterable of str for keep_repeats",
+                ));
+            }
+            for key in keys.iter()? {
+                keep.insert(key?.downcast::<PyString>()?.to_str()?.to_string());
+            }
+        }
+
+        // collect the first occurrence of each qualifier
+        let list = slf.qualifiers.to_shared(py)?.into_bound(py);
+        let mut seen = HashSet::new();
+        let mut kept = Vec::with_capacity(list.len());
+        for object in list.iter() {
+            let qualifier = object.downcast::<Qualifier>()?.borrow();
+            let key = qualifier.with_key(py, str::to_string)?;
+            if keep.contains(&key) || seen.insert((key, qualifier.value.clone())) {
+                kept.push(object.clone());
+            }
+        }
+
+        // update the list in place so that references to it see the change
+        let removed = list.len() - kept.len();
+        if removed > 0 {
+            list.del_slice(0, list.len())?;
+            for object in kept {
+                list.append(object)?;
+            }
+        }
+        Ok(removed)
+    }
+
+    fn __copy__(mut slf: PyRefMut<'_, Self>) -> PyResult<Self> {
+        // convert the attributes so that the copy shares them with the feature
+        let py = slf.py();
+        let feature = slf.deref_mut();
+        feature.location.to_shared(py)?;
+        feature.qualifiers.to_shared(py)?;
+        Ok(feature.clone())
+    }
+
+    fn __deepcopy__(slf: Bound<'_, Self>, _memo: Bound<'_, PyAny>) -> PyResult<Self> {
+        let py = slf.py();
+        let quoting = slf.borrow().quoting(py)?;
+        let feature: gb_io::seq::Feature = Extract::extract(py, slf.unbind())?;
+        let mut copy = Feature {
+            kind: feature.kind.into(),
+            location: feature.location.into(),
+            qualifiers: feature.qualifiers.into(),
+        };
+        copy.set_quoting(py, &quoting)?;
+        Ok(copy)
+    }
+
+    fn __getnewargs__(
+        mut slf: PyRefMut<'_, Self>,
+    ) -> PyResult<(Py<PyString>, Py<Location>, Py<PyList>)> {
+        let py = slf.py();
+        let feature = slf.deref_mut();
+        Ok((
+            feature.kind.to_shared(py)?,
+            feature.location.to_shared(py)?,
+            feature.qualifiers.to_shared(py)?,
+        ))
+    }
+}
+
+impl Feature {
+    /// Get the explicit quoting of the qualifiers, with their index.
+    fn quoting(&self, py: Python) -> PyResult<Vec<(usize, bool)>> {
+        let mut quoting = Vec::new();
+        if let Coa::Shared(qualifiers) = &self.qualifiers {
+            for (index, qualifier) in qualifiers.bind(py).iter().enumerate() {
+                if let Some(quoted) = qualifier.downcast::<Qualifier>()?.borrow().quoted {
+                    quoting.push((index, quoted));
+                }
+            }
+        }
+        Ok(quoting)
+    }
+
+    /// Set the explicit quoting of the qualifiers at the given indices.
+    fn set_quoting(&mut self, py: Python, quoting: &[(usize, bool)]) -> PyResult<()> {
+        if !quoting.is_empty() {
+            let qualifiers = self.qualifiers.to_shared(py)?;
+            for &(index, quoted) in quoting {
+                let qualifier = qualifiers.bind(py).get_item(index)?;
+                qualifier.downcast::<Qualifier>()?.borrow_mut().quoted = Some(quoted);
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the location of the feature as a native `gb_io` location.
+    fn native_location(&self, py: Python) -> PyResult<SeqLocation> {
+        match &self.location {
+            Coa::Owned(location) => Ok(location.clone()),
+            Coa::Shared(location) => Extract::extract(py, location.clone_ref(py)),
+        }
+    }
+
+    /// Extract the sequence covered by a location from a record.
+    fn extract_sequence(location: &SeqLocation, record: &Bound<Record>) -> PyResult<Vec<u8>> {
+        let py = record.py();
+        match &record.borrow().sequence {
+            Coa::Owned(sequence) => location::extract(location, sequence),
+            Coa::Shared(sequence) => {
+                // SAFETY: the bytearray is not mutated while we hold the GIL.
+                location::extract(location, unsafe { sequence.bind(py).as_bytes() })
+            }
+        }
+        .map_err(PyValueError::new_err)
+    }
+
+    /// Check whether the feature is of the given kind, without conversion.
+    fn has_kind(&self, py: Python, kind: &str) -> PyResult<bool> {
+        match &self.kind {
+            Coa::Owned(k) => Ok(k.as_ref() == kind),
+            Coa::Shared(k) => Ok(k.bind(py).to_str()? == kind),
+        }
+    }
+
+    /// Get the value of the first qualifier with the given key.
+    fn qualifier_value(&self, py: Python, key: &str) -> PyResult<Option<String>> {
+        match &self.qualifiers {
+            Coa::Owned(qualifiers) => Ok(qualifiers
+                .iter()
+                .find(|(k, _)| k.as_ref() == key)
+                .and_then(|(_, value)| value.clone())),
+            Coa::Shared(qualifiers) => {
+                for object in qualifiers.bind(py).iter() {
+                    let qualifier = object.downcast::<Qualifier>()?.borrow();
+                    if qualifier.has_key(py, key)? {
+                        return Ok(qualifier.value.clone());
+                    }
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    /// Set the value of the first qualifier with the given key, or add it.
+    fn set_qualifier_value(&mut self, py: Python, key: &str, value: String) -> PyResult<()> {
+        match &mut self.qualifiers {
+            Coa::Owned(qualifiers) => {
+                match qualifiers.iter_mut().find(|(k, _)| k.as_ref() == key) {
+                    Some((_, v)) => *v = Some(value),
+                    None => qualifiers.push((gb_io::QualifierKey::from(key), Some(value))),
+                }
+                Ok(())
+            }
+            Coa::Shared(qualifiers) => {
+                let list = qualifiers.bind(py);
+                for object in list.iter() {
+                    let mut qualifier = object.downcast::<Qualifier>()?.borrow_mut();
+                    if qualifier.has_key(py, key)? {
+                        qualifier.value = Some(value);
+                        return Ok(());
+                    }
+                }
+                let key = PyString::intern_bound(py, key);
+                list.append(Py::new(py, Qualifier::__new__(key, Some(value), None))?)
+            }
+        }
+    }
+
+    /// Remove all the qualifiers with the given key.
+    fn remove_qualifiers(&mut self, py: Python, key: &str) -> PyResult<()> {
+        match &mut self.qualifiers {
+            Coa::Owned(qualifiers) => qualifiers.retain(|(k, _)| k.as_ref() != key),
+            Coa::Shared(qualifiers) => {
+                let list = qualifiers.bind(py);
+                let mut retained = Vec::with_capacity(list.len());
+                for object in list.iter() {
+                    if !object.downcast::<Qualifier>()?.borrow().has_key(py, key)? {
+                        retained.push(object);
+                    }
+                }
+                list.set_slice(0, list.len(), &PyList::new_bound(py, retained))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Check whether the feature intersects the given range.
+    fn intersects_range(&self, py: Python, start: i64, end: i64) -> PyResult<bool> {
+        match &self.location {
+            Coa::Owned(location) => Ok(location::intersects_range(location, start, end)),
+            Coa::Shared(location) => {
+                let location: SeqLocation = Extract::extract(py, location.clone_ref(py))?;
+                Ok(location::intersects_range(&location, start, end))
+            }
+        }
     }
+}
 
-    #[setter]
-    fn set_location<'py>(mut slf: PyRefMut<'py, Self>, kind: Py<Location>) {
-        slf.location = Coa::Shared(kind.clone_ref(slf.py()));
-    }
+/// An iterator over the features of a `Record`.
+///
+/// Use `Record.iter_features` to create a new iterator.
+///
+/// .. versionadded:: 0.4.0
+///
+#[pyclass(module = "gb_io")]
+pub struct FeatureIterator {
+    record: Py<Record>,
+    index: usize,
+    /// The number of features of the record when the iterator was created.
+    length: usize,
+    kind: Option<String>,
+    in_range: Option<(i64, i64)>,
+}
 
-    /// `list`: A list of `Qualifier` for this particular feature.
-    #[getter]
-    fn get_qualifiers<'py>(mut slf: PyRefMut<'py, Self>) -> PyResult<Py<PyList>> {
-        let py = slf.py();
-        slf.qualifiers.to_shared(py)
+#[pymethods]
+impl FeatureIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
     }
 
-    #[setter]
-    fn set_qualifiers<'py>(mut slf: PyRefMut<'py, Self>, qualifiers: Py<PyList>) {
-        slf.qualifiers = Coa::Shared(qualifiers.clone_ref(slf.py()));
+    fn __next__(mut slf: PyRefMut<'_, Self>) -> PyResult<Option<Py<Feature>>> {
+        let py = slf.py();
+        let this = slf.deref_mut();
+        let record = this.record.bind(py).borrow();
+        let features = match &record.features {
+            Coa::Shared(features) => features.bind(py),
+            Coa::Owned(features) => {
+                // only the matching features are converted, the other
+                // features of the record are left untouched
+                let end = this.length.min(features.len());
+                while this.index < end {
+                    let feature = &features[this.index];
+                    this.index += 1;
+                    if this
+                        .kind
+                        .as_ref()
+                        .is_none_or(|kind| feature.kind.as_ref() == kind)
+                        && this.in_range.is_none_or(|(start, end)| {
+                            location::intersects_range(&feature.location, start, end)
+                        })
+                    {
+                        return feature.clone().convert(py).map(Some);
+                    }
+                }
+                return Ok(None);
+            }
+        };
+        while this.index < this.length.min(features.len()) {
+            let object = features.get_item(this.index)?.extract::<Py<Feature>>()?;
+            this.index += 1;
+            let feature = object.bind(py).borrow();
+            if let Some(kind) = &this.kind {
+                if !feature.has_kind(py, kind)? {
+                    continue;
+                }
+            }
+            if let Some((start, end)) = this.in_range {
+                if !feature.intersects_range(py, start, end)? {
+                    continue;
+                }
+            }
+            drop(feature);
+            return Ok(Some(object));
+        }
+        Ok(None)
     }
 }
 
@@ -555,29 +3870,62 @@ pub struct Qualifier {
     /// `str` or `None`: An optional value for the qualifier.
     #[pyo3(get, set)]
     value: Option<String>,
+    /// `bool` or `None`: Whether the value of the qualifier is written
+    /// quoted, or `None` to decide from the key of the qualifier.
+    ///
+    /// Values of qualifiers such as ``/codon_start`` are written without
+    /// quotes by default, and the values of all other qualifiers are
+    /// written quoted. Qualifiers without a value are always written as
+    /// a bare ``/key``, e.g. ``/pseudo``.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyo3(get, set)]
+    quoted: Option<bool>,
 }
 
 #[pymethods]
 impl Qualifier {
     #[new]
-    #[pyo3(signature = (key, value = None))]
-    fn __new__(key: Bound<PyString>, value: Option<String>) -> PyClassInitializer<Self> {
+    #[pyo3(signature = (key, value = None, *, quoted = None))]
+    fn __new__(
+        key: Bound<PyString>,
+        value: Option<String>,
+        quoted: Option<bool>,
+    ) -> PyClassInitializer<Self> {
         PyClassInitializer::from(Self {
             key: Coa::Shared(key.unbind()),
             value,
+            quoted,
         })
     }
 
     fn __repr__<'py>(mut slf: PyRefMut<'py, Self>) -> PyResult<Bound<'py, PyAny>> {
         let py = slf.py();
         let key = slf.key.to_shared(py)?;
+        let quoted = match slf.quoted {
+            Some(true) => ", quoted=True",
+            Some(false) => ", quoted=False",
+            None => "",
+        };
         if let Some(v) = &slf.value {
-            PyString::new_bound(py, "Qualifier({!r}, {!r})").call_method1("format", (key, v))
+            PyString::new_bound(py, "Qualifier({!r}, {!r}{})")
+                .call_method1("format", (key, v, quoted))
         } else {
-            PyString::new_bound(py, "Qualifier({!r})").call_method1("format", (key,))
+            PyString::new_bound(py, "Qualifier({!r}{})").call_method1("format", (key, quoted))
         }
     }
 
+    fn __getnewargs_ex__<'py>(
+        mut slf: PyRefMut<'py, Self>,
+    ) -> PyResult<(PyObject, Bound<'py, PyDict>)> {
+        let py = slf.py();
+        let kwargs = PyDict::new_bound(py);
+        kwargs.set_item("quoted", slf.quoted)?;
+        let args = (slf.key.to_shared(py)?, slf.value.clone());
+        Ok((args.into_py(py), kwargs))
+    }
+
     /// `str`: The qualifier key.
     #[getter]
     fn get_key<'py>(mut slf: PyRefMut<'py, Self>) -> PyResult<Py<PyString>> {
@@ -589,6 +3937,55 @@ impl Qualifier {
     fn set_key<'py>(mut slf: PyRefMut<'py, Self>, key: Bound<'py, PyString>) {
         slf.key = Coa::Shared(key.unbind());
     }
+
+    fn __richcmp__(
+        slf: PyRef<'_, Self>,
+        other: Bound<'_, PyAny>,
+        op: CompareOp,
+    ) -> PyResult<PyObject> {
+        let py = slf.py();
+        let other = match other.extract::<PyRef<Qualifier>>() {
+            Ok(other) if matches!(op, CompareOp::Eq | CompareOp::Ne) => other,
+            _ => return Ok(py.NotImplemented()),
+        };
+        let equal = slf.value == other.value
+            && slf.with_key(py, |a| other.with_key(py, |b| a == b))??
+            && (slf.value.is_none() || slf.is_quoted(py)? == other.is_quoted(py)?);
+        match op {
+            CompareOp::Eq => Ok(equal.into_py(py)),
+            _ => Ok((!equal).into_py(py)),
+        }
+    }
+
+    fn __hash__(slf: PyRef<'_, Self>) -> PyResult<u64> {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        slf.with_key(slf.py(), |key| key.hash(&mut hasher))?;
+        slf.value.hash(&mut hasher);
+        Ok(hasher.finish())
+    }
+}
+
+impl Qualifier {
+    /// Check whether the value of the qualifier is written quoted.
+    fn is_quoted(&self, py: Python) -> PyResult<bool> {
+        match self.quoted {
+            Some(quoted) => Ok(quoted),
+            None => self.with_key(py, writer::is_quoted_by_default),
+        }
+    }
+
+    /// Check whether the qualifier has the given key, without conversion.
+    fn has_key(&self, py: Python, key: &str) -> PyResult<bool> {
+        self.with_key(py, |k| k == key)
+    }
+
+    /// Call a function with the key of the qualifier, without conversion.
+    fn with_key<T>(&self, py: Python, f: impl FnOnce(&str) -> T) -> PyResult<T> {
+        match &self.key {
+            Coa::Owned(k) => Ok(f(k.as_ref())),
+            Coa::Shared(k) => Ok(f(k.bind(py).to_str()?)),
+        }
+    }
 }
 
 impl Convert for gb_io::QualifierKey {
@@ -613,6 +4010,7 @@ impl Convert for (gb_io::QualifierKey, Option<String>) {
             Qualifier {
                 key: self.0.into(),
                 value: self.1,
+                quoted: None,
             },
         )
     }
@@ -674,66 +4072,484 @@ impl IntoPy<Py<PyString>> for Strand {
 /// This class cannot be instantiated directly, and should not be derived
 /// to avoid breakage in the Rust code. It can however be used for type
 /// annotations where any concrete `Location` subclass can be given.
+///
+/// Locations can be compared and sorted: they are ordered by start
+/// position, then by end position, with ties broken by location type
+/// and representation, so that only identical locations compare equal.
+///
+/// Converting a location to a string gives its GenBank representation,
+/// as written by `gb_io.dump`, which can be parsed back with
+/// `gb_io.parse_location`. Single-base ranges are written as a single
+/// coordinate, e.g. ``467`` rather than ``467..467``.
+///
+/// Calling `len` on a location gives the number of positions it covers.
+/// The lengths of the parts of a `Join`, `Order` or `Bond` are summed,
+/// so the length of a join is its spliced length, not the length of its
+/// span. The length of a `OneOf` or an `External` location is undefined,
+/// and raises a `ValueError`.
+///
+/// .. versionadded:: 0.4.0
+///    Support for comparison and hashing, conversion to `str`, and `len`.
 #[pyclass(module = "gb_io", subclass)]
 #[derive(Debug)]
 pub struct Location;
 
+#[pymethods]
+impl Location {
+    /// Check whether the location intersects the given range.
+    ///
+    /// Arguments:
+    ///     start (`int`): The start of the range (zero-based, inclusive).
+    ///     end (`int`): The end of the range (zero-based, exclusive).
+    ///
+    /// Returns:
+    ///     `bool`: `True` if any part of the location overlaps with the
+    ///     range, `False` otherwise. External locations never intersect.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    fn intersects_range(slf: Bound<'_, Self>, start: i64, end: i64) -> PyResult<bool> {
+        let location: SeqLocation = Extract::extract(slf.py(), slf.unbind())?;
+        Ok(location::intersects_range(&location, start, end))
+    }
+
+    /// Check whether the location overlaps another location.
+    ///
+    /// Arguments:
+    ///     other (`Location`): The location to compare with.
+    ///     same_strand (`bool`): Pass `True` to only report an overlap
+    ///         when both locations are on the same strand.
+    ///
+    /// Returns:
+    ///     `bool`: `True` if any part of the location overlaps any part
+    ///     of ``other``, `False` otherwise. External locations never
+    ///     overlap. With ``same_strand=True``, compound locations with
+    ///     parts on both strands never overlap other locations.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyo3(signature = (other, *, same_strand = false))]
+    fn overlaps(slf: Bound<'_, Self>, other: Py<Location>, same_strand: bool) -> PyResult<bool> {
+        let py = slf.py();
+        let a: SeqLocation = Extract::extract(py, slf.unbind())?;
+        let b: SeqLocation = Extract::extract(py, other)?;
+        Ok((!same_strand || location::same_strand(&a, &b)) && location::overlaps(&a, &b))
+    }
+
+    /// Check whether the location contains another location.
+    ///
+    /// Arguments:
+    ///     other (`Location`): The location to compare with.
+    ///     same_strand (`bool`): Pass `True` to only report containment
+    ///         when both locations are on the same strand.
+    ///
+    /// Returns:
+    ///     `bool`: `True` if every part of ``other`` lies within a single
+    ///     part of the location, `False` otherwise. External locations
+    ///     are never contained. With ``same_strand=True``, compound
+    ///     locations with parts on both strands are never contained.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyo3(signature = (other, *, same_strand = false))]
+    fn contains(slf: Bound<'_, Self>, other: Py<Location>, same_strand: bool) -> PyResult<bool> {
+        let py = slf.py();
+        let a: SeqLocation = Extract::extract(py, slf.unbind())?;
+        let b: SeqLocation = Extract::extract(py, other)?;
+        Ok((!same_strand || location::same_strand(&a, &b)) && location::contains(&a, &b))
+    }
+
+    /// Mirror the location within a sequence of the given length.
+    ///
+    /// This is the transformation applied to the locations of features
+    /// when reverse-complementing a record: each coordinate ``x`` becomes
+    /// ``length - x``, the strand is flipped, the parts of compound
+    /// locations are reversed, and the ``before`` and ``after`` flags of
+    /// ranges are swapped.
+    ///
+    /// Arguments:
+    ///     length (`int`): The length of the sequence the location refers to.
+    ///
+    /// Returns:
+    ///     `Location`: The flipped location. `External` locations and
+    ///     gaps are returned unchanged.
+    ///
+    /// Raises:
+    ///     `ValueError`: When the location extends beyond ``length``.
+    ///
+    /// Example:
+    ///     Flip a range that is partial on its 3' end::
+    ///
+    ///         >>> loc = gb_io.Range(0, 10, after=True)
+    ///         >>> loc.flip(100)
+    ///         Complement(Range(90, 100, before=True))
+    ///         >>> loc.flip(100).flip(100)
+    ///         Range(0, 10, after=True)
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    fn flip(slf: Bound<'_, Self>, length: i64) -> PyResult<Py<Location>> {
+        let py = slf.py();
+        let location: SeqLocation = Extract::extract(py, slf.unbind())?;
+        if let Some((start, end)) = location::span(&location) {
+            if start < 0 || end > length {
+                return Err(PyValueError::new_err(format!(
+                    "location {} extends beyond sequence length {}",
+                    location.to_gb_format(),
+                    length
+                )));
+            }
+        }
+        location::flip(&location, length).convert(py)
+    }
+
+    /// Translate all the positions of the location by a fixed offset.
+    ///
+    /// This is the transformation to apply to the locations of features
+    /// when prepending ``offset`` bases to a sequence, e.g. when records
+    /// are concatenated. The parts of compound locations are shifted
+    /// recursively, and the location itself is not modified.
+    ///
+    /// Arguments:
+    ///     offset (`int`): The number of positions to shift the location
+    ///         by, which may be negative.
+    ///
+    /// Returns:
+    ///     `Location`: The shifted location, as a new object. `External`
+    ///     locations and gaps are returned unchanged.
+    ///
+    /// Raises:
+    ///     `ValueError`: When the shifted location would start before
+    ///         the beginning of the sequence.
+    ///     `OverflowError`: When a shifted position would not fit in a
+    ///         64-bit integer.
+    ///
+    /// Example:
+    ///     Shift a join to account for 100 bases before it::
+    ///
+    ///         >>> loc = gb_io.Join([gb_io.Range(0, 10), gb_io.Range(20, 30)])
+    ///         >>> loc.shift(100)
+    ///         Join([Range(100, 110), Range(120, 130)])
+    ///         >>> gb_io.Complement(gb_io.Between(4, 5)).shift(-4)
+    ///         Complement(Between(0, 1))
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    fn shift(slf: Bound<'_, Self>, offset: i64) -> PyResult<Py<Location>> {
+        let py = slf.py();
+        let location: SeqLocation = Extract::extract(py, slf.unbind())?;
+        let shifted = location::checked_shift(&location, offset).ok_or_else(|| {
+            PyOverflowError::new_err(format!(
+                "location {} shifted by {} overflows",
+                location.to_gb_format(),
+                offset
+            ))
+        })?;
+        if let Some((start, _)) = location::span(&shifted) {
+            if start < 0 {
+                return Err(PyValueError::new_err(format!(
+                    "location {} shifted by {} starts before the sequence",
+                    location.to_gb_format(),
+                    offset
+                )));
+            }
+        }
+        shifted.convert(py)
+    }
+
+    /// Get a simplified version of the location.
+    ///
+    /// Compound locations with a single part, such as ``join(1..100)``,
+    /// are replaced by that part. Joins nested in a `Join` and orders
+    /// nested in an `Order` are flattened, and double complements cancel
+    /// out. The location itself is not modified.
+    ///
+    /// Returns:
+    ///     `Location`: The simplified location, describing the same
+    ///     positions as the original one.
+    ///
+    /// Example:
+    ///     Collapse a join with a single element::
+    ///
+    ///         >>> loc = gb_io.Join([gb_io.Complement(gb_io.Range(0, 10))])
+    ///         >>> loc.simplify()
+    ///         Complement(Range(0, 10))
+    ///         >>> loc = gb_io.Join([gb_io.Join([gb_io.Range(0, 2), gb_io.Range(4, 6)]), gb_io.Range(8, 9)])
+    ///         >>> loc.simplify()
+    ///         Join([Range(0, 2), Range(4, 6), Site(8)])
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    fn simplify(slf: Bound<'_, Self>) -> PyResult<Py<Location>> {
+        let py = slf.py();
+        let location: SeqLocation = Extract::extract(py, slf.unbind())?;
+        location::simplify(&location).convert(py)
+    }
+
+    /// Get a representation of the location as nested tuples.
+    ///
+    /// The first element of each tuple is the kind of location, followed
+    /// by its attributes, in the same order as the constructor arguments
+    /// of the corresponding class:
+    ///
+    /// - ``("range", start, end, before, after)`` for a `Range`.
+    /// - ``("between", start, end)`` for a `Between`.
+    /// - ``("complement", location)`` for a `Complement`.
+    /// - ``("join", [locations...])`` for a `Join`, and likewise
+    ///   ``"order"``, ``"bond"`` and ``"one_of"`` for an `Order`, a
+    ///   `Bond` and a `OneOf`.
+    /// - ``("external", accession, location)`` for an `External`, where
+    ///   ``location`` is `None` if the external location has none.
+    ///
+    /// Coordinates are zero-based with an exclusive end, like the
+    /// attributes of `Range` and `Between`.
+    ///
+    /// Returns:
+    ///     `tuple`: The location as plain data, which can be converted back
+    ///     with `Location.from_tuple`.
+    ///
+    /// Example:
+    ///     Get the parts of a complemented join::
+    ///
+    ///         >>> loc = gb_io.parse_location("complement(join(1..10,<21..30))")
+    ///         >>> loc.to_tuple()
+    ///         ('complement', ('join', [('range', 0, 10, False, False), ('range', 20, 30, True, False)]))
+    ///         >>> gb_io.Location.from_tuple(loc.to_tuple()) == loc
+    ///         True
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    fn to_tuple(slf: Bound<'_, Self>) -> PyResult<PyObject> {
+        let py = slf.py();
+        let location: SeqLocation = Extract::extract(py, slf.unbind())?;
+        Location::native_to_tuple(py, &location)
+    }
+
+    /// Create a new location from its representation as nested tuples.
+    ///
+    /// Arguments:
+    ///     data (`tuple`): The location as nested tuples, in the format
+    ///         returned by `Location.to_tuple`. The locations of compound
+    ///         locations can be given in any iterable.
+    ///
+    /// Returns:
+    ///     `Location`: The location described by the tuple.
+    ///
+    /// Raises:
+    ///     `ValueError`: When the kind of a location is unknown, when a
+    ///         tuple has the wrong number of elements, or when the
+    ///         accession of an external location is invalid.
+    ///     `TypeError`: When an element of a tuple has the wrong type.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[staticmethod]
+    fn from_tuple(data: Bound<'_, PyAny>) -> PyResult<Py<Location>> {
+        Location::native_from_tuple(&data)?.convert(data.py())
+    }
+
+    fn __str__(slf: Bound<'_, Self>) -> PyResult<String> {
+        let location: SeqLocation = Extract::extract(slf.py(), slf.unbind())?;
+        Ok(location.to_gb_format())
+    }
+
+    fn __len__(slf: Bound<'_, Self>) -> PyResult<usize> {
+        let location: SeqLocation = Extract::extract(slf.py(), slf.unbind())?;
+        location::length(&location).map_err(PyValueError::new_err)
+    }
+
+    fn __bool__(&self) -> bool {
+        // locations are always truthy, even when their length is zero or
+        // undefined, as they were before supporting `len`
+        true
+    }
+
+    fn __richcmp__(
+        slf: Bound<'_, Self>,
+        other: Bound<'_, PyAny>,
+        op: CompareOp,
+    ) -> PyResult<PyObject> {
+        let py = slf.py();
+        let other = match other.extract::<Py<Location>>() {
+            Ok(other) => other,
+            Err(_) => return Ok(py.NotImplemented()),
+        };
+        let a: SeqLocation = Extract::extract(py, slf.unbind())?;
+        let b: SeqLocation = Extract::extract(py, other)?;
+        Ok(op.matches(location::compare(&a, &b)).into_py(py))
+    }
+
+    fn __hash__(slf: Bound<'_, Self>) -> PyResult<u64> {
+        let location: SeqLocation = Extract::extract(slf.py(), slf.unbind())?;
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        location.to_gb_format().hash(&mut hasher);
+        Ok(hasher.finish())
+    }
+}
+
+impl Location {
+    /// Convert a native location into nested tuples.
+    fn native_to_tuple(py: Python, location: &SeqLocation) -> PyResult<PyObject> {
+        let parts = |kind: &str, locations: &[SeqLocation]| -> PyResult<PyObject> {
+            let list = PyList::empty_bound(py);
+            for location in locations {
+                list.append(Location::native_to_tuple(py, location)?)?;
+            }
+            Ok((kind, list).into_py(py))
+        };
+        match location {
+            SeqLocation::Range((start, Before(before)), (end, After(after))) => {
+                Ok(("range", *start, *end, *before, *after).into_py(py))
+            }
+            SeqLocation::Between(start, end) => Ok(("between", *start, *end).into_py(py)),
+            SeqLocation::Complement(inner) => {
+                Ok(("complement", Location::native_to_tuple(py, inner)?).into_py(py))
+            }
+            SeqLocation::Join(locations) => parts("join", locations),
+            SeqLocation::Order(locations) => parts("order", locations),
+            SeqLocation::Bond(locations) => parts("bond", locations),
+            SeqLocation::OneOf(locations) => parts("one_of", locations),
+            SeqLocation::External(accession, location) => {
+                let inner = location
+                    .as_deref()
+                    .map(|location| Location::native_to_tuple(py, location))
+                    .transpose()?;
+                Ok(("external", accession, inner).into_py(py))
+            }
+            SeqLocation::Gap(_) => Err(PyNotImplementedError::new_err(format!(
+                "conversion of {:?}",
+                location
+            ))),
+        }
+    }
+
+    /// Build a native location from nested tuples.
+    fn native_from_tuple(data: &Bound<PyAny>) -> PyResult<SeqLocation> {
+        let tuple = data.downcast::<PyTuple>()?;
+        if tuple.is_empty() {
+            return Err(PyValueError::new_err("empty location tuple"));
+        }
+        let kind = tuple.get_item(0)?.extract::<String>()?;
+        let expect_len = |n: usize| {
+            if tuple.len() == n {
+                Ok(())
+            } else {
+                Err(PyValueError::new_err(format!(
+                    "expected {} elements in {:?} location tuple, found {}",
+                    n,
+                    kind,
+                    tuple.len()
+                )))
+            }
+        };
+        let parts = |object: Bound<PyAny>| -> PyResult<Vec<SeqLocation>> {
+            object
+                .iter()?
+                .map(|item| Location::native_from_tuple(&item?))
+                .collect()
+        };
+        match kind.as_str() {
+            "range" => {
+                expect_len(5)?;
+                let (_, start, end, before, after): (String, i64, i64, bool, bool) =
+                    tuple.extract()?;
+                Ok(SeqLocation::Range(
+                    (start, Before(before)),
+                    (end, After(after)),
+                ))
+            }
+            "between" => {
+                expect_len(3)?;
+                let (_, start, end): (String, i64, i64) = tuple.extract()?;
+                Ok(SeqLocation::Between(start, end))
+            }
+            "complement" => {
+                expect_len(2)?;
+                let inner = Location::native_from_tuple(&tuple.get_item(1)?)?;
+                Ok(SeqLocation::Complement(Box::new(inner)))
+            }
+            "join" => {
+                expect_len(2)?;
+                Ok(SeqLocation::Join(parts(tuple.get_item(1)?)?))
+            }
+            "order" => {
+                expect_len(2)?;
+                Ok(SeqLocation::Order(parts(tuple.get_item(1)?)?))
+            }
+            "bond" => {
+                expect_len(2)?;
+                Ok(SeqLocation::Bond(parts(tuple.get_item(1)?)?))
+            }
+            "one_of" => {
+                expect_len(2)?;
+                Ok(SeqLocation::OneOf(parts(tuple.get_item(1)?)?))
+            }
+            "external" => {
+                expect_len(3)?;
+                let accession = tuple.get_item(1)?.extract::<String>()?;
+                if !location::is_valid_accession(&accession) {
+                    return Err(PyValueError::new_err(format!(
+                        "invalid accession: {:?}",
+                        accession
+                    )));
+                }
+                let inner = tuple.get_item(2)?;
+                let inner = if inner.is_none() {
+                    None
+                } else {
+                    Some(Box::new(Location::native_from_tuple(&inner)?))
+                };
+                Ok(SeqLocation::External(accession, inner))
+            }
+            other => Err(PyValueError::new_err(format!(
+                "invalid location kind: {:?}",
+                other
+            ))),
+        }
+    }
+}
+
 impl Convert for gb_io::seq::Location {
     type Output = Location;
+    #[allow(clippy::only_used_in_recursion)]
     fn convert_with(self, py: Python, interner: &mut PyInterner) -> PyResult<Py<Self::Output>> {
         macro_rules! convert_vec {
-            ($ty:ident, $inner:expr) => {{
+            ($ty:ident, $inner:expr $(, $arg:expr)*) => {{
                 let objects: PyObject = $inner
                     .into_iter()
                     .map(|loc| loc.convert_with(py, interner))
                     .collect::<PyResult<Vec<Py<Location>>>>()
                     .map(|objects| PyList::new_bound(py, objects))
                     .and_then(|list| list.to_object(py).extract(py))?;
-                Join::__new__(py, objects)
+                $ty::__new__(py, objects $(, $arg)*)
                     .and_then(|x| Py::new(py, x))
-                    .and_then(|x| match x.to_object(py).extract::<Py<Location>>(py) {
-                        Ok(pyref) => Ok(pyref.clone_ref(py)),
-                        Err(e) => Err(PyErr::from(e)),
-                    })
+                    .and_then(|x| x.to_object(py).extract::<Py<Location>>(py))
             }};
         }
 
         match self {
-            SeqLocation::Range((start, Before(before)), (end, After(after))) => {
-                Py::new(py, Range::__new__(start, end, before, after)).and_then(|x| {
-                    match x.to_object(py).extract::<Py<Location>>(py) {
-                        Ok(pyref) => Ok(pyref.clone_ref(py)),
-                        Err(e) => Err(PyErr::from(e)),
-                    }
-                })
+            SeqLocation::Range((start, Before(false)), (end, After(false))) if end == start + 1 => {
+                Py::new(py, Site::__new__(start)?)
+                    .and_then(|x| x.to_object(py).extract::<Py<Location>>(py))
             }
-            SeqLocation::Between(start, end) => {
-                Py::new(py, Between::__new__(start, end)).and_then(|x| {
-                    match x.to_object(py).extract::<Py<Location>>(py) {
-                        Ok(pyref) => Ok(pyref.clone_ref(py)),
-                        Err(e) => Err(PyErr::from(e)),
-                    }
-                })
+            SeqLocation::Range((start, Before(before)), (end, After(after))) => {
+                Py::new(py, Range::__new__(start, end, before, after))
+                    .and_then(|x| x.to_object(py).extract::<Py<Location>>(py))
             }
+            SeqLocation::Between(start, end) => Py::new(py, Between::__new__(start, end))
+                .and_then(|x| x.to_object(py).extract::<Py<Location>>(py)),
             SeqLocation::Complement(inner_location) => (*inner_location)
                 .convert_with(py, interner)
                 .and_then(|inner| Py::new(py, Complement::__new__(inner)))
-                .and_then(|x| match x.to_object(py).extract::<Py<Location>>(py) {
-                    Ok(pyref) => Ok(pyref.clone_ref(py)),
-                    Err(e) => Err(PyErr::from(e)),
-                }),
-            SeqLocation::Join(inner_locations) => convert_vec!(Join, inner_locations),
+                .and_then(|x| x.to_object(py).extract::<Py<Location>>(py)),
+            SeqLocation::Join(inner_locations) => convert_vec!(Join, inner_locations, false),
             SeqLocation::Order(inner_locations) => convert_vec!(Order, inner_locations),
             SeqLocation::Bond(inner_locations) => convert_vec!(Bond, inner_locations),
             SeqLocation::OneOf(inner_locations) => convert_vec!(OneOf, inner_locations),
             SeqLocation::External(accession, location) => {
                 let loc = location.map(|x| x.convert_with(py, interner)).transpose()?;
-                Py::new(py, External::__new__(accession, loc)).and_then(|x| {
-                    match x.to_object(py).extract::<Py<Location>>(py) {
-                        Ok(pyref) => Ok(pyref.clone_ref(py)),
-                        Err(e) => Err(PyErr::from(e)),
-                    }
-                })
+                Py::new(py, External::init(accession, loc))
+                    .and_then(|x| x.to_object(py).extract::<Py<Location>>(py))
             }
             _ => Err(PyNotImplementedError::new_err(format!(
                 "conversion of {:?}",
@@ -792,7 +4608,17 @@ impl Extract for gb_io::seq::Location {
 /// For instance, a feature location of ``<1..206`` can be created with
 /// ``Range(1, 206, before=True)``.
 ///
-#[pyclass(module = "gb_io", extends = Location)]
+/// A range whose ``start`` equals its ``end`` is empty: its length is zero,
+/// and it extracts an empty sequence. Ranges over a single position, without
+/// the ``before`` and ``after`` flags, are returned as `Site` locations when
+/// reading records, and by the `Location` methods returning a new location,
+/// such as `Location.shift` or `Location.simplify`.
+///
+/// .. versionchanged:: 0.4.0
+///    Single positions are returned as `Site` locations, a subclass of
+///    `Range`.
+///
+#[pyclass(module = "gb_io", extends = Location, subclass)]
 #[derive(Debug)]
 pub struct Range {
     #[pyo3(get, set)]
@@ -824,10 +4650,10 @@ impl Range {
     #[pyo3(signature = (start, end, before = false, after = false))]
     fn __new__(start: i64, end: i64, before: bool, after: bool) -> PyClassInitializer<Self> {
         PyClassInitializer::from(Location).add_subclass(Self {
-            start: start,
-            end: end,
-            before: before,
-            after: after,
+            start,
+            end,
+            before,
+            after,
         })
     }
 
@@ -843,12 +4669,98 @@ impl Range {
         }
     }
 
+    fn __getnewargs__(&self) -> (i64, i64, bool, bool) {
+        (self.start, self.end, self.before, self.after)
+    }
+
     #[getter]
     fn get_strand(slf: PyRef<'_, Self>) -> Py<PyString> {
         Strand::Direct.into_py(slf.py())
     }
 }
 
+/// A location for a `Feature` spanning a single position.
+///
+/// Single positions are written as a bare number in GenBank files, e.g.
+/// ``467`` for the position at index 466. Sites are ranges over a single
+/// position, without the ``before`` and ``after`` flags, so they can be
+/// used wherever a `Range` is expected. The ``start``, ``end``, ``before``
+/// and ``after`` attributes of a site are read-only: use ``position`` to
+/// move it to another position.
+///
+/// Example:
+///     >>> gb_io.parse_location("467")
+///     Site(466)
+///     >>> str(gb_io.Site(466))
+///     '467'
+///     >>> isinstance(gb_io.Site(466), gb_io.Range)
+///     True
+///
+/// .. versionadded:: 0.4.0
+///
+#[pyclass(module = "gb_io", extends = Range)]
+#[derive(Debug)]
+pub struct Site {}
+
+#[pymethods]
+impl Site {
+    #[new]
+    fn __new__(position: i64) -> PyResult<PyClassInitializer<Self>> {
+        let end = position
+            .checked_add(1)
+            .ok_or_else(|| PyOverflowError::new_err("site position is too large"))?;
+        Ok(Range::__new__(position, end, false, false).add_subclass(Self {}))
+    }
+
+    fn __repr__(slf: PyRef<'_, Self>) -> String {
+        format!("Site({})", slf.as_ref().start)
+    }
+
+    fn __getnewargs__(slf: PyRef<'_, Self>) -> (i64,) {
+        (slf.as_ref().start,)
+    }
+
+    /// `int`: The index of the position, counted from zero.
+    #[getter]
+    fn get_position(slf: PyRef<'_, Self>) -> i64 {
+        slf.as_ref().start
+    }
+
+    #[setter]
+    fn set_position(mut slf: PyRefMut<'_, Self>, position: i64) -> PyResult<()> {
+        let range: &mut Range = slf.as_mut();
+        range.end = position
+            .checked_add(1)
+            .ok_or_else(|| PyOverflowError::new_err("site position is too large"))?;
+        range.start = position;
+        Ok(())
+    }
+
+    /// `int`: The start of the site, equal to its position.
+    #[getter]
+    fn get_start(slf: PyRef<'_, Self>) -> i64 {
+        slf.as_ref().start
+    }
+
+    /// `int`: The end of the site, right after its position.
+    #[getter]
+    fn get_end(slf: PyRef<'_, Self>) -> i64 {
+        slf.as_ref().end
+    }
+
+    /// `bool`: Whether the site starts before its position, always `False`.
+    #[getter]
+    fn get_before(&self) -> bool {
+        false
+    }
+
+    /// `bool`: Whether the site extends after its position, always `False`.
+    #[getter]
+    fn get_after(&self) -> bool {
+        false
+    }
+}
+
 /// A location for a `Feature` located between two consecutive positions.
 #[pyclass(module = "gb_io", extends = Location)]
 #[derive(Debug)]
@@ -865,16 +4777,17 @@ pub struct Between {
 impl Between {
     #[new]
     fn __new__(start: i64, end: i64) -> PyClassInitializer<Self> {
-        PyClassInitializer::from(Location).add_subclass(Self {
-            start: start,
-            end: end,
-        })
+        PyClassInitializer::from(Location).add_subclass(Self { start, end })
     }
 
     fn __repr__(&self) -> String {
         format!("Between({}, {})", self.start, self.end)
     }
 
+    fn __getnewargs__(&self) -> (i64, i64) {
+        (self.start, self.end)
+    }
+
     #[getter]
     fn get_strand(slf: PyRef<'_, Self>) -> Py<PyString> {
         Strand::Direct.into_py(slf.py())
@@ -890,6 +4803,21 @@ pub struct Complement {
     location: Py<Location>,
 }
 
+impl Complement {
+    /// Get the smallest and largest coordinates of the wrapped location.
+    fn bounds(slf: &PyRef<'_, Self>, coordinate: &str) -> PyResult<(i64, i64)> {
+        let py = slf.py();
+        let location: SeqLocation = Extract::extract(py, slf.location.clone_ref(py))?;
+        location::bounds(&location).ok_or_else(|| {
+            PyValueError::new_err(format!(
+                "cannot get {} coordinate of {}",
+                coordinate,
+                location.to_gb_format()
+            ))
+        })
+    }
+}
+
 #[pymethods]
 impl Complement {
     #[new]
@@ -903,37 +4831,74 @@ impl Complement {
             .call_method1("format", (Py::clone_ref(&slf.location, py),))
     }
 
+    fn __getnewargs__(slf: PyRef<'_, Self>) -> (Py<Location>,) {
+        (slf.location.clone_ref(slf.py()),)
+    }
+
+    /// `int`: The start of the location, read on the complement strand.
+    ///
+    /// This is the largest coordinate of the wrapped location, which may
+    /// be a compound location such as a `Join`.
+    ///
+    /// .. versionchanged:: 0.4.0
+    ///    Support wrapped compound locations.
+    ///
     #[getter]
-    fn get_start<'py>(slf: PyRef<'py, Self>) -> PyResult<i32> {
-        let py = slf.py();
-        slf.location
-            .getattr(py, "end")
-            .and_then(|end| end.extract(py))
+    fn get_start<'py>(slf: PyRef<'py, Self>) -> PyResult<i64> {
+        Self::bounds(&slf, "start").map(|(_, end)| end)
     }
 
+    /// `int`: The end of the location, read on the complement strand.
+    ///
+    /// This is the smallest coordinate of the wrapped location.
+    ///
+    /// .. versionchanged:: 0.4.0
+    ///    Support wrapped compound locations.
+    ///
     #[getter]
-    fn get_end<'py>(slf: PyRef<'py, Self>) -> PyResult<i32> {
-        let py = slf.py();
-        slf.location
-            .getattr(py, "start")
-            .and_then(|start| start.extract(py))
+    fn get_end<'py>(slf: PyRef<'py, Self>) -> PyResult<i64> {
+        Self::bounds(&slf, "end").map(|(start, _)| start)
     }
 
     #[getter]
-    fn get_strand(slf: PyRef<'_, Self>) -> PyResult<Py<PyString>> {
+    fn get_strand(slf: PyRef<'_, Self>) -> PyResult<Option<Py<PyString>>> {
         let py = slf.py();
         match slf
             .location
             .getattr(py, "strand")
             .and_then(|start| start.extract(py))?
         {
-            Strand::Direct => Ok(Strand::Reverse.into_py(py)),
-            Strand::Reverse => Ok(Strand::Direct.into_py(py)),
+            Some(Strand::Direct) => Ok(Some(Strand::Reverse.into_py(py))),
+            Some(Strand::Reverse) => Ok(Some(Strand::Direct.into_py(py))),
+            None => Ok(None),
         }
     }
 }
 
+/// Get the strand of a compound location from the strand of its parts.
+fn compound_strand(py: Python, locations: &Py<PyList>) -> PyResult<Option<Py<PyString>>> {
+    let parts = locations
+        .bind(py)
+        .iter()
+        .map(|object| SeqLocation::extract(py, object.extract()?))
+        .collect::<PyResult<Vec<_>>>()?;
+    let strand = match location::is_reverse(&SeqLocation::Join(parts)) {
+        Some(true) => Some(Strand::Reverse.into_py(py)),
+        Some(false) => Some(Strand::Direct.into_py(py)),
+        None => None,
+    };
+    Ok(strand)
+}
+
 /// A location for a `Feature` consisting in joined sequence spans.
+///
+/// An empty join has a length of zero and extracts an empty sequence, but
+/// it has no ``start`` or ``end`` coordinate and cannot be written to a
+/// GenBank file. Pass ``strict=True`` to reject empty joins on creation.
+///
+/// .. versionadded:: 0.4.0
+///    The ``strict`` keyword argument.
+///
 #[pyclass(module = "gb_io", extends = Location)]
 #[derive(Debug)]
 pub struct Join {
@@ -942,16 +4907,51 @@ pub struct Join {
     locations: Py<PyList>,
 }
 
+impl Join {
+    /// Get the smallest and largest coordinates of the joined locations.
+    fn bounds(slf: &PyRef<'_, Self>, coordinate: &str) -> PyResult<(i64, i64)> {
+        let py = slf.py();
+        let locations = slf
+            .locations
+            .bind(py)
+            .iter()
+            .map(|object| Extract::extract(py, object.extract::<Py<Location>>()?))
+            .collect::<PyResult<Vec<SeqLocation>>>()?;
+        if locations.is_empty() {
+            return Err(PyValueError::new_err(format!(
+                "cannot get {} coordinate of empty list of locations",
+                coordinate
+            )));
+        }
+        let location = SeqLocation::Join(locations);
+        location::bounds(&location).ok_or_else(|| {
+            PyValueError::new_err(format!(
+                "cannot get {} coordinate of {}",
+                coordinate,
+                location.to_gb_format()
+            ))
+        })
+    }
+}
+
 #[pymethods]
 impl Join {
     #[new]
-    fn __new__(py: Python, locations: PyObject) -> PyResult<PyClassInitializer<Self>> {
+    #[pyo3(signature = (locations, *, strict = false))]
+    fn __new__(
+        py: Python,
+        locations: PyObject,
+        strict: bool,
+    ) -> PyResult<PyClassInitializer<Self>> {
         let list = PyList::empty_bound(py);
         for result in locations.bind(py).iter()? {
             let object = result?;
             object.extract::<Bound<Location>>()?;
             list.append(object)?;
         }
+        if strict && list.is_empty() {
+            return Err(PyValueError::new_err("cannot create an empty join"));
+        }
         Ok(PyClassInitializer::from(Location).add_subclass(Self {
             locations: Py::from(list),
         }))
@@ -962,36 +4962,39 @@ impl Join {
         PyString::new_bound(py, "Join({!r})").call_method1("format", (&slf.locations,))
     }
 
+    fn __getnewargs__(slf: PyRef<'_, Self>) -> (Py<PyList>,) {
+        (slf.locations.clone_ref(slf.py()),)
+    }
+
+    /// `str` or `None`: The strand of the parts of the location, or `None`
+    /// if they are on different strands or if the strand of any part is
+    /// undefined.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[getter]
+    fn get_strand(slf: PyRef<'_, Self>) -> PyResult<Option<Py<PyString>>> {
+        compound_strand(slf.py(), &slf.locations)
+    }
+
+    /// `int`: The smallest coordinate of the joined locations.
+    ///
+    /// .. versionchanged:: 0.4.0
+    ///    Support coordinates beyond 32-bit and nested compound locations.
+    ///
     #[getter]
-    fn get_start<'py>(slf: PyRef<'py, Self>) -> PyResult<i32> {
-        let py = slf.py();
-        let mut min: Option<i32> = None;
-        for obj in slf.locations.bind(py) {
-            let start = obj.getattr("start")?.extract::<i32>()?;
-            min = match min {
-                Some(i) if i < start => Some(i),
-                _ => Some(start),
-            }
-        }
-        min.ok_or(PyValueError::new_err(
-            "cannot get start coordinate of empty list of locations",
-        ))
+    fn get_start<'py>(slf: PyRef<'py, Self>) -> PyResult<i64> {
+        Self::bounds(&slf, "start").map(|(start, _)| start)
     }
 
+    /// `int`: The largest coordinate of the joined locations.
+    ///
+    /// .. versionchanged:: 0.4.0
+    ///    Support coordinates beyond 32-bit and nested compound locations.
+    ///
     #[getter]
-    fn get_end<'py>(slf: PyRef<'py, Self>) -> PyResult<i32> {
-        let py = slf.py();
-        let mut min: Option<i32> = None;
-        for obj in slf.locations.bind(py) {
-            let end = obj.getattr("end")?.extract::<i32>()?;
-            min = match min {
-                Some(i) if i > end => Some(i),
-                _ => Some(end),
-            }
-        }
-        min.ok_or(PyValueError::new_err(
-            "cannot get end coordinate of empty list of locations",
-        ))
+    fn get_end<'py>(slf: PyRef<'py, Self>) -> PyResult<i64> {
+        Self::bounds(&slf, "end").map(|(_, end)| end)
     }
 }
 
@@ -1023,6 +5026,21 @@ impl Order {
         let py = slf.py();
         PyString::new_bound(py, "Order({!r})").call_method1("format", (&slf.locations,))
     }
+
+    fn __getnewargs__(slf: PyRef<'_, Self>) -> (Py<PyList>,) {
+        (slf.locations.clone_ref(slf.py()),)
+    }
+
+    /// `str` or `None`: The strand of the parts of the location, or `None`
+    /// if they are on different strands or if the strand of any part is
+    /// undefined.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[getter]
+    fn get_strand(slf: PyRef<'_, Self>) -> PyResult<Option<Py<PyString>>> {
+        compound_strand(slf.py(), &slf.locations)
+    }
 }
 
 /// A location for a `Feature` corresponding to a bond between locations.
@@ -1052,6 +5070,21 @@ impl Bond {
         let py = slf.py();
         PyString::new_bound(py, "Bond({!r})").call_method1("format", (&slf.locations,))
     }
+
+    fn __getnewargs__(slf: PyRef<'_, Self>) -> (Py<PyList>,) {
+        (slf.locations.clone_ref(slf.py()),)
+    }
+
+    /// `str` or `None`: The strand of the parts of the location, or `None`
+    /// if they are on different strands or if the strand of any part is
+    /// undefined.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[getter]
+    fn get_strand(slf: PyRef<'_, Self>) -> PyResult<Option<Py<PyString>>> {
+        compound_strand(slf.py(), &slf.locations)
+    }
 }
 
 /// A location for a `Feature` located at one of the given locations.
@@ -1082,6 +5115,21 @@ impl OneOf {
         let py = slf.py();
         PyString::new_bound(py, "OneOf({!r})").call_method1("format", (&slf.locations,))
     }
+
+    fn __getnewargs__(slf: PyRef<'_, Self>) -> (Py<PyList>,) {
+        (slf.locations.clone_ref(slf.py()),)
+    }
+
+    /// `str` or `None`: The strand of the parts of the location, or `None`
+    /// if they are on different strands or if the strand of any part is
+    /// undefined.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[getter]
+    fn get_strand(slf: PyRef<'_, Self>) -> PyResult<Option<Py<PyString>>> {
+        compound_strand(slf.py(), &slf.locations)
+    }
 }
 
 /// A location for a `Feature` located in an external record.
@@ -1098,12 +5146,52 @@ pub struct External {
 
 #[pymethods]
 impl External {
+    /// Create a new external location.
+    ///
+    /// Arguments:
+    ///     accession (`str`): The accession of the external record, with
+    ///         an optional version (e.g. ``J00194.1``).
+    ///     location (`Location` or `str`, optional): The location in the
+    ///         external record, either as a `Location` object or as a
+    ///         string in GenBank format (e.g. ``"1..100"``).
+    ///
+    /// Raises:
+    ///     `ValueError`: When the location string could not be parsed.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///    Support for giving the location as a string.
+    ///
+    /// .. deprecated:: 0.4.0
+    ///    Creating an `External` with an invalid accession emits a
+    ///    `DeprecationWarning`, and will raise a `ValueError` in a future
+    ///    version.
+    ///
     #[new]
-    fn __new__(accession: String, location: Option<Py<Location>>) -> PyClassInitializer<Self> {
-        PyClassInitializer::from(Location).add_subclass(Self {
-            accession,
-            location,
-        })
+    #[pyo3(signature = (accession, location = None))]
+    fn __new__(
+        py: Python<'_>,
+        accession: String,
+        location: Option<Bound<'_, PyAny>>,
+    ) -> PyResult<PyClassInitializer<Self>> {
+        if !location::is_valid_accession(&accession) {
+            let msg = format!(
+                "invalid accession: {:?}, this will raise a ValueError in a future version",
+                accession
+            );
+            PyErr::warn_bound(py, &py.get_type_bound::<PyDeprecationWarning>(), &msg, 1)?;
+        }
+        let location = match location {
+            None => None,
+            Some(obj) => match obj.downcast::<PyString>() {
+                Ok(text) => Some(
+                    location::parse(text.to_str()?)
+                        .map_err(PyValueError::new_err)?
+                        .convert(obj.py())?,
+                ),
+                Err(_) => Some(obj.extract::<Py<Location>>()?),
+            },
+        };
+        Ok(Self::init(accession, location))
     }
 
     fn __repr__<'py>(slf: PyRef<'py, Self>) -> PyResult<Bound<'py, PyAny>> {
@@ -1116,6 +5204,23 @@ impl External {
             }
         }
     }
+
+    fn __getnewargs__(slf: PyRef<'_, Self>) -> (String, Option<Py<Location>>) {
+        let py = slf.py();
+        (
+            slf.accession.clone(),
+            slf.location.as_ref().map(|l| l.clone_ref(py)),
+        )
+    }
+}
+
+impl External {
+    fn init(accession: String, location: Option<Py<Location>>) -> PyClassInitializer<Self> {
+        PyClassInitializer::from(Location).add_subclass(Self {
+            accession,
+            location,
+        })
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -1168,38 +5273,582 @@ impl Reference {
             remark,
         })
     }
-}
 
-impl Convert for gb_io::seq::Reference {
-    type Output = Reference;
-    fn convert_with(self, py: Python, _interner: &mut PyInterner) -> PyResult<Py<Self::Output>> {
-        Py::new(
-            py,
-            Reference {
-                description: self.description,
-                authors: self.authors,
-                consortium: self.consortium,
-                title: self.title,
-                journal: self.journal,
-                pubmed: self.pubmed,
-                remark: self.remark,
-            },
-        )
+    fn __getnewargs__(slf: PyRef<'_, Self>) -> PyObject {
+        (
+            slf.title.clone(),
+            slf.description.clone(),
+            slf.authors.clone(),
+            slf.consortium.clone(),
+            slf.journal.clone(),
+            slf.pubmed.clone(),
+            slf.remark.clone(),
+        )
+            .into_py(slf.py())
+    }
+}
+
+impl Convert for gb_io::seq::Reference {
+    type Output = Reference;
+    fn convert_with(self, py: Python, _interner: &mut PyInterner) -> PyResult<Py<Self::Output>> {
+        // continuation lines of wrapped fields are joined with a space so
+        // that the fields can be re-wrapped when written
+        let unwrap = |field: String| field.replace('\n', " ");
+        Py::new(
+            py,
+            Reference {
+                description: self.description,
+                authors: self.authors.map(unwrap),
+                consortium: self.consortium,
+                title: unwrap(self.title),
+                journal: self.journal,
+                pubmed: self.pubmed,
+                remark: self.remark,
+            },
+        )
+    }
+}
+
+impl Extract for gb_io::seq::Reference {
+    fn extract(py: Python, object: Py<<Self as Convert>::Output>) -> PyResult<Self> {
+        let reference = object.bind(py).borrow();
+        Ok(gb_io::seq::Reference {
+            description: reference.description.clone(),
+            authors: reference.authors.clone(),
+            consortium: reference.consortium.clone(),
+            title: reference.title.clone(),
+            journal: reference.journal.clone(),
+            pubmed: reference.pubmed.clone(),
+            remark: reference.remark.clone(),
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+/// Parse all the records of a stream, decompressing it if needed.
+fn read_records<R: Read>(
+    py: Python,
+    stream: R,
+    normalize_kinds: Option<&KindNormalization>,
+    drop_qualifiers: Option<&QualifierFilter>,
+    threads: usize,
+    decompress: bool,
+    on_error: ErrorPolicy,
+) -> PyResult<Py<PyList>> {
+    // create the reader, decompressing the input if needed
+    let stream = Decoder::with_decompression(std::io::BufReader::new(stream), decompress)
+        .map_err(|e| PyErr::take(py).unwrap_or_else(|| convert_io_error(e)))?;
+
+    // convert each parsed record and add it to the list
+    let mut interner = PyInterner::default();
+    let records = PyList::empty_bound(py);
+    let mut append = |mut seq: gb_io::seq::Seq, record_extras: RecordExtras| -> PyResult<()> {
+        if let Some(normalize_kinds) = normalize_kinds {
+            normalize_kinds.apply(&mut seq);
+        }
+        if let Some(drop_qualifiers) = drop_qualifiers {
+            drop_qualifiers.apply(&mut seq);
+        }
+        let record = seq.convert_with(py, &mut interner)?;
+        record_extras.apply(&mut record.borrow_mut(py));
+        records.append(record)
+    };
+
+    if threads > 1 || on_error != ErrorPolicy::Raise {
+        // split the stream into records while holding the GIL, since it may
+        // be a Python file-handle, and parse them in chunks without the GIL;
+        // this also allows skipping the records that could not be parsed
+        let mut splitter = RecordSplitter::new(std::io::BufReader::new(stream));
+        loop {
+            let mut texts = Vec::new();
+            let mut offsets = Vec::new();
+            let mut size = 0;
+            while size < threads * CHUNK_SIZE {
+                match splitter.next() {
+                    None => break,
+                    Some(Ok(text)) => {
+                        size += text.len();
+                        texts.push(text);
+                        offsets.push(splitter.offset());
+                    }
+                    Some(Err(e)) => {
+                        return Err(PyErr::take(py).unwrap_or_else(|| convert_io_error(e)))
+                    }
+                }
+            }
+            if texts.is_empty() {
+                break;
+            }
+            let results = py.allow_threads(|| reader::parse_parallel(&texts, threads));
+            for (result, offset) in results.into_iter().zip(offsets) {
+                let error = match result {
+                    Some(Ok((seq, record_extras))) => {
+                        append(seq, record_extras)?;
+                        continue;
+                    }
+                    Some(Err(e)) => convert_parser_error(py, e),
+                    None => PyValueError::new_err("parser failed: incomplete record"),
+                };
+                on_error.recover(py, error, offset)?;
+            }
+        }
+    } else {
+        let extras = Extras::default();
+        let reader = SeqReader::new(ExtrasScanner::new(stream, extras.clone()));
+        for result in reader {
+            match result {
+                Ok(seq) => {
+                    let record_extras = extras.lock().unwrap().pop_front().unwrap_or_default();
+                    append(seq, record_extras)?;
+                }
+                Err(e) => return Err(convert_parser_error(py, e)),
+            }
+        }
+    }
+
+    // return records
+    Ok(records.unbind())
+}
+
+/// Write records to memory with the given formatting options, as a string.
+#[allow(clippy::too_many_arguments)]
+fn write_string(
+    py: Python,
+    records: Bound<PyIterator>,
+    escape_locus: bool,
+    truncate_locus: bool,
+    wrap_width: usize,
+    format: Format,
+    line_width: usize,
+    validate: bool,
+) -> PyResult<String> {
+    check_wrap_width(wrap_width)?;
+    check_line_width(line_width)?;
+    let mut writer = SeqWriter::new(Vec::new());
+    writer
+        .truncate_locus(truncate_locus)
+        .escape_locus(escape_locus)
+        .wrap_width(wrap_width)
+        .field_width(Some(wrap_width))
+        .format(format)
+        .line_width(line_width)
+        .validate(validate);
+    let (buffer, _) = write_records(py, records, writer)?;
+    String::from_utf8(buffer).map_err(|_| PyValueError::new_err("record text is not valid UTF-8"))
+}
+
+/// Check that a line width leaves enough room to wrap the records.
+fn check_wrap_width(width: usize) -> PyResult<()> {
+    check_width("wrap_width", width)
+}
+
+/// Check that a number of bases per sequence line is not zero.
+fn check_line_width(width: usize) -> PyResult<()> {
+    if width == 0 {
+        Err(PyValueError::new_err("line_width must be at least 1"))
+    } else {
+        Ok(())
+    }
+}
+
+/// Check that the line width given for an argument is large enough.
+fn check_width(name: &str, width: usize) -> PyResult<()> {
+    if width < writer::MIN_WIDTH {
+        Err(PyValueError::new_err(format!(
+            "{} must be at least {}, got {}",
+            name,
+            writer::MIN_WIDTH,
+            width
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+/// Check that a compression level is between 0 and 9.
+fn check_compresslevel(level: u32) -> PyResult<()> {
+    if level > 9 {
+        Err(PyValueError::new_err(format!(
+            "invalid compression level: {}",
+            level
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+/// Create a writer configured with the formatting options of `gb_io.dump`.
+///
+/// The writer writes to an empty sink, and must be given a stream with
+/// `SeqWriter::with_stream` before writing records.
+#[allow(clippy::too_many_arguments)]
+fn configure_writer<'py>(
+    py: Python<'py>,
+    escape_locus: bool,
+    truncate_locus: bool,
+    emit_gi: bool,
+    unquoted_qualifiers: Option<Bound<'py, PyAny>>,
+    date: Option<Bound<'py, PyAny>>,
+    simplify_locations: bool,
+    wrap_width: usize,
+    linearize_circular: bool,
+    wrap_definition: Option<Bound<'py, PyAny>>,
+    canonical_qualifier_order: Option<Bound<'py, PyAny>>,
+    format: Format,
+    line_width: usize,
+    validate: bool,
+) -> PyResult<SeqWriter<std::io::Sink>> {
+    check_wrap_width(wrap_width)?;
+    check_line_width(line_width)?;
+
+    // get the width of the header fields, if they must be wrapped
+    let field_width = match wrap_definition {
+        None => Some(wrap_width),
+        Some(wrap) => match wrap.downcast::<PyBool>() {
+            Ok(wrap) => Some(wrap_width).filter(|_| wrap.is_true()),
+            Err(_) => {
+                let width = wrap.extract::<usize>()?;
+                check_width("wrap_definition", width)?;
+                Some(width)
+            }
+        },
+    };
+
+    // get the priority list of the qualifiers, if they must be sorted
+    let qualifier_order = match canonical_qualifier_order {
+        None => None,
+        Some(order) => match order.downcast::<PyBool>() {
+            Ok(canonical) => canonical
+                .is_true()
+                .then(|| writer::CANONICAL_QUALIFIER_ORDER.to_vec()),
+            Err(_) if order.is_instance_of::<PyString>() => {
+                return Err(PyTypeError::new_err(
+                    "expected bool or iterable of str for canonical_qualifier_order",
+                ));
+            }
+            Err(_) => {
+                let mut keys = Vec::new();
+                for key in order.iter()? {
+                    keys.push(gb_io::QualifierKey::from(
+                        key?.downcast::<PyString>()?.to_str()?,
+                    ));
+                }
+                Some(keys)
+            }
+        },
+    };
+
+    // collect the additional unquoted qualifier keys
+    let mut unquoted = Vec::new();
+    if let Some(keys) = unquoted_qualifiers {
+        for key in keys.iter()? {
+            unquoted.push(gb_io::QualifierKey::from(
+                key?.downcast::<PyString>()?.to_str()?,
+            ));
+        }
+    }
+
+    // extract the date to write instead of the record dates
+    let date = match date {
+        None => None,
+        Some(date) => {
+            let date = match date.downcast::<PyString>() {
+                Ok(s) => py
+                    .import_bound("datetime")?
+                    .getattr("date")?
+                    .call_method1("fromisoformat", (s,))?,
+                Err(_) => date,
+            };
+            let date = date.downcast_into::<PyDate>()?;
+            Some(Extract::extract(py, date.unbind())?)
+        }
+    };
+
+    let mut writer = SeqWriter::new(std::io::sink());
+    writer
+        .truncate_locus(truncate_locus)
+        .escape_locus(escape_locus)
+        .emit_gi(emit_gi)
+        .simplify_locations(simplify_locations)
+        .wrap_width(wrap_width)
+        .field_width(field_width)
+        .linearize_circular(linearize_circular)
+        .qualifier_order(qualifier_order)
+        .date(date)
+        .format(format)
+        .line_width(line_width)
+        .validate(validate)
+        .unquoted_qualifiers(unquoted);
+    Ok(writer)
+}
+
+/// Write a single record, with its index in the error messages.
+fn write_record<W: Write>(
+    py: Python,
+    writer: &mut SeqWriter<W>,
+    index: usize,
+    record: Py<Record>,
+) -> PyResult<()> {
+    let mut extras = RecordExtras::from(&*record.borrow(py));
+    // the quoting is indexed by the position of the features and of their
+    // qualifiers, which `Extract` preserves, and the record cannot change
+    // in between since the GIL is held
+    extras.quoted = record.borrow(py).quoting(py)?;
+    let seq: gb_io::seq::Seq = Extract::extract(py, record)?;
+    // write the seq, with the record identifier in the error message
+    if let Err(err) = writer.write(&seq, &extras) {
+        // errors raised by a Python file handle take precedence
+        if let Some(error) = PyErr::take(py) {
+            return Err(error);
+        }
+        let id = Record::display_id(
+            seq.version.as_deref(),
+            seq.accession.as_deref(),
+            seq.name.as_deref(),
+        );
+        let context = match id {
+            Some(id) => format!("failed to write record {} ({})", index, id),
+            None => format!("failed to write record {}", index),
+        };
+        let invalid = err
+            .get_ref()
+            .filter(|e| e.is::<InvalidFeature>() || e.is::<InvalidSequence>());
+        return match invalid {
+            Some(invalid) => Err(PyValueError::new_err(format!("{}: {}", context, invalid))),
+            None => match err.raw_os_error() {
+                Some(code) => Err(PyIOError::new_err((code, format!("{}: {}", context, err)))),
+                None => Err(PyIOError::new_err(format!("{}: {}", context, err))),
+            },
+        };
+    }
+    Ok(())
+}
+
+/// Write all the records from a Python iterator to the given stream.
+///
+/// The stream is returned along with the number of bytes written to it.
+/// Errors are reported with the index and the accession of the record
+/// that failed to be written, and invalid features raise a `ValueError`
+/// naming the offending feature.
+fn write_records<W: Write>(
+    py: Python,
+    records: Bound<PyIterator>,
+    mut writer: SeqWriter<W>,
+) -> PyResult<(W, usize)> {
+    let convert_error = |err: std::io::Error| match err.raw_os_error() {
+        Some(code) => PyIOError::new_err((code, err.to_string())),
+        None => PyIOError::new_err(err.to_string()),
+    };
+
+    // write sequences
+    for (index, result) in records.enumerate() {
+        // make sure we received a Record object
+        let record = result?.extract::<Py<Record>>()?;
+        write_record(py, &mut writer, index, record)?;
+    }
+
+    // make sure everything was written to the stream
+    writer.flush().map_err(convert_error)?;
+    let written = writer.bytes_written();
+    Ok((writer.into_inner(), written))
+}
+
+// ---------------------------------------------------------------------------
+
+/// The output stream of a `Writer`.
+type Output = Encoder<Box<dyn Write + Send>>;
+
+/// A writer for `~gb_io.Record` objects, to a path or a file handle.
+///
+/// Writers accept the same formatting options as `gb_io.dump`, but let
+/// records be written one at a time, without collecting them first, e.g.
+/// while they are being generated. Use the writer as a context manager to
+/// make sure the end of compressed files is written once done.
+///
+/// Arguments:
+///     fh (`str` or file-handle): The path to the file to write, which
+///         is compressed if it ends with ``.gz`` or ``.zst``, or a file
+///         handle opened in binary or text mode.
+///
+/// Keyword Arguments:
+///     escape_locus (`bool`): Pass `True` to escape any whitespace in
+///         the locus name with an underscore character.
+///     truncate_locus (`bool`): Pass `True` to trim the locus fields
+///         so that the locus line is no longer than 79 characters.
+///
+/// The other keyword arguments are the formatting options of `gb_io.dump`.
+///
+/// Example:
+///     Write records to a file as they are created::
+///
+///         >>> import io
+///         >>> buffer = io.BytesIO()
+///         >>> with gb_io.Writer(buffer) as writer:
+///         ...     for name in ("A", "B"):
+///         ...         writer.write(gb_io.Record(b"ATGC", name=name))
+///         >>> [record.name for record in gb_io.loads(buffer.getvalue())]
+///         ['A', 'B']
+///
+/// .. versionadded:: 0.4.0
+///
+#[pyclass(module = "gb_io")]
+pub struct Writer {
+    writer: Option<SeqWriter<Output>>,
+    count: usize,
+}
+
+impl Writer {
+    /// Get the underlying writer, unless the writer was closed.
+    fn writer(&mut self) -> PyResult<&mut SeqWriter<Output>> {
+        self.writer
+            .as_mut()
+            .ok_or_else(|| PyValueError::new_err("I/O operation on closed writer"))
+    }
+}
+
+impl Drop for Writer {
+    fn drop(&mut self) {
+        // finish compressed files if the writer was not closed, reporting
+        // the errors which cannot be raised anymore as unraisable
+        if let Some(writer) = self.writer.take() {
+            if let Err(e) = writer.into_inner().finish() {
+                Python::with_gil(|py| {
+                    let err = PyErr::take(py).unwrap_or_else(|| convert_io_error(e));
+                    err.write_unraisable_bound(py, None);
+                });
+            }
+        }
+    }
+}
+
+#[pymethods]
+impl Writer {
+    #[new]
+    #[pyo3(
+        signature = (fh, *, escape_locus = false, truncate_locus = false, compresslevel = 9, emit_gi = false, unquoted_qualifiers = None, date = None, simplify_locations = false, wrap_width = writer::MAX_WIDTH, linearize_circular = false, wrap_definition = None, canonical_qualifier_order = None, format = Format::GenBank, line_width = writer::SEQUENCE_WIDTH, validate = false),
+        text_signature = "(fh, *, escape_locus=False, truncate_locus=False, compresslevel=9, emit_gi=False, unquoted_qualifiers=None, date=None, simplify_locations=False, wrap_width=79, linearize_circular=False, wrap_definition=None, canonical_qualifier_order=None, format='genbank', line_width=60, validate=False)"
+    )]
+    #[allow(clippy::too_many_arguments)]
+    fn __new__<'py>(
+        py: Python<'py>,
+        fh: Bound<'py, PyAny>,
+        escape_locus: bool,
+        truncate_locus: bool,
+        compresslevel: u32,
+        emit_gi: bool,
+        unquoted_qualifiers: Option<Bound<'py, PyAny>>,
+        date: Option<Bound<'py, PyAny>>,
+        simplify_locations: bool,
+        wrap_width: usize,
+        linearize_circular: bool,
+        wrap_definition: Option<Bound<'py, PyAny>>,
+        canonical_qualifier_order: Option<Bound<'py, PyAny>>,
+        format: Format,
+        line_width: usize,
+        validate: bool,
+    ) -> PyResult<Self> {
+        check_compresslevel(compresslevel)?;
+        let options = configure_writer(
+            py,
+            escape_locus,
+            truncate_locus,
+            emit_gi,
+            unquoted_qualifiers,
+            date,
+            simplify_locations,
+            wrap_width,
+            linearize_circular,
+            wrap_definition,
+            canonical_qualifier_order,
+            format,
+            line_width,
+            validate,
+        )?;
+
+        let output: Output = if let Ok(s) = fh.downcast::<PyString>() {
+            let path = s.to_str()?;
+            let file: Box<dyn Write + Send> =
+                Box::new(std::fs::File::create(path).map_err(convert_io_error)?);
+            Encoder::for_path(path, file, compresslevel)?
+        } else {
+            match PyFileGILWrite::from_ref(fh) {
+                Ok(f) => Encoder::Plain(Box::new(f)),
+                Err(e) => {
+                    let err = PyTypeError::new_err("expected path or file handle");
+                    err.set_cause(py, Some(e));
+                    return Err(err);
+                }
+            }
+        };
+
+        Ok(Writer {
+            writer: Some(options.with_stream(output)),
+            count: 0,
+        })
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    #[pyo3(signature = (*_args))]
+    fn __exit__(slf: PyRefMut<'_, Self>, _args: Bound<'_, PyAny>) -> PyResult<bool> {
+        Writer::close(slf)?;
+        Ok(false)
+    }
+
+    /// `bool`: Whether the writer has been closed.
+    #[getter]
+    fn get_closed(&self) -> bool {
+        self.writer.is_none()
+    }
+
+    /// Write a record.
+    ///
+    /// Arguments:
+    ///     record (`Record`): The record to write.
+    ///
+    /// Raises:
+    ///     `ValueError`: When a feature cannot be written, as with
+    ///         `gb_io.dump`, or when the writer is closed. The index
+    ///         given in error messages counts the records written so far.
+    ///     `OSError`: When writing to the file fails.
+    ///
+    fn write(mut slf: PyRefMut<'_, Self>, record: Py<Record>) -> PyResult<()> {
+        let py = slf.py();
+        let index = slf.count;
+        write_record(py, slf.writer()?, index, record)?;
+        slf.count += 1;
+        Ok(())
+    }
+
+    /// Flush the records written so far to the underlying stream.
+    ///
+    /// The records of compressed files may still be buffered by the
+    /// compressor until the writer is closed.
+    ///
+    fn flush(mut slf: PyRefMut<'_, Self>) -> PyResult<()> {
+        let py = slf.py();
+        slf.writer()?
+            .flush()
+            .map_err(|e| PyErr::take(py).unwrap_or_else(|| convert_io_error(e)))
     }
-}
 
-impl Extract for gb_io::seq::Reference {
-    fn extract(py: Python, object: Py<<Self as Convert>::Output>) -> PyResult<Self> {
-        let reference = object.bind(py).borrow();
-        Ok(gb_io::seq::Reference {
-            description: reference.description.clone(),
-            authors: reference.authors.clone(),
-            consortium: reference.consortium.clone(),
-            title: reference.title.clone(),
-            journal: reference.journal.clone(),
-            pubmed: reference.pubmed.clone(),
-            remark: reference.remark.clone(),
-        })
+    /// Close the writer, writing the end of compressed files.
+    ///
+    /// File handles given to the writer are flushed, but not closed.
+    /// Closing a closed writer has no effect.
+    ///
+    fn close(mut slf: PyRefMut<'_, Self>) -> PyResult<()> {
+        let py = slf.py();
+        if let Some(writer) = slf.writer.take() {
+            writer
+                .into_inner()
+                .finish()
+                .map_err(|e| PyErr::take(py).unwrap_or_else(|| convert_io_error(e)))?;
+        }
+        Ok(())
     }
 }
 
@@ -1238,6 +5887,7 @@ pub fn init(py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     m.add_class::<self::Location>()?;
     m.add_class::<self::Range>()?;
     m.add_class::<self::Complement>()?;
+    m.add_class::<self::Site>()?;
     m.add_class::<self::Between>()?;
     m.add_class::<self::Join>()?;
     m.add_class::<self::Order>()?;
@@ -1246,10 +5896,33 @@ pub fn init(py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     m.add_class::<self::External>()?;
     m.add_class::<self::Qualifier>()?;
     m.add_class::<self::Feature>()?;
+    m.add_class::<self::FeatureIterator>()?;
     m.add_class::<self::Record>()?;
     m.add_class::<self::RecordReader>()?;
+    m.add_class::<self::RecordBatchReader>()?;
+    m.add_class::<self::GrepReader>()?;
+    m.add_class::<self::RecordIndex>()?;
+    m.add_class::<self::Writer>()?;
     m.add_class::<self::Reference>()?;
     m.add_class::<self::Source>()?;
+    m.add(
+        "FeatureKeys",
+        keys::namespace(
+            py,
+            "FeatureKeys",
+            "The feature keys of the INSDC feature table.\n\n.. versionadded:: 0.4.0\n",
+            keys::FEATURE_KEYS,
+        )?,
+    )?;
+    m.add(
+        "QualifierKeys",
+        keys::namespace(
+            py,
+            "QualifierKeys",
+            "The qualifier keys of the INSDC feature table.\n\n.. versionadded:: 0.4.0\n",
+            keys::QUALIFIER_KEYS,
+        )?,
+    )?;
     m.add("__package__", "gb_io")?;
     m.add("__build__", pyo3_built!(py, built))?;
     m.add("__version__", env!("CARGO_PKG_VERSION"))?;
@@ -1260,26 +5933,87 @@ pub fn init(py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     /// Arguments:
     ///     fh (`str` or file-handle): The path to a GenBank file, or a
     ///         stream that contains data serialized in GenBank format.
+    ///     normalize_kinds (`str` or `dict`, optional): A normalization to
+    ///         apply to the kinds of the features, either ``"lower"`` or
+    ///         ``"upper"`` to change their case, or a `dict` mapping kinds
+    ///         to their replacement. Kinds are left unchanged by default.
+    ///     drop_qualifiers (iterable of `str`, optional): The keys of the
+    ///         qualifiers to remove from the features, e.g. ``{"translation"}``
+    ///         to save memory. The removed qualifiers are never converted
+    ///         to Python objects.
+    ///     threads (`int`): The number of threads to use to parse the
+    ///         records. Pass ``0`` to use as many threads as there are
+    ///         available CPUs. By default, records are parsed on the
+    ///         calling thread.
+    ///     decompress (`bool`): Pass `False` to read the input unchanged,
+    ///         without detecting compressed data from its first bytes.
+    ///     on_error (`str`): The policy for records that could not be
+    ///         parsed, either ``raise`` to raise a `ValueError`, ``skip``
+    ///         to skip them silently, or ``warn`` to emit a `UserWarning`
+    ///         with the offset of each such record and skip it.
     ///
     /// Returns:
-    ///     `list` of `Record`: A list containing all the records in the file.
+    ///     `list` of `Record`: A list containing all the records in the file,
+    ///     in file order.
+    ///
+    /// Hint:
+    ///     Gzip-compressed inputs are detected from their magic bytes and
+    ///     decompressed transparently, whether ``fh`` is a path or a binary
+    ///     file-handle. Zstandard-compressed inputs are supported as well
+    ///     when the extension was built with the ``zstd`` feature.
+    ///
+    /// Hint:
+    ///     When the extension was built with the ``http`` feature, ``fh``
+    ///     can also be an ``http://`` or ``https://`` URL, whose response
+    ///     is streamed to the parser without being downloaded in full.
+    ///     Other URL schemes raise a `ValueError`.
+    ///
+    /// Hint:
+    ///     With several ``threads``, the file is split into records at
+    ///     their ``//`` terminator, and chunks of records are parsed in
+    ///     parallel without holding the GIL, which is only acquired to
+    ///     read the file and to create the `Record` objects. This mostly
+    ///     helps with large files containing many records, but needs
+    ///     memory for the raw text of a few megabytes of records per
+    ///     thread.
+    ///
+    /// Hint:
+    ///     Unless ``on_error`` is ``raise``, the file is split into records
+    ///     before they are parsed, as with several ``threads``. A record
+    ///     which could not be parsed ends at its ``//`` terminator, or
+    ///     before the next ``LOCUS`` line if it has no terminator, and
+    ///     reading resumes with the following record.
+    ///
+    /// .. versionchanged:: 0.4.0
+    ///    Detect and decompress compressed inputs, read from HTTP URLs,
+    ///    and added the ``normalize_kinds``, ``drop_qualifiers``, ``threads``,
+    ///    ``decompress`` and ``on_error`` keyword arguments.
     ///
     #[pyfn(m)]
-    #[pyo3(name = "load", text_signature = "(fh)")]
-    fn load(py: Python, fh: &Bound<PyAny>) -> PyResult<Py<PyList>> {
+    #[pyo3(
+        name = "load",
+        signature = (fh, *, normalize_kinds = None, drop_qualifiers = None, threads = 1, decompress = true, on_error = ErrorPolicy::Raise),
+        text_signature = "(fh, *, normalize_kinds=None, drop_qualifiers=None, threads=1, decompress=True, on_error='raise')"
+    )]
+    fn load(
+        py: Python,
+        fh: &Bound<PyAny>,
+        normalize_kinds: Option<KindNormalization>,
+        drop_qualifiers: Option<QualifierFilter>,
+        threads: usize,
+        decompress: bool,
+        on_error: ErrorPolicy,
+    ) -> PyResult<Py<PyList>> {
+        let threads = match threads {
+            0 => std::thread::available_parallelism().map_or(1, |n| n.get()),
+            n => n,
+        };
+
         // extract either a path or a file-handle from the arguments
         // let path: Option<String>;
         let stream: Box<dyn Read> = if let Ok(s) = fh.downcast::<PyString>() {
-            // get a buffered reader to the resources pointed by `path`
-            let bf = match std::fs::File::open(s.to_str()?) {
-                Ok(f) => f,
-                Err(e) => {
-                    return match e.raw_os_error() {
-                        Some(code) => Err(PyOSError::new_err((code, e.to_string()))),
-                        None => Err(PyOSError::new_err(e.to_string())),
-                    }
-                }
-            };
+            // get a reader to the resources pointed by `path`
+            let bf = Handle::open(s.to_str()?)?;
             // store the path for later
             // path = Some(s.to_str()?.to_string());
             // send the file reader to the heap.
@@ -1302,35 +6036,80 @@ pub fn init(py: Python, m: &Bound<PyModule>) -> PyResult<()> {
             Box::new(bf)
         };
 
-        // create the reader
-        let reader = SeqReader::new(stream);
-
-        // parse all records
-        let mut interner = PyInterner::default();
-        let records = PyList::empty_bound(py);
-        for result in reader {
-            match result {
-                Ok(seq) => {
-                    records.append(Py::new(py, seq.convert_with(py, &mut interner)?)?)?;
-                }
-                Err(GbParserError::Io(e)) => {
-                    return match e.raw_os_error() {
-                        Some(code) => Err(PyOSError::new_err((code, e.to_string()))),
-                        None => match PyErr::take(py) {
-                            Some(e) => Err(e),
-                            None => Err(PyOSError::new_err(e.to_string())),
-                        },
-                    };
-                }
-                Err(GbParserError::SyntaxError(e)) => {
-                    let msg = format!("parser failed: {}", e);
-                    return Err(PyValueError::new_err(msg));
-                }
-            }
-        }
+        read_records(
+            py,
+            stream,
+            normalize_kinds.as_ref(),
+            drop_qualifiers.as_ref(),
+            threads,
+            decompress,
+            on_error,
+        )
+    }
 
-        // return records
-        Ok(records.unbind())
+    /// Load all GenBank records from a string or a bytes object.
+    ///
+    /// Arguments:
+    ///     data (`str` or `bytes`): The GenBank records, serialized in
+    ///         GenBank format. Strings are encoded to UTF-8 before parsing.
+    ///     normalize_kinds (`str` or `dict`, optional): A normalization to
+    ///         apply to the kinds of the features, as with `gb_io.load`.
+    ///     drop_qualifiers (iterable of `str`, optional): The keys of the
+    ///         qualifiers to remove from the features, as with `gb_io.load`.
+    ///
+    /// Returns:
+    ///     `list` of `Record`: A list containing all the records in the data.
+    ///
+    /// Raises:
+    ///     `TypeError`: When ``data`` is neither a `str` nor a `bytes` object.
+    ///     `ValueError`: When the data could not be parsed.
+    ///
+    /// Example:
+    ///     Parse a record written to memory by `gb_io.dump`::
+    ///
+    ///         >>> data = gb_io.dump(gb_io.Record(b"ATGC", name="TEST")).getvalue()
+    ///         >>> gb_io.loads(data)[0].name
+    ///         'TEST'
+    ///         >>> gb_io.loads(data.decode())[0].sequence
+    ///         bytearray(b'ATGC')
+    ///
+    /// Hint:
+    ///     Compressed `bytes` are decompressed transparently, as with
+    ///     `gb_io.load`.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyfn(m)]
+    #[pyo3(
+        name = "loads",
+        signature = (data, *, normalize_kinds = None, drop_qualifiers = None),
+        text_signature = "(data, *, normalize_kinds=None, drop_qualifiers=None)"
+    )]
+    fn loads(
+        py: Python,
+        data: &Bound<PyAny>,
+        normalize_kinds: Option<KindNormalization>,
+        drop_qualifiers: Option<QualifierFilter>,
+    ) -> PyResult<Py<PyList>> {
+        let bytes = if let Ok(s) = data.downcast::<PyString>() {
+            s.to_str()?.as_bytes()
+        } else if let Ok(b) = data.downcast::<PyBytes>() {
+            b.as_bytes()
+        } else {
+            return Err(PyTypeError::new_err(format!(
+                "expected str or bytes, found {}",
+                data.get_type().name()?
+            )));
+        };
+        read_records(
+            py,
+            std::io::Cursor::new(bytes),
+            normalize_kinds.as_ref(),
+            drop_qualifiers.as_ref(),
+            1,
+            true,
+            ErrorPolicy::Raise,
+        )
     }
 
     /// Iterate over the GenBank records in the given file or file handle.
@@ -1339,18 +6118,395 @@ pub fn init(py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     ///     fh (`str` or file-handle): The path to a GenBank file, or a
     ///         stream that contains data serialized in GenBank format.
     ///
+    ///     with_text (`bool`): Pass `True` to yield the verbatim text of
+    ///         each record alongside the parsed `Record`.
+    ///     normalize_kinds (`str` or `dict`, optional): A normalization to
+    ///         apply to the kinds of the features, as with `gb_io.load`.
+    ///     drop_qualifiers (iterable of `str`, optional): The keys of the
+    ///         qualifiers to remove from the features, as with `gb_io.load`.
+    ///     reuse (`bool`): Pass `True` to fill and return the same `Record`
+    ///         object for every record of the file, instead of creating a
+    ///         new one each time. See the caution below.
+    ///     prefetch (`int`): The number of records to parse ahead on a
+    ///         background thread, or ``0`` to parse each record when the
+    ///         iterator advances. See the hint below.
+    ///     require_qualifiers (`dict`, optional): A mapping of feature
+    ///         kinds to the keys of the qualifiers that every feature of
+    ///         that kind must have, e.g. ``{"CDS": ["locus_tag", "product"]}``.
+    ///         Requirements are checked after ``normalize_kinds`` and
+    ///         ``drop_qualifiers`` are applied.
+    ///     errors (`str`): The policy for features missing a required
+    ///         qualifier, either ``raise`` to raise a `ValueError`, ``skip``
+    ///         to skip their records silently, or ``warn`` to emit a
+    ///         `UserWarning` for each such feature and yield the record
+    ///         anyway.
+    ///     decompress (`bool`): Pass `False` to read the input unchanged,
+    ///         without detecting compressed data, as with `gb_io.load`.
+    ///     on_error (`str`): The policy for records that could not be
+    ///         parsed, either ``raise``, ``skip`` or ``warn``, as with
+    ///         `gb_io.load`. Records cannot be prefetched unless it is
+    ///         ``raise``.
+    ///
     /// Returns:
     ///     `~gb_io.RecordReader`: An iterator over the GenBank records in
-    ///     the given file or file-handle.
+    ///     the given file or file-handle. When ``with_text`` is `True`,
+    ///     the iterator yields ``(text, record)`` tuples, where ``text``
+    ///     is a `bytes` object with the original content of the record,
+    ///     from its ``LOCUS`` line to its ``//`` terminator.
+    ///
+    /// Raises:
+    ///     `RuntimeError`: When collecting the records of a reader created
+    ///         with ``reuse=True``, e.g. with `list`.
+    ///     `ValueError`: When ``prefetch`` is not zero and ``with_text``
+    ///         is `True` or ``on_error`` is not ``raise``, when a record
+    ///         could not be parsed with ``on_error="raise"``, or when a
+    ///         feature misses a required qualifier with ``errors="raise"``.
+    ///         The error message gives the index of the feature and the
+    ///         accession of its record.
+    ///     `OSError`: When the file could not be read, with the error
+    ///         code of the original error, if any.
+    ///
+    /// Hint:
+    ///     Compressed inputs are decompressed transparently, and HTTP
+    ///     URLs are streamed, as with `gb_io.load`.
+    ///
+    /// Example:
+    ///     Check that all coding sequences have a ``/gene`` and a
+    ///     ``/protein_id`` qualifier, using a specification that could be
+    ///     loaded from a JSON file::
+    ///
+    ///         >>> path = "tests/data/biopython/cor6_6.gb"
+    ///         >>> spec = {"CDS": ["gene", "protein_id"]}
+    ///         >>> len(list(gb_io.iter(path, require_qualifiers=spec)))
+    ///         6
+    ///         >>> next(gb_io.iter(path, require_qualifiers={"CDS": "product"}))
+    ///         Traceback (most recent call last):
+    ///           ...
+    ///         ValueError: feature 2 (CDS) of record X55053 is missing required qualifiers: product
+    ///
+    /// Hint:
+    ///     With ``prefetch``, records are read and parsed by a worker
+    ///     thread while the loop body runs, which helps when reading
+    ///     large files from slow storage. Only the conversion to Python
+    ///     objects happens when the iterator advances. Errors encountered
+    ///     by the worker are raised when the iterator reaches the failed
+    ///     record.
+    ///
+    /// Caution:
+    ///     With ``reuse=True``, each iteration overwrites the content of
+    ///     the record returned by the previous one, so records must not
+    ///     be retained across iterations: a reference kept to a record
+    ///     will see the content of the next record once the iterator
+    ///     advances. Attributes obtained from a record before that keep
+    ///     their values. Copy the record with `copy.deepcopy` to keep it.
+    ///     This mode is only useful to scan large files in tight loops,
+    ///     where each record is processed and discarded right away.
+    ///
+    /// .. versionchanged:: 0.4.0
+    ///    Added the ``with_text``, ``normalize_kinds``, ``drop_qualifiers``,
+    ///    ``reuse``, ``prefetch``, ``require_qualifiers``, ``errors``,
+    ///    ``decompress`` and ``on_error`` keyword arguments, detect and
+    ///    decompress compressed inputs, and read from HTTP URLs. Raise a
+    ///    `ValueError` instead of a `RuntimeError` when a record could not
+    ///    be parsed.
+    ///
+    #[pyfn(m)]
+    #[pyo3(
+        name = "iter",
+        signature = (fh, *, with_text = false, normalize_kinds = None, drop_qualifiers = None, reuse = false, prefetch = 0, require_qualifiers = None, errors = ErrorPolicy::Raise, decompress = true, on_error = ErrorPolicy::Raise),
+        text_signature = "(fh, *, with_text=False, normalize_kinds=None, drop_qualifiers=None, reuse=False, prefetch=0, require_qualifiers=None, errors='raise', decompress=True, on_error='raise')"
+    )]
+    #[allow(clippy::too_many_arguments)]
+    fn iter(
+        py: Python,
+        fh: Bound<PyAny>,
+        with_text: bool,
+        normalize_kinds: Option<KindNormalization>,
+        drop_qualifiers: Option<QualifierFilter>,
+        reuse: bool,
+        prefetch: usize,
+        require_qualifiers: Option<QualifierRequirements>,
+        errors: ErrorPolicy,
+        decompress: bool,
+        on_error: ErrorPolicy,
+    ) -> PyResult<Py<RecordReader>> {
+        let mut reader = match fh.downcast::<PyString>() {
+            Ok(s) => RecordReader::from_path(
+                py,
+                s.to_str()?,
+                with_text,
+                normalize_kinds,
+                decompress,
+                on_error,
+            )?,
+            Err(_) => {
+                RecordReader::from_handle(fh, with_text, normalize_kinds, decompress, on_error)?
+            }
+        };
+        reader
+            .reuse_records(reuse)
+            .drop_qualifiers(drop_qualifiers)
+            .require_qualifiers(require_qualifiers, errors);
+        Py::new(py, reader.prefetch(prefetch)?)
+    }
+
+    /// Iterate over batches of GenBank records in the given file or file handle.
+    ///
+    /// Grouping records in batches reduces the overhead of iterating over
+    /// many small records, and allows parsing each batch without holding
+    /// the GIL, so that other Python threads can run in the meantime.
+    ///
+    /// Arguments:
+    ///     fh (`str` or file-handle): The path to a GenBank file, or a
+    ///         stream that contains data serialized in GenBank format.
+    ///     batch_size (`int`): The maximum number of records in each batch.
+    ///
+    ///     normalize_kinds (`str` or `dict`, optional): A normalization to
+    ///         apply to the kinds of the features, as with `gb_io.load`.
+    ///     release_gil (`bool`): Pass `False` to keep holding the GIL while
+    ///         parsing the records of a batch.
+    ///
+    /// Returns:
+    ///     `~gb_io.RecordBatchReader`: An iterator over `list` of `Record`
+    ///     objects, all of ``batch_size`` records except possibly the last
+    ///     one, which holds the remaining records of the file.
+    ///
+    /// Raises:
+    ///     `ValueError`: When ``batch_size`` is not strictly positive.
+    ///
+    /// Example:
+    ///     Read the six records of a file in batches of four records::
+    ///
+    ///         >>> path = "tests/data/biopython/cor6_6.gb"
+    ///         >>> [len(batch) for batch in gb_io.iter_batches(path, 4)]
+    ///         [4, 2]
+    ///
+    /// Hint:
+    ///     When a record of a batch fails to parse, the error is raised
+    ///     and the records of the batch parsed before it are discarded.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyfn(m)]
+    #[pyo3(
+        name = "iter_batches",
+        signature = (fh, batch_size = 1000, *, normalize_kinds = None, release_gil = true),
+        text_signature = "(fh, batch_size=1000, *, normalize_kinds=None, release_gil=True)"
+    )]
+    fn iter_batches(
+        py: Python,
+        fh: Bound<PyAny>,
+        batch_size: usize,
+        normalize_kinds: Option<KindNormalization>,
+        release_gil: bool,
+    ) -> PyResult<Py<RecordBatchReader>> {
+        let reader = match fh.downcast::<PyString>() {
+            Ok(s) => RecordReader::from_path(
+                py,
+                s.to_str()?,
+                false,
+                normalize_kinds,
+                true,
+                ErrorPolicy::Raise,
+            )?,
+            Err(_) => {
+                RecordReader::from_handle(fh, false, normalize_kinds, true, ErrorPolicy::Raise)?
+            }
+        };
+        Py::new(py, RecordBatchReader::new(reader, batch_size, release_gil)?)
+    }
+
+    /// Search the GenBank records of a file for a regular expression.
+    ///
+    /// Records are streamed from the file, and matched against the pattern
+    /// before being converted to Python objects, so that searching a large
+    /// file is efficient when few records match.
+    ///
+    /// Arguments:
+    ///     fh (`str` or file-handle): The path to a GenBank file, or a
+    ///         stream that contains data serialized in GenBank format.
+    ///     pattern (`str`): The regular expression to search for.
+    ///
+    ///     fields (iterable of `str`, optional): The fields of the records
+    ///         to search, in order, among ``definition`` for the
+    ///         ``DEFINITION`` line, ``features`` for the qualifier values of
+    ///         the features, and ``organism`` for the ``ORGANISM`` line of
+    ///         the source. Defaults to ``("definition", "features")``.
+    ///
+    /// Returns:
+    ///     `~gb_io.GrepReader`: An iterator over ``(record, field)`` tuples
+    ///     for each record matching the pattern, where ``field`` is the
+    ///     name of the first field in ``fields`` that matched.
+    ///
+    /// Raises:
+    ///     `ValueError`: When the pattern is not a valid regular expression,
+    ///         or when a field is unknown.
+    ///
+    /// Example:
+    ///     Find the records of a file mentioning a *kin* gene, and the
+    ///     records of a given genus::
+    ///
+    ///         >>> path = "tests/data/biopython/cor6_6.gb"
+    ///         >>> for record, field in gb_io.grep(path, r"kin\d"):
+    ///         ...     print(record.name, field)
+    ///         ATKIN2 definition
+    ///         BNAKINI definition
+    ///         >>> matches = gb_io.grep(path, "^Brassica", fields=["organism"])
+    ///         >>> [record.name for record, field in matches]
+    ///         ['BNAKINI', 'BRRBIF72', 'AF297471']
+    ///
+    /// Caution:
+    ///     The pattern is compiled with the Rust `regex
+    ///     <https://docs.rs/regex>`_ crate, whose syntax is close to the
+    ///     one of the `re` module, but which does not support look-around
+    ///     assertions nor backreferences.
+    ///
+    /// .. versionadded:: 0.4.0
     ///
     #[pyfn(m)]
-    #[pyo3(name = "iter", text_signature = "(fh)")]
-    fn iter(py: Python, fh: Bound<PyAny>) -> PyResult<Py<RecordReader>> {
+    #[pyo3(
+        name = "grep",
+        signature = (fh, pattern, *, fields = None),
+        text_signature = "(fh, pattern, *, fields=None)"
+    )]
+    fn grep(
+        py: Python,
+        fh: Bound<PyAny>,
+        pattern: &str,
+        fields: Option<Bound<PyAny>>,
+    ) -> PyResult<Py<GrepReader>> {
+        let fields = match fields {
+            None => vec![Field::Definition, Field::Features],
+            Some(fields) if fields.is_instance_of::<PyString>() => vec![fields.extract()?],
+            Some(fields) => fields
+                .iter()?
+                .map(|field| field?.extract())
+                .collect::<PyResult<Vec<Field>>>()?,
+        };
+        let pattern = Pattern::new(pattern, fields)?;
         let reader = match fh.downcast::<PyString>() {
-            Ok(s) => RecordReader::from_path(s.to_str()?)?,
-            Err(_) => RecordReader::from_handle(fh)?,
+            Ok(s) => {
+                RecordReader::from_path(py, s.to_str()?, false, None, true, ErrorPolicy::Raise)?
+            }
+            Err(_) => RecordReader::from_handle(fh, false, None, true, ErrorPolicy::Raise)?,
+        };
+        Py::new(py, GrepReader::new(reader, pattern))
+    }
+
+    /// Index the GenBank records of a file for random access.
+    ///
+    /// The file is scanned for the ``LOCUS`` line and ``//`` terminator of
+    /// each record, without parsing the records, and only the position of
+    /// each record is kept in memory. Records are then parsed on demand
+    /// when accessed by identifier, like with `Bio.SeqIO.index`.
+    ///
+    /// Arguments:
+    ///     path (`str`): The path to an uncompressed GenBank file.
+    ///
+    ///     key (`str`): The identifier to index records by, either
+    ///         ``accession`` for the first accession of the ``ACCESSION``
+    ///         line, falling back to the record name, or ``name`` for the
+    ///         name of the ``LOCUS`` line.
+    ///
+    /// Returns:
+    ///     `~gb_io.RecordIndex`: A read-only mapping of the identifiers of
+    ///     the records to `Record` objects.
+    ///
+    /// Raises:
+    ///     `OSError`: When the file could not be opened or read.
+    ///     `ValueError`: When the file is compressed, when several records
+    ///         have the same identifier, or when ``key`` is invalid.
+    ///
+    /// Example:
+    ///     >>> index = gb_io.index("tests/data/biopython/cor6_6.gb")
+    ///     >>> len(index)
+    ///     6
+    ///     >>> index["L31939"].name
+    ///     'BRRBIF72'
+    ///     >>> gb_io.index("tests/data/biopython/cor6_6.gb", key="name").keys()[:2]
+    ///     ['ATCOR66M', 'ATKIN2']
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyfn(m)]
+    #[pyo3(
+        name = "index",
+        signature = (path, *, key = IndexKey::Accession),
+        text_signature = "(path, *, key='accession')"
+    )]
+    fn index(py: Python, path: &str, key: IndexKey) -> PyResult<Py<RecordIndex>> {
+        Py::new(py, RecordIndex::build(path, key)?)
+    }
+
+    /// Call a function on each GenBank record of a path or file handle.
+    ///
+    /// Records are parsed one at a time and passed to ``callback``, so
+    /// that no more than one record is held in memory at once, unless
+    /// the callback keeps a reference to it.
+    ///
+    /// Arguments:
+    ///     fh (`str` or file-handle): The path to a GenBank file, or a
+    ///         stream that contains data serialized in GenBank format.
+    ///     callback (`callable`): A function to call with each `Record`.
+    ///         Return `False` from the callback to stop iterating early.
+    ///
+    /// Returns:
+    ///     `int`: The number of records passed to the callback.
+    ///
+    /// Example:
+    ///     Collect the sequence length of each record in a file::
+    ///
+    ///         >>> lengths = []
+    ///         >>> def callback(record):
+    ///         ...     lengths.append(len(record.sequence))
+    ///         >>> gb_io.for_each("tests/data/AY048670.1.gb", callback)
+    ///         1
+    ///         >>> lengths
+    ///         [85163]
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyfn(m)]
+    #[pyo3(name = "for_each", text_signature = "(fh, callback)")]
+    fn for_each(py: Python, fh: Bound<PyAny>, callback: Bound<PyAny>) -> PyResult<usize> {
+        let mut reader = match fh.downcast::<PyString>() {
+            Ok(s) => {
+                RecordReader::from_path(py, s.to_str()?, false, None, true, ErrorPolicy::Raise)?
+            }
+            Err(_) => RecordReader::from_handle(fh, false, None, true, ErrorPolicy::Raise)?,
         };
-        Py::new(py, reader)
+        let mut count = 0;
+        while let Some(record) = reader.next_record(py)? {
+            count += 1;
+            let result = callback.call1((record,))?;
+            if result.is(&false.into_py(py)) {
+                break;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Parse a location from a string in GenBank format.
+    ///
+    /// Arguments:
+    ///     text (`str`): The location to parse, e.g. ``"complement(1..100)"``
+    ///         or ``"J00194.1:100..202"`` for a location in another record.
+    ///
+    /// Returns:
+    ///     `Location`: The parsed location.
+    ///
+    /// Raises:
+    ///     `ValueError`: When the location could not be parsed, or when it
+    ///         refers to an invalid accession.
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyfn(m)]
+    #[pyo3(name = "parse_location", text_signature = "(text)")]
+    fn parse_location(py: Python, text: &str) -> PyResult<Py<Location>> {
+        location::parse(text)
+            .map_err(PyValueError::new_err)?
+            .convert(py)
     }
 
     /// Write one or more GenBank records to the given path or file handle.
@@ -1358,41 +6514,206 @@ pub fn init(py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     /// Arguments:
     ///     records (`Record` or iterable of `Record`): The records to write
     ///         to the file.
-    ///     fh (`str` or file-handle): The path to a GenBank file, or a stream
-    ///         that contains data serialized in GenBank format.
+    ///     fh (`str` or file-handle, optional): The path to a GenBank file,
+    ///         or a stream that contains data serialized in GenBank format.
+    ///         If `None` given, the records are written to a new
+    ///         `io.BytesIO` buffer which is returned.
     ///     escape_locus (`bool`): Pass `True` to escape any whitespace in
     ///         the locus name with an underscore character.
     ///     truncate_locus (`bool`): Pass `True` to trim the locus fields
     ///          so that the locus line is no longer than 79 characters.
+    ///     compresslevel (`int`): The compression level to use when
+    ///         ``fh`` is a path ending with ``.gz`` or ``.zst``, between
+    ///         0 and 9.
+    ///     emit_gi (`bool`): Pass `True` to write the GI number of records
+    ///         on their ``VERSION`` line, for compatibility with legacy
+    ///         parsers. By default, only the accession and version are
+    ///         written, following the current NCBI format.
+    ///     unquoted_qualifiers (iterable of `str`, optional): The keys of
+    ///         additional qualifiers whose values should be written without
+    ///         quotes, e.g. for custom numeric qualifiers. Values of the
+    ///         ``codon_start`` or ``transl_table`` qualifiers, among others,
+    ///         are never quoted.
+    ///     date (`~datetime.date` or `str`, optional): A date to write on
+    ///         the ``LOCUS`` line of every record instead of their own
+    ///         `Record.date`, e.g. for reproducible outputs. Strings must
+    ///         be in ISO format (``YYYY-MM-DD``). The records are not
+    ///         modified.
+    ///     return_count (`bool`): Pass `True` to return the number of bytes
+    ///         written. For compressed files, this is the number of bytes
+    ///         before compression.
+    ///     simplify_locations (`bool`): Pass `True` to write the locations
+    ///         of features as simplified by `Location.simplify`, e.g.
+    ///         ``1..100`` instead of ``join(1..100)``. The records are not
+    ///         modified.
+    ///     wrap_width (`int`): The maximum width of the lines of wrapped
+    ///         fields, locations and qualifiers, of at least 40 characters.
+    ///         The ``LOCUS`` line and the sequence lines are not wrapped.
+    ///     linearize_circular (`bool`): Pass `True` to write circular
+    ///         records as linear records, for tools which do not support
+    ///         circular topologies. Features spanning the origin are split
+    ///         in two parts, at the end and at the start of the sequence.
+    ///         The records are not modified.
+    ///     wrap_definition (`bool`, `int` or `None`): Whether to wrap the
+    ///         lines of the header fields, such as ``DEFINITION``,
+    ///         ``KEYWORDS`` or ``COMMENT``. Pass `True` or `None` to wrap
+    ///         them at ``wrap_width``, `False` to write them on a single
+    ///         line except for the line breaks of their text, or a width
+    ///         of at least 40 characters to wrap them at a different width
+    ///         than other lines.
+    ///     canonical_qualifier_order (`bool`, iterable of `str` or `None`):
+    ///         Pass `True` to write the qualifiers of each feature in a
+    ///         canonical order, for reproducible outputs: ``/gene``,
+    ///         ``/locus_tag`` and ``/product`` first, then the other
+    ///         qualifiers sorted by key. Pass an iterable of keys to use a
+    ///         different priority list. Qualifiers with the same key keep
+    ///         their relative order, and the records are not modified.
+    ///         Pass `False` or `None` to keep the original order.
+    ///     format (`str`): The flat file format to write the records in,
+    ///         either ``genbank`` or ``embl``. In EMBL format, the locus
+    ///         options and ``emit_gi`` are ignored, and the fields without
+    ///         an EMBL equivalent, such as the ``PRIMARY`` table, are not
+    ///         written.
+    ///     line_width (`int`): The number of bases written on each line
+    ///         of the sequence, in groups of 10, after the ``ORIGIN``
+    ///         keyword. Lines of 60 bases are written by default.
+    ///     validate (`bool`): Pass `True` to check the sequence of each
+    ///         record against its `Record.molecule_type` before writing
+    ///         it. Nucleotide sequences must only contain IUPAC nucleotide
+    ///         codes, and protein sequences amino acid codes, as checked
+    ///         by `Record.validate_sequence`. Protein sequences made only
+    ///         of nucleotides are rejected as well, when longer than 10
+    ///         symbols. Records with another molecule type are not checked.
+    ///
+    /// Returns:
+    ///     `io.BytesIO` or `int` or `None`: A buffer containing the records,
+    ///     rewound to its start, if ``fh`` is `None`. Otherwise, the number
+    ///     of bytes written if ``return_count`` is `True`, or `None`.
+    ///
+    /// Raises:
+    ///     `ValueError`: When a feature cannot be written, e.g. because
+    ///         its kind contains whitespace or its location extends beyond
+    ///         the record sequence. The message names the index and the
+    ///         accession of the record, and the index of the feature.
+    ///         With ``validate=True``, also raised when a sequence contains
+    ///         symbols invalid for its molecule type, which are listed with
+    ///         their positions.
+    ///     `OSError`: When writing to the file fails.
+    ///
+    /// Example:
+    ///     Write a record to memory and read it back::
+    ///
+    ///         >>> record = gb_io.Record(b"ATGC", name="TEST")
+    ///         >>> gb_io.load(gb_io.dump(record))[0].name
+    ///         'TEST'
+    ///
+    /// Example:
+    ///     Write a record in EMBL format::
+    ///
+    ///         >>> record = gb_io.Record(b"ATGC", name="TEST", accession="X56734")
+    ///         >>> gb_io.dump(record, format="embl").readline()
+    ///         b'ID   X56734; SV 1; linear; unassigned DNA; STD; UNC; 4 BP.\n'
+    ///
+    /// Hint:
+    ///     Records can also be written to a `gzip.GzipFile` opened in
+    ///     binary mode. The file handle is flushed once all records have
+    ///     been written, but it must still be closed by the caller to
+    ///     write the end of the compressed stream.
     ///
     /// .. versionadded:: 0.2.0
+    ///
+    /// .. versionadded:: 0.4.0
+    ///    The ``compresslevel``, ``emit_gi``, ``unquoted_qualifiers`` and
+    ///    ``date``, ``return_count``, ``simplify_locations``, ``wrap_width``,
+    ///    ``linearize_circular``, ``wrap_definition``,
+    ///    ``canonical_qualifier_order``, ``format``, ``line_width`` and
+    ///    ``validate`` arguments.
+    ///
+    /// .. versionchanged:: 0.4.0
+    ///    Validate features before writing each record, and report the
+    ///    failing record in error messages.
+    ///
+    /// .. versionchanged:: 0.4.0
+    ///    Return a `io.BytesIO` buffer when no file is given.
     #[pyfn(m)]
     #[pyo3(
         name = "dump",
-        signature = (records, fh, escape_locus = false, truncate_locus = false),
-        text_signature = "(records, fh, *, escape_locus=False, truncate_locus=False)"
+        signature = (records, fh = None, escape_locus = false, truncate_locus = false, compresslevel = 9, emit_gi = false, unquoted_qualifiers = None, date = None, *, return_count = false, simplify_locations = false, wrap_width = writer::MAX_WIDTH, linearize_circular = false, wrap_definition = None, canonical_qualifier_order = None, format = Format::GenBank, line_width = writer::SEQUENCE_WIDTH, validate = false),
+        text_signature = "(records, fh=None, escape_locus=False, truncate_locus=False, compresslevel=9, emit_gi=False, unquoted_qualifiers=None, date=None, *, return_count=False, simplify_locations=False, wrap_width=79, linearize_circular=False, wrap_definition=None, canonical_qualifier_order=None, format='genbank', line_width=60, validate=False)"
     )]
+    #[allow(clippy::too_many_arguments)]
     fn dump<'py>(
         py: Python<'py>,
         records: Bound<'py, PyAny>,
-        fh: Bound<'py, PyAny>,
+        fh: Option<Bound<'py, PyAny>>,
         escape_locus: bool,
         truncate_locus: bool,
-    ) -> PyResult<()> {
+        compresslevel: u32,
+        emit_gi: bool,
+        unquoted_qualifiers: Option<Bound<'py, PyAny>>,
+        date: Option<Bound<'py, PyAny>>,
+        return_count: bool,
+        simplify_locations: bool,
+        wrap_width: usize,
+        linearize_circular: bool,
+        wrap_definition: Option<Bound<'py, PyAny>>,
+        canonical_qualifier_order: Option<Bound<'py, PyAny>>,
+        format: Format,
+        line_width: usize,
+        validate: bool,
+    ) -> PyResult<PyObject> {
+        check_compresslevel(compresslevel)?;
+        let options = configure_writer(
+            py,
+            escape_locus,
+            truncate_locus,
+            emit_gi,
+            unquoted_qualifiers,
+            date,
+            simplify_locations,
+            wrap_width,
+            linearize_circular,
+            wrap_definition,
+            canonical_qualifier_order,
+            format,
+            line_width,
+            validate,
+        )?;
+
+        // if a single record was given, wrap it in an iterable
+        let it = if let Ok(record) = records.extract::<Bound<'_, Record>>() {
+            PyIterator::from_bound_object(&PyTuple::new_bound(py, [record]))?
+        } else {
+            PyIterator::from_bound_object(&records)?
+        };
+
+        // write to a new in-memory buffer if no file was given
+        let (fh, buffer) = match fh {
+            Some(fh) => (fh, None),
+            None if return_count => {
+                return Err(PyValueError::new_err(
+                    "cannot use return_count without a path or file handle",
+                ));
+            }
+            None => {
+                let buffer = py.import_bound("io")?.getattr("BytesIO")?.call0()?;
+                (buffer.clone(), Some(buffer))
+            }
+        };
+
         // extract either a path or a file-handle from the arguments
-        let stream: Box<dyn Write> = if let Ok(s) = fh.downcast::<PyString>() {
+        let written = if let Ok(s) = fh.downcast::<PyString>() {
             // get a buffered reader to the resources pointed by `path`
-            let bf = match std::fs::File::create(s.to_str()?) {
-                Ok(f) => f,
-                Err(e) => {
-                    return match e.raw_os_error() {
-                        Some(code) => Err(PyOSError::new_err((code, e.to_string()))),
-                        None => Err(PyOSError::new_err(e.to_string())),
-                    }
-                }
-            };
-            // send the file reader to the heap.
-            Box::new(bf)
+            let path = s.to_str()?;
+            let bf = std::fs::File::create(path).map_err(convert_io_error)?;
+            // compress the output according to the extension of the path
+            let encoder = Encoder::for_path(path, bf, compresslevel)?;
+            let (encoder, written) = write_records(py, it, options.with_stream(encoder))?;
+            encoder.finish().map_err(|err| match err.raw_os_error() {
+                Some(code) => PyIOError::new_err((code, err.to_string())),
+                None => PyIOError::new_err(err.to_string()),
+            })?;
+            written
         } else {
             // get a buffered writer by wrapping the file handle
             let bf = match PyFileWrite::from_ref(fh) {
@@ -1407,35 +6728,93 @@ pub fn init(py: Python, m: &Bound<PyModule>) -> PyResult<()> {
                     return Err(err);
                 }
             };
-            // send the Python file-handle reference to the heap.
-            Box::new(bf)
+            write_records(py, it, options.with_stream(bf))?.1
         };
 
-        // create the writer
-        let mut writer = SeqWriter::new(stream);
-        writer.truncate_locus(truncate_locus);
-        writer.escape_locus(escape_locus);
+        match buffer {
+            Some(buffer) => {
+                buffer.call_method1("seek", (0,))?;
+                Ok(buffer.unbind())
+            }
+            None if return_count => Ok(written.into_py(py)),
+            None => Ok(py.None()),
+        }
+    }
 
+    /// Serialize one or more GenBank records to a string.
+    ///
+    /// Arguments:
+    ///     records (`Record` or iterable of `Record`): The records to write.
+    ///
+    ///     escape_locus (`bool`): Pass `True` to escape any whitespace in
+    ///         the locus name with an underscore character.
+    ///     truncate_locus (`bool`): Pass `True` to trim the locus fields
+    ///          so that the locus line is no longer than 79 characters.
+    ///     wrap_width (`int`): The maximum width of the lines of wrapped
+    ///         fields, locations and qualifiers, of at least 40 characters.
+    ///     format (`str`): The flat file format to write the records in,
+    ///         either ``genbank`` or ``embl``.
+    ///     line_width (`int`): The number of bases written on each line
+    ///         of the sequence, as with `gb_io.dump`.
+    ///     validate (`bool`): Pass `True` to check the sequence of each
+    ///         record against its `Record.molecule_type`, as with
+    ///         `gb_io.dump`.
+    ///
+    /// Returns:
+    ///     `str`: The serialized records, exactly as they would be written
+    ///     by `gb_io.dump` with the same arguments.
+    ///
+    /// Raises:
+    ///     `ValueError`: When a feature or a sequence cannot be written,
+    ///         as with `gb_io.dump`, or when ``wrap_width`` or
+    ///         ``line_width`` is too small.
+    ///
+    /// Example:
+    ///     Serialize records to a string and parse them back::
+    ///
+    ///         >>> records = [gb_io.Record(b"ATGC", name=n) for n in ("A", "B")]
+    ///         >>> text = gb_io.dumps(records)
+    ///         >>> text.count("LOCUS")
+    ///         2
+    ///         >>> [record.name for record in gb_io.loads(text)]
+    ///         ['A', 'B']
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    #[pyfn(m)]
+    #[pyo3(
+        name = "dumps",
+        signature = (records, *, escape_locus = false, truncate_locus = false, wrap_width = writer::MAX_WIDTH, format = Format::GenBank, line_width = writer::SEQUENCE_WIDTH, validate = false),
+        text_signature = "(records, *, escape_locus=False, truncate_locus=False, wrap_width=79, format='genbank', line_width=60, validate=False)"
+    )]
+    #[allow(clippy::too_many_arguments)]
+    fn dumps<'py>(
+        py: Python<'py>,
+        records: Bound<'py, PyAny>,
+        escape_locus: bool,
+        truncate_locus: bool,
+        wrap_width: usize,
+        format: Format,
+        line_width: usize,
+        validate: bool,
+    ) -> PyResult<Bound<'py, PyString>> {
         // if a single record was given, wrap it in an iterable
         let it = if let Ok(record) = records.extract::<Bound<'_, Record>>() {
             PyIterator::from_bound_object(&PyTuple::new_bound(py, [record]))?
         } else {
             PyIterator::from_bound_object(&records)?
         };
-
-        // write sequences
-        for result in it {
-            // make sure we received a Record object
-            let record = result?.extract::<Py<Record>>()?;
-            let seq = Extract::extract(py, record)?;
-            // write the seq
-            writer.write(&seq).map_err(|err| match err.raw_os_error() {
-                Some(code) => PyIOError::new_err((code, err.to_string())),
-                None => PyIOError::new_err(err.to_string()),
-            })?;
-        }
-
-        Ok(())
+        let text = write_string(
+            py,
+            it,
+            escape_locus,
+            truncate_locus,
+            wrap_width,
+            format,
+            line_width,
+            validate,
+        )?;
+        Ok(PyString::new_bound(py, &text))
     }
 
     Ok(())
@@ -0,0 +1,600 @@
+//! Algorithms on native `gb_io` locations.
+
+use std::cmp::Ordering;
+
+use gb_io::seq::After;
+use gb_io::seq::Before;
+use gb_io::seq::Location;
+
+/// Get the smallest span covering all the positions of a location.
+///
+/// Returns `None` for locations that do not refer to positions of the
+/// current record, such as external locations and gaps, or for compound
+/// locations without any local part.
+pub fn span(location: &Location) -> Option<(i64, i64)> {
+    match location {
+        Location::Range((start, _), (end, _)) => Some((*start, *end)),
+        Location::Between(start, end) => Some((*start, *end + 1)),
+        Location::Complement(inner) => span(inner),
+        Location::Join(locations)
+        | Location::Order(locations)
+        | Location::Bond(locations)
+        | Location::OneOf(locations) => locations
+            .iter()
+            .filter_map(span)
+            .reduce(|(s1, e1), (s2, e2)| (s1.min(s2), e1.max(e2))),
+        Location::External(_, _) | Location::Gap(_) => None,
+    }
+}
+
+/// Get the smallest and largest coordinates of a location.
+///
+/// Unlike `span`, the coordinates of a `Between` location are used as is,
+/// so that the bounds match the `start` and `end` attributes of the
+/// location parts. The strand of the parts is ignored.
+pub fn bounds(location: &Location) -> Option<(i64, i64)> {
+    match location {
+        Location::Range((start, _), (end, _)) => Some((*start, *end)),
+        Location::Between(start, end) => Some((*start, *end)),
+        Location::Complement(inner) => bounds(inner),
+        Location::Join(locations)
+        | Location::Order(locations)
+        | Location::Bond(locations)
+        | Location::OneOf(locations) => locations
+            .iter()
+            .filter_map(bounds)
+            .reduce(|(s1, e1), (s2, e2)| (s1.min(s2), e1.max(e2))),
+        Location::External(_, _) | Location::Gap(_) => None,
+    }
+}
+
+/// Check whether a location intersects the half-open range `[start, end)`.
+pub fn intersects_range(location: &Location, start: i64, end: i64) -> bool {
+    // check the span first to avoid recursing into compound locations
+    match span(location) {
+        Some((s, e)) if s < end && e > start => {}
+        _ => return false,
+    }
+    match location {
+        Location::Range(_, _) | Location::Between(_, _) => true,
+        Location::Complement(inner) => intersects_range(inner, start, end),
+        Location::Join(locations)
+        | Location::Order(locations)
+        | Location::Bond(locations)
+        | Location::OneOf(locations) => locations
+            .iter()
+            .any(|location| intersects_range(location, start, end)),
+        Location::External(_, _) | Location::Gap(_) => false,
+    }
+}
+
+/// Collect the spans of the simple parts of a location.
+fn parts(location: &Location, spans: &mut Vec<(i64, i64)>) {
+    match location {
+        Location::Range(_, _) | Location::Between(_, _) => spans.extend(span(location)),
+        Location::Complement(inner) => parts(inner, spans),
+        Location::Join(locations)
+        | Location::Order(locations)
+        | Location::Bond(locations)
+        | Location::OneOf(locations) => {
+            for location in locations {
+                parts(location, spans);
+            }
+        }
+        Location::External(_, _) | Location::Gap(_) => (),
+    }
+}
+
+/// Get the spans of the simple parts of a location, with their strand.
+///
+/// The parts are returned in genomic order, whatever the order in which
+/// they are transcribed, with a flag set for parts on the reverse strand.
+/// Parts are not sorted by coordinates, so that the order of the parts
+/// of a location spanning the origin of a circular sequence is kept.
+/// External locations and gaps are skipped.
+pub fn exons(location: &Location) -> Vec<(i64, i64, bool)> {
+    // collect the parts in transcription order
+    fn collect(location: &Location, reverse: bool, exons: &mut Vec<(i64, i64, bool)>) {
+        match location {
+            Location::Range(_, _) | Location::Between(_, _) => {
+                exons.extend(span(location).map(|(start, end)| (start, end, reverse)))
+            }
+            Location::Complement(inner) => {
+                let n = exons.len();
+                collect(inner, !reverse, exons);
+                exons[n..].reverse();
+            }
+            Location::Join(locations)
+            | Location::Order(locations)
+            | Location::Bond(locations)
+            | Location::OneOf(locations) => {
+                for location in locations {
+                    collect(location, reverse, exons);
+                }
+            }
+            Location::External(_, _) | Location::Gap(_) => (),
+        }
+    }
+
+    let mut exons = Vec::new();
+    collect(location, false, &mut exons);
+    if is_reverse(location) == Some(true) {
+        exons.reverse();
+    }
+    exons
+}
+
+/// Get whether a location is on the reverse strand.
+///
+/// Returns `None` when the strand is undefined, e.g. for compound locations
+/// with parts on both strands, external locations and gaps.
+pub fn is_reverse(location: &Location) -> Option<bool> {
+    match location {
+        Location::Range(_, _) | Location::Between(_, _) => Some(false),
+        Location::Complement(inner) => is_reverse(inner).map(|reverse| !reverse),
+        Location::Join(locations)
+        | Location::Order(locations)
+        | Location::Bond(locations)
+        | Location::OneOf(locations) => {
+            let mut strands = locations.iter().map(is_reverse);
+            let first = strands.next()??;
+            strands.all(|strand| strand == Some(first)).then_some(first)
+        }
+        Location::External(_, _) | Location::Gap(_) => None,
+    }
+}
+
+/// Check whether two locations have the same, well-defined strand.
+pub fn same_strand(a: &Location, b: &Location) -> bool {
+    matches!((is_reverse(a), is_reverse(b)), (Some(x), Some(y)) if x == y)
+}
+
+/// Check whether any part of a location overlaps any part of another.
+pub fn overlaps(a: &Location, b: &Location) -> bool {
+    let mut spans = Vec::new();
+    parts(a, &mut spans);
+    spans
+        .into_iter()
+        .any(|(start, end)| intersects_range(b, start, end))
+}
+
+/// Check whether every part of a location lies within a part of another.
+///
+/// Returns `false` if `inner` has no part on the current record.
+pub fn contains(outer: &Location, inner: &Location) -> bool {
+    let mut outer_spans = Vec::new();
+    let mut inner_spans = Vec::new();
+    parts(outer, &mut outer_spans);
+    parts(inner, &mut inner_spans);
+    !inner_spans.is_empty()
+        && inner_spans
+            .iter()
+            .all(|(start, end)| outer_spans.iter().any(|(s, e)| s <= start && end <= e))
+}
+
+/// Get the indices of all the pairs of overlapping locations.
+///
+/// Locations are sorted by the start of their span and swept from left
+/// to right, so that only the locations whose spans overlap are compared
+/// part by part with `overlaps`. Pairs are returned as `(i, j)` with
+/// `i < j`, sorted in lexicographic order.
+pub fn overlapping_pairs(locations: &[&Location], same_strand: bool) -> Vec<(usize, usize)> {
+    let mut spans = locations
+        .iter()
+        .enumerate()
+        .filter_map(|(i, location)| span(location).map(|(start, end)| (start, end, i)))
+        .collect::<Vec<_>>();
+    spans.sort_unstable();
+
+    let mut pairs = Vec::new();
+    let mut active: Vec<(i64, usize)> = Vec::new();
+    for (start, end, i) in spans {
+        active.retain(|&(e, _)| e > start);
+        for &(_, j) in &active {
+            let (a, b) = (locations[i], locations[j]);
+            if (!same_strand || self::same_strand(a, b)) && overlaps(a, b) {
+                pairs.push((i.min(j), i.max(j)));
+            }
+        }
+        active.push((end, i));
+    }
+
+    pairs.sort_unstable();
+    pairs
+}
+
+/// Check whether a string is a valid sequence accession.
+///
+/// Accessions are made of an alphabetic prefix followed by alphanumeric
+/// characters or underscores, with an optional numeric version suffix
+/// (e.g. `J00194`, `NZ_CP009072.1`).
+pub fn is_valid_accession(accession: &str) -> bool {
+    let (name, version) = match accession.split_once('.') {
+        Some((name, version)) => (name, Some(version)),
+        None => (accession, None),
+    };
+    name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && version.is_none_or(|v| !v.is_empty() && v.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Parse a location in GenBank format, validating external accessions.
+pub fn parse(text: &str) -> Result<Location, String> {
+    fn validate(location: &Location) -> Result<(), String> {
+        match location {
+            Location::External(accession, _) if !is_valid_accession(accession) => {
+                Err(format!("invalid accession: {:?}", accession))
+            }
+            Location::External(_, inner) => inner.as_deref().map_or(Ok(()), validate),
+            Location::Complement(inner) => validate(inner),
+            Location::Join(locations)
+            | Location::Order(locations)
+            | Location::Bond(locations)
+            | Location::OneOf(locations) => locations.iter().try_for_each(validate),
+            Location::Range(_, _) | Location::Between(_, _) | Location::Gap(_) => Ok(()),
+        }
+    }
+    let location = Location::from_gb_format(text.trim())
+        .map_err(|_| format!("invalid location: {:?}", text))?;
+    validate(&location)?;
+    Ok(location)
+}
+
+/// Compare two locations for sorting.
+///
+/// Locations are ordered by start, then by end, then by type, and finally
+/// by their GenBank representation, so that two locations compare equal
+/// only if they are identical. Locations without a span sort last.
+pub fn compare(a: &Location, b: &Location) -> Ordering {
+    fn rank(location: &Location) -> u8 {
+        match location {
+            Location::Range(_, _) => 0,
+            Location::Between(_, _) => 1,
+            Location::Complement(_) => 2,
+            Location::Join(_) => 3,
+            Location::Order(_) => 4,
+            Location::Bond(_) => 5,
+            Location::OneOf(_) => 6,
+            Location::External(_, _) => 7,
+            Location::Gap(_) => 8,
+        }
+    }
+    let key = |location: &Location| span(location).unwrap_or((i64::MAX, i64::MAX));
+    key(a)
+        .cmp(&key(b))
+        .then_with(|| rank(a).cmp(&rank(b)))
+        .then_with(|| a.to_gb_format().cmp(&b.to_gb_format()))
+}
+
+/// Extract the sequence covered by a location.
+///
+/// Complemented locations are reverse-complemented, and the parts of
+/// compound locations are concatenated in order.
+pub fn extract(location: &Location, sequence: &[u8]) -> Result<Vec<u8>, String> {
+    match location {
+        Location::Range((start, _), (end, _)) => usize::try_from(*start)
+            .ok()
+            .zip(usize::try_from(*end).ok())
+            .and_then(|(start, end)| sequence.get(start..end))
+            .map(<[u8]>::to_vec)
+            .ok_or_else(|| format!("location out of bounds: {}", location)),
+        Location::Between(_, _) => Ok(Vec::new()),
+        Location::Complement(inner) => {
+            extract(inner, sequence).map(|s| crate::translation::reverse_complement(&s))
+        }
+        Location::Join(locations) | Location::Order(locations) | Location::Bond(locations) => {
+            let mut extracted = Vec::new();
+            for location in locations {
+                extracted.extend(extract(location, sequence)?);
+            }
+            Ok(extracted)
+        }
+        Location::OneOf(_) => Err(format!("ambiguous location: {}", location)),
+        Location::External(_, _) => Err(format!("external location: {}", location)),
+        Location::Gap(_) => Err(format!("gap location: {}", location)),
+    }
+}
+
+/// Get the number of positions covered by a location.
+///
+/// The parts of compound locations are summed, so that the length of a
+/// `Join` is its spliced length rather than the length of its span.
+pub fn length(location: &Location) -> Result<usize, String> {
+    match location {
+        Location::Range((start, _), (end, _)) => {
+            usize::try_from(end - start).map_err(|_| format!("invalid location: {}", location))
+        }
+        Location::Between(_, _) => Ok(0),
+        Location::Complement(inner) => length(inner),
+        Location::Join(locations) | Location::Order(locations) | Location::Bond(locations) => {
+            locations.iter().map(length).sum()
+        }
+        Location::OneOf(_) => Err(format!("ambiguous location: {}", location)),
+        Location::External(_, _) => Err(format!("external location: {}", location)),
+        Location::Gap(_) => Err(format!("gap location: {}", location)),
+    }
+}
+
+/// Check whether the 5' end of a location is partial.
+///
+/// This takes the strand into account, so the 5' end of a complemented
+/// location is its last position.
+pub fn is_five_prime_partial(location: &Location) -> bool {
+    fn partial(location: &Location, reverse: bool) -> bool {
+        match location {
+            Location::Range((_, Before(before)), (_, After(after))) => {
+                if reverse {
+                    *after
+                } else {
+                    *before
+                }
+            }
+            Location::Complement(inner) => partial(inner, !reverse),
+            Location::Join(locations)
+            | Location::Order(locations)
+            | Location::Bond(locations)
+            | Location::OneOf(locations) => {
+                let part = if reverse {
+                    locations.last()
+                } else {
+                    locations.first()
+                };
+                part.is_some_and(|part| partial(part, reverse))
+            }
+            Location::External(_, inner) => inner
+                .as_deref()
+                .is_some_and(|inner| partial(inner, reverse)),
+            Location::Between(_, _) | Location::Gap(_) => false,
+        }
+    }
+    partial(location, false)
+}
+
+/// Clip a location to the half-open range `[start, end)`.
+///
+/// Parts of the location extending beyond the range are truncated, and
+/// marked as partial with the `before` and `after` flags. External and
+/// gap locations have no position within the range, so they are dropped
+/// from compound locations. Returns `None` if no part of the location lies
+/// within the range.
+pub fn clip(location: &Location, start: i64, end: i64) -> Option<Location> {
+    let clip_all = |locations: &[Location]| {
+        let clipped = locations
+            .iter()
+            .filter_map(|location| clip(location, start, end))
+            .collect::<Vec<_>>();
+        (!clipped.is_empty()).then_some(clipped)
+    };
+    match location {
+        Location::Range((a, Before(before)), (b, After(after))) => {
+            if *b <= start || *a >= end {
+                None
+            } else {
+                Some(Location::Range(
+                    ((*a).max(start), Before(*before || *a < start)),
+                    ((*b).min(end), After(*after || *b > end)),
+                ))
+            }
+        }
+        Location::Between(a, _) => (*a >= start && *a < end).then(|| location.clone()),
+        Location::Complement(inner) => {
+            clip(inner, start, end).map(|inner| Location::Complement(Box::new(inner)))
+        }
+        Location::Join(locations) => clip_all(locations).map(|mut locations| {
+            if locations.len() == 1 {
+                locations.remove(0)
+            } else {
+                Location::Join(locations)
+            }
+        }),
+        Location::Order(locations) => clip_all(locations).map(Location::Order),
+        Location::Bond(locations) => clip_all(locations).map(Location::Bond),
+        Location::OneOf(locations) => clip_all(locations).map(Location::OneOf),
+        Location::External(_, _) | Location::Gap(_) => None,
+    }
+}
+
+/// Shift all the positions of a location by the given offset.
+///
+/// Panics if a shifted position overflows, which cannot happen for the
+/// positions of a location within a sequence.
+pub fn shift(location: &Location, offset: i64) -> Location {
+    checked_shift(location, offset).expect("shifted position overflows")
+}
+
+/// Shift all the positions of a location by the given offset, or return
+/// `None` if a shifted position overflows.
+pub fn checked_shift(location: &Location, offset: i64) -> Option<Location> {
+    let shift_all = |locations: &[Location]| {
+        locations
+            .iter()
+            .map(|location| checked_shift(location, offset))
+            .collect::<Option<Vec<_>>>()
+    };
+    match location {
+        Location::Range((a, before), (b, after)) => Some(Location::Range(
+            (a.checked_add(offset)?, *before),
+            (b.checked_add(offset)?, *after),
+        )),
+        Location::Between(a, b) => Some(Location::Between(
+            a.checked_add(offset)?,
+            b.checked_add(offset)?,
+        )),
+        Location::Complement(inner) => {
+            checked_shift(inner, offset).map(|inner| Location::Complement(Box::new(inner)))
+        }
+        Location::Join(locations) => shift_all(locations).map(Location::Join),
+        Location::Order(locations) => shift_all(locations).map(Location::Order),
+        Location::Bond(locations) => shift_all(locations).map(Location::Bond),
+        Location::OneOf(locations) => shift_all(locations).map(Location::OneOf),
+        Location::External(_, _) | Location::Gap(_) => Some(location.clone()),
+    }
+}
+
+/// Wrap the ranges of a location around the origin of a circular sequence.
+///
+/// Ranges starting outside of the sequence are shifted by a multiple of
+/// the length, and ranges extending past the end of the sequence are then
+/// split in two parts, at the end and at the start of the sequence. Ranges
+/// longer than the sequence are kept out of bounds. The length must be
+/// strictly positive.
+pub fn wrap(location: &Location, length: i64) -> Location {
+    let wrap_all = |locations: &[Location]| {
+        locations
+            .iter()
+            .map(|location| wrap(location, length))
+            .collect()
+    };
+    match location {
+        Location::Range((a, before), (b, after)) => {
+            let offset = a.div_euclid(length) * length;
+            let (a, b) = (a - offset, b - offset);
+            if b <= length || b - length > a {
+                Location::Range((a, *before), (b, *after))
+            } else {
+                Location::Join(vec![
+                    Location::Range((a, *before), (length, After(false))),
+                    Location::Range((0, Before(false)), (b - length, *after)),
+                ])
+            }
+        }
+        Location::Complement(inner) => Location::Complement(Box::new(wrap(inner, length))),
+        Location::Join(locations) => Location::Join(wrap_all(locations)),
+        Location::Order(locations) => Location::Order(wrap_all(locations)),
+        Location::Bond(locations) => Location::Bond(wrap_all(locations)),
+        Location::OneOf(locations) => Location::OneOf(wrap_all(locations)),
+        Location::Between(_, _) | Location::External(_, _) | Location::Gap(_) => location.clone(),
+    }
+}
+
+/// Move the origin of a circular sequence to the given position.
+///
+/// Positions are shifted so that `origin` becomes the first position, and
+/// ranges spanning the new origin are split with `wrap`. Parts of a join
+/// split this way are flattened into the join. Ranges covering the whole
+/// sequence, such as the `source` feature, are kept unchanged. The length
+/// must be strictly positive.
+pub fn rotate(location: &Location, origin: i64, length: i64) -> Location {
+    let rotate_all = |locations: &[Location]| {
+        locations
+            .iter()
+            .map(|location| rotate(location, origin, length))
+            .collect()
+    };
+    match location {
+        Location::Range((0, _), (b, _)) if *b == length => location.clone(),
+        Location::Range(_, _) => wrap(&shift(location, -origin), length),
+        Location::Between(a, b) => Location::Between(
+            (a - origin).rem_euclid(length),
+            (b - origin).rem_euclid(length),
+        ),
+        Location::Complement(inner) => {
+            Location::Complement(Box::new(rotate(inner, origin, length)))
+        }
+        Location::Join(locations) => {
+            let mut parts = Vec::with_capacity(locations.len());
+            for location in locations {
+                match (location, rotate(location, origin, length)) {
+                    (Location::Range(_, _), Location::Join(split)) => parts.extend(split),
+                    (_, rotated) => parts.push(rotated),
+                }
+            }
+            Location::Join(parts)
+        }
+        Location::Order(locations) => Location::Order(rotate_all(locations)),
+        Location::Bond(locations) => Location::Bond(rotate_all(locations)),
+        Location::OneOf(locations) => Location::OneOf(rotate_all(locations)),
+        Location::External(_, _) | Location::Gap(_) => location.clone(),
+    }
+}
+
+/// Mirror a location within a sequence of the given length.
+///
+/// Coordinates are mirrored, `before` and `after` flags are swapped, the
+/// order of compound locations is reversed, and the strand of the whole
+/// location is flipped, like `gb_io::seq::Seq::revcomp` does for features.
+/// External locations and gaps are returned unchanged.
+pub fn flip(location: &Location, length: i64) -> Location {
+    match location {
+        Location::External(_, _) | Location::Gap(_) => location.clone(),
+        Location::Complement(inner) => mirror(inner, length),
+        _ => Location::Complement(Box::new(mirror(location, length))),
+    }
+}
+
+/// Mirror the coordinates of a location without changing its strand.
+fn mirror(location: &Location, length: i64) -> Location {
+    let mirror_all = |locations: &[Location]| {
+        locations
+            .iter()
+            .rev()
+            .map(|location| mirror(location, length))
+            .collect()
+    };
+    match location {
+        Location::Range((a, Before(before)), (b, After(after))) => {
+            Location::Range((length - b, Before(*after)), (length - a, After(*before)))
+        }
+        Location::Between(a, b) => Location::Between(length - b - 1, length - a - 1),
+        Location::Complement(inner) => Location::Complement(Box::new(mirror(inner, length))),
+        Location::Join(locations) => Location::Join(mirror_all(locations)),
+        Location::Order(locations) => Location::Order(mirror_all(locations)),
+        Location::Bond(locations) => Location::Bond(mirror_all(locations)),
+        Location::OneOf(locations) => Location::OneOf(mirror_all(locations)),
+        Location::External(_, _) | Location::Gap(_) => location.clone(),
+    }
+}
+
+/// Simplify a location by removing redundant nesting.
+///
+/// Compound locations with a single part are replaced by that part, joins
+/// and orders nested in a location of the same kind are flattened, and
+/// double complements cancel out.
+pub fn simplify(location: &Location) -> Location {
+    let single_or = |mut locations: Vec<Location>, wrap: fn(Vec<Location>) -> Location| {
+        if locations.len() == 1 {
+            locations.remove(0)
+        } else {
+            wrap(locations)
+        }
+    };
+    match location {
+        Location::Complement(inner) => match simplify(inner) {
+            Location::Complement(inner) => *inner,
+            inner => Location::Complement(Box::new(inner)),
+        },
+        Location::Join(locations) => {
+            let mut parts = Vec::with_capacity(locations.len());
+            for part in locations.iter().map(simplify) {
+                match part {
+                    Location::Join(inner) => parts.extend(inner),
+                    part => parts.push(part),
+                }
+            }
+            single_or(parts, Location::Join)
+        }
+        Location::Order(locations) => {
+            let mut parts = Vec::with_capacity(locations.len());
+            for part in locations.iter().map(simplify) {
+                match part {
+                    Location::Order(inner) => parts.extend(inner),
+                    part => parts.push(part),
+                }
+            }
+            single_or(parts, Location::Order)
+        }
+        Location::Bond(locations) => {
+            single_or(locations.iter().map(simplify).collect(), Location::Bond)
+        }
+        Location::OneOf(locations) => {
+            single_or(locations.iter().map(simplify).collect(), Location::OneOf)
+        }
+        Location::External(accession, Some(inner)) => {
+            Location::External(accession.clone(), Some(Box::new(simplify(inner))))
+        }
+        Location::Range(_, _)
+        | Location::Between(_, _)
+        | Location::External(_, None)
+        | Location::Gap(_) => location.clone(),
+    }
+}
@@ -5,6 +5,7 @@ use std::io::Write;
 
 use pyo3::exceptions::PyOSError;
 use pyo3::exceptions::PyTypeError;
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use pyo3::types::PyBytes;
 use pyo3::types::PyString;
@@ -29,7 +30,7 @@ macro_rules! transmute_file_error {
         // silently, wrapping the Python error, and returning a
         // generic Rust error instead.
         $e.restore($py);
-        Err(IoError::new(std::io::ErrorKind::Other, $msg))
+        Err(IoError::other($msg))
     }};
 }
 
@@ -78,27 +79,13 @@ pub struct PyFileReadBin<'p> {
 
 impl<'p> PyFileReadBin<'p> {
     pub fn new(file: Bound<'p, PyAny>) -> PyResult<Self> {
-        #[cfg(feature = "cpython")]
-        {
-            if file.hasattr("readinto")? {
-                Ok(Self {
-                    readinto: Some(file.getattr("readinto")?),
-                    file,
-                })
-            } else {
-                Ok(Self {
-                    file,
-                    readinto: None,
-                })
-            }
-        }
-        #[cfg(not(feature = "cpython"))]
-        {
-            Ok(Self {
-                file,
-                readinto: None,
-            })
-        }
+        // use the zero-copy `readinto` method whenever the file provides it
+        let readinto = if file.hasattr("readinto")? {
+            Some(file.getattr("readinto")?)
+        } else {
+            None
+        };
+        Ok(Self { file, readinto })
     }
 }
 
@@ -108,30 +95,40 @@ impl<'p> Read for PyFileReadBin<'p> {
         if let Some(method) = &self.readinto {
             // prepare a `memoryview` to expose the buffer
             let memoryview = unsafe {
-                Bound::from_owned_ptr(
+                Bound::from_owned_ptr_or_err(
                     self.file.py(),
                     pyo3::ffi::PyMemoryView_FromMemory(
                         buf.as_mut_ptr() as *mut libc::c_char,
                         buf.len() as isize,
                         pyo3::ffi::PyBUF_WRITE,
                     ),
-                )
+                )?
             };
-            // read directly into the `memoryview`
-            match method.call1((memoryview,)) {
-                Ok(obj) => {
-                    if let Ok(n) = obj.extract::<usize>() {
-                        Ok(n)
-                    } else {
+            // read directly into the `memoryview`, then release it so that
+            // the buffer cannot be accessed after this function returns
+            let result = method.call1((&memoryview,));
+            memoryview.call_method0("release")?;
+            match result {
+                Ok(obj) => match obj.extract::<Option<usize>>() {
+                    Ok(Some(n)) if n <= buf.len() => Ok(n),
+                    Ok(Some(n)) => {
+                        let msg = format!(
+                            "readinto method returned {} bytes, expected at most {}",
+                            n,
+                            buf.len()
+                        );
+                        PyValueError::new_err(msg).restore(self.file.py());
+                        Err(IoError::other("readinto method returned too many bytes"))
+                    }
+                    // non-blocking streams return `None` when no data is available
+                    Ok(None) => Err(IoError::from(IoErrorKind::WouldBlock)),
+                    Err(_) => {
                         let ty = obj.get_type().name()?.to_string();
                         let msg = format!("expected int, found {}", ty);
                         PyTypeError::new_err(msg).restore(self.file.py());
-                        Err(IoError::new(
-                            std::io::ErrorKind::Other,
-                            "readinto method did not return int",
-                        ))
+                        Err(IoError::other("readinto method did not return int"))
                     }
-                }
+                },
                 Err(e) => {
                     transmute_file_error!(self, e, "readinto method failed", self.file.py())
                 }
@@ -142,16 +139,22 @@ impl<'p> Read for PyFileReadBin<'p> {
                     // Check `fh.read` returned bytes, else raise a `TypeError`.
                     if let Ok(bytes) = obj.extract::<&PyBytes>() {
                         let b = bytes.as_bytes();
+                        if b.len() > buf.len() {
+                            let msg = format!(
+                                "read method returned {} bytes, expected at most {}",
+                                b.len(),
+                                buf.len()
+                            );
+                            PyValueError::new_err(msg).restore(self.file.py());
+                            return Err(IoError::other("read method returned too many bytes"));
+                        }
                         buf[..b.len()].copy_from_slice(b);
                         Ok(b.len())
                     } else {
                         let ty = obj.get_type().name()?.to_string();
                         let msg = format!("expected bytes, found {}", ty);
                         PyTypeError::new_err(msg).restore(self.file.py());
-                        Err(IoError::new(
-                            std::io::ErrorKind::Other,
-                            "read method did not return bytes",
-                        ))
+                        Err(IoError::other("read method did not return bytes"))
                     }
                 }
                 Err(e) => {
@@ -208,10 +211,7 @@ impl<'p> Read for PyFileReadText<'p> {
                     let ty = obj.get_type().name()?.to_string();
                     let msg = format!("expected str, found {}", ty);
                     PyTypeError::new_err(msg).restore(self.file.py());
-                    Err(IoError::new(
-                        std::io::ErrorKind::Other,
-                        "read method did not return str",
-                    ))
+                    Err(IoError::other("read method did not return str"))
                 }
             }
             Err(e) => {
@@ -267,29 +267,12 @@ pub struct PyFileGILReadBin {
 }
 
 impl PyFileGILReadBin {
-    #[allow(unused_variables)]
     pub fn new(py: Python, file: PyObject) -> PyResult<Self> {
-        #[cfg(feature = "cpython")]
-        {
-            if file.bind(py).hasattr("readinto")? {
-                Ok(Self {
-                    readinto: Some(file.bind(py).getattr("readinto")?.unbind()),
-                    file,
-                })
-            } else {
-                Ok(Self {
-                    file,
-                    readinto: None,
-                })
-            }
-        }
-        #[cfg(not(feature = "cpython"))]
-        {
-            Ok(Self {
-                file,
-                readinto: None,
-            })
-        }
+        let reader = PyFileReadBin::new(file.bind(py).clone())?;
+        Ok(Self {
+            readinto: reader.readinto.map(Bound::unbind),
+            file,
+        })
     }
 }
 
@@ -384,6 +367,52 @@ impl<'p> Write for PyFileWrite<'p> {
 
 // ---------------------------------------------------------------------------
 
+/// A wrapper for a writable Python file that can outlive the GIL.
+#[derive(Debug)]
+pub enum PyFileGILWrite {
+    Binary(PyObject),
+    Text(PyObject),
+}
+
+impl PyFileGILWrite {
+    pub fn from_ref(file: Bound<PyAny>) -> PyResult<PyFileGILWrite> {
+        match PyFileWrite::from_ref(file.clone())? {
+            PyFileWrite::Binary(_) => Ok(Self::Binary(file.unbind())),
+            PyFileWrite::Text(_) => Ok(Self::Text(file.unbind())),
+        }
+    }
+}
+
+impl Write for PyFileGILWrite {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, IoError> {
+        Python::with_gil(|py| match self {
+            PyFileGILWrite::Binary(file) => PyFileWriteBin {
+                file: file.bind(py).clone(),
+            }
+            .write(buf),
+            PyFileGILWrite::Text(file) => PyFileWriteText {
+                file: file.bind(py).clone(),
+            }
+            .write(buf),
+        })
+    }
+
+    fn flush(&mut self) -> Result<(), IoError> {
+        Python::with_gil(|py| match self {
+            PyFileGILWrite::Binary(file) => PyFileWriteBin {
+                file: file.bind(py).clone(),
+            }
+            .flush(),
+            PyFileGILWrite::Text(file) => PyFileWriteText {
+                file: file.bind(py).clone(),
+            }
+            .flush(),
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+
 #[derive(Debug, Clone)]
 pub struct PyFileWriteBin<'p> {
     file: Bound<'p, PyAny>,
@@ -409,10 +438,7 @@ impl<'p> Write for PyFileWriteBin<'p> {
                     let ty = obj.get_type().name()?.to_string();
                     let msg = format!("expected int, found {}", ty);
                     PyTypeError::new_err(msg).restore(self.file.py());
-                    Err(IoError::new(
-                        std::io::ErrorKind::Other,
-                        "write method did not return int",
-                    ))
+                    Err(IoError::other("write method did not return int"))
                 }
             }
             Err(e) => {
@@ -422,6 +448,10 @@ impl<'p> Write for PyFileWriteBin<'p> {
     }
 
     fn flush(&mut self) -> Result<(), IoError> {
+        // file-like objects are not required to implement `flush`
+        if !self.file.hasattr("flush")? {
+            return Ok(());
+        }
         match self.file.call_method0("flush") {
             Ok(_) => Ok(()),
             Err(e) => {
@@ -459,15 +489,12 @@ impl<'p> Write for PyFileWriteText<'p> {
         match self.file.call_method1("write", (s,)) {
             Ok(obj) => {
                 if let Ok(len) = obj.extract() {
-                    Ok(decoded[..len].as_bytes().len())
+                    Ok(decoded[..len].len())
                 } else {
                     let ty = obj.get_type().name()?.to_string();
                     let msg = format!("expected int, found {}", ty);
                     PyTypeError::new_err(msg).restore(self.file.py());
-                    Err(IoError::new(
-                        std::io::ErrorKind::Other,
-                        "write method did not return int",
-                    ))
+                    Err(IoError::other("write method did not return int"))
                 }
             }
             Err(e) => {
@@ -477,6 +504,10 @@ impl<'p> Write for PyFileWriteText<'p> {
     }
 
     fn flush(&mut self) -> Result<(), IoError> {
+        // file-like objects are not required to implement `flush`
+        if !self.file.hasattr("flush")? {
+            return Ok(());
+        }
         match self.file.call_method0("flush") {
             Ok(_) => Ok(()),
             Err(e) => {
@@ -1,16 +1,42 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::fs::File;
+use std::io::BufRead;
+use std::io::BufReader;
 use std::io::Error as IoError;
 use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
 use std::ops::DerefMut;
-use std::path::Path;
-use std::path::PathBuf;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc::Receiver;
+use std::sync::mpsc::SyncSender;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::thread::JoinHandle;
 
+use flate2::bufread::MultiGzDecoder;
+use gb_io::reader::GbParserError;
 use gb_io::reader::SeqReader;
+use gb_io::seq::Seq;
+use gb_io::FeatureKind;
 
+use pyo3::exceptions::PyKeyError;
 use pyo3::exceptions::PyOSError;
 use pyo3::exceptions::PyRuntimeError;
+use pyo3::exceptions::PyUserWarning;
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use pyo3::types::PyIterator;
+use pyo3::types::PyList;
+use pyo3::types::PyMapping;
+use pyo3::types::PyString;
 
+use super::grep::Field;
+use super::grep::Pattern;
 use super::pyfile::PyFileGILRead;
 use super::Convert;
 use super::PyInterner;
@@ -18,65 +44,1091 @@ use super::Record;
 
 // ---------------------------------------------------------------------------
 
-/// An enum providing `Read` for either Python file-handles or filesystem files.
+/// An enum providing `Read` for Python file-handles, filesystem files, or URLs.
 pub enum Handle {
-    FsFile(File, PathBuf),
+    FsFile(File),
     PyFile(PyFileGILRead),
+    #[cfg(feature = "http")]
+    Http(Box<dyn Read + Send + Sync>),
 }
 
-impl TryFrom<PathBuf> for Handle {
-    type Error = std::io::Error;
-    fn try_from(p: PathBuf) -> Result<Self, Self::Error> {
-        let file = File::open(&p)?;
-        Ok(Handle::FsFile(file, p))
+impl Handle {
+    /// Open a handle to a local path, or to an HTTP or HTTPS URL.
+    ///
+    /// URLs require the `http` feature to be enabled, and are streamed
+    /// rather than downloaded in full before reading.
+    pub fn open(path: &str) -> PyResult<Self> {
+        match url_scheme(path) {
+            Some("http" | "https") => Self::open_url(path),
+            Some(scheme) => Err(PyValueError::new_err(format!(
+                "unsupported URL scheme: {:?}",
+                scheme
+            ))),
+            None => File::open(path)
+                .map(Handle::FsFile)
+                .map_err(convert_io_error),
+        }
+    }
+
+    #[cfg(feature = "http")]
+    fn open_url(url: &str) -> PyResult<Self> {
+        // release the GIL while waiting for the server, which may be
+        // running in another Python thread
+        let result = Python::with_gil(|py| {
+            py.allow_threads(|| match ureq::get(url).call() {
+                Ok(response) => Ok(response.into_reader()),
+                Err(ureq::Error::Status(code, response)) => {
+                    Err(format!("HTTP {} {}", code, response.status_text()))
+                }
+                Err(e) => Err(e.to_string()),
+            })
+        });
+        match result {
+            Ok(reader) => Ok(Handle::Http(reader)),
+            Err(e) => Err(PyOSError::new_err(format!(
+                "failed to fetch {}: {}",
+                url, e
+            ))),
+        }
+    }
+
+    #[cfg(not(feature = "http"))]
+    fn open_url(_url: &str) -> PyResult<Self> {
+        Err(PyValueError::new_err(
+            "reading from a URL requires the `http` feature",
+        ))
     }
 }
 
+/// Get the scheme of a URL, or `None` if the path is not a URL.
+///
+/// Single-letter schemes are ignored so that Windows paths with a drive
+/// letter are not mistaken for URLs.
+fn url_scheme(path: &str) -> Option<&str> {
+    let (scheme, _) = path.split_once("://")?;
+    let mut chars = scheme.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    (valid && scheme.len() > 1).then_some(scheme)
+}
+
 impl Read for Handle {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize, IoError> {
         match self {
-            Handle::FsFile(f, _) => f.read(buf),
+            Handle::FsFile(f) => f.read(buf),
             Handle::PyFile(f) => f.read(buf),
+            #[cfg(feature = "http")]
+            Handle::Http(r) => Python::with_gil(|py| py.allow_threads(|| r.read(buf))),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+/// A reader decompressing its input according to its magic bytes.
+///
+/// Gzip streams are always supported, while Zstandard streams require
+/// the `zstd` feature to be enabled. Other inputs are read unchanged.
+pub enum Decoder<R: BufRead> {
+    Plain(R),
+    Gzip(MultiGzDecoder<R>),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::stream::read::Decoder<'static, R>),
+}
+
+impl<R: BufRead> Decoder<R> {
+    const GZIP_MAGIC: &'static [u8] = &[0x1F, 0x8B];
+    const ZSTD_MAGIC: &'static [u8] = &[0x28, 0xB5, 0x2F, 0xFD];
+
+    /// Create a reader which only decompresses its input if `decompress`
+    /// is set, and reads it unchanged otherwise.
+    pub fn with_decompression(reader: R, decompress: bool) -> Result<Self, IoError> {
+        if decompress {
+            Self::new(reader)
+        } else {
+            Ok(Decoder::Plain(reader))
+        }
+    }
+
+    pub fn new(mut reader: R) -> Result<Self, IoError> {
+        let magic = reader.fill_buf()?;
+        if magic.starts_with(Self::GZIP_MAGIC) {
+            Ok(Decoder::Gzip(MultiGzDecoder::new(reader)))
+        } else if magic.starts_with(Self::ZSTD_MAGIC) {
+            #[cfg(feature = "zstd")]
+            return zstd::stream::read::Decoder::with_buffer(reader).map(Decoder::Zstd);
+            #[cfg(not(feature = "zstd"))]
+            return Err(IoError::new(
+                std::io::ErrorKind::Unsupported,
+                "zstd-compressed input requires the `zstd` feature",
+            ));
+        } else {
+            Ok(Decoder::Plain(reader))
+        }
+    }
+
+    /// Check whether the stream is decompressed while being read.
+    pub fn is_compressed(&self) -> bool {
+        !matches!(self, Decoder::Plain(_))
+    }
+}
+
+impl<R: BufRead> Read for Decoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, IoError> {
+        match self {
+            Decoder::Plain(r) => r.read(buf),
+            Decoder::Gzip(r) => r.read(buf),
+            #[cfg(feature = "zstd")]
+            Decoder::Zstd(r) => r.read(buf),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+/// A row of the `PRIMARY` table of a TPA record.
+///
+/// The fields are the span in the TPA record, the identifier of the primary
+/// record, the span in the primary record, and whether the primary span is
+/// complemented.
+pub type PrimarySpan = (String, String, String, bool);
+
+/// The lines of a record which are not stored in a `Seq` by the parser.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecordExtras {
+    /// The annotation following the `ORIGIN` keyword, if any.
+    pub origin_note: Option<String>,
+    /// The accession ranges of the `WGS` lines.
+    pub wgs: Vec<String>,
+    /// The accession ranges of the `WGS_SCAFLD` lines.
+    pub wgs_scafld: Vec<String>,
+    /// The rows of the `PRIMARY` table.
+    pub primary: Vec<PrimarySpan>,
+    /// The offset of the `LOCUS` line in the stream the record was read
+    /// from, which is not moved to the record.
+    pub offset: u64,
+    /// The explicit quoting of qualifiers, keyed by the index of the feature
+    /// and the index of the qualifier, which is only used when writing
+    /// records.
+    pub quoted: HashMap<(usize, usize), bool>,
+}
+
+impl RecordExtras {
+    /// Move the extra lines to the given record.
+    pub fn apply(self, record: &mut Record) {
+        record.origin_note = self.origin_note;
+        record.wgs = self.wgs;
+        record.wgs_scafld = self.wgs_scafld;
+        record.primary = self.primary;
+    }
+}
+
+impl From<&Record> for RecordExtras {
+    fn from(record: &Record) -> Self {
+        Self {
+            origin_note: record.origin_note.clone(),
+            wgs: record.wgs.clone(),
+            wgs_scafld: record.wgs_scafld.clone(),
+            primary: record.primary.clone(),
+            offset: 0,
+            quoted: HashMap::new(),
+        }
+    }
+}
+
+/// A queue of the extra lines of the records being read.
+pub type Extras = Arc<Mutex<VecDeque<RecordExtras>>>;
+
+/// A reader recording the lines of each record that the parser drops.
+///
+/// The `gb_io` parser reads the text following the `ORIGIN` keyword but
+/// does not store it in the `Seq`, and fails on the `WGS` and `WGS_SCAFLD`
+/// lines of WGS master records, so the lines are scanned as they are read
+/// by the parser instead, and the `WGS` lines are removed from the stream.
+/// An entry is queued for each `LOCUS` line, with the offset of the line,
+/// and filled with the lines of the record. Since these lines are always
+/// followed by the `//` line of the record, the entry of a record is
+/// complete once the parser returned.
+pub struct ExtrasScanner<R: Read> {
+    inner: R,
+    extras: Extras,
+    offset: u64,
+    line: Vec<u8>,
+    output: Vec<u8>,
+    position: usize,
+    chunk: Box<[u8]>,
+    overlong: bool,
+    primary: bool,
+}
+
+impl<R: Read> ExtrasScanner<R> {
+    pub fn new(inner: R, extras: Extras) -> Self {
+        Self {
+            inner,
+            extras,
+            offset: 0,
+            line: Vec::new(),
+            output: Vec::new(),
+            position: 0,
+            chunk: vec![0; 8192].into_boxed_slice(),
+            overlong: false,
+            primary: false,
+        }
+    }
+
+    /// Process a complete line, and copy it to the output unless dropped.
+    fn scan_line(&mut self) {
+        let offset = self.offset;
+        self.offset += self.line.len() as u64;
+        // the continuation of an overlong line was already scanned
+        if self.overlong {
+            self.output.extend_from_slice(&self.line);
+            self.line.clear();
+            return;
+        }
+        let line = self.line.as_slice();
+        let text = |keyword: &str| {
+            String::from_utf8_lossy(&line[keyword.len()..])
+                .trim()
+                .to_string()
+        };
+        let mut extras = self.extras.lock().unwrap();
+        let mut keep = true;
+        // the rows of the `PRIMARY` table follow its header line
+        if self.primary && !line.starts_with(b" ") {
+            self.primary = false;
+        }
+        if self.primary {
+            let row = String::from_utf8_lossy(line);
+            let mut fields = row.split_whitespace();
+            if let (Some(last), Some(tpa_span)) = (extras.back_mut(), fields.next()) {
+                last.primary.push((
+                    tpa_span.to_string(),
+                    fields.next().unwrap_or_default().to_string(),
+                    fields.next().unwrap_or_default().to_string(),
+                    fields.next() == Some("c"),
+                ));
+            }
+            keep = false;
+        } else if line.starts_with(b"LOCUS") {
+            extras.push_back(RecordExtras {
+                offset,
+                ..Default::default()
+            });
+        } else if line.starts_with(b"ORIGIN") {
+            let note = text("ORIGIN");
+            if let Some(last) = extras.back_mut().filter(|_| !note.is_empty()) {
+                last.origin_note = Some(note);
+            }
+        } else if line.starts_with(b"WGS_SCAFLD") {
+            if let Some(last) = extras.back_mut() {
+                last.wgs_scafld.push(text("WGS_SCAFLD"));
+            }
+            keep = false;
+        } else if line.starts_with(b"WGS ") || line.starts_with(b"WGS") && text("WGS").is_empty() {
+            if let Some(last) = extras.back_mut() {
+                last.wgs.push(text("WGS"));
+            }
+            keep = false;
+        } else if line.starts_with(b"PRIMARY ") {
+            self.primary = true;
+            keep = false;
+        }
+        drop(extras);
+        if keep {
+            self.output.extend_from_slice(&self.line);
+        }
+        self.line.clear();
+    }
+}
+
+impl<R: Read> Read for ExtrasScanner<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, IoError> {
+        // lines are buffered until complete, since they can only be
+        // dropped from the stream once their keyword is known
+        while self.position == self.output.len() {
+            self.output.clear();
+            self.position = 0;
+            let n = self.inner.read(&mut self.chunk)?;
+            if n == 0 {
+                if self.line.is_empty() {
+                    return Ok(0);
+                }
+                self.scan_line();
+                continue;
+            }
+            let mut start = 0;
+            while let Some(i) = self.chunk[start..n].iter().position(|&c| c == b'\n') {
+                self.line.extend_from_slice(&self.chunk[start..=start + i]);
+                start += i + 1;
+                self.scan_line();
+                self.overlong = false;
+            }
+            self.line.extend_from_slice(&self.chunk[start..n]);
+            // avoid buffering a line without end, which cannot be a line
+            // to be dropped anyway
+            if self.line.len() >= self.chunk.len() {
+                self.scan_line();
+                self.overlong = true;
+            }
+        }
+        let n = buf.len().min(self.output.len() - self.position);
+        buf[..n].copy_from_slice(&self.output[self.position..self.position + n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+/// A normalization applied to the feature kinds of the records being read.
+#[derive(Debug, Clone)]
+pub enum KindNormalization {
+    /// Convert the feature kinds to lowercase.
+    Lower,
+    /// Convert the feature kinds to uppercase.
+    Upper,
+    /// Rename the feature kinds found in the mapping.
+    Map(HashMap<String, String>),
+}
+
+impl KindNormalization {
+    /// Normalize the kinds of the features of a record in place.
+    ///
+    /// Normalized kinds are atoms like parsed kinds, so they are interned
+    /// when the features are converted to Python objects.
+    pub fn apply(&self, seq: &mut Seq) {
+        for feature in seq.features.iter_mut() {
+            let kind = match self {
+                KindNormalization::Lower if feature.kind.chars().any(char::is_uppercase) => {
+                    FeatureKind::from(feature.kind.to_lowercase())
+                }
+                KindNormalization::Upper if feature.kind.chars().any(char::is_lowercase) => {
+                    FeatureKind::from(feature.kind.to_uppercase())
+                }
+                KindNormalization::Map(mapping) => match mapping.get(feature.kind.as_ref()) {
+                    Some(kind) => FeatureKind::from(kind.as_str()),
+                    None => continue,
+                },
+                _ => continue,
+            };
+            feature.kind = kind;
+        }
+    }
+}
+
+impl<'py> FromPyObject<'py> for KindNormalization {
+    fn extract(ob: &'py PyAny) -> PyResult<Self> {
+        if let Ok(value) = ob.downcast::<PyString>() {
+            match value.to_str()? {
+                "lower" => Ok(KindNormalization::Lower),
+                "upper" => Ok(KindNormalization::Upper),
+                other => Err(PyValueError::new_err(format!(
+                    "invalid kind normalization: {:?}",
+                    other
+                ))),
+            }
+        } else {
+            ob.extract().map(KindNormalization::Map)
+        }
+    }
+}
+
+/// A set of qualifiers removed from the records being read.
+#[derive(Debug, Clone, Default)]
+pub struct QualifierFilter {
+    keys: HashSet<String>,
+}
+
+impl QualifierFilter {
+    /// Remove the qualifiers with a filtered key from the record features.
+    pub fn apply(&self, seq: &mut Seq) {
+        for feature in seq.features.iter_mut() {
+            feature
+                .qualifiers
+                .retain(|(key, _)| !self.keys.contains(key.as_ref()));
+        }
+    }
+}
+
+impl<'py> FromPyObject<'py> for QualifierFilter {
+    fn extract(ob: &'py PyAny) -> PyResult<Self> {
+        let mut keys = HashSet::new();
+        if let Ok(key) = ob.downcast::<PyString>() {
+            keys.insert(key.to_str()?.to_string());
+        } else {
+            for key in ob.iter()? {
+                keys.insert(key?.downcast::<PyString>()?.to_str()?.to_string());
+            }
+        }
+        Ok(Self { keys })
+    }
+}
+
+/// The qualifiers required on the features of the records being read.
+#[derive(Debug, Clone, Default)]
+pub struct QualifierRequirements {
+    required: HashMap<String, Vec<String>>,
+}
+
+impl QualifierRequirements {
+    /// Find the features of a record missing some required qualifiers.
+    ///
+    /// Each violation is reported with the index of the feature, its kind,
+    /// and the keys of the missing qualifiers, in the order they were given.
+    pub fn check<'a>(&'a self, seq: &'a Seq) -> Vec<(usize, &'a str, Vec<&'a str>)> {
+        let mut violations = Vec::new();
+        for (index, feature) in seq.features.iter().enumerate() {
+            if let Some(required) = self.required.get(feature.kind.as_ref()) {
+                let missing = required
+                    .iter()
+                    .map(String::as_str)
+                    .filter(|key| !feature.qualifiers.iter().any(|(k, _)| k.as_ref() == *key))
+                    .collect::<Vec<_>>();
+                if !missing.is_empty() {
+                    violations.push((index, feature.kind.as_ref(), missing));
+                }
+            }
+        }
+        violations
+    }
+}
+
+impl<'py> FromPyObject<'py> for QualifierRequirements {
+    fn extract(ob: &'py PyAny) -> PyResult<Self> {
+        let mut required = HashMap::new();
+        for item in ob.downcast::<PyMapping>()?.items()?.iter()? {
+            let (kind, keys): (String, &PyAny) = item?.extract()?;
+            let keys = if let Ok(key) = keys.downcast::<PyString>() {
+                vec![key.to_str()?.to_string()]
+            } else {
+                keys.iter()?
+                    .map(|key| Ok(key?.downcast::<PyString>()?.to_str()?.to_string()))
+                    .collect::<PyResult<Vec<_>>>()?
+            };
+            required.insert(kind, keys);
+        }
+        Ok(Self { required })
+    }
+}
+
+/// The policy for records that fail to be read.
+///
+/// The same policy is used for records that could not be parsed, and for
+/// records failing a check such as the required qualifiers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ErrorPolicy {
+    /// Raise a `ValueError` for the first failure.
+    #[default]
+    Raise,
+    /// Skip the failing records silently, and keep reading.
+    Skip,
+    /// Emit a `UserWarning` for each failure, and keep reading. Records
+    /// that could not be parsed are skipped, while records failing a check
+    /// are kept.
+    Warn,
+}
+
+impl ErrorPolicy {
+    /// Handle the parser error of the record starting at `offset`.
+    ///
+    /// Returns the error with the `Raise` policy, or `Ok` if the record
+    /// should be skipped.
+    pub fn recover(&self, py: Python, error: PyErr, offset: u64) -> PyResult<()> {
+        match self {
+            ErrorPolicy::Raise => Err(error),
+            ErrorPolicy::Skip => Ok(()),
+            ErrorPolicy::Warn => {
+                let msg = format!(
+                    "skipped record at offset {}: {}",
+                    offset,
+                    error.value_bound(py)
+                );
+                PyErr::warn_bound(py, &py.get_type_bound::<PyUserWarning>(), &msg, 1)
+            }
+        }
+    }
+}
+
+impl<'py> FromPyObject<'py> for ErrorPolicy {
+    fn extract(ob: &'py PyAny) -> PyResult<Self> {
+        match ob.downcast::<PyString>()?.to_str()? {
+            "raise" => Ok(ErrorPolicy::Raise),
+            "skip" => Ok(ErrorPolicy::Skip),
+            "warn" => Ok(ErrorPolicy::Warn),
+            other => Err(PyValueError::new_err(format!(
+                "invalid error policy: {:?}",
+                other
+            ))),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+/// An iterator over the raw text of the records contained in a stream.
+///
+/// Each item contains the bytes of a record from its ``LOCUS`` line to its
+/// ``//`` terminator, both included. Any text between records is skipped.
+/// A record missing its terminator ends before the next ``LOCUS`` line, so
+/// that a malformed record does not swallow the following ones.
+pub struct RecordSplitter<R: BufRead> {
+    reader: R,
+    position: u64,
+    start: u64,
+    pending: Vec<u8>,
+}
+
+impl<R: BufRead> RecordSplitter<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            position: 0,
+            start: 0,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Get the offset of the first line of the last record in the stream.
+    pub fn offset(&self) -> u64 {
+        self.start
+    }
+}
+
+impl<R: BufRead> Iterator for RecordSplitter<R> {
+    type Item = Result<Vec<u8>, IoError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        // resume with the `LOCUS` line read at the end of the previous record
+        let mut text = std::mem::take(&mut self.pending);
+        if !text.is_empty() {
+            self.start = self.position - text.len() as u64;
+        }
+        loop {
+            let start = text.len();
+            let result = self.reader.read_until(b'\n', &mut text);
+            if start == 0 {
+                self.start = self.position;
+            }
+            if let Ok(n) = result {
+                self.position += n as u64;
+            }
+            match result {
+                Err(e) => return Some(Err(e)),
+                Ok(0) if text.is_empty() => return None,
+                Ok(0) => return Some(Ok(text)),
+                Ok(_) if start == 0 && !text.starts_with(b"LOCUS") => text.clear(),
+                Ok(_) if text[start..].starts_with(b"//") => return Some(Ok(text)),
+                Ok(_) if start > 0 && text[start..].starts_with(b"LOCUS") => {
+                    self.pending = text.split_off(start);
+                    return Some(Ok(text));
+                }
+                Ok(_) => (),
+            }
         }
     }
 }
 
+/// The number of bytes of record text to parse in parallel, per thread.
+pub const CHUNK_SIZE: usize = 1 << 22;
+
+/// A record parsed from its raw text, with its extra lines.
+pub type ParsedText = Option<Result<(Seq, RecordExtras), GbParserError>>;
+
+/// Parse the raw text of several records on a pool of threads.
+///
+/// Each text is expected to contain a single record, as produced by a
+/// `RecordSplitter`. Threads take the next text to parse as soon as they
+/// are done with the previous one, so that large records do not stall
+/// the others, and the results are returned in the order of the texts.
+/// This does not need the GIL, and should be called without it.
+pub fn parse_parallel(texts: &[Vec<u8>], threads: usize) -> Vec<ParsedText> {
+    let next = AtomicUsize::new(0);
+    let parse = || {
+        let mut results = Vec::new();
+        loop {
+            let i = next.fetch_add(1, Ordering::Relaxed);
+            let text = match texts.get(i) {
+                Some(text) => text,
+                None => return results,
+            };
+            let extras = Extras::default();
+            let scanner = ExtrasScanner::new(text.as_slice(), extras.clone());
+            let result = SeqReader::new(scanner).next().map(|result| {
+                result.map(|seq| (seq, extras.lock().unwrap().pop_front().unwrap_or_default()))
+            });
+            results.push((i, result));
+        }
+    };
+
+    let mut parsed = std::iter::repeat_with(|| None)
+        .take(texts.len())
+        .collect::<Vec<_>>();
+    std::thread::scope(|scope| {
+        let workers = (0..threads.min(texts.len()))
+            .map(|_| scope.spawn(parse))
+            .collect::<Vec<_>>();
+        for worker in workers {
+            for (i, result) in worker.join().unwrap() {
+                parsed[i] = result;
+            }
+        }
+    });
+    parsed
+}
+
 // ---------------------------------------------------------------------------
 
+/// The decompressed stream of a `RecordReader`.
+type Stream = Decoder<BufReader<Handle>>;
+
+/// The parser of the records of a `RecordReader`.
+type Parser = SeqReader<ExtrasScanner<Stream>>;
+
+/// A record parsed by a background thread, with its extra lines.
+type Prefetched = PyResult<(Seq, RecordExtras)>;
+
+/// The source of the records of a `RecordReader`.
+enum Records {
+    Parsed(Parser),
+    Raw(RecordSplitter<BufReader<Stream>>),
+    Prefetched(Prefetcher),
+}
+
+/// A background thread parsing the records of a `RecordReader` ahead.
+struct Prefetcher {
+    receiver: Option<Receiver<Prefetched>>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl Prefetcher {
+    /// Receive the next record, or `None` once the worker has stopped.
+    fn recv(&mut self, py: Python) -> Option<Prefetched> {
+        let receiver = self.receiver.as_mut()?;
+        // release the GIL so that the worker can read from a Python
+        // file-handle while we wait for the next record
+        py.allow_threads(move || receiver.recv()).ok()
+    }
+}
+
+impl Drop for Prefetcher {
+    fn drop(&mut self) {
+        // dropping the receiving end of the channel signals the worker to
+        // stop, at the latest once it tries to send its current record
+        drop(self.receiver.take());
+        if let Some(worker) = self.worker.take() {
+            // wait without the GIL, which the worker may need to finish
+            // reading from a Python file-handle; a panic of the worker was
+            // already reported by the panic hook, so it is ignored here
+            let _ = Python::with_gil(|py| py.allow_threads(move || worker.join()));
+        }
+    }
+}
+
+/// Parse records on the current thread and send them to a `RecordReader`.
+///
+/// The worker stops after the first error, or once the receiving end of
+/// the channel has been dropped.
+fn prefetch_worker(parser: Parser, extras: Extras, sender: SyncSender<Prefetched>) {
+    // keep a Python thread state alive while parsing, otherwise an error
+    // raised by a file-handle would be lost when the GIL is released after
+    // the read, before the parser fails and the error can be fetched
+    Python::with_gil(|py| {
+        py.allow_threads(move || {
+            for result in parser {
+                let item = match result {
+                    Ok(seq) => Ok((seq, extras.lock().unwrap().pop_front().unwrap_or_default())),
+                    Err(e) => Err(Python::with_gil(|py| convert_parser_error(py, e))),
+                };
+                let failed = item.is_err();
+                if sender.send(item).is_err() || failed {
+                    break;
+                }
+            }
+        })
+    })
+}
+
 /// An iterator over the `~gb_io.Record` contained in a file.
 #[pyclass(module = "gb_io")]
 pub struct RecordReader {
-    reader: SeqReader<Handle>,
+    records: Records,
     interner: PyInterner,
+    normalize_kinds: Option<KindNormalization>,
+    drop_qualifiers: Option<QualifierFilter>,
+    require_qualifiers: Option<QualifierRequirements>,
+    errors: ErrorPolicy,
+    on_error: ErrorPolicy,
+    with_text: bool,
+    reuse: bool,
+    buffer: Option<Py<Record>>,
+    extras: Extras,
+    base_offset: u64,
+    offset: Option<u64>,
 }
 
 impl RecordReader {
-    fn new(reader: SeqReader<Handle>) -> PyResult<Self> {
+    fn new(
+        py: Python,
+        handle: Handle,
+        with_text: bool,
+        normalize_kinds: Option<KindNormalization>,
+        decompress: bool,
+        on_error: ErrorPolicy,
+        base_offset: u64,
+    ) -> PyResult<Self> {
+        let stream = Decoder::with_decompression(BufReader::new(handle), decompress)
+            .map_err(|e| PyErr::take(py).unwrap_or_else(|| convert_io_error(e)))?;
+        // offsets of compressed inputs are counted in the decompressed data,
+        // so the position of the handle in the compressed data is irrelevant
+        let base_offset = match stream.is_compressed() {
+            true => 0,
+            false => base_offset,
+        };
+        let extras = Extras::default();
+        // records are split before being parsed when they may be skipped,
+        // since the parser cannot resume after an error
+        let records = if with_text || on_error != ErrorPolicy::Raise {
+            Records::Raw(RecordSplitter::new(BufReader::new(stream)))
+        } else {
+            Records::Parsed(SeqReader::new(ExtrasScanner::new(stream, extras.clone())))
+        };
         Ok(Self {
-            reader,
+            records,
             interner: Default::default(),
+            normalize_kinds,
+            drop_qualifiers: None,
+            require_qualifiers: None,
+            errors: ErrorPolicy::default(),
+            on_error,
+            with_text,
+            reuse: false,
+            buffer: None,
+            extras,
+            base_offset,
+            offset: None,
         })
     }
 
-    pub fn from_path<P: AsRef<Path>>(path: P) -> PyResult<Self> {
-        let p = path.as_ref();
-        match Handle::try_from(p.to_owned()) {
-            Ok(handle) => Self::new(SeqReader::new(handle)),
-            Err(e) => {
-                if let Some(code) = e.raw_os_error() {
-                    Err(PyOSError::new_err((code, e.to_string())))
-                } else {
-                    Err(PyOSError::new_err(e.to_string()))
+    /// Set whether the same `Record` object should be filled and returned
+    /// for every record, instead of creating a new one each time.
+    pub fn reuse_records(&mut self, reuse: bool) -> &mut Self {
+        self.reuse = reuse;
+        self
+    }
+
+    /// Set the qualifiers to remove from the features before conversion.
+    pub fn drop_qualifiers(&mut self, filter: Option<QualifierFilter>) -> &mut Self {
+        self.drop_qualifiers = filter;
+        self
+    }
+
+    /// Set the qualifiers required on the features of each record, and the
+    /// policy for records missing some of them.
+    pub fn require_qualifiers(
+        &mut self,
+        requirements: Option<QualifierRequirements>,
+        errors: ErrorPolicy,
+    ) -> &mut Self {
+        self.require_qualifiers = requirements;
+        self.errors = errors;
+        self
+    }
+
+    /// Parse up to `size` records ahead on a background thread.
+    ///
+    /// Records are sent to the reader through a bounded channel, and are
+    /// only converted to Python objects when the reader advances. A size
+    /// of zero leaves the reader unchanged.
+    pub fn prefetch(self, size: usize) -> PyResult<Self> {
+        let records = match self.records {
+            Records::Parsed(parser) if size > 0 => {
+                let (sender, receiver) = std::sync::mpsc::sync_channel(size);
+                let extras = self.extras.clone();
+                let worker = std::thread::Builder::new()
+                    .name(String::from("gb-io-prefetch"))
+                    .spawn(move || prefetch_worker(parser, extras, sender))
+                    .map_err(convert_io_error)?;
+                Records::Prefetched(Prefetcher {
+                    receiver: Some(receiver),
+                    worker: Some(worker),
+                })
+            }
+            Records::Raw(_) if size > 0 => {
+                return Err(PyValueError::new_err(match self.with_text {
+                    true => "cannot prefetch records read with `with_text`",
+                    false => "cannot prefetch records read with `on_error`",
+                }))
+            }
+            records => records,
+        };
+        Ok(Self { records, ..self })
+    }
+
+    pub fn from_path(
+        py: Python,
+        path: &str,
+        with_text: bool,
+        normalize_kinds: Option<KindNormalization>,
+        decompress: bool,
+        on_error: ErrorPolicy,
+    ) -> PyResult<Self> {
+        let handle = Handle::open(path)?;
+        Self::new(
+            py,
+            handle,
+            with_text,
+            normalize_kinds,
+            decompress,
+            on_error,
+            0,
+        )
+    }
+
+    pub fn from_handle(
+        obj: Bound<PyAny>,
+        with_text: bool,
+        normalize_kinds: Option<KindNormalization>,
+        decompress: bool,
+        on_error: ErrorPolicy,
+    ) -> PyResult<Self> {
+        let py = obj.py();
+        // offsets are counted from the current position of binary files,
+        // when it is known
+        let base_offset = obj
+            .call_method0("tell")
+            .and_then(|position| position.extract::<u64>())
+            .ok();
+        let file = PyFileGILRead::from_ref(obj)?;
+        let base_offset = match file {
+            PyFileGILRead::Binary(_) => base_offset.unwrap_or(0),
+            _ => 0,
+        };
+        Self::new(
+            py,
+            Handle::PyFile(file),
+            with_text,
+            normalize_kinds,
+            decompress,
+            on_error,
+            base_offset,
+        )
+    }
+
+    /// Convert the result of the parser into a `Record`.
+    ///
+    /// Returns `None` at the end of the stream, or if the record was
+    /// skipped by the `errors` policy of the reader.
+    fn convert(
+        &mut self,
+        py: Python,
+        result: Option<Result<Seq, GbParserError>>,
+    ) -> PyResult<Option<Py<Record>>> {
+        match result {
+            None => Ok(None),
+            Some(Ok(seq)) => {
+                let extras = self.extras.lock().unwrap().pop_front().unwrap_or_default();
+                self.convert_seq(py, seq, extras)
+            }
+            Some(Err(e)) => Err(convert_parser_error(py, e)),
+        }
+    }
+
+    /// Convert a parsed record and its extra lines into a `Record`.
+    ///
+    /// Returns `None` if the record is missing required qualifiers and is
+    /// skipped by the `errors` policy of the reader.
+    fn convert_seq(
+        &mut self,
+        py: Python,
+        mut seq: Seq,
+        extras: RecordExtras,
+    ) -> PyResult<Option<Py<Record>>> {
+        if let Some(normalize_kinds) = &self.normalize_kinds {
+            normalize_kinds.apply(&mut seq);
+        }
+        if let Some(drop_qualifiers) = &self.drop_qualifiers {
+            drop_qualifiers.apply(&mut seq);
+        }
+        self.offset = Some(self.base_offset + extras.offset);
+        if let Some(requirements) = &self.require_qualifiers {
+            let id = seq
+                .accession
+                .as_deref()
+                .and_then(|accession| accession.split_whitespace().next())
+                .or(seq.name.as_deref())
+                .unwrap_or("<unnamed>");
+            for (index, kind, missing) in requirements.check(&seq) {
+                let msg = format!(
+                    "feature {} ({}) of record {} is missing required qualifiers: {}",
+                    index,
+                    kind,
+                    id,
+                    missing.join(", ")
+                );
+                match self.errors {
+                    ErrorPolicy::Raise => return Err(PyValueError::new_err(msg)),
+                    ErrorPolicy::Skip => return Ok(None),
+                    ErrorPolicy::Warn => {
+                        PyErr::warn_bound(py, &py.get_type_bound::<PyUserWarning>(), &msg, 1)?
+                    }
+                }
+            }
+        }
+        if !self.reuse {
+            let record = seq.convert_with(py, &mut self.interner)?;
+            extras.apply(&mut record.borrow_mut(py));
+            return Ok(Some(record));
+        }
+        let mut record = Record::from_parsed(py, seq)?;
+        extras.apply(&mut record);
+        match &self.buffer {
+            Some(buffer) => {
+                *buffer.bind(py).try_borrow_mut()? = record;
+                Ok(Some(buffer.clone_ref(py)))
+            }
+            None => {
+                let buffer = Py::new(py, record)?;
+                self.buffer = Some(buffer.clone_ref(py));
+                Ok(Some(buffer))
+            }
+        }
+    }
+
+    /// Read the raw text of the next record and parse it.
+    ///
+    /// Records that could not be parsed are handled according to the
+    /// `on_error` policy of the reader.
+    fn next_text_record(&mut self, py: Python) -> PyResult<Option<(Vec<u8>, Py<Record>)>> {
+        loop {
+            let (text, offset) = match &mut self.records {
+                Records::Raw(splitter) => match splitter.next() {
+                    None => return Ok(None),
+                    Some(Ok(text)) => (text, self.base_offset + splitter.offset()),
+                    Some(Err(e)) => {
+                        return Err(PyErr::take(py).unwrap_or_else(|| convert_io_error(e)))
+                    }
+                },
+                _ => unreachable!("reader was not created with `with_text` or `on_error`"),
+            };
+            let scanner = ExtrasScanner::new(text.as_slice(), self.extras.clone());
+            let error = match SeqReader::new(scanner).next() {
+                Some(Ok(seq)) => {
+                    let extras = self.extras.lock().unwrap().pop_front().unwrap_or_default();
+                    let record = self.convert_seq(py, seq, extras);
+                    self.offset = Some(offset);
+                    match record? {
+                        Some(record) => return Ok(Some((text, record))),
+                        None => continue,
+                    }
                 }
+                Some(Err(e)) => convert_parser_error(py, e),
+                None => PyValueError::new_err("parser failed: incomplete record"),
+            };
+            // discard the extra lines of the failed record
+            self.extras.lock().unwrap().clear();
+            self.offset = Some(offset);
+            self.on_error.recover(py, error, offset)?;
+        }
+    }
+
+    /// Read the next record, or return `None` at the end of the file.
+    pub fn next_record(&mut self, py: Python) -> PyResult<Option<Py<Record>>> {
+        loop {
+            let (seq, extras) = match &mut self.records {
+                Records::Parsed(reader) => match reader.next() {
+                    None => return Ok(None),
+                    Some(Ok(seq)) => {
+                        let extras = self.extras.lock().unwrap().pop_front().unwrap_or_default();
+                        (seq, extras)
+                    }
+                    Some(Err(e)) => return Err(convert_parser_error(py, e)),
+                },
+                Records::Raw(_) => return Ok(self.next_text_record(py)?.map(|(_, record)| record)),
+                Records::Prefetched(prefetcher) => match prefetcher.recv(py) {
+                    Some(Ok(item)) => item,
+                    Some(Err(e)) => return Err(e),
+                    None => return Ok(None),
+                },
+            };
+            // records missing required qualifiers may be skipped
+            if let Some(record) = self.convert_seq(py, seq, extras)? {
+                return Ok(Some(record));
+            }
+        }
+    }
+
+    /// Read the next record matching a pattern, with the matching field.
+    ///
+    /// Records are matched before being converted, so that records not
+    /// matching the pattern are never converted to Python objects.
+    pub fn next_match(
+        &mut self,
+        py: Python,
+        pattern: &Pattern,
+    ) -> PyResult<Option<(Py<Record>, Field)>> {
+        loop {
+            let reader = match &mut self.records {
+                Records::Parsed(reader) => reader,
+                _ => unreachable!("reader was created with `with_text` or `prefetch`"),
+            };
+            let (result, field) = match reader.next() {
+                Some(Ok(seq)) => match pattern.find(&seq) {
+                    Some(field) => (Some(Ok(seq)), Some(field)),
+                    None => {
+                        // discard the extra lines of the skipped record
+                        self.extras.lock().unwrap().pop_front();
+                        continue;
+                    }
+                },
+                other => (other, None),
+            };
+            let end = result.is_none();
+            match self.convert(py, result)? {
+                Some(record) => return Ok(field.map(|field| (record, field))),
+                None if end => return Ok(None),
+                None => continue,
             }
         }
     }
 
-    pub fn from_handle(obj: Bound<PyAny>) -> PyResult<Self> {
-        match PyFileGILRead::from_ref(obj).map(Handle::PyFile) {
-            Ok(handle) => Self::new(SeqReader::new(handle)),
-            Err(e) => Err(e),
+    /// Read up to `size` records, or return an empty batch at the end of the file.
+    ///
+    /// When `release_gil` is set, the records of the batch are parsed without
+    /// holding the GIL, which is only acquired to convert them afterwards.
+    pub fn next_batch(
+        &mut self,
+        py: Python,
+        size: usize,
+        release_gil: bool,
+    ) -> PyResult<Vec<Py<Record>>> {
+        let reader = match &mut self.records {
+            Records::Parsed(reader) => reader,
+            _ => unreachable!("reader was created with `with_text` or `prefetch`"),
+        };
+        let mut parse = || {
+            let mut results = Vec::with_capacity(size);
+            for result in reader.take(size) {
+                // stop at the first error so that it is reported last
+                let failed = result.is_err();
+                results.push(result);
+                if failed {
+                    break;
+                }
+            }
+            results
+        };
+        let results = if release_gil {
+            py.allow_threads(parse)
+        } else {
+            parse()
+        };
+        let mut batch = Vec::with_capacity(results.len());
+        for result in results {
+            if let Some(record) = self.convert(py, Some(result))? {
+                batch.push(record);
+            }
         }
+        Ok(batch)
     }
 }
 
@@ -86,24 +1138,354 @@ impl RecordReader {
         Ok(slf)
     }
 
-    fn __next__<'p>(mut slf: PyRefMut<'p, Self>) -> PyResult<Option<Py<Record>>> {
-        let slf = slf.deref_mut();
-        match slf.reader.next() {
-            None => Ok(None),
+    /// Get the offset of the last record read in the stream.
+    ///
+    /// Returns:
+    ///     `int` or `None`: The position of the ``LOCUS`` line of the
+    ///     record yielded last, in bytes from the start of the file, or
+    ///     `None` if no record was read yet. For binary file-handles,
+    ///     positions are counted from the position of the handle when
+    ///     the reader was created, if known. For compressed inputs,
+    ///     positions are given in the decompressed data, counted from
+    ///     the start of the compressed stream.
+    ///
+    /// Example:
+    ///     Build an index of the records of a file::
+    ///
+    ///         >>> path = "tests/data/biopython/cor6_6.gb"
+    ///         >>> reader = gb_io.iter(path)
+    ///         >>> index = {record.name: reader.tell() for record in reader}
+    ///         >>> with open(path, "rb") as f:
+    ///         ...     _ = f.seek(index["ATKIN2"])
+    ///         ...     next(gb_io.iter(f)).name
+    ///         'ATKIN2'
+    ///
+    /// .. versionadded:: 0.4.0
+    ///
+    fn tell(slf: PyRef<'_, Self>) -> Option<u64> {
+        slf.offset
+    }
+
+    fn __length_hint__(slf: PyRef<'_, Self>) -> PyResult<PyObject> {
+        // collecting the records of a reusing reader would only give
+        // the same object over and over, so fail early instead
+        if slf.reuse {
+            Err(PyRuntimeError::new_err(
+                "cannot collect the records of a reader created with reuse=True",
+            ))
+        } else {
+            Ok(slf.py().NotImplemented())
+        }
+    }
+
+    fn __next__<'p>(mut slf: PyRefMut<'p, Self>) -> PyResult<Option<PyObject>> {
+        let py = slf.py();
+        let reader = slf.deref_mut();
+        if reader.with_text {
+            Ok(reader
+                .next_text_record(py)?
+                .map(|(text, record)| (PyBytes::new_bound(py, &text), record).into_py(py)))
+        } else {
+            Ok(reader.next_record(py)?.map(|record| record.into_py(py)))
+        }
+    }
+}
+
+/// An iterator over batches of `~gb_io.Record` contained in a file.
+#[pyclass(module = "gb_io")]
+pub struct RecordBatchReader {
+    reader: RecordReader,
+    batch_size: usize,
+    release_gil: bool,
+}
+
+impl RecordBatchReader {
+    pub fn new(reader: RecordReader, batch_size: usize, release_gil: bool) -> PyResult<Self> {
+        if batch_size == 0 {
+            return Err(PyValueError::new_err(
+                "batch_size must be strictly positive",
+            ));
+        }
+        Ok(Self {
+            reader,
+            batch_size,
+            release_gil,
+        })
+    }
+}
+
+#[pymethods]
+impl RecordBatchReader {
+    fn __iter__<'p>(slf: PyRefMut<'p, Self>) -> PyResult<PyRefMut<'p, Self>> {
+        Ok(slf)
+    }
+
+    fn __next__<'p>(mut slf: PyRefMut<'p, Self>) -> PyResult<Option<Py<PyList>>> {
+        let py = slf.py();
+        let this = slf.deref_mut();
+        let batch = this
+            .reader
+            .next_batch(py, this.batch_size, this.release_gil)?;
+        if batch.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(PyList::new_bound(py, batch).unbind()))
+        }
+    }
+}
+
+/// An iterator over the `~gb_io.Record` of a file matching a pattern.
+#[pyclass(module = "gb_io")]
+pub struct GrepReader {
+    reader: RecordReader,
+    pattern: Pattern,
+}
+
+impl GrepReader {
+    pub fn new(reader: RecordReader, pattern: Pattern) -> Self {
+        Self { reader, pattern }
+    }
+}
+
+#[pymethods]
+impl GrepReader {
+    fn __iter__<'p>(slf: PyRefMut<'p, Self>) -> PyResult<PyRefMut<'p, Self>> {
+        Ok(slf)
+    }
+
+    fn __next__<'p>(mut slf: PyRefMut<'p, Self>) -> PyResult<Option<PyObject>> {
+        let py = slf.py();
+        let this = slf.deref_mut();
+        Ok(this
+            .reader
+            .next_match(py, &this.pattern)?
+            .map(|(record, field)| (record, field.name()).into_py(py)))
+    }
+}
+
+/// The identifier used to index the records of a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexKey {
+    /// The first accession of the `ACCESSION` line, or the record name.
+    Accession,
+    /// The name of the `LOCUS` line.
+    Name,
+}
+
+impl<'py> FromPyObject<'py> for IndexKey {
+    fn extract(ob: &'py PyAny) -> PyResult<Self> {
+        match ob.downcast::<PyString>()?.to_str()? {
+            "accession" => Ok(IndexKey::Accession),
+            "name" => Ok(IndexKey::Name),
+            other => Err(PyValueError::new_err(format!(
+                "invalid index key: {:?}",
+                other
+            ))),
+        }
+    }
+}
+
+/// A read-only mapping of identifiers to the `~gb_io.Record` of a file.
+///
+/// The file is scanned once when the index is created, and only the
+/// position of each record is kept in memory. Records are parsed from
+/// the file each time they are accessed.
+///
+/// .. versionadded:: 0.4.0
+///
+#[pyclass(module = "gb_io", mapping)]
+pub struct RecordIndex {
+    path: String,
+    keys: Vec<String>,
+    spans: HashMap<String, (u64, u64)>,
+}
+
+impl RecordIndex {
+    /// Index the records of the file at the given path.
+    pub fn build(path: &str, key: IndexKey) -> PyResult<Self> {
+        let file = File::open(path).map_err(convert_io_error)?;
+        let mut reader = match Decoder::new(BufReader::new(file)).map_err(convert_io_error)? {
+            Decoder::Plain(reader) => reader,
+            _ => return Err(PyValueError::new_err("cannot index a compressed file")),
+        };
+
+        let mut index = Self {
+            path: path.to_string(),
+            keys: Vec::new(),
+            spans: HashMap::new(),
+        };
+        let token = |text: &[u8]| {
+            String::from_utf8_lossy(text)
+                .split_whitespace()
+                .next()
+                .map(String::from)
+        };
+        let mut line = Vec::new();
+        let mut position = 0;
+        // the offset, name and accession of the current record, if any
+        let mut current: Option<(u64, Option<String>, Option<String>)> = None;
+        loop {
+            line.clear();
+            let n = reader
+                .read_until(b'\n', &mut line)
+                .map_err(convert_io_error)? as u64;
+            if n == 0 {
+                break;
+            }
+            position += n;
+            if line.starts_with(b"LOCUS") {
+                current = Some((position - n, token(&line[5..]), None));
+            } else if line.starts_with(b"ACCESSION") {
+                if let Some((_, _, accession @ None)) = &mut current {
+                    *accession = token(&line[9..]);
+                }
+            } else if line.starts_with(b"//") {
+                if let Some(record) = current.take() {
+                    index.insert(key, record, position)?;
+                }
+            }
+        }
+        if let Some(record) = current.take() {
+            index.insert(key, record, position)?;
+        }
+        Ok(index)
+    }
+
+    /// Add a record spanning from its offset to the given end position.
+    fn insert(
+        &mut self,
+        key: IndexKey,
+        (offset, name, accession): (u64, Option<String>, Option<String>),
+        end: u64,
+    ) -> PyResult<()> {
+        let id = match key {
+            IndexKey::Accession => accession.or(name),
+            IndexKey::Name => name,
+        };
+        let id = id.ok_or_else(|| {
+            PyValueError::new_err(format!("record at offset {} has no identifier", offset))
+        })?;
+        if self.spans.contains_key(&id) {
+            return Err(PyValueError::new_err(format!("duplicate key: {:?}", id)));
+        }
+        self.spans.insert(id.clone(), (offset, end - offset));
+        self.keys.push(id);
+        Ok(())
+    }
+
+    /// Get the offset and length of the record with the given identifier.
+    fn span(&self, key: &str) -> PyResult<(u64, u64)> {
+        self.spans
+            .get(key)
+            .copied()
+            .ok_or_else(|| PyKeyError::new_err(key.to_string()))
+    }
+}
+
+#[pymethods]
+impl RecordIndex {
+    fn __len__(slf: PyRef<'_, Self>) -> usize {
+        slf.keys.len()
+    }
+
+    fn __contains__(slf: PyRef<'_, Self>, key: Bound<'_, PyAny>) -> PyResult<bool> {
+        match key.downcast::<PyString>() {
+            Ok(key) => Ok(slf.spans.contains_key(key.to_str()?)),
+            Err(_) => Ok(false),
+        }
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyResult<Py<PyIterator>> {
+        let py = slf.py();
+        Ok(PyList::new_bound(py, &slf.keys).as_any().iter()?.unbind())
+    }
+
+    fn __getitem__(slf: PyRef<'_, Self>, key: &str) -> PyResult<Py<Record>> {
+        let py = slf.py();
+        let (offset, length) = slf.span(key)?;
+        let mut text = vec![0; length as usize];
+        let mut file = File::open(&slf.path).map_err(convert_io_error)?;
+        file.seek(SeekFrom::Start(offset))
+            .and_then(|_| file.read_exact(&mut text))
+            .map_err(convert_io_error)?;
+
+        let extras = Extras::default();
+        let scanner = ExtrasScanner::new(text.as_slice(), extras.clone());
+        match SeqReader::new(scanner).next() {
             Some(Ok(seq)) => {
-                Python::with_gil(|py| Ok(Some(seq.convert_with(py, &mut slf.interner)?)))
-            }
-            Some(Err(e)) => {
-                Python::with_gil(|py| {
-                    if PyErr::occurred(py) {
-                        Err(PyErr::fetch(py))
-                    } else {
-                        // FIXME: error management
-                        let msg = format!("parser failed: {}", e);
-                        Err(PyRuntimeError::new_err(msg))
-                    }
-                })
+                let mut record = Record::from_parsed(py, seq)?;
+                if let Some(extras) = extras.lock().unwrap().pop_front() {
+                    extras.apply(&mut record);
+                }
+                Py::new(py, record)
             }
+            Some(Err(e)) => Err(convert_parser_error(py, e)),
+            None => Err(PyValueError::new_err("parser failed: incomplete record")),
         }
     }
+
+    /// Get the record with the given identifier, or a default value.
+    ///
+    /// Arguments:
+    ///     key (`str`): The identifier of the record.
+    ///     default (`object`): The value to return if the identifier
+    ///         is not in the index.
+    ///
+    #[pyo3(signature = (key, default = None))]
+    fn get(slf: PyRef<'_, Self>, key: &str, default: Option<PyObject>) -> PyResult<PyObject> {
+        let py = slf.py();
+        if slf.spans.contains_key(key) {
+            Ok(RecordIndex::__getitem__(slf, key)?.into_py(py))
+        } else {
+            Ok(default.unwrap_or_else(|| py.None()))
+        }
+    }
+
+    /// Get the identifiers of the indexed records, in file order.
+    ///
+    /// Returns:
+    ///     `list` of `str`: The identifiers of the records.
+    ///
+    fn keys(slf: PyRef<'_, Self>) -> Vec<String> {
+        slf.keys.clone()
+    }
+
+    /// Get the offset of a record in the indexed file.
+    ///
+    /// Arguments:
+    ///     key (`str`): The identifier of the record.
+    ///
+    /// Returns:
+    ///     `int`: The position of the ``LOCUS`` line of the record, in
+    ///     bytes from the start of the file.
+    ///
+    /// Raises:
+    ///     `KeyError`: When the identifier is not in the index.
+    ///
+    fn offset(slf: PyRef<'_, Self>, key: &str) -> PyResult<u64> {
+        slf.span(key).map(|(offset, _)| offset)
+    }
+}
+
+/// Convert a parser error into a Python exception.
+///
+/// I/O errors become an `OSError`, unless they were raised by a Python
+/// file-handle, in which case the original exception is returned. Syntax
+/// errors become a `ValueError`.
+pub fn convert_parser_error(py: Python, e: GbParserError) -> PyErr {
+    match e {
+        GbParserError::Io(e) => match e.raw_os_error() {
+            Some(_) => convert_io_error(e),
+            None => PyErr::take(py).unwrap_or_else(|| convert_io_error(e)),
+        },
+        GbParserError::SyntaxError(e) => PyValueError::new_err(format!("parser failed: {}", e)),
+    }
+}
+
+/// Convert an I/O error into an `OSError`, keeping the error code if any.
+pub fn convert_io_error(e: IoError) -> PyErr {
+    match e.raw_os_error() {
+        Some(code) => PyOSError::new_err((code, e.to_string())),
+        None => PyOSError::new_err(e.to_string()),
+    }
 }
@@ -0,0 +1,132 @@
+//! Translation of nucleotide sequences with NCBI genetic codes.
+
+/// A genetic code, as listed in the NCBI Taxonomy.
+///
+/// Codons are indexed in `TCAG` order, with the first base varying the
+/// slowest, following the layout of the NCBI `gc.prt` file. Start codons
+/// are marked with `M` in the `starts` table.
+pub struct GeneticCode {
+    pub id: u8,
+    pub amino_acids: &'static [u8; 64],
+    pub starts: &'static [u8; 64],
+}
+
+#[rustfmt::skip]
+const GENETIC_CODES: &[GeneticCode] = &[
+    GeneticCode { id: 1,  amino_acids: b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", starts: b"---M---------------M---------------M----------------------------" },
+    GeneticCode { id: 2,  amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSS**VVVVAAAADDEEGGGG", starts: b"--------------------------------MMMM---------------M------------" },
+    GeneticCode { id: 3,  amino_acids: b"FFLLSSSSYY**CCWWTTTTPPPPHHQQRRRRIIMMTTTTNNKKSSRRVVVVAAAADDEEGGGG", starts: b"----------------------------------MM---------------M------------" },
+    GeneticCode { id: 4,  amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", starts: b"--MM---------------M------------MMMM---------------M------------" },
+    GeneticCode { id: 5,  amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSSSVVVVAAAADDEEGGGG", starts: b"---M----------------------------MMMM---------------M------------" },
+    GeneticCode { id: 6,  amino_acids: b"FFLLSSSSYYQQCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", starts: b"-----------------------------------M----------------------------" },
+    GeneticCode { id: 9,  amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNNKSSSSVVVVAAAADDEEGGGG", starts: b"-----------------------------------M---------------M------------" },
+    GeneticCode { id: 10, amino_acids: b"FFLLSSSSYY**CCCWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", starts: b"-----------------------------------M----------------------------" },
+    GeneticCode { id: 11, amino_acids: b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", starts: b"---M---------------M------------MMMM---------------M------------" },
+    GeneticCode { id: 12, amino_acids: b"FFLLSSSSYY**CC*WLLLSPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", starts: b"-------------------M---------------M----------------------------" },
+    GeneticCode { id: 13, amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSGGVVVVAAAADDEEGGGG", starts: b"---M------------------------------MM---------------M------------" },
+    GeneticCode { id: 14, amino_acids: b"FFLLSSSSYYY*CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNNKSSSSVVVVAAAADDEEGGGG", starts: b"-----------------------------------M----------------------------" },
+    GeneticCode { id: 16, amino_acids: b"FFLLSSSSYY*LCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", starts: b"-----------------------------------M----------------------------" },
+    GeneticCode { id: 21, amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNNKSSSSVVVVAAAADDEEGGGG", starts: b"-----------------------------------M---------------M------------" },
+    GeneticCode { id: 22, amino_acids: b"FFLLSS*SYY*LCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", starts: b"-----------------------------------M----------------------------" },
+    GeneticCode { id: 23, amino_acids: b"FF*LSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", starts: b"--------------------------------M--M---------------M------------" },
+    GeneticCode { id: 24, amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSSKVVVVAAAADDEEGGGG", starts: b"---M---------------M---------------M---------------M------------" },
+    GeneticCode { id: 25, amino_acids: b"FFLLSSSSYY**CCGWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG", starts: b"---M-------------------------------M---------------M------------" },
+];
+
+impl GeneticCode {
+    /// Get the genetic code with the given NCBI identifier.
+    pub fn from_id(id: u8) -> Option<&'static Self> {
+        GENETIC_CODES.iter().find(|code| code.id == id)
+    }
+
+    /// Get the indices of the bases matching a nucleotide symbol.
+    fn base_indices(base: u8) -> &'static [usize] {
+        match base.to_ascii_uppercase() {
+            b'T' | b'U' => &[0],
+            b'C' => &[1],
+            b'A' => &[2],
+            b'G' => &[3],
+            b'Y' => &[0, 1],
+            b'W' => &[0, 2],
+            b'K' => &[0, 3],
+            b'M' => &[1, 2],
+            b'S' => &[1, 3],
+            b'R' => &[2, 3],
+            b'H' => &[0, 1, 2],
+            b'B' => &[0, 1, 3],
+            b'D' => &[0, 2, 3],
+            b'V' => &[1, 2, 3],
+            b'N' => &[0, 1, 2, 3],
+            _ => &[],
+        }
+    }
+
+    /// Get the residue encoded by a codon in the given table.
+    ///
+    /// Ambiguous codons are resolved when all the codons they match encode
+    /// the same residue, and translated as `X` otherwise.
+    fn residue(table: &[u8; 64], codon: &[u8]) -> u8 {
+        let mut residue = None;
+        for &i in Self::base_indices(codon[0]) {
+            for &j in Self::base_indices(codon[1]) {
+                for &k in Self::base_indices(codon[2]) {
+                    let r = table[i * 16 + j * 4 + k];
+                    if residue.replace(r).is_some_and(|previous| previous != r) {
+                        return b'X';
+                    }
+                }
+            }
+        }
+        residue.unwrap_or(b'X')
+    }
+
+    /// Translate a nucleotide sequence into a protein sequence.
+    ///
+    /// If `start` is `true`, the first codon is translated as a methionine
+    /// when it is one of the start codons of the genetic code. Incomplete
+    /// trailing codons are ignored.
+    pub fn translate(&self, sequence: &[u8], start: bool) -> Vec<u8> {
+        let mut protein = Vec::with_capacity(sequence.len() / 3);
+        for (i, codon) in sequence.chunks_exact(3).enumerate() {
+            if i == 0 && start && Self::residue(self.starts, codon) == b'M' {
+                protein.push(b'M');
+            } else {
+                protein.push(Self::residue(self.amino_acids, codon));
+            }
+        }
+        protein
+    }
+}
+
+/// Get the reverse complement of a nucleotide sequence.
+///
+/// IUPAC ambiguity codes are complemented, and the case of each base is
+/// preserved. Unknown symbols are kept as-is.
+pub fn reverse_complement(sequence: &[u8]) -> Vec<u8> {
+    sequence
+        .iter()
+        .rev()
+        .map(|&base| {
+            let complement = match base.to_ascii_uppercase() {
+                b'A' => b'T',
+                b'T' | b'U' => b'A',
+                b'G' => b'C',
+                b'C' => b'G',
+                b'R' => b'Y',
+                b'Y' => b'R',
+                b'K' => b'M',
+                b'M' => b'K',
+                b'B' => b'V',
+                b'V' => b'B',
+                b'D' => b'H',
+                b'H' => b'D',
+                other => other,
+            };
+            if base.is_ascii_lowercase() {
+                complement.to_ascii_lowercase()
+            } else {
+                complement
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,1060 @@
+//! GenBank writer with configurable formatting.
+//!
+//! Adapted from the `gb_io::writer` module, which is itself ported from
+//! Biopython's `InsdcIO.py`, to support formatting options that are not
+//! exposed by the upstream `SeqWriter`. Records can also be written in the
+//! EMBL format, using the `embl` module for the lines specific to EMBL.
+
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::io::Error as IoError;
+use std::io::ErrorKind as IoErrorKind;
+use std::io::Write;
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use gb_io::qualifier_key;
+use gb_io::seq::Date;
+use gb_io::seq::Feature;
+use gb_io::seq::Location;
+use gb_io::seq::Seq;
+use gb_io::seq::Topology;
+use gb_io::QualifierKey;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyString;
+
+use super::alphabet;
+use super::alphabet::Alphabet;
+use super::embl;
+use super::location;
+#[cfg(feature = "zstd")]
+use super::reader::convert_io_error;
+use super::reader::RecordExtras;
+
+/// The default maximum width of the wrapped lines.
+pub const MAX_WIDTH: usize = 79;
+/// The minimum width of the wrapped lines, leaving room for the qualifiers.
+pub const MIN_WIDTH: usize = 40;
+/// The default number of bases written on each line of the sequence.
+pub const SEQUENCE_WIDTH: usize = 60;
+const QUALIFIER_INDENT: &str = "                     ";
+const FIELD_INDENT: &str = "            ";
+
+/// The qualifiers whose values are written without quotes by default.
+const FTQUAL_NO_QUOTE: &[QualifierKey] = &[
+    qualifier_key!("anticodon"),
+    qualifier_key!("codon_start"),
+    qualifier_key!("estimated_length"),
+    qualifier_key!("number"),
+    qualifier_key!("rpt_type"),
+    qualifier_key!("transl_except"),
+    qualifier_key!("transl_table"),
+];
+
+/// Check whether the value of a qualifier is quoted by default.
+pub fn is_quoted_by_default(key: &str) -> bool {
+    !FTQUAL_NO_QUOTE.iter().any(|k| k.as_ref() == key)
+}
+
+/// The qualifiers written first, in this order, in the canonical order.
+pub const CANONICAL_QUALIFIER_ORDER: &[QualifierKey] = &[
+    qualifier_key!("gene"),
+    qualifier_key!("locus_tag"),
+    qualifier_key!("product"),
+];
+
+/// The qualifiers whose values should be formatted like locations.
+const POS_QUAL: &[QualifierKey] = &[qualifier_key!("transl_except"), qualifier_key!("anticodon")];
+
+/// The flat file format of the records written by a `SeqWriter`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    /// The GenBank format.
+    #[default]
+    GenBank,
+    /// The EMBL format.
+    Embl,
+}
+
+impl<'py> FromPyObject<'py> for Format {
+    fn extract(ob: &'py PyAny) -> PyResult<Self> {
+        match ob.downcast::<PyString>()?.to_str()? {
+            "genbank" => Ok(Format::GenBank),
+            "embl" => Ok(Format::Embl),
+            other => Err(PyValueError::new_err(format!(
+                "invalid format: {:?}",
+                other
+            ))),
+        }
+    }
+}
+
+/// An error describing why a feature cannot be written.
+///
+/// This is wrapped in an `std::io::Error` of kind `InvalidInput` by
+/// `SeqWriter::write`, so that callers can distinguish formatting
+/// failures from failures of the underlying stream.
+#[derive(Debug, Clone)]
+pub struct InvalidFeature {
+    /// The index of the feature in the record.
+    pub index: usize,
+    /// The kind of the feature.
+    pub kind: String,
+    /// A description of the problem.
+    pub message: String,
+}
+
+impl Display for InvalidFeature {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(
+            f,
+            "feature {} ({}): {}",
+            self.index, self.kind, self.message
+        )
+    }
+}
+
+impl std::error::Error for InvalidFeature {}
+
+/// An error raised when a sequence does not match its molecule type.
+///
+/// Like `InvalidFeature`, this is wrapped in an `std::io::Error` of kind
+/// `InvalidInput` by `SeqWriter::write`.
+#[derive(Debug, Clone)]
+pub enum InvalidSequence {
+    /// The sequence contains symbols not allowed by the molecule type,
+    /// given with their positions in the sequence.
+    Symbols {
+        molecule_type: String,
+        symbols: Vec<(u8, Vec<usize>)>,
+    },
+    /// The sequence of a protein record only contains nucleotides.
+    Nucleotides { molecule_type: String },
+}
+
+impl Display for InvalidSequence {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        /// The maximum number of positions reported for each symbol.
+        const MAX_POSITIONS: usize = 5;
+        let (molecule_type, symbols) = match self {
+            InvalidSequence::Symbols {
+                molecule_type,
+                symbols,
+            } => (molecule_type, symbols),
+            InvalidSequence::Nucleotides { molecule_type } => {
+                return write!(
+                    f,
+                    "nucleotide sequence for molecule type {:?}",
+                    molecule_type
+                )
+            }
+        };
+        write!(f, "invalid symbols for molecule type {:?}: ", molecule_type)?;
+        for (i, (symbol, positions)) in symbols.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{:?} at ", char::from(*symbol))?;
+            let shown = positions
+                .iter()
+                .take(MAX_POSITIONS)
+                .map(|position| position.to_string())
+                .collect::<Vec<_>>();
+            match positions.len() {
+                1 => write!(f, "position {}", shown[0])?,
+                n if n > MAX_POSITIONS => write!(f, "positions {}, ...", shown.join(", "))?,
+                _ => write!(f, "positions {}", shown.join(", "))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for InvalidSequence {}
+
+/// A writer compressing its output according to the extension of a path.
+///
+/// Paths ending with `.gz` are compressed with gzip, and paths ending with
+/// `.zst` with Zstandard, which requires the `zstd` feature to be enabled.
+/// Other outputs are written unchanged.
+pub enum Encoder<W: Write> {
+    Plain(W),
+    Gzip(GzEncoder<W>),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::stream::write::Encoder<'static, W>),
+}
+
+impl<W: Write> Encoder<W> {
+    pub fn for_path(path: &str, writer: W, level: u32) -> PyResult<Self> {
+        if path.ends_with(".gz") {
+            Ok(Encoder::Gzip(GzEncoder::new(
+                writer,
+                Compression::new(level),
+            )))
+        } else if path.ends_with(".zst") {
+            #[cfg(feature = "zstd")]
+            return zstd::stream::write::Encoder::new(writer, level as i32)
+                .map(Encoder::Zstd)
+                .map_err(convert_io_error);
+            #[cfg(not(feature = "zstd"))]
+            return Err(PyValueError::new_err(
+                "writing zstd-compressed files requires the `zstd` feature",
+            ));
+        } else {
+            Ok(Encoder::Plain(writer))
+        }
+    }
+
+    /// Write the end of the compressed stream, and return the inner writer.
+    pub fn finish(self) -> Result<W, IoError> {
+        match self {
+            Encoder::Plain(mut w) => w.flush().map(|_| w),
+            Encoder::Gzip(w) => w.finish(),
+            #[cfg(feature = "zstd")]
+            Encoder::Zstd(w) => w.finish(),
+        }
+    }
+}
+
+impl<W: Write> Write for Encoder<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, IoError> {
+        match self {
+            Encoder::Plain(w) => w.write(buf),
+            Encoder::Gzip(w) => w.write(buf),
+            #[cfg(feature = "zstd")]
+            Encoder::Zstd(w) => w.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), IoError> {
+        match self {
+            Encoder::Plain(w) => w.flush(),
+            Encoder::Gzip(w) => w.flush(),
+            #[cfg(feature = "zstd")]
+            Encoder::Zstd(w) => w.flush(),
+        }
+    }
+}
+
+/// A stream wrapper counting the number of bytes written.
+#[derive(Debug)]
+struct CountingWriter<W: Write> {
+    inner: W,
+    count: usize,
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, IoError> {
+        let n = self.inner.write(buf)?;
+        self.count += n;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<(), IoError> {
+        self.inner.flush()
+    }
+}
+
+/// A writer for GenBank records.
+#[derive(Debug)]
+pub struct SeqWriter<W: Write> {
+    stream: CountingWriter<W>,
+    truncate_locus: bool,
+    escape_locus: bool,
+    unquoted: Vec<QualifierKey>,
+    emit_gi: bool,
+    simplify_locations: bool,
+    date: Option<Date>,
+    wrap_width: usize,
+    field_width: Option<usize>,
+    linearize_circular: bool,
+    qualifier_order: Option<Vec<QualifierKey>>,
+    format: Format,
+    line_width: usize,
+    validate: bool,
+}
+
+impl<W: Write> SeqWriter<W> {
+    /// Create a new `SeqWriter` to write GenBank files to the given stream.
+    pub fn new(stream: W) -> Self {
+        Self {
+            stream: CountingWriter {
+                inner: stream,
+                count: 0,
+            },
+            truncate_locus: false,
+            escape_locus: true,
+            unquoted: FTQUAL_NO_QUOTE.to_vec(),
+            emit_gi: false,
+            simplify_locations: false,
+            date: None,
+            wrap_width: MAX_WIDTH,
+            field_width: Some(MAX_WIDTH),
+            linearize_circular: false,
+            qualifier_order: None,
+            format: Format::GenBank,
+            line_width: SEQUENCE_WIDTH,
+            validate: false,
+        }
+    }
+
+    /// Set whether the locus fields should be truncated to 79 characters.
+    pub fn truncate_locus(&mut self, truncate: bool) -> &mut Self {
+        self.truncate_locus = truncate;
+        self
+    }
+
+    /// Set whether whitespace in the locus name should be escaped.
+    pub fn escape_locus(&mut self, escape: bool) -> &mut Self {
+        self.escape_locus = escape;
+        self
+    }
+
+    /// Add qualifiers whose values should be written without quotes.
+    pub fn unquoted_qualifiers<I>(&mut self, keys: I) -> &mut Self
+    where
+        I: IntoIterator<Item = QualifierKey>,
+    {
+        self.unquoted.extend(keys);
+        self
+    }
+
+    /// Set whether the GI number should be written on the `VERSION` line.
+    pub fn emit_gi(&mut self, emit: bool) -> &mut Self {
+        self.emit_gi = emit;
+        self
+    }
+
+    /// Set whether feature locations should be simplified before writing.
+    pub fn simplify_locations(&mut self, simplify: bool) -> &mut Self {
+        self.simplify_locations = simplify;
+        self
+    }
+
+    /// Set a date to write on the locus line instead of the record date.
+    pub fn date(&mut self, date: Option<Date>) -> &mut Self {
+        self.date = date;
+        self
+    }
+
+    /// Set the maximum width of the lines of wrapped fields and features.
+    ///
+    /// The width should be at least `MIN_WIDTH`. The lines of the locus
+    /// and of the sequence have a fixed layout, and are never wrapped.
+    pub fn wrap_width(&mut self, width: usize) -> &mut Self {
+        self.wrap_width = width;
+        self
+    }
+
+    /// Set the maximum width of the lines of the header fields.
+    ///
+    /// The header fields, such as `DEFINITION`, are not wrapped when `None`
+    /// is given, and only the line breaks of their text are kept.
+    pub fn field_width(&mut self, width: Option<usize>) -> &mut Self {
+        self.field_width = width;
+        self
+    }
+
+    /// Set whether circular records should be written as linear records.
+    ///
+    /// The locations of features spanning the origin are split in two
+    /// parts, at the end and at the start of the sequence.
+    pub fn linearize_circular(&mut self, linearize: bool) -> &mut Self {
+        self.linearize_circular = linearize;
+        self
+    }
+
+    /// Set a priority list to write the qualifiers of features in order.
+    ///
+    /// Qualifiers with a key in the list are written first, in the order
+    /// of the list, followed by the other qualifiers sorted by key. The
+    /// qualifiers sharing a key keep their relative order. Qualifiers are
+    /// written in their original order when `None` is given.
+    pub fn qualifier_order(&mut self, order: Option<Vec<QualifierKey>>) -> &mut Self {
+        self.qualifier_order = order;
+        self
+    }
+
+    /// Set the flat file format of the records.
+    ///
+    /// The formatting options which do not apply to the EMBL format, such
+    /// as the escaping of the locus name, are ignored when writing EMBL.
+    pub fn format(&mut self, format: Format) -> &mut Self {
+        self.format = format;
+        self
+    }
+
+    /// Set the number of bases written on each line of the sequence.
+    ///
+    /// The bases are written in groups of 10, and the width should not
+    /// be zero.
+    pub fn line_width(&mut self, width: usize) -> &mut Self {
+        self.line_width = width;
+        self
+    }
+
+    /// Set whether to check the sequence alphabet against the molecule type.
+    ///
+    /// Records with an unknown molecule type are never checked.
+    pub fn validate(&mut self, validate: bool) -> &mut Self {
+        self.validate = validate;
+        self
+    }
+
+    /// Get the number of bytes written to the underlying stream so far.
+    pub fn bytes_written(&self) -> usize {
+        self.stream.count
+    }
+
+    /// Consume the writer and return the underlying stream.
+    pub fn into_inner(self) -> W {
+        self.stream.inner
+    }
+
+    /// Create a writer with the same options, writing to another stream.
+    pub fn with_stream<V: Write>(self, stream: V) -> SeqWriter<V> {
+        SeqWriter {
+            stream: CountingWriter {
+                inner: stream,
+                count: 0,
+            },
+            truncate_locus: self.truncate_locus,
+            escape_locus: self.escape_locus,
+            unquoted: self.unquoted,
+            emit_gi: self.emit_gi,
+            simplify_locations: self.simplify_locations,
+            date: self.date,
+            wrap_width: self.wrap_width,
+            field_width: self.field_width,
+            linearize_circular: self.linearize_circular,
+            qualifier_order: self.qualifier_order,
+            format: self.format,
+            line_width: self.line_width,
+            validate: self.validate,
+        }
+    }
+
+    /// Flush the underlying stream.
+    pub fn flush(&mut self) -> Result<(), IoError> {
+        self.stream.flush()
+    }
+
+    /// Generate the locus line for the record.
+    fn locus_line(&self, record: &Seq) -> String {
+        let mut locus = record.name.clone().unwrap_or_else(|| {
+            record
+                .accession
+                .clone()
+                .unwrap_or_else(|| "UNTITLED".into())
+        });
+        let length = format!("{}", record.len());
+        if self.truncate_locus && locus.len() + 1 + length.len() > 28 {
+            locus = locus[..27 - length.len()].into();
+        }
+
+        if self.escape_locus && locus.split_whitespace().count() > 1 {
+            locus = locus.split_whitespace().collect::<Vec<_>>().join("_");
+        }
+
+        let mol_type = match record.molecule_type {
+            Some(ref m) if m.len() > 7 && self.truncate_locus => "",
+            Some(ref m) => m,
+            None => "",
+        };
+
+        if locus.len() + 1 + length.len() >= 28 {
+            locus.push(' ');
+            locus.push_str(&length);
+        } else {
+            let length = format!("{:>28}", length);
+            locus.push_str(&length[locus.len()..]);
+        }
+
+        format!(
+            "LOCUS       {} bp    {:<7} {:<8} {} {}\n",
+            locus,
+            mol_type,
+            if self.linearize_circular {
+                Topology::Linear
+            } else {
+                record.topology.clone()
+            },
+            record.division,
+            self.date
+                .as_ref()
+                .or(record.date.as_ref())
+                .unwrap_or(&Date::from_ymd(1970, 1, 1).unwrap())
+        )
+    }
+
+    /// Write a record to the stream.
+    ///
+    /// Features are validated before anything is written, so that an
+    /// invalid feature does not leave a truncated record in the stream.
+    /// The lines which are not stored in `Seq`, such as the note after the
+    /// `ORIGIN` keyword, are written from the given `extras`.
+    pub fn write(&mut self, record: &Seq, extras: &RecordExtras) -> Result<(), IoError> {
+        let length = Some(record.len()).filter(|&length| length > 0);
+
+        // wrap the locations of circular records if they must be linearized,
+        // without modifying the record itself
+        let linearize = self.linearize_circular && record.is_circular() && length.is_some();
+        let locations = record
+            .features
+            .iter()
+            .map(|feature| {
+                if linearize {
+                    record
+                        .wrap_location(feature.location.clone())
+                        .map(Cow::Owned)
+                        .unwrap_or(Cow::Borrowed(&feature.location))
+                } else {
+                    Cow::Borrowed(&feature.location)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if self.validate {
+            let molecule_type = record.molecule_type.as_deref().unwrap_or_default();
+            if let Some(alphabet) = Alphabet::for_molecule_type(molecule_type) {
+                let symbols = alphabet.invalid_symbols(&record.seq);
+                let molecule_type = molecule_type.to_string();
+                if !symbols.is_empty() {
+                    return Err(IoError::new(
+                        IoErrorKind::InvalidInput,
+                        InvalidSequence::Symbols {
+                            molecule_type,
+                            symbols,
+                        },
+                    ));
+                }
+                // nucleotides are valid amino acids, so they must be checked
+                // separately for protein records
+                if alphabet == Alphabet::Protein && alphabet::is_nucleotide_like(&record.seq) {
+                    return Err(IoError::new(
+                        IoErrorKind::InvalidInput,
+                        InvalidSequence::Nucleotides { molecule_type },
+                    ));
+                }
+            }
+        }
+
+        for (index, feature) in record.features.iter().enumerate() {
+            if let Err(message) = check_feature(feature, &locations[index], length, self.wrap_width)
+            {
+                return Err(IoError::new(
+                    IoErrorKind::InvalidInput,
+                    InvalidFeature {
+                        index,
+                        kind: feature.kind.to_string(),
+                        message,
+                    },
+                ));
+            }
+        }
+
+        match self.format {
+            Format::GenBank => self.write_genbank(record, extras, &locations),
+            Format::Embl => self.write_embl(record, extras, &locations),
+        }
+    }
+
+    /// Write a record in the GenBank format, once its features are checked.
+    fn write_genbank(
+        &mut self,
+        record: &Seq,
+        extras: &RecordExtras,
+        locations: &[Cow<Location>],
+    ) -> Result<(), IoError> {
+        let origin_note = extras.origin_note.as_deref();
+        let locus_line = self.locus_line(record);
+        self.stream.write_all(locus_line.as_bytes())?;
+
+        // fields
+        write_field_maybe(
+            &mut self.stream,
+            self.field_width,
+            &record.definition,
+            "DEFINITION",
+        )?;
+        write_field_maybe(
+            &mut self.stream,
+            self.field_width,
+            &record.accession,
+            "ACCESSION",
+        )?;
+        if let Some(version) = &record.version {
+            let version = match split_version(version) {
+                (accession, Some(gi)) if self.emit_gi => format!("{}  GI:{}", accession, gi),
+                (accession, _) => accession.to_string(),
+            };
+            write_field(&mut self.stream, self.field_width, &version, "VERSION")?;
+        }
+        write_field_maybe(&mut self.stream, self.field_width, &record.dblink, "DBLINK")?;
+        write_field_maybe(
+            &mut self.stream,
+            self.field_width,
+            &record.keywords,
+            "KEYWORDS",
+        )?;
+        if let Some(ref source) = record.source {
+            write_field(&mut self.stream, self.field_width, &source.source, "SOURCE")?;
+            write_field_maybe(
+                &mut self.stream,
+                self.field_width,
+                &source.organism,
+                "  ORGANISM",
+            )?;
+        }
+        for r in &record.references {
+            write_field(
+                &mut self.stream,
+                self.field_width,
+                &r.description,
+                "REFERENCE",
+            )?;
+            write_field_maybe(&mut self.stream, self.field_width, &r.authors, "  AUTHORS")?;
+            write_field_maybe(
+                &mut self.stream,
+                self.field_width,
+                &r.consortium,
+                "  CONSRTM",
+            )?;
+            write_field(&mut self.stream, self.field_width, &r.title, "  TITLE")?;
+            write_field_maybe(&mut self.stream, self.field_width, &r.journal, "  JOURNAL")?;
+            write_field_maybe(&mut self.stream, self.field_width, &r.pubmed, "   PUBMED")?;
+            write_field_maybe(&mut self.stream, self.field_width, &r.remark, "  REMARK")?;
+        }
+        for comment in &record.comments {
+            write_field(&mut self.stream, self.field_width, comment, "COMMENT")?;
+        }
+        if !extras.primary.is_empty() {
+            self.stream.write_all(
+                b"PRIMARY     TPA_SPAN            PRIMARY_IDENTIFIER PRIMARY_SPAN        COMP\n",
+            )?;
+            for (tpa_span, identifier, primary_span, complement) in &extras.primary {
+                let row = format!(
+                    "{}{:<19} {:<18} {:<19} {}",
+                    FIELD_INDENT,
+                    tpa_span,
+                    identifier,
+                    primary_span,
+                    if *complement { "c" } else { "" }
+                );
+                writeln!(&mut self.stream, "{}", row.trim_end())?;
+            }
+        }
+
+        // features
+        if !record.features.is_empty() {
+            self.stream
+                .write_all(b"FEATURES             Location/Qualifiers\n")?;
+            self.write_features(record, extras, locations, "     ", QUALIFIER_INDENT)?;
+        }
+
+        // contig
+        if let Some(ref contig) = record.contig {
+            wrap_location(
+                &mut self.stream,
+                &contig.to_gb_format(),
+                self.wrap_width,
+                "CONTIG      ",
+                FIELD_INDENT,
+            )?;
+        }
+
+        // wgs
+        for range in &extras.wgs {
+            writeln!(&mut self.stream, "WGS         {}", range)?;
+        }
+        for range in &extras.wgs_scafld {
+            writeln!(&mut self.stream, "WGS_SCAFLD  {}", range)?;
+        }
+
+        // origin
+        if let Some(note) = origin_note.filter(|_| record.seq.is_empty()) {
+            writeln!(&mut self.stream, "ORIGIN      {}", note)?;
+        } else if !record.seq.is_empty() {
+            let mut line = Vec::with_capacity(MAX_WIDTH);
+            line.extend_from_slice(b"ORIGIN      ");
+            if let Some(note) = origin_note {
+                line.extend_from_slice(note.as_bytes());
+            }
+            for (i, &b) in record.seq.iter().enumerate() {
+                let column = i % self.line_width;
+                if column == 0 {
+                    line.push(b'\n');
+                    self.stream.write_all(&line)?;
+                    line.clear();
+                    write!(&mut line, "{:>9}", i + 1)?;
+                }
+                if column.is_multiple_of(10) {
+                    line.push(b' ');
+                }
+                line.push(b);
+            }
+            line.push(b'\n');
+            self.stream.write_all(&line)?;
+        }
+
+        writeln!(&mut self.stream, "//")
+    }
+
+    /// Write a record in the EMBL format, once its features are checked.
+    fn write_embl(
+        &mut self,
+        record: &Seq,
+        extras: &RecordExtras,
+        locations: &[Cow<Location>],
+    ) -> Result<(), IoError> {
+        let topology = if self.linearize_circular {
+            Topology::Linear
+        } else {
+            record.topology.clone()
+        };
+        let date = self.date.as_ref().or(record.date.as_ref());
+        embl::write_header(&mut self.stream, record, &topology, date, self.field_width)?;
+
+        // features
+        if !record.features.is_empty() {
+            self.stream.write_all(embl::FEATURE_HEADER)?;
+            self.write_features(
+                record,
+                extras,
+                locations,
+                embl::FEATURE_PREFIX,
+                embl::QUALIFIER_INDENT,
+            )?;
+            writeln!(&mut self.stream, "XX")?;
+        }
+
+        // contig, without a sequence for CON-division records
+        if let Some(ref contig) = record.contig {
+            wrap_location(
+                &mut self.stream,
+                &contig.to_gb_format(),
+                self.wrap_width,
+                "CO   ",
+                "CO   ",
+            )?;
+            if record.seq.is_empty() {
+                return writeln!(&mut self.stream, "//");
+            }
+            writeln!(&mut self.stream, "XX")?;
+        }
+
+        embl::write_sequence(&mut self.stream, &record.seq, self.line_width)?;
+        writeln!(&mut self.stream, "//")
+    }
+
+    /// Write the feature table of a record.
+    ///
+    /// The first line of each feature starts with `prefix`, followed by the
+    /// feature kind, and the other lines start with `indent`, which is as
+    /// long as the prefix and the padded feature kind. Qualifier values are
+    /// quoted unless their key is unquoted, or unless `extras` overrides it.
+    fn write_features(
+        &mut self,
+        record: &Seq,
+        extras: &RecordExtras,
+        locations: &[Cow<Location>],
+        prefix: &str,
+        indent: &str,
+    ) -> Result<(), IoError> {
+        debug_assert!(extras.quoted.keys().all(|&(f, q)| {
+            record
+                .features
+                .get(f)
+                .is_some_and(|feature| q < feature.qualifiers.len())
+        }));
+        for (f_index, (f, location)) in record.features.iter().zip(locations).enumerate() {
+            let first_indent = format!("{}{:<15} ", prefix, f.kind);
+            let location = if self.simplify_locations {
+                location::simplify(location).to_gb_format()
+            } else {
+                location.to_gb_format()
+            };
+            wrap_location(
+                &mut self.stream,
+                &location,
+                self.wrap_width,
+                &first_indent,
+                indent,
+            )?;
+            let mut qualifiers = f.qualifiers.iter().enumerate().collect::<Vec<_>>();
+            if let Some(order) = &self.qualifier_order {
+                let rank = |key: &QualifierKey| {
+                    let position = order.iter().position(|k| k == key);
+                    (position.unwrap_or(order.len()), key.to_string())
+                };
+                qualifiers.sort_by_cached_key(|(_, (key, _))| rank(key));
+            }
+            for (q_index, (key, val)) in qualifiers {
+                match val {
+                    None => writeln!(&mut self.stream, "{}/{}", indent, key)?,
+                    Some(val) => {
+                        let first_indent = format!("{}/{}=", indent, key);
+                        let quoted = extras.quoted.get(&(f_index, q_index)).copied();
+                        if POS_QUAL.contains(key) && quoted != Some(true) {
+                            wrap_location(
+                                &mut self.stream,
+                                val,
+                                self.wrap_width,
+                                &first_indent,
+                                indent,
+                            )?;
+                        } else {
+                            let quote = quoted.unwrap_or_else(|| !self.unquoted.contains(key));
+                            wrap_text(
+                                &mut self.stream,
+                                val,
+                                self.wrap_width,
+                                &first_indent,
+                                indent,
+                                quote,
+                            )?;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Split a `VERSION` line into the accession and the optional GI number.
+pub fn split_version(version: &str) -> (&str, Option<&str>) {
+    let mut tokens = version.split_whitespace();
+    let accession = tokens.next().unwrap_or_default();
+    let gi = tokens.find_map(|token| token.strip_prefix("GI:"));
+    (accession, gi)
+}
+
+/// Check that a feature can be written in the GenBank format.
+fn check_feature(
+    feature: &Feature,
+    location: &Location,
+    length: Option<i64>,
+    width: usize,
+) -> Result<(), String> {
+    if feature.kind.is_empty() || feature.kind.contains(char::is_whitespace) {
+        return Err(format!("invalid feature kind {:?}", feature.kind.as_ref()));
+    }
+    if first_indent_len(&feature.kind) >= width {
+        return Err(format!(
+            "feature kind is too long ({} characters)",
+            feature.kind.len()
+        ));
+    }
+    for (key, _) in &feature.qualifiers {
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return Err(format!("invalid qualifier key {:?}", key.as_ref()));
+        }
+    }
+    check_location(location, length)
+}
+
+/// Get the length of the indent before the location of a feature.
+fn first_indent_len(kind: &str) -> usize {
+    5 + kind.len().max(15) + 1
+}
+
+/// Check that the coordinates of a location are within the sequence.
+///
+/// The upper bound is only checked when the sequence length is known.
+fn check_location(location: &Location, length: Option<i64>) -> Result<(), String> {
+    match location {
+        Location::Range((start, _), (end, _)) => {
+            if *start < 0 || *end < 0 {
+                Err(format!(
+                    "Range({}, {}) has negative coordinates",
+                    start, end
+                ))
+            } else if start > end {
+                Err(format!("Range({}, {}) starts after its end", start, end))
+            } else if length.is_some_and(|length| *end > length) {
+                Err(format!(
+                    "Range({}, {}) extends beyond the sequence length ({})",
+                    start,
+                    end,
+                    length.unwrap()
+                ))
+            } else {
+                Ok(())
+            }
+        }
+        Location::Between(start, end) => {
+            if *start < 0 || *end < 0 {
+                Err(format!(
+                    "Between({}, {}) has negative coordinates",
+                    start, end
+                ))
+            } else if length.is_some_and(|length| *start >= length || *end >= length) {
+                Err(format!(
+                    "Between({}, {}) extends beyond the sequence length ({})",
+                    start,
+                    end,
+                    length.unwrap()
+                ))
+            } else {
+                Ok(())
+            }
+        }
+        Location::Complement(inner) => check_location(inner, length),
+        Location::Join(locations)
+        | Location::Order(locations)
+        | Location::Bond(locations)
+        | Location::OneOf(locations) => {
+            if locations.is_empty() {
+                return Err(format!("{} has no parts", location.to_gb_format()));
+            }
+            locations.iter().try_for_each(|l| check_location(l, length))
+        }
+        Location::External(_, _) | Location::Gap(_) => Ok(()),
+    }
+}
+
+fn write_field<W: Write>(
+    mut file: W,
+    width: Option<usize>,
+    field: &str,
+    keyword: &str,
+) -> Result<(), IoError> {
+    let keyword = format!("{:<12}", keyword);
+    match width {
+        Some(width) => wrap_text(file, field, width, &keyword, FIELD_INDENT, false),
+        None => {
+            let mut indent = keyword.as_str();
+            for line in field.split('\n') {
+                writeln!(file, "{}{}", indent, line)?;
+                indent = FIELD_INDENT;
+            }
+            Ok(())
+        }
+    }
+}
+
+fn write_field_maybe<W, S>(
+    file: W,
+    width: Option<usize>,
+    field: &Option<S>,
+    keyword: &str,
+) -> Result<(), IoError>
+where
+    W: Write,
+    S: AsRef<str>,
+{
+    match field {
+        Some(field) => write_field(file, width, field.as_ref(), keyword),
+        None => Ok(()),
+    }
+}
+
+/// Wrap a GenBank location, splitting on commas if possible.
+fn wrap_location<W: Write>(
+    mut file: W,
+    mut text: &str,
+    max_width: usize,
+    first_indent: &str,
+    subsequent_indent: &str,
+) -> Result<(), IoError> {
+    let mut indent = first_indent;
+    while indent.len() + text.len() > max_width {
+        let split_at = match text[..max_width - indent.len()].rfind(',') {
+            Some(i) => i + 1,
+            None => max_width - indent.len(),
+        };
+        writeln!(file, "{}{}", indent, &text[..split_at])?;
+        text = &text[split_at..];
+        indent = subsequent_indent;
+    }
+    if !text.is_empty() {
+        writeln!(file, "{}{}", indent, text)?;
+    }
+    Ok(())
+}
+
+/// Fill `line` with as much of `input` as fits in `max_len` bytes.
+///
+/// Wrapping occurs on a space if possible, and `"` are escaped as `""`
+/// when `quote` is set. Returns the rest of `input`.
+fn wrap_get_line<'a>(line: &mut String, input: &'a str, max_len: usize, quote: bool) -> &'a str {
+    let mut consumed = 0;
+    let mut last_space_in = None;
+    let mut last_space_out = 0;
+    let mut chars = input.char_indices();
+    while line.len() < max_len {
+        match chars.next() {
+            Some((idx, ch)) => {
+                match ch {
+                    ' ' => {
+                        last_space_in = Some(idx);
+                        last_space_out = line.len();
+                        line.push(' ');
+                    }
+                    '\n' => return &input[idx + 1..],
+                    '"' if quote => {
+                        // avoid splitting the escape sequence across lines
+                        if line.len() >= max_len - 1 {
+                            break;
+                        }
+                        line.push_str("\"\"");
+                    }
+                    _ => line.push(ch),
+                }
+                consumed = idx + ch.len_utf8();
+            }
+            None => return &input[consumed..],
+        }
+    }
+    // check whether the line really needs to be split
+    match chars.next() {
+        Some((idx, ' ')) | Some((idx, '\n')) => return &input[idx + 1..],
+        Some(_) => (),
+        None => return &input[consumed..],
+    }
+    // try to wrap at the last space
+    match last_space_in {
+        Some(idx) => {
+            line.truncate(last_space_out);
+            &input[idx + 1..]
+        }
+        None => &input[consumed..],
+    }
+}
+
+/// Wrap a line of text, optionally quoting it.
+pub fn wrap_text<W: Write>(
+    mut file: W,
+    mut text: &str,
+    max_width: usize,
+    first_indent: &str,
+    subsequent_indent: &str,
+    quote: bool,
+) -> Result<(), IoError> {
+    let mut line = String::with_capacity(max_width);
+    let mut indent = first_indent;
+    if quote {
+        line.push('"');
+    }
+    text = wrap_get_line(&mut line, text, max_width - indent.len(), quote);
+    write!(file, "{}{}", indent, line)?;
+    while !text.is_empty() {
+        indent = subsequent_indent;
+        line.clear();
+        text = wrap_get_line(&mut line, text, max_width - indent.len(), quote);
+        write!(file, "\n{}{}", indent, line)?;
+    }
+    if quote {
+        if line.len() + indent.len() >= max_width {
+            writeln!(file, "\n{}\"", subsequent_indent)
+        } else {
+            writeln!(file, "\"")
+        }
+    } else {
+        writeln!(file)
+    }
+}
@@ -13,11 +13,18 @@
 
 import os
 import sys
+import types
 import unittest
+import unittest.mock
 from datetime import datetime
 
 import gb_io
 
+try:
+    import Bio.SeqIO
+except ImportError:
+    Bio = None
+
 DATA_FOLDER = os.path.realpath(os.path.join(__file__, os.path.pardir, "data", "biopython"))
 
 
@@ -3217,6 +3224,336 @@ class TestRecordParser(unittest.TestCase):
         )
 
 
+class ExactPosition(int):
+    pass
+
+
+class BeforePosition(int):
+    def __str__(self):
+        return "<{}".format(int(self))
+
+
+class AfterPosition(int):
+    def __str__(self):
+        return ">{}".format(int(self))
+
+
+class SimpleLocation(object):
+    def __init__(self, start, end, strand=None, ref=None):
+        self.start = start if type(start) is not int else ExactPosition(start)
+        self.end = end if type(end) is not int else ExactPosition(end)
+        self.strand = strand
+        self.ref = ref
+
+
+class CompoundLocation(object):
+    def __init__(self, parts, operator="join"):
+        self.parts = parts
+        self.operator = operator
+
+
+class SeqFeature(object):
+    def __init__(self, location=None, type="", qualifiers=None):
+        self.location = location
+        self.type = type
+        self.qualifiers = qualifiers or {}
+
+
+class UndefinedSequenceError(ValueError):
+    pass
+
+
+class Seq(object):
+    def __init__(self, data, length=None):
+        self.data = data
+        self.length = length
+
+    def __str__(self):
+        if self.data is None:
+            raise UndefinedSequenceError("Sequence content is undefined")
+        return self.data
+
+    def __len__(self):
+        return self.length if self.data is None else len(self.data)
+
+
+def _fake_biopython():
+    bio = types.ModuleType("Bio")
+    bio.Seq = types.ModuleType("Bio.Seq")
+    bio.Seq.Seq = Seq
+    bio.SeqRecord = types.ModuleType("Bio.SeqRecord")
+    bio.SeqRecord.SeqRecord = SeqRecord
+    bio.SeqFeature = types.ModuleType("Bio.SeqFeature")
+    for cls in (ExactPosition, BeforePosition, AfterPosition, SimpleLocation, CompoundLocation, SeqFeature):
+        setattr(bio.SeqFeature, cls.__name__, cls)
+    modules = {"Bio": bio, "Bio.Seq": bio.Seq, "Bio.SeqRecord": bio.SeqRecord}
+    modules["Bio.SeqFeature"] = bio.SeqFeature
+    return unittest.mock.patch.dict(sys.modules, modules)
+
+
+class SeqRecord(object):
+    def __init__(
+        self,
+        seq,
+        id="<unknown id>",
+        name="<unknown name>",
+        description="<unknown description>",
+        annotations=None,
+        features=None,
+    ):
+        self.seq = seq
+        self.id = id
+        self.name = name
+        self.description = description
+        self.annotations = annotations or {}
+        self.features = features or []
+
+
+class TestFromBiopython(unittest.TestCase):
+
+    def test_attributes(self):
+        seqrecord = SeqRecord(
+            Seq("ATGC" * 25),
+            id="AB000001.2",
+            name="TEST",
+            description="A test record",
+            annotations={
+                "molecule_type": "DNA",
+                "topology": "circular",
+                "date": "15-OCT-2001",
+                "accessions": ["AB000001", "AB000002"],
+            },
+        )
+        record = gb_io.Record.from_biopython(seqrecord)
+        self.assertEqual(record.sequence, b"ATGC" * 25)
+        self.assertEqual(record.name, "TEST")
+        self.assertEqual(record.definition, "A test record.")
+        self.assertEqual(record.accession, "AB000001 AB000002")
+        self.assertEqual(record.version, "AB000001.2")
+        self.assertEqual(record.molecule_type, "DNA")
+        self.assertTrue(record.circular)
+        self.assertEqual(record.date, datetime(2001, 10, 15).date())
+
+    def test_unknown_attributes(self):
+        record = gb_io.Record.from_biopython(SeqRecord(Seq("ATGC")))
+        self.assertIs(record.name, None)
+        self.assertIs(record.definition, None)
+        self.assertIs(record.accession, None)
+        self.assertIs(record.version, None)
+        self.assertIs(record.date, None)
+        self.assertFalse(record.circular)
+        record = gb_io.Record.from_biopython(SeqRecord(Seq("ATGC"), id="AB000001.2"))
+        self.assertEqual(record.accession, "AB000001")
+
+    def test_undefined_sequence(self):
+        record = gb_io.Record.from_biopython(SeqRecord(Seq(None, 500)))
+        self.assertEqual(record.sequence, b"")
+        self.assertEqual(record.length, 500)
+        self.assertEqual(len(record), 500)
+
+    def test_invalid_date(self):
+        seqrecord = SeqRecord(Seq("ATGC"), annotations={"date": "32-OCT-2001"})
+        self.assertRaises(ValueError, gb_io.Record.from_biopython, seqrecord)
+
+    def test_features(self):
+        seqrecord = SeqRecord(
+            Seq("ATGC" * 25),
+            features=[
+                SeqFeature(
+                    SimpleLocation(BeforePosition(0), AfterPosition(10), 1),
+                    "gene",
+                    {"gene": ["abc"], "pseudo": [""]},
+                ),
+                SeqFeature(
+                    CompoundLocation([SimpleLocation(19, 30, -1), SimpleLocation(0, 10, -1)]),
+                    "CDS",
+                    {"codon_start": ["1"], "note": "single value"},
+                ),
+                SeqFeature(
+                    CompoundLocation([SimpleLocation(0, 10, 1), SimpleLocation(19, 30, -1)], "order"),
+                    "misc_feature",
+                ),
+                SeqFeature(SimpleLocation(4, 10, -1, ref="J00194.1"), "misc_feature"),
+            ],
+        )
+        record = gb_io.Record.from_biopython(seqrecord)
+        self.assertEqual(len(record.features), 4)
+        gene, cds, misc, external = record.features
+        self.assertEqual(gene.kind, "gene")
+        self.assertEqual(gene.location, gb_io.Range(0, 10, before=True, after=True))
+        self.assertEqual(gene.qualifiers, [gb_io.Qualifier("gene", "abc"), gb_io.Qualifier("pseudo")])
+        self.assertEqual(
+            cds.location,
+            gb_io.Complement(gb_io.Join([gb_io.Range(0, 10), gb_io.Range(19, 30)])),
+        )
+        self.assertEqual(
+            cds.qualifiers,
+            [gb_io.Qualifier("codon_start", "1"), gb_io.Qualifier("note", "single value")],
+        )
+        self.assertEqual(
+            misc.location,
+            gb_io.Order([gb_io.Range(0, 10), gb_io.Complement(gb_io.Range(19, 30))]),
+        )
+        self.assertEqual(
+            external.location,
+            gb_io.Complement(gb_io.External("J00194.1", gb_io.Range(4, 10))),
+        )
+
+    def test_between_location(self):
+        location = SimpleLocation(ExactPosition(2), ExactPosition(2), 1)
+        seqrecord = SeqRecord(Seq("ATGC"), features=[SeqFeature(location, "misc_feature")])
+        record = gb_io.Record.from_biopython(seqrecord)
+        self.assertEqual(record.features[0].location, gb_io.Between(1, 2))
+
+    def test_feature_without_location(self):
+        seqrecord = SeqRecord(Seq("ATGC"), features=[SeqFeature(None, "gene")])
+        self.assertRaises(ValueError, gb_io.Record.from_biopython, seqrecord)
+
+    def test_invalid_operator(self):
+        location = CompoundLocation([SimpleLocation(0, 2)], "merge")
+        seqrecord = SeqRecord(Seq("ATGC"), features=[SeqFeature(location, "gene")])
+        self.assertRaises(ValueError, gb_io.Record.from_biopython, seqrecord)
+
+
+class TestToBiopython(unittest.TestCase):
+
+    def setUp(self):
+        self.patch = _fake_biopython()
+        self.patch.start()
+
+    def tearDown(self):
+        self.patch.stop()
+
+    def test_attributes(self):
+        record = gb_io.Record(
+            b"ATGC" * 25,
+            name="TEST",
+            definition="A test\nrecord.",
+            accession="AB000001 AB000002",
+            version="AB000001.2  GI:123",
+            molecule_type="DNA",
+            division="BCT",
+            circular=True,
+            date=datetime(2001, 10, 15).date(),
+        )
+        seqrecord = record.to_biopython()
+        self.assertIsInstance(seqrecord, SeqRecord)
+        self.assertEqual(str(seqrecord.seq), "ATGC" * 25)
+        self.assertEqual(seqrecord.id, "AB000001.2")
+        self.assertEqual(seqrecord.name, "TEST")
+        self.assertEqual(seqrecord.description, "A test record")
+        self.assertEqual(seqrecord.annotations["molecule_type"], "DNA")
+        self.assertEqual(seqrecord.annotations["topology"], "circular")
+        self.assertEqual(seqrecord.annotations["data_file_division"], "BCT")
+        self.assertEqual(seqrecord.annotations["date"], "15-OCT-2001")
+        self.assertEqual(seqrecord.annotations["accessions"], ["AB000001", "AB000002"])
+        self.assertEqual(seqrecord.annotations["sequence_version"], 2)
+
+    def test_unknown_attributes(self):
+        seqrecord = gb_io.Record(b"ATGC").to_biopython()
+        self.assertEqual(seqrecord.id, "<unknown id>")
+        self.assertEqual(seqrecord.name, "<unknown name>")
+        self.assertEqual(seqrecord.description, "<unknown description>")
+        self.assertNotIn("molecule_type", seqrecord.annotations)
+        self.assertEqual(seqrecord.annotations["topology"], "linear")
+
+    def test_undefined_sequence(self):
+        seqrecord = gb_io.Record(b"", length=500).to_biopython()
+        self.assertEqual(len(seqrecord.seq), 500)
+        self.assertRaises(ValueError, str, seqrecord.seq)
+
+    def test_features(self):
+        record = gb_io.Record(b"ATGC" * 25)
+        record.features = [
+            gb_io.Feature(
+                "gene",
+                gb_io.Range(0, 10, before=True),
+                [gb_io.Qualifier("gene", "abc"), gb_io.Qualifier("pseudo")],
+            ),
+            gb_io.Feature(
+                "CDS",
+                gb_io.Complement(gb_io.Join([gb_io.Range(0, 10), gb_io.Range(19, 30)])),
+                [gb_io.Qualifier("note", "two\nlines"), gb_io.Qualifier("note", "more")],
+            ),
+            gb_io.Feature("misc_feature", gb_io.Between(4, 5)),
+            gb_io.Feature(
+                "misc_feature",
+                gb_io.External("J00194.1", gb_io.Range(4, 10)),
+            ),
+        ]
+        gene, cds, site, external = record.to_biopython().features
+        self.assertEqual(gene.type, "gene")
+        self.assertIsInstance(gene.location.start, BeforePosition)
+        self.assertEqual((gene.location.start, gene.location.end), (0, 10))
+        self.assertEqual(gene.location.strand, 1)
+        self.assertEqual(gene.qualifiers, {"gene": ["abc"], "pseudo": [""]})
+        self.assertIsInstance(cds.location, CompoundLocation)
+        self.assertEqual(cds.location.operator, "join")
+        parts = [(p.start, p.end, p.strand) for p in cds.location.parts]
+        self.assertEqual(parts, [(19, 30, -1), (0, 10, -1)])
+        self.assertEqual(cds.qualifiers, {"note": ["two lines", "more"]})
+        self.assertEqual((site.location.start, site.location.end), (5, 5))
+        self.assertEqual(external.location.ref, "J00194.1")
+
+    def test_invalid_location(self):
+        record = gb_io.Record(b"ATGC" * 25)
+        location = gb_io.OneOf([gb_io.Range(0, 10), gb_io.Range(0, 20)])
+        record.features.append(gb_io.Feature("gene", location))
+        self.assertRaises(ValueError, record.to_biopython)
+        record.features[0].location = gb_io.External("J00194.1")
+        self.assertRaises(ValueError, record.to_biopython)
+
+    def test_roundtrip(self):
+        record = gb_io.load(os.path.join(DATA_FOLDER, "NC_005816.gb"))[0]
+        copy = gb_io.Record.from_biopython(record.to_biopython())
+        self.assertEqual(copy.sequence, record.sequence)
+        self.assertEqual(copy.name, record.name)
+        self.assertEqual(copy.accession, record.accession)
+        self.assertEqual(copy.definition, record.definition.replace("\n", " "))
+        self.assertEqual(copy.date, record.date)
+        self.assertEqual(len(copy.features), len(record.features))
+        for feature1, feature2 in zip(copy.features, record.features):
+            self.assertEqual(feature1.kind, feature2.kind)
+            self.assertEqual(feature1.location, feature2.location)
+
+    def test_missing_biopython(self):
+        modules = dict.fromkeys(["Bio", "Bio.Seq", "Bio.SeqRecord", "Bio.SeqFeature"])
+        with unittest.mock.patch.dict(sys.modules, modules):
+            with self.assertRaises(ImportError) as ctx:
+                gb_io.Record(b"ATGC").to_biopython()
+        self.assertIn("pip install biopython", str(ctx.exception))
+
+    def test_outdated_biopython(self):
+        with _fake_biopython():
+            del sys.modules["Bio.SeqFeature"].SimpleLocation
+            with self.assertRaises(ImportError) as ctx:
+                gb_io.Record(b"ATGC").to_biopython()
+        self.assertIn("Biopython 1.80", str(ctx.exception))
+
+
+@unittest.skipUnless(Bio, "Biopython not installed")
+class TestBiopythonRoundtrip(unittest.TestCase):
+
+    def test_roundtrip(self):
+        path = os.path.join(DATA_FOLDER, "NC_005816.gb")
+        record = gb_io.load(path)[0]
+        seqrecord = record.to_biopython()
+        expected = Bio.SeqIO.read(path, "genbank")
+        self.assertEqual(seqrecord.id, expected.id)
+        self.assertEqual(seqrecord.seq, expected.seq)
+        self.assertEqual(seqrecord.description, expected.description)
+        self.assertEqual(len(seqrecord.features), len(expected.features))
+        for feature1, feature2 in zip(seqrecord.features, expected.features):
+            self.assertEqual(feature1.type, feature2.type)
+            self.assertEqual(feature1.location, feature2.location)
+        copy = gb_io.Record.from_biopython(expected)
+        self.assertEqual(copy.sequence, record.sequence)
+        self.assertEqual(
+            [f.location for f in copy.features],
+            [f.location for f in record.features],
+        )
+
+
 if __name__ == "__main__":
     runner = unittest.TextTestRunner(verbosity=2)
     unittest.main(testRunner=runner)
@@ -0,0 +1,309 @@
+import copy
+import datetime
+import io
+import os
+import pickle
+import unittest
+
+import gb_io
+
+DATA_FOLDER = os.path.realpath(os.path.join(__file__, os.path.pardir, "data"))
+
+
+def _dump(record):
+    buffer = io.BytesIO()
+    gb_io.dump(record, buffer)
+    return buffer.getvalue()
+
+
+def _pickle(obj):
+    return pickle.loads(pickle.dumps(obj))
+
+
+class _TestRecordCopy(object):
+    """Tests for copying records, independent of the attribute states."""
+
+    filename = NotImplemented
+
+    def load(self):
+        return gb_io.load(os.path.join(DATA_FOLDER, *self.filename))[0]
+
+    def make_owned(self):
+        # a freshly loaded record has not converted any attribute yet
+        return self.load()
+
+    def make_shared(self):
+        # accessing the attributes converts them to Python objects
+        record = self.load()
+        record.sequence
+        record.source
+        record.date
+        record.references
+        for feature in record.features:
+            feature.kind
+            feature.location
+            for qualifier in feature.qualifiers:
+                qualifier.key
+        return record
+
+    def assertNotAliased(self, record, copied):
+        self.assertIsNot(copied, record)
+        self.assertIsNot(copied.sequence, record.sequence)
+        self.assertIsNot(copied.features, record.features)
+        self.assertIsNot(copied.references, record.references)
+        if record.source is not None:
+            self.assertIsNot(copied.source, record.source)
+        for f1, f2 in zip(record.features, copied.features):
+            self.assertIsNot(f1, f2)
+            self.assertIsNot(f1.location, f2.location)
+            self.assertIsNot(f1.qualifiers, f2.qualifiers)
+        for r1, r2 in zip(record.references, copied.references):
+            self.assertIsNot(r1, r2)
+
+    def assertIndependent(self, record, copied):
+        expected = _dump(record)
+        copied.sequence[:4] = b"nnnn"
+        copied.features[0].location = gb_io.Range(0, 1)
+        copied.features[-1].qualifiers.append(gb_io.Qualifier("note", "copy"))
+        if copied.references:
+            copied.references[0].title = "Copied"
+        if copied.source is not None:
+            copied.source.organism = "Copied"
+        copied.name = "COPIED"
+        self.assertEqual(_dump(record), expected)
+
+    def test_copy_owned(self):
+        record = self.make_owned()
+        copied = copy.copy(record)
+        self.assertEqual(_dump(copied), _dump(record))
+        self.assertIs(copied.sequence, record.sequence)
+        self.assertIs(copied.features, record.features)
+        self.assertIs(copied.references, record.references)
+        self.assertIs(copied.source, record.source)
+
+    def test_copy_shared(self):
+        record = self.make_shared()
+        copied = copy.copy(record)
+        self.assertEqual(_dump(copied), _dump(record))
+        self.assertIs(copied.sequence, record.sequence)
+        self.assertIs(copied.features, record.features)
+        self.assertIs(copied.references, record.references)
+        self.assertIs(copied.source, record.source)
+        copied.name = "COPIED"
+        self.assertNotEqual(record.name, "COPIED")
+
+    def test_deepcopy_owned(self):
+        record = self.make_owned()
+        copied = copy.deepcopy(record)
+        self.assertEqual(_dump(copied), _dump(record))
+        self.assertNotAliased(record, copied)
+        self.assertIndependent(record, copied)
+
+    def test_deepcopy_shared(self):
+        record = self.make_shared()
+        copied = copy.deepcopy(record)
+        self.assertEqual(_dump(copied), _dump(record))
+        self.assertNotAliased(record, copied)
+        self.assertIndependent(record, copied)
+
+    def test_pickle_owned(self):
+        record = self.make_owned()
+        copied = _pickle(record)
+        self.assertEqual(_dump(copied), _dump(record))
+        self.assertTrue(copied.equals(record))
+        self.assertNotAliased(record, copied)
+        self.assertIndependent(record, copied)
+
+    def test_pickle_shared(self):
+        record = self.make_shared()
+        copied = _pickle(record)
+        self.assertEqual(_dump(copied), _dump(record))
+        self.assertTrue(copied.equals(record))
+        self.assertNotAliased(record, copied)
+        self.assertIndependent(record, copied)
+
+    def test_pickle_protocols(self):
+        record = self.make_owned()
+        for protocol in range(2, pickle.HIGHEST_PROTOCOL + 1):
+            copied = pickle.loads(pickle.dumps(record, protocol=protocol))
+            self.assertEqual(_dump(copied), _dump(record))
+
+
+class TestRecordCopyAY048670(_TestRecordCopy, unittest.TestCase):
+    filename = ("AY048670.1.gb",)
+
+
+class TestRecordCopyNC005816(_TestRecordCopy, unittest.TestCase):
+    filename = ("biopython", "NC_005816.gb")
+
+    def test_pickle_lifted_source(self):
+        record = self.make_owned()
+        record.source.plasmid = "pTEST"
+        copied = _pickle(record)
+        self.assertEqual(copied.source.plasmid, "pTEST")
+        self.assertEqual(copy.deepcopy(record).source.plasmid, "pTEST")
+
+
+class TestRecordCopyNT019265(_TestRecordCopy, unittest.TestCase):
+    # a record with a `CONTIG` line and no sequence
+    filename = ("biopython", "NT_019265.gb")
+
+    def assertIndependent(self, record, copied):
+        expected = _dump(record)
+        copied.features[0].location = gb_io.Range(0, 1)
+        copied.name = "COPIED"
+        self.assertEqual(_dump(record), expected)
+
+
+class TestRecordCopyPython(unittest.TestCase):
+
+    def make_record(self):
+        return gb_io.Record(
+            b"ATGCNNATGC",
+            name="TEST",
+            accession="TEST0001",
+            circular=True,
+            date=datetime.date(2024, 4, 14),
+            source=gb_io.Source("synthetic construct", mol_type="other DNA"),
+            references=[gb_io.Reference("A title", "1  (bases 1 to 10)")],
+            features=[
+                gb_io.Feature(
+                    "CDS",
+                    gb_io.Join([gb_io.Range(0, 3), gb_io.Complement(gb_io.Range(6, 9))]),
+                    qualifiers=[gb_io.Qualifier("gene", "test"), gb_io.Qualifier("pseudo")],
+                ),
+                gb_io.Feature("misc_feature", gb_io.External("J00194.1", gb_io.Between(1, 2))),
+            ],
+        )
+
+    def test_pickle(self):
+        record = self.make_record()
+        copied = _pickle(record)
+        self.assertEqual(copied.name, "TEST")
+        self.assertTrue(copied.circular)
+        self.assertEqual(copied.date, datetime.date(2024, 4, 14))
+        self.assertEqual(copied.source.mol_type, "other DNA")
+        self.assertEqual(copied.references[0].title, "A title")
+        self.assertEqual(copied.features[0].location, record.features[0].location)
+        self.assertEqual(copied.features[1].location, record.features[1].location)
+        self.assertEqual(_dump(copied), _dump(record))
+
+    def test_pickle_equal(self):
+        record = self.make_record()
+        record.origin_note = "Some note"
+        record.wgs = ["AAAA02000001-AAAA02050231"]
+        self.assertEqual(_pickle(record), record)
+        for protocol in range(2, pickle.HIGHEST_PROTOCOL + 1):
+            copied = pickle.loads(pickle.dumps(record, protocol=protocol))
+            self.assertEqual(copied, record)
+
+    def test_pickle_multiprocessing(self):
+        # records sent to `multiprocessing` workers use the same protocol
+        from multiprocessing.reduction import ForkingPickler
+
+        records = gb_io.load(os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb"))
+        copied = pickle.loads(ForkingPickler.dumps(records))
+        self.assertEqual(copied, records)
+
+    def test_pickle_invalid_date(self):
+        data = io.BytesIO(
+            b"LOCUS       TEST                       4 bp    DNA     linear   UNK 31-FEB-2020\n"
+            b"ORIGIN\n"
+            b"        1 atgc\n"
+            b"//\n"
+        )
+        with self.assertWarns(UserWarning):
+            record = gb_io.load(data)[0]
+        copied = _pickle(record)
+        self.assertIs(copied.date, None)
+        self.assertEqual(copied.date_string, "31-FEB-2020")
+
+    def test_deepcopy(self):
+        record = self.make_record()
+        copied = copy.deepcopy(record)
+        cds = next(f for f in copied.features if f.kind == "CDS")
+        cds.location.locations[1].location.start = 7
+        cds.qualifiers[0].value = "copy"
+        copied.source.mol_type = "genomic DNA"
+        cds = next(f for f in record.features if f.kind == "CDS")
+        self.assertEqual(cds.location.locations[1].location.start, 6)
+        self.assertEqual(cds.qualifiers[0].value, "test")
+        self.assertEqual(record.source.mol_type, "other DNA")
+
+
+class TestObjectCopy(unittest.TestCase):
+
+    objects = [
+        gb_io.Range(0, 10, before=True),
+        gb_io.Site(466),
+        gb_io.Between(4, 5),
+        gb_io.Complement(gb_io.Range(0, 10)),
+        gb_io.Join([gb_io.Range(0, 10), gb_io.Range(20, 30)]),
+        gb_io.Order([gb_io.Range(0, 10), gb_io.Range(20, 30)]),
+        gb_io.Bond([gb_io.Between(4, 5), gb_io.Range(20, 21)]),
+        gb_io.OneOf([gb_io.Range(0, 10), gb_io.Range(0, 20)]),
+        gb_io.External("J00194.1", gb_io.Range(0, 10)),
+        gb_io.External("J00194.1"),
+    ]
+
+    def test_location_copies(self):
+        for location in self.objects:
+            for copier in (copy.copy, copy.deepcopy, _pickle):
+                copied = copier(location)
+                self.assertIsNot(copied, location)
+                self.assertIs(type(copied), type(location))
+                self.assertEqual(copied, location)
+
+    def test_location_deepcopy_not_aliased(self):
+        location = gb_io.Join([gb_io.Range(0, 10), gb_io.Range(20, 30)])
+        shallow = copy.copy(location)
+        self.assertIs(shallow.locations[0], location.locations[0])
+        deep = copy.deepcopy(location)
+        self.assertIsNot(deep.locations, location.locations)
+        deep.locations[0].start = 5
+        self.assertEqual(location.locations[0].start, 0)
+
+    def test_qualifier(self):
+        qualifier = gb_io.Qualifier("gene", "test")
+        for copier in (copy.copy, copy.deepcopy, _pickle):
+            copied = copier(qualifier)
+            self.assertIsNot(copied, qualifier)
+            self.assertEqual(copied.key, "gene")
+            self.assertEqual(copied.value, "test")
+            self.assertIs(copied.quoted, None)
+        qualifier = gb_io.Qualifier("gene", "test", quoted=False)
+        for copier in (copy.copy, copy.deepcopy, _pickle):
+            self.assertIs(copier(qualifier).quoted, False)
+
+    def test_reference(self):
+        reference = gb_io.Reference("Title", "1  (bases 1 to 10)", authors="Doe,J.", pubmed="123")
+        for copier in (copy.copy, copy.deepcopy, _pickle):
+            copied = copier(reference)
+            self.assertIsNot(copied, reference)
+            self.assertEqual(copied.title, "Title")
+            self.assertEqual(copied.authors, "Doe,J.")
+            self.assertEqual(copied.pubmed, "123")
+
+    def test_source(self):
+        source = gb_io.Source("Escherichia coli", "Escherichia coli", plasmid="pX")
+        for copier in (copy.copy, copy.deepcopy, _pickle):
+            copied = copier(source)
+            self.assertIsNot(copied, source)
+            self.assertEqual(repr(copied), repr(source))
+
+    def test_feature(self):
+        record = gb_io.load(os.path.join(DATA_FOLDER, "AY048670.1.gb"))[0]
+        feature = record.features[1]
+        shallow = copy.copy(feature)
+        self.assertIs(shallow.location, feature.location)
+        self.assertIs(shallow.qualifiers, feature.qualifiers)
+        for copier in (copy.deepcopy, _pickle):
+            copied = copier(feature)
+            self.assertEqual(copied.kind, feature.kind)
+            self.assertEqual(copied.location, feature.location)
+            self.assertIsNot(copied.location, feature.location)
+            self.assertIsNot(copied.qualifiers, feature.qualifiers)
+            self.assertEqual(
+                [(q.key, q.value) for q in copied.qualifiers],
+                [(q.key, q.value) for q in feature.qualifiers],
+            )
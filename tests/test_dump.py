@@ -1,6 +1,9 @@
+import copy
 import datetime
+import gzip
 import unittest
 import os
+import sys
 import tempfile
 import io
 
@@ -25,6 +28,30 @@ class TestDump(unittest.TestCase):
         lines_expected = self.contents.strip().splitlines()
         self.assertMultiLineEqual("\n".join(lines_actual[1:]), "\n".join(lines_expected[1:]))
 
+    def test_dump_single_base_location(self):
+        record = gb_io.Record(
+            b"ATGC" * 200,
+            name="TEST",
+            features=[gb_io.Feature("misc_feature", gb_io.Range(466, 467))],
+        )
+        buffer = io.BytesIO()
+        gb_io.dump(record, buffer)
+        self.assertIn(b"     misc_feature    467\n", buffer.getvalue())
+        buffer.seek(0)
+        loaded = gb_io.load(buffer)[0]
+        self.assertEqual(loaded.features[0].location, gb_io.Range(466, 467))
+        self.assertIsInstance(loaded.features[0].location, gb_io.Site)
+
+    def test_dump_site_location(self):
+        record = gb_io.Record(
+            b"ATGC" * 200,
+            name="TEST",
+            features=[gb_io.Feature("misc_feature", gb_io.Site(466))],
+        )
+        buffer = io.BytesIO()
+        gb_io.dump(record, buffer)
+        self.assertIn(b"     misc_feature    467\n", buffer.getvalue())
+
     def test_dump_binary_file(self):
         buffer = io.BytesIO()
         gb_io.dump(self.records, buffer)
@@ -46,6 +73,70 @@ class TestDump(unittest.TestCase):
         lines_expected = self.contents.strip().splitlines()
         self.assertMultiLineEqual("\n".join(lines_actual[1:]), "\n".join(lines_expected[1:]))
 
+    def test_dump_gzip_path(self):
+        with tempfile.TemporaryDirectory() as dst:
+            path = os.path.join(dst, "records.gb.gz")
+            gb_io.dump(self.records, path, compresslevel=6)
+            with gzip.open(path, "rb") as f:
+                records = gb_io.load(f)
+        expected = io.BytesIO()
+        gb_io.dump(self.records, expected)
+        actual = io.BytesIO()
+        gb_io.dump(records, actual)
+        self.assertEqual(len(records), len(self.records))
+        self.assertMultiLineEqual(actual.getvalue().decode(), expected.getvalue().decode())
+
+    def test_dump_gzip_file(self):
+        buffer = io.BytesIO()
+        with gzip.GzipFile(fileobj=buffer, mode="wb") as f:
+            gb_io.dump(self.records, f)
+        lines_actual = gzip.decompress(buffer.getvalue()).strip().decode().splitlines()
+        lines_expected = self.contents.strip().splitlines()
+        self.assertMultiLineEqual("\n".join(lines_actual[1:]), "\n".join(lines_expected[1:]))
+
+    def test_dump_returns_none(self):
+        buffer = io.BytesIO()
+        self.assertIs(gb_io.dump(self.records, buffer), None)
+
+    def test_dump_return_count(self):
+        buffer = io.BytesIO()
+        buffer.write(b"header\n")
+        count = gb_io.dump(self.records, buffer, return_count=True)
+        self.assertEqual(count, len(buffer.getvalue()) - 7)
+        with tempfile.TemporaryDirectory() as dst:
+            path = os.path.join(dst, "records.gb.gz")
+            count = gb_io.dump(self.records, path, return_count=True)
+            with gzip.open(path, "rb") as f:
+                self.assertEqual(count, len(f.read()))
+        self.assertEqual(gb_io.dump([], io.BytesIO(), return_count=True), 0)
+
+    def test_dump_to_buffer(self):
+        buffer = gb_io.dump(self.records)
+        self.assertIsInstance(buffer, io.BytesIO)
+        self.assertEqual(buffer.tell(), 0)
+        expected = io.BytesIO()
+        gb_io.dump(self.records, expected)
+        self.assertEqual(buffer.getvalue(), expected.getvalue())
+        records = gb_io.load(gb_io.dump(self.records[0], None, emit_gi=True))
+        self.assertEqual(records[0].accession, self.records[0].accession)
+        self.assertRaises(ValueError, gb_io.dump, self.records, return_count=True)
+
+    def test_dump_simplify_locations(self):
+        record = gb_io.Record(
+            b"ATGC" * 50,
+            name="TEST",
+            features=[
+                gb_io.Feature("CDS", gb_io.Join([gb_io.Range(0, 100)])),
+                gb_io.Feature("misc_feature", gb_io.Order([gb_io.Order([gb_io.Range(0, 10), gb_io.Range(20, 30)])])),
+            ],
+        )
+        text = gb_io.dump(record).getvalue()
+        self.assertIn(b"     CDS             join(1..100)\n", text)
+        text = gb_io.dump(record, simplify_locations=True).getvalue()
+        self.assertIn(b"     CDS             1..100\n", text)
+        self.assertIn(b"     misc_feature    order(1..10,21..30)\n", text)
+        self.assertIsInstance(record.features[0].location, gb_io.Join)
+
     def test_python_record(self):
         record = gb_io.Record(
             sequence=b"ATGC",
@@ -73,6 +164,73 @@ class TestDump(unittest.TestCase):
             ]
         )
 
+    def test_dump_qualifier_quoting(self):
+        record = gb_io.Record(
+            sequence=b"ATGC",
+            name="test",
+            features=[
+                gb_io.Feature(
+                    "CDS",
+                    gb_io.Range(0, 3),
+                    [
+                        gb_io.Qualifier("codon_start", "1"),
+                        gb_io.Qualifier("transl_table", "11"),
+                        gb_io.Qualifier("product", "x"),
+                        gb_io.Qualifier("score", "42"),
+                    ]
+                ),
+            ]
+        )
+        buffer = io.BytesIO()
+        gb_io.dump(record, buffer)
+        lines = buffer.getvalue().decode().splitlines()
+        self.assertIn('                     /codon_start=1', lines)
+        self.assertIn('                     /transl_table=11', lines)
+        self.assertIn('                     /product="x"', lines)
+        self.assertIn('                     /score="42"', lines)
+
+        buffer = io.BytesIO()
+        gb_io.dump(record, buffer, unquoted_qualifiers={"score"})
+        lines = buffer.getvalue().decode().splitlines()
+        self.assertIn('                     /codon_start=1', lines)
+        self.assertIn('                     /product="x"', lines)
+        self.assertIn('                     /score=42', lines)
+
+    def test_dump_qualifier_quoted(self):
+        record = gb_io.Record(
+            sequence=b"ATGC",
+            name="test",
+            features=[
+                gb_io.Feature(
+                    "CDS",
+                    gb_io.Range(0, 3),
+                    [
+                        gb_io.Qualifier("codon_start", "1", quoted=True),
+                        gb_io.Qualifier("product", "x", quoted=False),
+                        gb_io.Qualifier("pseudo"),
+                        gb_io.Qualifier("ribosomal_slippage", quoted=True),
+                        gb_io.Qualifier("score", "42"),
+                    ]
+                ),
+            ]
+        )
+        buffer = io.BytesIO()
+        gb_io.dump(record, buffer, unquoted_qualifiers={"product", "score"})
+        lines = buffer.getvalue().decode().splitlines()
+        self.assertIn('                     /codon_start="1"', lines)
+        self.assertIn('                     /product=x', lines)
+        self.assertIn('                     /pseudo', lines)
+        self.assertIn('                     /ribosomal_slippage', lines)
+        self.assertIn('                     /score=42', lines)
+
+    def test_dump_qualifier_quoted_copy(self):
+        qualifier = gb_io.Qualifier("note", "x", quoted=False)
+        feature = gb_io.Feature("CDS", gb_io.Range(0, 3), [qualifier])
+        record = gb_io.Record(sequence=b"ATGC", name="test", features=[feature])
+        self.assertIn('/note=x\n', gb_io.dumps(copy.deepcopy(record)))
+        record.features = [copy.deepcopy(feature)]
+        self.assertIn('/note=x\n', gb_io.dumps(record))
+
 class TestDumpError(unittest.TestCase):
 
     @classmethod
@@ -85,7 +243,12 @@ class TestDumpError(unittest.TestCase):
         self.assertRaises(OSError, gb_io.dump, self.records, dirname)
 
     def test_dump_bad_file(self):
-        self.assertRaises(TypeError, gb_io.dump, self.records, None)
+        self.assertRaises(TypeError, gb_io.dump, self.records, 1)
+
+    def test_dump_invalid_compresslevel(self):
+        with tempfile.TemporaryDirectory() as dst:
+            path = os.path.join(dst, "records.gb.gz")
+            self.assertRaises(ValueError, gb_io.dump, self.records, path, compresslevel=10)
 
     def test_dump_none(self):
         buffer = io.BytesIO()
@@ -96,3 +259,693 @@ class TestDumpError(unittest.TestCase):
         buffer = io.BytesIO()
         dirname = os.path.dirname(__file__)
         self.assertRaises(TypeError, gb_io.dump, [None], buffer)
+
+    def test_dump_invalid_feature_range(self):
+        record = gb_io.Record(
+            b"ATGC" * 10,
+            accession="TEST0001",
+            features=[
+                gb_io.Feature("gene", gb_io.Range(0, 12)),
+                gb_io.Feature("CDS", gb_io.Range(0, 100)),
+            ],
+        )
+        buffer = io.BytesIO()
+        with self.assertRaises(ValueError) as ctx:
+            gb_io.dump([self.records[0], record], buffer)
+        message = str(ctx.exception)
+        self.assertIn("record 1 (TEST0001)", message)
+        self.assertIn("feature 1 (CDS)", message)
+        self.assertIn("Range(0, 100)", message)
+        # the invalid record should not be partially written
+        self.assertEqual(buffer.getvalue().count(b"LOCUS"), 1)
+        self.assertTrue(buffer.getvalue().endswith(b"//\n"))
+
+    def test_dump_invalid_feature_kind(self):
+        record = gb_io.Record(
+            b"ATGC",
+            name="TEST",
+            features=[gb_io.Feature("misc feature", gb_io.Range(0, 4))],
+        )
+        with self.assertRaises(ValueError) as ctx:
+            gb_io.dump(record, io.BytesIO())
+        self.assertIn("record 0 (TEST)", str(ctx.exception))
+        self.assertIn("feature kind", str(ctx.exception))
+
+    def test_dump_invalid_qualifier_key(self):
+        record = gb_io.Record(
+            b"ATGC",
+            features=[
+                gb_io.Feature(
+                    "gene",
+                    gb_io.Range(0, 4),
+                    qualifiers=[gb_io.Qualifier("locus tag", "x")],
+                )
+            ],
+        )
+        with self.assertRaises(ValueError) as ctx:
+            gb_io.dump(record, io.BytesIO())
+        self.assertIn("feature 0 (gene)", str(ctx.exception))
+        self.assertIn("qualifier key", str(ctx.exception))
+
+    def test_dump_negative_coordinates(self):
+        record = gb_io.Record(
+            b"ATGC",
+            version="TEST0001.2",
+            features=[gb_io.Feature("gene", gb_io.Complement(gb_io.Range(-2, 4)))],
+        )
+        with self.assertRaises(ValueError) as ctx:
+            gb_io.dump(record, io.BytesIO())
+        self.assertIn("record 0 (TEST0001.2)", str(ctx.exception))
+        self.assertIn("negative coordinates", str(ctx.exception))
+
+    def test_dump_file_error(self):
+        class BrokenFile(io.BytesIO):
+            def write(self, b):
+                if b:
+                    raise RuntimeError("broken")
+                return 0
+        self.assertRaises(RuntimeError, gb_io.dump, self.records, BrokenFile())
+
+
+class TestDumpGI(unittest.TestCase):
+
+    @classmethod
+    def setUpClass(cls):
+        path = os.path.join(DATA_FOLDER, "biopython", "NC_005816.gb")
+        cls.record = gb_io.load(path)[0]
+
+    def test_record_gi(self):
+        self.assertEqual(self.record.gi, 45478711)
+        record = gb_io.Record(b"", version="NC_005816.1")
+        self.assertIs(record.gi, None)
+        record.gi = 45478711
+        self.assertEqual(record.version, "NC_005816.1  GI:45478711")
+        record.gi = None
+        self.assertEqual(record.version, "NC_005816.1")
+
+    def test_dump_without_gi(self):
+        buffer = io.BytesIO()
+        gb_io.dump(self.record, buffer)
+        lines = buffer.getvalue().decode().splitlines()
+        self.assertIn("VERSION     NC_005816.1", lines)
+
+    def test_dump_with_gi(self):
+        buffer = io.BytesIO()
+        gb_io.dump(self.record, buffer, emit_gi=True)
+        lines = buffer.getvalue().decode().splitlines()
+        self.assertIn("VERSION     NC_005816.1  GI:45478711", lines)
+
+
+class TestDumpReference(unittest.TestCase):
+
+    def test_roundtrip_authors(self):
+        authors = ["Author{},A.B.".format(i) for i in range(29)]
+        authors_str = "{} and Lastauthor,Z.".format(", ".join(authors))
+        title = " ".join(["A very long title about bacterial genomes"] * 6)
+        reference = gb_io.Reference(
+            title, "1  (bases 1 to 8)", authors_str, None, "Unpublished", None, None
+        )
+        record = gb_io.Record(
+            b"ATGCATGC", name="TEST", molecule_type="DNA", references=[reference]
+        )
+
+        buffer = io.BytesIO()
+        gb_io.dump(record, buffer)
+        lines = buffer.getvalue().decode().splitlines()
+        self.assertTrue(all(len(line) <= 79 for line in lines))
+        self.assertEqual(lines[2][:12], "  AUTHORS   ")
+        self.assertTrue(lines[3].startswith(" " * 12 + "Author"))
+
+        buffer.seek(0)
+        loaded = gb_io.load(buffer)[0].references[0]
+        self.assertEqual(loaded.authors, authors_str)
+        self.assertEqual(loaded.title, title)
+        names = loaded.authors.replace(" and ", ", ").split(", ")
+        self.assertEqual(len(names), 30)
+        self.assertEqual(names[-1], "Lastauthor,Z.")
+
+        buffer2 = io.BytesIO()
+        gb_io.dump(gb_io.load(io.BytesIO(buffer.getvalue())), buffer2)
+        self.assertEqual(buffer2.getvalue(), buffer.getvalue())
+
+
+class TestDumpDate(unittest.TestCase):
+
+    @classmethod
+    def setUpClass(cls):
+        path = os.path.join(DATA_FOLDER, "biopython", "NC_005816.gb")
+        cls.record = gb_io.load(path)[0]
+
+    def test_dump_date(self):
+        buffer = io.BytesIO()
+        gb_io.dump(self.record, buffer, date=datetime.date(2020, 1, 2))
+        locus = buffer.getvalue().decode().splitlines()[0]
+        self.assertTrue(locus.endswith("02-JAN-2020"))
+        self.assertEqual(self.record.date, datetime.date(2008, 7, 21))
+
+    def test_dump_date_string(self):
+        buffer = io.BytesIO()
+        gb_io.dump(self.record, buffer, date="2020-01-02")
+        locus = buffer.getvalue().decode().splitlines()[0]
+        self.assertTrue(locus.endswith("02-JAN-2020"))
+
+    def test_dump_invalid_date(self):
+        buffer = io.BytesIO()
+        self.assertRaises(ValueError, gb_io.dump, self.record, buffer, date="2020-13-40")
+        self.assertRaises(TypeError, gb_io.dump, self.record, buffer, date=1)
+
+
+class TestDumpLineWidth(unittest.TestCase):
+
+    def setUp(self):
+        self.record = gb_io.Record(b"acgt" * 30 + b"a", name="TEST")
+
+    def origin(self, **kwargs):
+        text = gb_io.dumps(self.record, **kwargs)
+        return text.split("ORIGIN      \n")[1].splitlines()[:-1]
+
+    def test_default(self):
+        lines = self.origin()
+        self.assertEqual(lines, self.origin(line_width=60))
+        self.assertEqual(len(lines), 3)
+        self.assertEqual(lines[1], "       61 " + " ".join(["acgtacgtac", "gtacgtacgt"] * 3))
+
+    def test_line_width(self):
+        lines = self.origin(line_width=25)
+        self.assertEqual(len(lines), 5)
+        self.assertEqual(lines[0], "        1 acgtacgtac gtacgtacgt acgta")
+        self.assertEqual(lines[1], "       26 cgtacgtacg tacgtacgta cgtac")
+        self.assertEqual(lines[-1], "      101 acgtacgtac gtacgtacgt a")
+
+    def test_roundtrip(self):
+        for width in (1, 10, 25, 100):
+            buffer = gb_io.dump(self.record, line_width=width)
+            self.assertEqual(gb_io.load(buffer)[0].sequence, self.record.sequence)
+
+    def test_embl(self):
+        text = gb_io.dumps(self.record, format="embl", line_width=25)
+        lines = text.splitlines()
+        self.assertEqual(lines[-6], "     acgtacgtac gtacgtacgt acgta        25")
+        self.assertEqual(lines[-2], "     acgtacgtac gtacgtacgt a           121")
+
+    def test_invalid_line_width(self):
+        self.assertRaises(ValueError, gb_io.dumps, self.record, line_width=0)
+        self.assertRaises(ValueError, gb_io.dump, self.record, line_width=0)
+        self.assertRaises(OverflowError, gb_io.dumps, self.record, line_width=-1)
+
+
+class TestDumpValidate(unittest.TestCase):
+
+    def test_invalid_dna(self):
+        record = gb_io.Record(b"ATGEQCE", name="TEST", molecule_type="DNA")
+        with self.assertRaises(ValueError) as ctx:
+            gb_io.dump(record, validate=True)
+        message = str(ctx.exception)
+        self.assertIn("'E' at positions 3, 6", message)
+        self.assertIn("'Q' at position 4", message)
+
+    def test_disabled(self):
+        record = gb_io.Record(b"ATGEQCE", name="TEST", molecule_type="DNA")
+        buffer = gb_io.dump(record)
+        self.assertEqual(gb_io.load(buffer)[0].sequence, b"ATGEQCE")
+        buffer = gb_io.dump(record, validate=False)
+        self.assertEqual(gb_io.load(buffer)[0].sequence, b"ATGEQCE")
+
+    def test_ambiguous_dna(self):
+        record = gb_io.Record(b"ATGRYNNCA", name="TEST", molecule_type="DNA")
+        buffer = gb_io.dump(record, validate=True)
+        self.assertEqual(gb_io.load(buffer)[0].sequence, b"ATGRYNNCA")
+
+    def test_rna(self):
+        record = gb_io.Record(b"AUGGCU", name="TEST", molecule_type="mRNA")
+        gb_io.dump(record, validate=True)
+        record.sequence = bytearray(b"AUG1CU")
+        self.assertRaises(ValueError, gb_io.dump, record, validate=True)
+
+    def test_protein(self):
+        record = gb_io.Record(b"MKVLAAGIE*", name="TEST", molecule_type="protein")
+        gb_io.dump(record, validate=True)
+        record.sequence = bytearray(b"MKV1AAGIE")
+        with self.assertRaises(ValueError) as ctx:
+            gb_io.dump(record, validate=True)
+        self.assertIn("'1' at position 3", str(ctx.exception))
+
+    def test_protein_nucleotides(self):
+        for molecule_type in ("protein", "AA"):
+            record = gb_io.Record(b"ATGCATGCATGCAAAA", name="TEST", molecule_type=molecule_type)
+            with self.assertRaises(ValueError) as ctx:
+                gb_io.dump(record, validate=True)
+            self.assertIn("nucleotide sequence", str(ctx.exception))
+        record.sequence = bytearray(b"ACGT")
+        gb_io.dump(record, validate=True)
+
+    def test_dumps(self):
+        record = gb_io.Record(b"ATGEQC", name="TEST", molecule_type="DNA")
+        self.assertIn("ATGEQC", gb_io.dumps(record).upper())
+        self.assertRaises(ValueError, gb_io.dumps, record, validate=True)
+
+    def test_unknown_molecule_type(self):
+        record = gb_io.Record(b"ATG123", name="TEST")
+        gb_io.dump(record, validate=True)
+        record.molecule_type = "unknown"
+        gb_io.dump(record, validate=True)
+
+    def test_truncated_positions(self):
+        record = gb_io.Record(b"E" * 8, name="TEST", molecule_type="DNA")
+        with self.assertRaises(ValueError) as ctx:
+            gb_io.dump(record, validate=True)
+        self.assertIn("'E' at positions 0, 1, 2, 3, 4, ...", str(ctx.exception))
+
+    def test_writer(self):
+        record = gb_io.Record(b"ATGEQC", name="TEST", molecule_type="DNA")
+        with gb_io.Writer(io.BytesIO(), validate=True) as writer:
+            self.assertRaises(ValueError, writer.write, record)
+
+
+class TestDumpWrapWidth(unittest.TestCase):
+
+    @classmethod
+    def setUpClass(cls):
+        path = os.path.join(DATA_FOLDER, "biopython", "NC_005816.gb")
+        cls.record = gb_io.load(path)[0]
+
+    def test_default(self):
+        self.assertEqual(
+            gb_io.dump(self.record, wrap_width=79).getvalue(),
+            gb_io.dump(self.record).getvalue(),
+        )
+
+    def test_wrap_width(self):
+        def header(text):
+            # the locus and sequence lines have a fixed layout
+            lines = text.splitlines()
+            return lines[1:lines.index("ORIGIN      ")]
+
+        text = gb_io.dump(self.record, wrap_width=60).getvalue().decode()
+        self.assertTrue(all(len(line) <= 60 for line in header(text)))
+        default = gb_io.dump(self.record).getvalue().decode()
+        self.assertTrue(any(len(line) > 60 for line in header(default)))
+        record = gb_io.load(io.StringIO(text))[0]
+        self.assertEqual(record.sequence, self.record.sequence)
+        # line breaks of wrapped fields are kept by the parser
+        self.assertEqual(record.definition.split(), self.record.definition.split())
+        for f1, f2 in zip(record.features, self.record.features):
+            self.assertEqual(f1.location, f2.location)
+            for q1, q2 in zip(f1.qualifiers, f2.qualifiers):
+                self.assertEqual(q1.key, q2.key)
+                self.assertEqual((q1.value or "").split(), (q2.value or "").split())
+
+    def test_invalid_wrap_width(self):
+        self.assertRaises(ValueError, gb_io.dump, self.record, wrap_width=10)
+        self.assertRaises(OverflowError, gb_io.dump, self.record, wrap_width=-1)
+
+
+class TestDumpWrapDefinition(unittest.TestCase):
+
+    def setUp(self):
+        words = ["word{:02}".format(i) for i in range(43)]
+        self.definition = " ".join(words)
+        self.assertEqual(len(self.definition), 300)
+        self.record = gb_io.Record(b"ATGC", name="TEST", definition=self.definition)
+
+    def definition_lines(self, text):
+        lines = text.splitlines()
+        end = next(i for i, line in enumerate(lines) if line.startswith("ORIGIN"))
+        return lines[1:end]
+
+    def test_wrapped(self):
+        text = gb_io.dump(self.record, wrap_definition=True).getvalue().decode()
+        self.assertEqual(text, gb_io.dump(self.record).getvalue().decode())
+        lines = self.definition_lines(text)
+        self.assertGreater(len(lines), 1)
+        self.assertTrue(lines[0].startswith("DEFINITION  word00 "))
+        for line in lines:
+            self.assertLessEqual(len(line), 79)
+        for line in lines[1:]:
+            self.assertTrue(line.startswith(" " * 12))
+            self.assertNotEqual(line[12], " ")
+        self.assertEqual(" ".join(line[12:] for line in lines), self.definition)
+
+    def test_unwrapped(self):
+        text = gb_io.dump(self.record, wrap_definition=False).getvalue().decode()
+        lines = self.definition_lines(text)
+        self.assertEqual(lines, ["DEFINITION  " + self.definition])
+        record = gb_io.load(io.StringIO(text))[0]
+        self.assertEqual(record.definition, self.definition)
+
+    def test_width(self):
+        text = gb_io.dump(self.record, wrap_definition=50).getvalue().decode()
+        lines = self.definition_lines(text)
+        self.assertTrue(all(len(line) <= 50 for line in lines))
+        self.assertTrue(any(len(line) > 40 for line in lines))
+        self.assertEqual(" ".join(line[12:] for line in lines), self.definition)
+
+    def test_invalid(self):
+        self.assertRaises(ValueError, gb_io.dump, self.record, wrap_definition=10)
+        self.assertRaises(TypeError, gb_io.dump, self.record, wrap_definition="yes")
+
+
+class TestDumpLinearizeCircular(unittest.TestCase):
+
+    def setUp(self):
+        self.record = gb_io.Record(
+            b"a" * 100,
+            name="TEST",
+            circular=True,
+            features=[
+                gb_io.Feature("CDS", gb_io.Range(90, 110)),
+                gb_io.Feature("gene", gb_io.Complement(gb_io.Range(80, 115))),
+                gb_io.Feature("misc_feature", gb_io.Range(10, 20)),
+            ],
+        )
+
+    def test_linearize_circular(self):
+        text = gb_io.dump(self.record, linearize_circular=True).getvalue().decode()
+        self.assertIn(" linear ", text.splitlines()[0])
+        record = gb_io.load(io.StringIO(text))[0]
+        self.assertFalse(record.circular)
+        self.assertEqual(
+            [str(feature.location) for feature in record.features],
+            ["join(91..100,1..10)", "complement(join(81..100,1..15))", "11..20"],
+        )
+
+    def test_input_not_modified(self):
+        gb_io.dump(self.record, linearize_circular=True)
+        self.assertTrue(self.record.circular)
+        self.assertEqual(self.record.features[0].location, gb_io.Range(90, 110))
+
+    def test_disabled(self):
+        self.assertRaises(ValueError, gb_io.dump, self.record)
+        del self.record.features[:2]
+        text = gb_io.dump(self.record).getvalue().decode()
+        self.assertIn(" circular ", text.splitlines()[0])
+
+    def test_linear_record(self):
+        self.record.circular = False
+        self.assertRaises(ValueError, gb_io.dump, self.record, linearize_circular=True)
+
+
+class TestToGenBank(unittest.TestCase):
+
+    def test_matches_dump(self):
+        paths = [
+            os.path.join(DATA_FOLDER, "AY048670.1.gb"),
+            os.path.join(DATA_FOLDER, "biopython", "NC_005816.gb"),
+            os.path.join(DATA_FOLDER, "biopython", "arab1.gb"),
+            os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb"),
+        ]
+        for path in paths:
+            for record in gb_io.load(path):
+                self.assertEqual(record.to_genbank(), gb_io.dump(record).getvalue().decode())
+
+    def test_options(self):
+        record = gb_io.load(os.path.join(DATA_FOLDER, "biopython", "NC_005816.gb"))[0]
+        record.name = "A VERY LONG LOCUS NAME FOR A RECORD"
+        for kwargs in [
+            dict(escape_locus=True),
+            dict(truncate_locus=True),
+            dict(escape_locus=True, truncate_locus=True, wrap_width=50),
+        ]:
+            expected = gb_io.dump(record, **kwargs).getvalue().decode()
+            self.assertEqual(record.to_genbank(**kwargs), expected)
+
+    def test_python_record(self):
+        record = gb_io.Record(
+            b"ATGC" * 20,
+            name="TEST",
+            features=[gb_io.Feature("CDS", gb_io.Range(0, 12), [gb_io.Qualifier("gene", "x")])],
+        )
+        text = record.to_genbank()
+        self.assertIsInstance(text, str)
+        self.assertTrue(text.startswith("LOCUS       TEST"))
+        self.assertTrue(text.endswith("//\n"))
+        self.assertEqual(gb_io.load(io.StringIO(text))[0].sequence, record.sequence)
+
+    def test_errors(self):
+        record = gb_io.Record(b"ATGC", features=[gb_io.Feature("CDS", gb_io.Range(0, 12))])
+        self.assertRaises(ValueError, record.to_genbank)
+        self.assertRaises(ValueError, gb_io.Record(b"ATGC").to_genbank, wrap_width=39)
+        self.assertRaises(TypeError, gb_io.Record(b"ATGC").to_genbank, True)
+
+
+class TestDumps(unittest.TestCase):
+
+    @classmethod
+    def setUpClass(cls):
+        path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+        cls.records = gb_io.load(path)
+
+    def test_dumps(self):
+        text = gb_io.dumps(self.records)
+        self.assertIsInstance(text, str)
+        self.assertEqual(text, gb_io.dump(self.records).getvalue().decode())
+        self.assertEqual(text, "".join(record.to_genbank() for record in self.records))
+
+    def test_dumps_single_record(self):
+        self.assertEqual(gb_io.dumps(self.records[0]), self.records[0].to_genbank())
+
+    def test_dumps_empty(self):
+        self.assertEqual(gb_io.dumps([]), "")
+
+    def test_dumps_options(self):
+        record = gb_io.Record(b"ATGC" * 20, name="A VERY LONG LOCUS NAME FOR A RECORD")
+        for kwargs in [
+            dict(escape_locus=True),
+            dict(truncate_locus=True),
+            dict(escape_locus=True, truncate_locus=True, wrap_width=50),
+        ]:
+            expected = gb_io.dump(record, **kwargs).getvalue().decode()
+            self.assertEqual(gb_io.dumps(record, **kwargs), expected)
+
+    def test_dumps_roundtrip(self):
+        records = gb_io.loads(gb_io.dumps(self.records))
+        self.assertEqual([r.name for r in records], [r.name for r in self.records])
+        self.assertEqual([r.sequence for r in records], [r.sequence for r in self.records])
+
+    def test_dumps_errors(self):
+        self.assertRaises(TypeError, gb_io.dumps, None)
+        self.assertRaises(TypeError, gb_io.dumps, [1])
+        self.assertRaises(ValueError, gb_io.dumps, self.records, wrap_width=20)
+        record = gb_io.Record(b"ATGC", features=[gb_io.Feature("CDS", gb_io.Range(0, 12))])
+        self.assertRaises(ValueError, gb_io.dumps, [self.records[0], record])
+
+
+class TestDumpCanonicalQualifierOrder(unittest.TestCase):
+
+    def setUp(self):
+        self.qualifiers = [
+            gb_io.Qualifier("note", "first note"),
+            gb_io.Qualifier("product", "repressor"),
+            gb_io.Qualifier("db_xref", "GeneID:1"),
+            gb_io.Qualifier("gene", "rop"),
+            gb_io.Qualifier("note", "second note"),
+            gb_io.Qualifier("locus_tag", "b0001"),
+        ]
+        feature = gb_io.Feature("CDS", gb_io.Range(0, 3), self.qualifiers)
+        self.record = gb_io.Record(b"ATG", name="TEST", features=[feature])
+
+    def written_qualifiers(self, **kwargs):
+        text = gb_io.dump(self.record, **kwargs).getvalue()
+        feature = gb_io.load(io.BytesIO(text))[0].features[0]
+        return [(q.key, q.value) for q in feature.qualifiers]
+
+    def test_disabled(self):
+        expected = [(q.key, q.value) for q in self.qualifiers]
+        self.assertEqual(self.written_qualifiers(), expected)
+        self.assertEqual(self.written_qualifiers(canonical_qualifier_order=False), expected)
+
+    def test_default_order(self):
+        self.assertEqual(
+            self.written_qualifiers(canonical_qualifier_order=True),
+            [
+                ("gene", "rop"),
+                ("locus_tag", "b0001"),
+                ("product", "repressor"),
+                ("db_xref", "GeneID:1"),
+                ("note", "first note"),
+                ("note", "second note"),
+            ],
+        )
+        # the record itself is not modified
+        keys = [q.key for q in self.record.features[0].qualifiers]
+        self.assertEqual(keys, [q.key for q in self.qualifiers])
+
+    def test_custom_order(self):
+        self.assertEqual(
+            self.written_qualifiers(canonical_qualifier_order=["note", "locus_tag"]),
+            [
+                ("note", "first note"),
+                ("note", "second note"),
+                ("locus_tag", "b0001"),
+                ("db_xref", "GeneID:1"),
+                ("gene", "rop"),
+                ("product", "repressor"),
+            ],
+        )
+
+    def test_invalid(self):
+        self.assertRaises(TypeError, gb_io.dump, self.record, canonical_qualifier_order="gene")
+        self.assertRaises(TypeError, gb_io.dump, self.record, canonical_qualifier_order=[1])
+
+
+class TestDumpEMBL(unittest.TestCase):
+
+    @classmethod
+    def setUpClass(cls):
+        path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+        cls.records = gb_io.load(path)
+
+    def lines(self, record, code, **kwargs):
+        text = gb_io.dump(record, format="embl", **kwargs).getvalue().decode()
+        return [line[5:] for line in text.splitlines() if line.startswith(code)]
+
+    def test_dumps_matches_dump(self):
+        text = gb_io.dumps(self.records, format="embl")
+        self.assertEqual(text, gb_io.dump(self.records, format="embl").getvalue().decode())
+        self.assertEqual(text.count("\nID   "), len(self.records) - 1)
+        self.assertEqual(text.count("//\n"), len(self.records))
+
+    def test_id_line(self):
+        lines = self.lines(self.records[0], "ID")
+        self.assertEqual(lines, ["X55053; SV 1; linear; mRNA; STD; PLN; 513 BP."])
+
+    def test_id_line_defaults(self):
+        record = gb_io.Record(b"ATGC", name="TEST", division="BCT", circular=True)
+        lines = self.lines(record, "ID")
+        self.assertEqual(lines, ["TEST; SV 1; circular; unassigned DNA; STD; PRO; 4 BP."])
+        lines = self.lines(record, "ID", linearize_circular=True)
+        self.assertEqual(lines, ["TEST; SV 1; linear; unassigned DNA; STD; PRO; 4 BP."])
+
+    def test_header(self):
+        record = self.records[0]
+        self.assertEqual(self.lines(record, "AC"), ["X55053;"])
+        self.assertEqual(self.lines(record, "DE"), [record.definition])
+        self.assertEqual(self.lines(record, "OS"), [record.source.name])
+        self.assertEqual(self.lines(record, "RN"), ["[1]", "[2]"])
+        self.assertEqual(self.lines(record, "RP"), ["1-513", "1-513"])
+        self.assertEqual(self.lines(record, "RA")[0], "Thomashow M.F.;")
+        self.assertEqual(self.lines(record, "RT")[0], '"Direct Submission";')
+
+    def test_date(self):
+        record = self.records[0]
+        self.assertEqual(self.lines(record, "DT"), ["02-MAR-1992"])
+        self.assertEqual(self.lines(record, "DT", date="2024-01-31"), ["31-JAN-2024"])
+        self.assertEqual(self.lines(gb_io.Record(b"ATGC"), "DT"), [])
+
+    def test_features(self):
+        record = self.records[0]
+        text = gb_io.dumps(record, format="embl")
+        self.assertIn("FH   Key             Location/Qualifiers\nFH\n", text)
+        genbank = gb_io.dumps(record).split("FEATURES             Location/Qualifiers\n")[1]
+        genbank = genbank.split("ORIGIN")[0].splitlines()
+        embl = self.lines(record, "FT")
+        self.assertEqual(embl, [line[5:] for line in genbank])
+
+    def test_sequence(self):
+        record = gb_io.Record(b"acgt" * 20 + b"n", name="TEST")
+        text = gb_io.dumps(record, format="embl")
+        lines = text.splitlines()
+        self.assertEqual(lines[-4], "SQ   Sequence 81 BP; 20 A; 20 C; 20 G; 20 T; 1 other;")
+        self.assertEqual(lines[-3], "     " + " ".join(["acgtacgtac", "gtacgtacgt"] * 3) + "        60")
+        self.assertEqual(lines[-2], "     " + " ".join(["acgtacgtac", "gtacgtacgt", "n"]).ljust(65) + "        81")
+        self.assertEqual(lines[-1], "//")
+        self.assertTrue(all(len(line) <= 80 for line in lines))
+
+    def test_invalid_format(self):
+        self.assertRaises(ValueError, gb_io.dumps, self.records, format="fasta")
+        self.assertRaises(ValueError, gb_io.dump, self.records, format="fasta")
+        self.assertRaises(TypeError, gb_io.dumps, self.records, format=1)
+
+    def test_invalid_feature(self):
+        record = gb_io.Record(b"ATGC", features=[gb_io.Feature("CDS", gb_io.Range(0, 12))])
+        self.assertRaises(ValueError, gb_io.dumps, record, format="embl")
+
+
+class TestWriter(unittest.TestCase):
+
+    @classmethod
+    def setUpClass(cls):
+        path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+        cls.records = gb_io.load(path)
+
+    def test_write(self):
+        buffer = io.BytesIO()
+        with gb_io.Writer(buffer) as writer:
+            for record in self.records:
+                writer.write(record)
+        self.assertEqual(buffer.getvalue(), gb_io.dump(self.records).getvalue())
+
+    def test_write_text(self):
+        buffer = io.StringIO()
+        with gb_io.Writer(buffer) as writer:
+            writer.write(self.records[0])
+        self.assertEqual(buffer.getvalue(), gb_io.dumps(self.records[0]))
+
+    def test_write_options(self):
+        buffer = io.BytesIO()
+        with gb_io.Writer(buffer, format="embl", escape_locus=True, date="2024-01-31") as writer:
+            writer.write(self.records[0])
+        expected = gb_io.dump(self.records[0], format="embl", escape_locus=True, date="2024-01-31")
+        self.assertEqual(buffer.getvalue(), expected.getvalue())
+
+    def test_write_path(self):
+        with tempfile.TemporaryDirectory() as dst:
+            for name in ("records.gb", "records.gb.gz"):
+                path = os.path.join(dst, name)
+                with gb_io.Writer(path) as writer:
+                    for record in self.records:
+                        writer.write(record)
+                names = [record.name for record in gb_io.load(path)]
+                self.assertEqual(names, [record.name for record in self.records])
+
+    def test_flush(self):
+        buffer = io.BytesIO()
+        writer = gb_io.Writer(buffer)
+        writer.write(self.records[0])
+        writer.flush()
+        self.assertEqual(len(gb_io.load(io.BytesIO(buffer.getvalue()))), 1)
+        writer.close()
+
+    def test_close(self):
+        buffer = io.BytesIO()
+        writer = gb_io.Writer(buffer)
+        self.assertFalse(writer.closed)
+        writer.close()
+        self.assertTrue(writer.closed)
+        self.assertFalse(buffer.closed)
+        writer.close()
+        self.assertRaises(ValueError, writer.write, self.records[0])
+        self.assertRaises(ValueError, writer.flush)
+
+    def test_invalid_record(self):
+        buffer = io.BytesIO()
+        record = gb_io.Record(b"ATGC", features=[gb_io.Feature("CDS", gb_io.Range(0, 12))])
+        with gb_io.Writer(buffer) as writer:
+            writer.write(self.records[0])
+            with self.assertRaisesRegex(ValueError, "failed to write record 1"):
+                writer.write(record)
+            self.assertRaises(TypeError, writer.write, "record")
+
+    @unittest.skipUnless(hasattr(sys, "unraisablehook"), "requires sys.unraisablehook")
+    def test_drop_error(self):
+        class FailingIO(io.BytesIO):
+            def flush(self):
+                raise OSError("flush failed")
+
+        errors = []
+        hook = sys.unraisablehook
+        sys.unraisablehook = errors.append
+        try:
+            writer = gb_io.Writer(FailingIO())
+            del writer
+        finally:
+            sys.unraisablehook = hook
+        self.assertEqual(len(errors), 1)
+        self.assertIsInstance(errors[0].exc_value, OSError)
+
+    def test_invalid_arguments(self):
+        with self.assertRaisesRegex(TypeError, "expected path or file handle"):
+            gb_io.Writer(1)
+        self.assertRaises(ValueError, gb_io.Writer, io.BytesIO(), compresslevel=10)
+        self.assertRaises(ValueError, gb_io.Writer, io.BytesIO(), wrap_width=10)
+        self.assertRaises(ValueError, gb_io.Writer, io.BytesIO(), format="fasta")
@@ -0,0 +1,470 @@
+import io
+import os
+import unittest
+
+import gb_io
+
+DATA_FOLDER = os.path.realpath(os.path.join(__file__, os.path.pardir, "data"))
+
+
+class TestFeature(unittest.TestCase):
+
+    def _make_feature(self):
+        return gb_io.Feature(
+            "gene",
+            gb_io.Range(0, 100),
+            [
+                gb_io.Qualifier("gene", "rop"),
+                gb_io.Qualifier("gene_synonym", "rom; ECO57"),
+                gb_io.Qualifier("note", "some note"),
+                gb_io.Qualifier("gene_synonym", "repX/repY"),
+            ]
+        )
+
+    def test_gene_synonyms(self):
+        feature = self._make_feature()
+        self.assertEqual(feature.gene_synonyms, ["rom", "ECO57", "repX", "repY"])
+
+    def test_gene_synonyms_empty(self):
+        feature = gb_io.Feature("gene", gb_io.Range(0, 100))
+        self.assertEqual(feature.gene_synonyms, [])
+
+    def test_gene_synonyms_setter(self):
+        feature = self._make_feature()
+        qualifiers = feature.qualifiers
+        feature.gene_synonyms = ["a", "b"]
+        self.assertIs(feature.qualifiers, qualifiers)
+        self.assertEqual(
+            [(q.key, q.value) for q in feature.qualifiers],
+            [("gene", "rop"), ("gene_synonym", "a; b"), ("note", "some note")],
+        )
+        self.assertEqual(feature.gene_synonyms, ["a", "b"])
+        feature.gene_synonyms = []
+        self.assertEqual(
+            [(q.key, q.value) for q in feature.qualifiers],
+            [("gene", "rop"), ("note", "some note")],
+        )
+
+    def test_gene_synonyms_parsed(self):
+        record = gb_io.Record(b"", features=[self._make_feature()])
+        buffer = io.BytesIO()
+        gb_io.dump(record, buffer)
+        buffer.seek(0)
+        feature = gb_io.load(buffer)[0].features[0]
+        self.assertEqual(feature.gene_synonyms, ["rom", "ECO57", "repX", "repY"])
+        feature.gene_synonyms = ["rom"]
+        self.assertEqual(feature.gene_synonyms, ["rom"])
+        self.assertEqual(len(feature.qualifiers), 3)
+
+    def test_location_setter(self):
+        feature = self._make_feature()
+        location = gb_io.Complement(gb_io.Range(10, 20))
+        feature.location = location
+        self.assertIs(feature.location, location)
+
+    def test_strand(self):
+        self.assertEqual(gb_io.Feature("CDS", gb_io.Range(0, 9)).strand, "+")
+        self.assertEqual(gb_io.Feature("CDS", gb_io.Complement(gb_io.Range(0, 9))).strand, "-")
+        parts = [gb_io.Range(0, 3), gb_io.Complement(gb_io.Range(6, 9))]
+        self.assertIs(gb_io.Feature("CDS", gb_io.Join(parts)).strand, None)
+        external = gb_io.External("J00194.1", gb_io.Range(0, 9))
+        self.assertIs(gb_io.Feature("CDS", external).strand, None)
+
+    def test_strand_setter_reverse(self):
+        inner = gb_io.Range(0, 9)
+        feature = gb_io.Feature("CDS", inner)
+        feature.strand = "-"
+        self.assertEqual(feature.strand, "-")
+        self.assertEqual(feature.location, gb_io.Complement(gb_io.Range(0, 9)))
+        self.assertIs(feature.location.location, inner)
+        location = feature.location
+        feature.strand = "-"
+        self.assertIs(feature.location, location)
+
+    def test_strand_setter_direct(self):
+        inner = gb_io.Range(0, 9)
+        feature = gb_io.Feature("CDS", gb_io.Complement(inner))
+        feature.strand = "+"
+        self.assertEqual(feature.strand, "+")
+        self.assertIs(feature.location, inner)
+        feature.strand = "+"
+        self.assertIs(feature.location, inner)
+
+    def test_strand_setter_join(self):
+        parts = [gb_io.Complement(gb_io.Range(0, 3)), gb_io.Complement(gb_io.Range(6, 9))]
+        feature = gb_io.Feature("CDS", gb_io.Join(parts))
+        feature.strand = "+"
+        self.assertEqual(feature.location, gb_io.Complement(gb_io.Join(parts)))
+        self.assertEqual(feature.location.simplify().strand, "+")
+        feature.strand = "-"
+        self.assertEqual(feature.location, gb_io.Join(parts))
+
+    def test_strand_setter_parsed(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+        record = gb_io.load(path)[0]
+        features = record.features
+        cds = features[2]
+        sequence = cds.extract(record)
+        cds.strand = "-"
+        self.assertEqual(cds.strand, "-")
+        self.assertEqual(cds.extract(record), gb_io.Record(sequence).reverse_complement().sequence)
+        cds.strand = "+"
+        self.assertEqual(cds.extract(record), sequence)
+
+    def test_strand_setter_error(self):
+        parts = [gb_io.Range(0, 3), gb_io.Complement(gb_io.Range(6, 9))]
+        feature = gb_io.Feature("CDS", gb_io.Join(parts))
+        self.assertRaises(ValueError, setattr, feature, "strand", "-")
+        feature = gb_io.Feature("CDS", gb_io.Range(0, 9))
+        self.assertRaises(ValueError, setattr, feature, "strand", "x")
+        self.assertRaises(TypeError, setattr, feature, "strand", 1)
+        with self.assertRaises((AttributeError, TypeError)):
+            del feature.strand
+        self.assertEqual(feature.location, gb_io.Range(0, 9))
+
+    def test_location_setter_string(self):
+        feature = self._make_feature()
+        feature.location = "complement(11..20)"
+        self.assertEqual(feature.location, gb_io.Complement(gb_io.Range(10, 20)))
+        with self.assertRaises(ValueError):
+            feature.location = "(1..100"
+
+    def test_location_setter_sequence(self):
+        feature = self._make_feature()
+        exons = [gb_io.Range(0, 10), gb_io.Range(20, 30)]
+        feature.location = exons
+        self.assertIsInstance(feature.location, gb_io.Join)
+        self.assertEqual(feature.location.locations, exons)
+        self.assertIsNot(feature.location.locations, exons)
+        self.assertIs(feature.location.locations[0], exons[0])
+        feature.location = tuple(exons)
+        self.assertEqual(feature.location, gb_io.Join(exons))
+
+    def test_location_setter_dump(self):
+        feature = self._make_feature()
+        feature.location = [gb_io.Range(0, 10), gb_io.Range(20, 30)]
+        record = gb_io.Record(b"A" * 100, features=[feature])
+        buffer = io.BytesIO()
+        gb_io.dump(record, buffer)
+        self.assertIn(b"     gene            join(1..10,21..30)\n", buffer.getvalue())
+
+    def test_location_setter_error(self):
+        feature = self._make_feature()
+        location = feature.location
+        with self.assertRaises(ValueError):
+            feature.location = []
+        with self.assertRaises(TypeError):
+            feature.location = [gb_io.Range(0, 10), (20, 30)]
+        with self.assertRaises(TypeError):
+            feature.location = 1
+        self.assertIs(feature.location, location)
+
+    def test_len(self):
+        feature = gb_io.Feature("gene", gb_io.Complement(gb_io.Range(10, 20)))
+        self.assertEqual(len(feature), 10)
+        feature.location = "join(1..10,21..30)"
+        self.assertEqual(len(feature), 20)
+
+    def test_len_spliced(self):
+        record = gb_io.load(os.path.join(DATA_FOLDER, "biopython", "arab1.gb"))[0]
+        cds = next(f for f in record.features if f.kind == "CDS")
+        self.assertIsInstance(cds.location, gb_io.Join)
+        # spliced length, excluding the introns, not the span of 1871 bases
+        self.assertEqual(len(cds), 1401)
+        self.assertEqual(len(cds), len(cds.location))
+        self.assertEqual(len(cds), len(cds.extract(record)))
+
+    def test_set_translation_verify(self):
+        record = gb_io.load(os.path.join(DATA_FOLDER, "AY048670.1.gb"))[0]
+        for feature in record.features:
+            if feature.kind == "CDS":
+                expected = next(q.value for q in feature.qualifiers if q.key == "translation")
+                self.assertEqual(feature.set_translation(record), expected)
+
+    def test_set_translation(self):
+        record = gb_io.Record(b"TTGAAACCCTAAGG")
+        feature = gb_io.Feature("CDS", gb_io.Range(0, 12))
+        self.assertEqual(feature.set_translation(record), "MKP")
+        self.assertEqual([(q.key, q.value) for q in feature.qualifiers], [("translation", "MKP")])
+        self.assertEqual(feature.set_translation(record, table=2, verify=False), "LKP")
+
+    def test_set_translation_mismatch(self):
+        record = gb_io.Record(b"ATGAAACCCTAA")
+        feature = gb_io.Feature("CDS", gb_io.Range(0, 12), [gb_io.Qualifier("translation", "MKK")])
+        self.assertRaises(ValueError, feature.set_translation, record)
+        self.assertEqual(feature.qualifiers[0].value, "MKK")
+        self.assertEqual(feature.set_translation(record, verify=False), "MKP")
+        self.assertEqual(feature.qualifiers[0].value, "MKP")
+
+    def test_set_translation_partial(self):
+        record = gb_io.Record(b"ATTGAAACCCTAA")
+        feature = gb_io.Feature(
+            "CDS",
+            gb_io.Range(0, 13, before=True),
+            [gb_io.Qualifier("codon_start", "2")],
+        )
+        self.assertEqual(feature.set_translation(record), "LKP")
+
+    def test_set_translation_complement(self):
+        record = gb_io.Record(b"TTAGGGTTTCAT")
+        feature = gb_io.Feature("CDS", gb_io.Complement(gb_io.Range(0, 12)))
+        self.assertEqual(feature.set_translation(record), "MKP")
+
+    def test_set_translation_error(self):
+        record = gb_io.Record(b"ATGAAACCCTAA")
+        feature = gb_io.Feature("CDS", gb_io.Range(0, 12))
+        self.assertRaises(ValueError, feature.set_translation, record, table=7)
+        feature = gb_io.Feature("CDS", gb_io.Range(0, 20))
+        self.assertRaises(ValueError, feature.set_translation, record)
+
+    def test_extract(self):
+        record = gb_io.Record(b"ATGAAACCCTAA")
+        feature = gb_io.Feature("CDS", gb_io.Complement(gb_io.Range(0, 6)))
+        self.assertEqual(feature.extract(record), b"TTTCAT")
+        feature = gb_io.Feature("CDS", gb_io.External("J00194.1", gb_io.Range(0, 6)))
+        self.assertRaises(ValueError, feature.extract, record)
+        feature = gb_io.Feature("CDS", gb_io.Range(6, 15))
+        self.assertRaises(IndexError, feature.extract, record)
+
+    def test_extract_circular(self):
+        record = gb_io.Record(b"ATGAAACCCTAA", circular=True)
+        location = gb_io.Complement(gb_io.Range(9, 15))
+        feature = gb_io.Feature("CDS", location)
+        self.assertEqual(feature.extract(record), b"CATTTA")
+        self.assertEqual(feature.extract(record), record.extract(location))
+        joined = gb_io.Join([gb_io.Range(9, 12), gb_io.Range(0, 3)])
+        self.assertEqual(gb_io.Feature("CDS", joined).extract(record), b"TAAATG")
+
+    def test_exons(self):
+        feature = gb_io.Feature("gene", gb_io.Range(5, 10))
+        self.assertEqual(feature.exons(), [(5, 10, "+")])
+        parts = [gb_io.Range(0, 10), gb_io.Range(20, 30), gb_io.Range(40, 50)]
+        feature = gb_io.Feature("mRNA", gb_io.Join(parts))
+        self.assertEqual(feature.exons(), [(0, 10, "+"), (20, 30, "+"), (40, 50, "+")])
+        feature = gb_io.Feature("mRNA", gb_io.Complement(gb_io.Join(parts)))
+        self.assertEqual(feature.exons(), [(0, 10, "-"), (20, 30, "-"), (40, 50, "-")])
+        reversed_parts = [gb_io.Complement(part) for part in reversed(parts)]
+        feature = gb_io.Feature("mRNA", gb_io.Join(reversed_parts))
+        self.assertEqual(feature.exons(), [(0, 10, "-"), (20, 30, "-"), (40, 50, "-")])
+        feature = gb_io.Feature(
+            "mRNA",
+            gb_io.Join([
+                gb_io.Range(0, 10),
+                gb_io.External("J00194.1", gb_io.Range(0, 6)),
+                gb_io.Complement(gb_io.Range(20, 30)),
+            ]),
+        )
+        self.assertEqual(feature.exons(), [(0, 10, "+"), (20, 30, "-")])
+
+    def test_exons_origin(self):
+        parts = [gb_io.Range(90, 100), gb_io.Range(0, 10)]
+        feature = gb_io.Feature("gene", gb_io.Join(parts))
+        self.assertEqual(feature.exons(), [(90, 100, "+"), (0, 10, "+")])
+        feature = gb_io.Feature("gene", gb_io.Complement(gb_io.Join(parts)))
+        self.assertEqual(feature.exons(), [(90, 100, "-"), (0, 10, "-")])
+        reversed_parts = [gb_io.Complement(part) for part in reversed(parts)]
+        feature = gb_io.Feature("gene", gb_io.Join(reversed_parts))
+        self.assertEqual(feature.exons(), [(90, 100, "-"), (0, 10, "-")])
+
+    def test_ribosomal_slippage(self):
+        # -1 frameshift: the second part starts on the last base of the first
+        record = gb_io.Record(b"ATGAAACCCTTTGGGTAA", molecule_type="DNA")
+        parts = [gb_io.Range(0, 12), gb_io.Range(11, 18)]
+        feature = gb_io.Feature(
+            "CDS",
+            gb_io.Join(parts),
+            [
+                gb_io.Qualifier("ribosomal_slippage"),
+                gb_io.Qualifier("exception", "ribosomal slippage"),
+            ],
+        )
+        record.features.append(feature)
+        spliced = feature.extract(record)
+        self.assertEqual(len(spliced), sum(part.end - part.start for part in parts))
+        self.assertEqual(spliced, b"ATGAAACCCTTTTGGGTAA")
+        self.assertEqual(feature.set_translation(record, table=1), "MKPFWV")
+
+        buffer = io.BytesIO()
+        gb_io.dump(record, buffer)
+        buffer.seek(0)
+        loaded = gb_io.load(buffer)[0].features[0]
+        self.assertEqual(loaded.location, gb_io.Join(parts))
+        qualifiers = [(q.key, q.value) for q in loaded.qualifiers]
+        self.assertEqual(
+            qualifiers,
+            [
+                ("ribosomal_slippage", None),
+                ("exception", "ribosomal slippage"),
+                ("translation", "MKPFWV"),
+            ],
+        )
+
+
+class TestQualifiersDict(unittest.TestCase):
+
+    def test_qualifiers_dict(self):
+        feature = gb_io.Feature(
+            "CDS",
+            gb_io.Range(0, 10),
+            [
+                gb_io.Qualifier("gene", "rop"),
+                gb_io.Qualifier("pseudo"),
+                gb_io.Qualifier("note", "first"),
+                gb_io.Qualifier("gene", "rop2"),
+            ],
+        )
+        qualifiers = feature.qualifiers_dict()
+        self.assertEqual(qualifiers, {"gene": ["rop", "rop2"], "pseudo": [None], "note": ["first"]})
+        self.assertEqual(list(qualifiers), ["gene", "pseudo", "note"])
+        qualifiers["gene"].append("rop3")
+        self.assertEqual(len(feature.qualifiers), 4)
+
+    def test_empty(self):
+        feature = gb_io.Feature("gene", gb_io.Range(0, 10))
+        self.assertEqual(feature.qualifiers_dict(), {})
+
+    def test_parsed(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "NC_005816.gb")
+        record = gb_io.load(path)[0]
+        cds = next(feature for feature in record.features if feature.kind == "CDS")
+        qualifiers = cds.qualifiers_dict()
+        self.assertEqual(qualifiers["locus_tag"], ["YP_pPCP01"])
+        self.assertEqual(
+            sum(len(values) for values in qualifiers.values()),
+            len(cds.qualifiers),
+        )
+
+
+class TestDedupeQualifiers(unittest.TestCase):
+
+    def setUp(self):
+        self.feature = gb_io.Feature(
+            "CDS",
+            gb_io.Range(0, 12),
+            [
+                gb_io.Qualifier("gene", "rop"),
+                gb_io.Qualifier("db_xref", "GeneID:1"),
+                gb_io.Qualifier("gene", "rop"),
+                gb_io.Qualifier("db_xref", "GeneID:2"),
+                gb_io.Qualifier("ribosomal_slippage"),
+                gb_io.Qualifier("ribosomal_slippage"),
+                gb_io.Qualifier("gene", "rom"),
+            ],
+        )
+
+    def test_dedupe(self):
+        qualifiers = self.feature.qualifiers
+        self.assertEqual(self.feature.dedupe_qualifiers(), 2)
+        self.assertEqual(
+            [(q.key, q.value) for q in self.feature.qualifiers],
+            [
+                ("gene", "rop"),
+                ("db_xref", "GeneID:1"),
+                ("db_xref", "GeneID:2"),
+                ("ribosomal_slippage", None),
+                ("gene", "rom"),
+            ],
+        )
+        # the list is modified in place
+        self.assertEqual(len(qualifiers), 5)
+        self.assertEqual(self.feature.dedupe_qualifiers(), 0)
+
+    def test_keep_repeats(self):
+        self.assertEqual(self.feature.dedupe_qualifiers(keep_repeats={"ribosomal_slippage"}), 1)
+        keys = [q.key for q in self.feature.qualifiers]
+        self.assertEqual(keys.count("ribosomal_slippage"), 2)
+        self.assertEqual(keys.count("gene"), 2)
+
+    def test_keep_repeats_str(self):
+        self.assertRaises(TypeError, self.feature.dedupe_qualifiers, keep_repeats="note")
+        self.assertEqual(len(self.feature.qualifiers), 7)
+
+    def test_parsed(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "arab1.gb")
+        record = gb_io.load(path)[0]
+        feature = record.features[1]
+        feature.qualifiers.append(gb_io.Qualifier(feature.qualifiers[0].key, feature.qualifiers[0].value))
+        self.assertEqual(feature.dedupe_qualifiers(), 1)
+        self.assertEqual(record.features[1], gb_io.load(path)[0].features[1])
+
+
+class TestFeatureEq(unittest.TestCase):
+
+    def test_eq(self):
+        f1 = gb_io.Feature("gene", gb_io.Range(0, 10), [gb_io.Qualifier("gene", "rop")])
+        f2 = gb_io.Feature("gene", gb_io.Range(0, 10), [gb_io.Qualifier("gene", "rop")])
+        self.assertEqual(f1, f1)
+        self.assertEqual(f1, f2)
+        f2.qualifiers[0].value = "rom"
+        self.assertNotEqual(f1, f2)
+        self.assertNotEqual(f1, gb_io.Feature("CDS", gb_io.Range(0, 10), [gb_io.Qualifier("gene", "rop")]))
+        self.assertNotEqual(f1, gb_io.Feature("gene", gb_io.Range(0, 11), [gb_io.Qualifier("gene", "rop")]))
+        self.assertNotEqual(f1, "gene")
+        self.assertRaises(TypeError, hash, f1)
+
+    def test_eq_location(self):
+        join1 = gb_io.Join([gb_io.Range(0, 10), gb_io.Range(20, 30)])
+        join2 = gb_io.Join((gb_io.Range(0, 10), gb_io.Range(20, 30)))
+        self.assertEqual(gb_io.Feature("CDS", join1), gb_io.Feature("CDS", join2))
+        feature = gb_io.Feature("CDS", gb_io.Range(0, 1))
+        feature.location = "join(1..10,21..30)"
+        self.assertEqual(gb_io.Feature("CDS", join1), feature)
+
+    def test_eq_qualifiers_order(self):
+        q1 = gb_io.Qualifier("gene", "rop")
+        q2 = gb_io.Qualifier("note", "some note")
+        f1 = gb_io.Feature("gene", gb_io.Range(0, 10), [q1, q2])
+        f2 = gb_io.Feature("gene", gb_io.Range(0, 10), [q2, q1])
+        self.assertNotEqual(f1, f2)
+
+    def test_eq_parsed(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "arab1.gb")
+        features = gb_io.load(path)[0].features
+        others = gb_io.load(path)[0].features
+        self.assertEqual(features, others)
+        self.assertNotEqual(features[0], features[1])
+
+
+class TestQualifier(unittest.TestCase):
+
+    def test_eq(self):
+        self.assertEqual(gb_io.Qualifier("gene", "rop"), gb_io.Qualifier("gene", "rop"))
+        self.assertEqual(gb_io.Qualifier("pseudo"), gb_io.Qualifier("pseudo", None))
+        self.assertNotEqual(gb_io.Qualifier("gene", "rop"), gb_io.Qualifier("gene", "rom"))
+        self.assertNotEqual(gb_io.Qualifier("gene", "rop"), gb_io.Qualifier("note", "rop"))
+        self.assertNotEqual(gb_io.Qualifier("pseudo"), gb_io.Qualifier("pseudo", ""))
+        self.assertNotEqual(gb_io.Qualifier("gene", "rop"), ("gene", "rop"))
+        self.assertRaises(TypeError, lambda: gb_io.Qualifier("gene") < gb_io.Qualifier("note"))
+
+    def test_eq_quoted(self):
+        self.assertNotEqual(gb_io.Qualifier("gene", "rop"), gb_io.Qualifier("gene", "rop", quoted=False))
+        self.assertEqual(gb_io.Qualifier("gene", "rop"), gb_io.Qualifier("gene", "rop", quoted=True))
+        self.assertEqual(gb_io.Qualifier("codon_start", "1"), gb_io.Qualifier("codon_start", "1", quoted=False))
+        self.assertEqual(gb_io.Qualifier("pseudo"), gb_io.Qualifier("pseudo", quoted=True))
+
+    def test_quoted(self):
+        qualifier = gb_io.Qualifier("gene", "rop")
+        self.assertIs(qualifier.quoted, None)
+        qualifier.quoted = False
+        self.assertIs(qualifier.quoted, False)
+        self.assertEqual(repr(qualifier), "Qualifier('gene', 'rop', quoted=False)")
+        qualifier.quoted = None
+        self.assertEqual(repr(qualifier), "Qualifier('gene', 'rop')")
+        self.assertEqual(repr(gb_io.Qualifier("pseudo", quoted=True)), "Qualifier('pseudo', quoted=True)")
+
+    def test_hash(self):
+        self.assertEqual(hash(gb_io.Qualifier("gene", "rop")), hash(gb_io.Qualifier("gene", "rop")))
+        qualifiers = [
+            gb_io.Qualifier("gene", "rop"),
+            gb_io.Qualifier("note", "some note"),
+            gb_io.Qualifier("gene", "rop"),
+            gb_io.Qualifier("pseudo"),
+        ]
+        self.assertEqual(len(set(qualifiers)), 3)
+
+    def test_parsed(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "arab1.gb")
+        feature = gb_io.load(path)[0].features[1]
+        qualifiers = [gb_io.Qualifier(q.key, q.value) for q in feature.qualifiers]
+        self.assertEqual(feature.qualifiers, qualifiers)
+        self.assertEqual(set(feature.qualifiers), set(qualifiers))
@@ -0,0 +1,31 @@
+import os
+import unittest
+
+import gb_io
+
+DATA_FOLDER = os.path.realpath(os.path.join(__file__, os.path.pardir, "data"))
+
+
+class TestFeatureKeys(unittest.TestCase):
+
+    def test_constants(self):
+        self.assertEqual(gb_io.FeatureKeys.CDS, "CDS")
+        self.assertEqual(gb_io.FeatureKeys.MISC_RNA, "misc_RNA")
+        self.assertEqual(gb_io.FeatureKeys.D_LOOP, "D-loop")
+        self.assertEqual(gb_io.FeatureKeys.FIVE_PRIME_UTR, "5'UTR")
+        self.assertEqual(gb_io.FeatureKeys.THREE_PRIME_UTR, "3'UTR")
+        self.assertIsInstance(gb_io.FeatureKeys.SOURCE, str)
+
+    def test_record_kinds(self):
+        record = gb_io.load(os.path.join(DATA_FOLDER, "AY048670.1.gb"))[0]
+        self.assertTrue(record.has_feature(gb_io.FeatureKeys.CDS))
+        self.assertEqual(record.features[0].kind, gb_io.FeatureKeys.SOURCE)
+
+
+class TestQualifierKeys(unittest.TestCase):
+
+    def test_constants(self):
+        self.assertEqual(gb_io.QualifierKeys.GENE, "gene")
+        self.assertEqual(gb_io.QualifierKeys.EC_NUMBER, "EC_number")
+        self.assertEqual(gb_io.QualifierKeys.DB_XREF, "db_xref")
+        self.assertEqual(gb_io.QualifierKeys.TRANSL_TABLE, "transl_table")
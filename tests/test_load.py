@@ -1,5 +1,12 @@
+import copy
+import gzip
+import io
 import unittest
 import os
+import pickle
+import sys
+import tempfile
+import warnings
 
 import gb_io
 
@@ -22,6 +29,69 @@ class TestLoad(unittest.TestCase):
         path = os.path.join(DATA_FOLDER, "AY048670.1.gb")
         records = gb_io.load(path)
 
+    def test_load_invalid_date(self):
+        data = io.BytesIO(
+            b"LOCUS       TEST                       4 bp    DNA     linear   UNK 31-FEB-2020\n"
+            b"ORIGIN\n"
+            b"        1 atgc\n"
+            b"//\n"
+        )
+        with warnings.catch_warnings(record=True) as caught:
+            warnings.simplefilter("always")
+            records = gb_io.load(data)
+        self.assertEqual(len(caught), 1)
+        self.assertTrue(issubclass(caught[0].category, UserWarning))
+        self.assertEqual(len(records), 1)
+        self.assertEqual(records[0].name, "TEST")
+        self.assertIs(records[0].date, None)
+        self.assertEqual(records[0].date_string, "31-FEB-2020")
+        self.assertEqual(records[0].sequence, b"atgc")
+
+
+class TestLoads(unittest.TestCase):
+
+    path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+
+    def assertRecordsEqual(self, records, expected):
+        self.assertEqual(len(records), len(expected))
+        for record, other in zip(records, expected):
+            self.assertEqual(record.name, other.name)
+            self.assertEqual(record.sequence, other.sequence)
+            self.assertEqual(len(record.features), len(other.features))
+
+    def test_loads_bytes(self):
+        with open(self.path, "rb") as f:
+            records = gb_io.loads(f.read())
+        self.assertRecordsEqual(records, gb_io.load(self.path))
+
+    def test_loads_str(self):
+        with open(self.path) as f:
+            records = gb_io.loads(f.read())
+        self.assertRecordsEqual(records, gb_io.load(self.path))
+
+    def test_loads_gzip(self):
+        with open(self.path, "rb") as f:
+            data = gzip.compress(f.read())
+        self.assertRecordsEqual(gb_io.loads(data), gb_io.load(self.path))
+
+    def test_loads_normalize_kinds(self):
+        with open(self.path, "rb") as f:
+            records = gb_io.loads(f.read(), normalize_kinds={"CDS": "cds"})
+        kinds = {feature.kind for record in records for feature in record.features}
+        self.assertIn("cds", kinds)
+        self.assertNotIn("CDS", kinds)
+
+    def test_loads_empty(self):
+        self.assertEqual(gb_io.loads(b""), [])
+        self.assertEqual(gb_io.loads(""), [])
+
+    def test_loads_error(self):
+        self.assertRaises(TypeError, gb_io.loads, 1)
+        self.assertRaises(TypeError, gb_io.loads, bytearray(b"LOCUS"))
+        with open(self.path, "rb") as f:
+            data = f.read().replace(b"FEATURES ", b"FEATURES\x00", 1)
+        self.assertRaises(ValueError, gb_io.loads, data)
+
 
 class TestLoadError(unittest.TestCase):
 
@@ -62,3 +132,1110 @@ class TestLoadError(unittest.TestCase):
 
         r = Reader()
         self.assertRaises(MyError, gb_io.load, r)
+
+
+class TestLoadThreads(unittest.TestCase):
+
+    def _test_same_records(self, path):
+        expected = gb_io.load(path)
+        for threads in (0, 2, 4):
+            records = gb_io.load(path, threads=threads)
+            self.assertEqual(records, expected)
+
+    def test_cor6_6(self):
+        self._test_same_records(os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb"))
+
+    def test_gbvrl1_start(self):
+        self._test_same_records(os.path.join(DATA_FOLDER, "biopython", "gbvrl1_start.seq"))
+
+    def test_compressed(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+        with open(path, "rb") as f:
+            data = gzip.compress(f.read())
+        records = gb_io.load(io.BytesIO(data), threads=2)
+        self.assertEqual(records, gb_io.load(path))
+
+    def test_order(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+        with open(path, "rb") as f:
+            data = f.read()
+        names = [record.name for record in gb_io.load(path)]
+        records = gb_io.load(io.BytesIO(data * 20), threads=4)
+        self.assertEqual([record.name for record in records], names * 20)
+
+    def test_binary_file(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+        with open(path, "rb") as f:
+            records = gb_io.load(f, threads=2)
+        self.assertEqual(records, gb_io.load(path))
+
+    def test_options(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "NC_005816.gb")
+        options = dict(normalize_kinds="upper", drop_qualifiers={"translation"})
+        expected = gb_io.load(path, **options)
+        self.assertEqual(gb_io.load(path, threads=2, **options), expected)
+
+    def test_syntax_error(self):
+        data = io.BytesIO(b"LOCUS       TEST\nFOO\n//\n")
+        self.assertRaises(ValueError, gb_io.load, data, threads=2)
+
+    def test_error_propagation(self):
+
+        class MyError(ValueError):
+            pass
+
+        class Reader(object):
+            def __init__(self):
+                self.called = 0
+            def read(self, n):
+                if self.called == 0:
+                    self.called += 1
+                    return b"LOCUS"
+                raise MyError("my error")
+
+        self.assertRaises(MyError, gb_io.load, Reader(), threads=2)
+
+    def test_invalid_threads(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+        self.assertRaises(OverflowError, gb_io.load, path, threads=-1)
+
+
+class TestOnError(unittest.TestCase):
+
+    @classmethod
+    def setUpClass(cls):
+        path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+        cls.texts = [text for text, _ in gb_io.iter(path, with_text=True)]
+        cls.names = [record.name for record in gb_io.load(path)]
+        # break the third record, leaving the others unchanged
+        texts = list(cls.texts)
+        texts[2] = texts[2].replace(b"ORIGIN", b"ORIGIX")
+        cls.data = b"".join(texts)
+        cls.offset = len(texts[0]) + len(texts[1])
+
+    def test_load_raise(self):
+        self.assertRaises(ValueError, gb_io.load, io.BytesIO(self.data))
+        self.assertRaises(ValueError, gb_io.load, io.BytesIO(self.data), on_error="raise")
+
+    def test_load_skip(self):
+        expected = self.names[:2] + self.names[3:]
+        for threads in (1, 2):
+            with warnings.catch_warnings():
+                warnings.simplefilter("error")
+                records = gb_io.load(io.BytesIO(self.data), threads=threads, on_error="skip")
+            self.assertEqual([record.name for record in records], expected)
+
+    def test_load_warn(self):
+        with warnings.catch_warnings(record=True) as caught:
+            warnings.simplefilter("always")
+            records = gb_io.load(io.BytesIO(self.data), on_error="warn")
+        self.assertEqual(len(records), 5)
+        self.assertEqual(len(caught), 1)
+        self.assertTrue(issubclass(caught[0].category, UserWarning))
+        self.assertIn("offset {}".format(self.offset), str(caught[0].message))
+
+    def test_load_warn_error(self):
+        with warnings.catch_warnings():
+            warnings.simplefilter("error")
+            self.assertRaises(UserWarning, gb_io.load, io.BytesIO(self.data), on_error="warn")
+
+    def test_missing_terminator(self):
+        texts = list(self.texts)
+        texts[2] = texts[2].replace(b"//\n", b"")
+        data = b"".join(texts)
+        self.assertRaises(ValueError, gb_io.load, io.BytesIO(data))
+        records = gb_io.load(io.BytesIO(data), on_error="skip")
+        self.assertEqual([record.name for record in records], self.names)
+
+    def test_iter_raise(self):
+        reader = gb_io.iter(io.BytesIO(self.data))
+        self.assertEqual(next(reader).name, self.names[0])
+        self.assertEqual(next(reader).name, self.names[1])
+        self.assertRaises(ValueError, next, reader)
+
+    def test_iter_skip(self):
+        reader = gb_io.iter(io.BytesIO(self.data), on_error="skip")
+        self.assertEqual([record.name for record in reader], self.names[:2] + self.names[3:])
+
+    def test_iter_warn(self):
+        reader = gb_io.iter(io.BytesIO(self.data), on_error="warn")
+        self.assertEqual(next(reader).name, self.names[0])
+        self.assertEqual(next(reader).name, self.names[1])
+        with warnings.catch_warnings(record=True) as caught:
+            warnings.simplefilter("always")
+            self.assertEqual(next(reader).name, self.names[3])
+        self.assertEqual(len(caught), 1)
+        self.assertIn("offset {}".format(self.offset), str(caught[0].message))
+        self.assertEqual(reader.tell(), self.offset + len(self.texts[2]))
+
+    def test_iter_with_text(self):
+        reader = gb_io.iter(io.BytesIO(self.data), with_text=True, on_error="skip")
+        texts = [text for text, _ in reader]
+        self.assertEqual(texts, self.texts[:2] + self.texts[3:])
+
+    def test_iter_prefetch(self):
+        self.assertRaises(ValueError, gb_io.iter, io.BytesIO(self.data), prefetch=2, on_error="skip")
+
+    def test_invalid_policy(self):
+        self.assertRaises(ValueError, gb_io.load, io.BytesIO(self.data), on_error="ignore")
+        self.assertRaises(ValueError, gb_io.iter, io.BytesIO(self.data), on_error="ignore")
+
+
+class TestForEach(unittest.TestCase):
+
+    def test_for_each(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+        names = []
+        count = gb_io.for_each(path, lambda record: names.append(record.name))
+        self.assertEqual(count, 6)
+        self.assertEqual(names, [record.name for record in gb_io.load(path)])
+
+    def test_for_each_stop(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+        names = []
+        def callback(record):
+            names.append(record.name)
+            return len(names) < 2
+        self.assertEqual(gb_io.for_each(path, callback), 2)
+        self.assertEqual(len(names), 2)
+
+    def test_for_each_error(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+        def callback(record):
+            raise ValueError(record.name)
+        self.assertRaises(ValueError, gb_io.for_each, path, callback)
+
+
+class TestIterWithText(unittest.TestCase):
+
+    def test_with_text(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+        with open(path, "rb") as f:
+            data = f.read()
+        items = list(gb_io.iter(path, with_text=True))
+        self.assertEqual(len(items), 6)
+        self.assertEqual(b"".join(text for text, _ in items), data)
+        for text, record in items:
+            self.assertIsInstance(text, bytes)
+            self.assertTrue(text.startswith(b"LOCUS"))
+            self.assertTrue(text.rstrip().endswith(b"//"))
+            self.assertIn(record.name.encode(), text.splitlines()[0])
+
+    def test_with_text_handle(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+        with open(path, "rb") as f:
+            items = list(gb_io.iter(f, with_text=True))
+        names = [record.name for record in gb_io.load(path)]
+        self.assertEqual([record.name for _, record in items], names)
+
+    def test_with_text_skips_header(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+        with open(path, "rb") as f:
+            data = f.read()
+        handle = io.BytesIO(b"GENBANK HEADER\n\n" + data)
+        text, record = next(gb_io.iter(handle, with_text=True))
+        self.assertTrue(text.startswith(b"LOCUS"))
+        self.assertTrue(data.startswith(text))
+
+    def test_without_text(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+        record = next(gb_io.iter(path))
+        self.assertIsInstance(record, gb_io.Record)
+
+
+class TestIterReuse(unittest.TestCase):
+
+    path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+
+    def test_reuse(self):
+        expected = gb_io.load(self.path)
+        first = None
+        count = 0
+        for record, other in zip(gb_io.iter(self.path, reuse=True), expected):
+            if first is None:
+                first = record
+            self.assertIs(record, first)
+            self.assertEqual(record.name, other.name)
+            self.assertEqual(record.sequence, other.sequence)
+            self.assertEqual(len(record.features), len(other.features))
+            count += 1
+        self.assertEqual(count, len(expected))
+
+    def test_reuse_attributes(self):
+        reader = gb_io.iter(self.path, reuse=True)
+        record = next(reader)
+        name = record.name
+        features = record.features
+        n = len(features)
+        next(reader)
+        self.assertNotEqual(record.name, name)
+        self.assertIsNot(record.features, features)
+        self.assertEqual(len(features), n)
+
+    def test_reuse_with_text(self):
+        names = [record.name for record in gb_io.load(self.path)]
+        records = set()
+        for text, record in gb_io.iter(self.path, with_text=True, reuse=True):
+            self.assertIn(record.name.encode(), text.splitlines()[0])
+            records.add(id(record))
+            names.remove(record.name)
+        self.assertEqual(names, [])
+        self.assertEqual(len(records), 1)
+
+    def test_reuse_list(self):
+        self.assertRaises(RuntimeError, list, gb_io.iter(self.path, reuse=True))
+        self.assertEqual(len(list(gb_io.iter(self.path))), 6)
+
+    def test_reuse_deepcopy(self):
+        kept = [copy.deepcopy(r) for r in gb_io.iter(self.path, reuse=True)]
+        names = [record.name for record in gb_io.load(self.path)]
+        self.assertEqual([record.name for record in kept], names)
+
+
+class TestIterPrefetch(unittest.TestCase):
+
+    path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+
+    def assertRecordsEqual(self, records, expected):
+        self.assertEqual(len(records), len(expected))
+        for record, other in zip(records, expected):
+            self.assertEqual(record.name, other.name)
+            self.assertEqual(record.sequence, other.sequence)
+            self.assertEqual(len(record.features), len(other.features))
+
+    def test_path(self):
+        expected = gb_io.load(self.path)
+        for prefetch in (1, 2, 10):
+            records = list(gb_io.iter(self.path, prefetch=prefetch))
+            self.assertRecordsEqual(records, expected)
+
+    def test_handle(self):
+        expected = gb_io.load(self.path)
+        with open(self.path, "rb") as f:
+            records = list(gb_io.iter(f, prefetch=2))
+        self.assertRecordsEqual(records, expected)
+
+    def test_extras(self):
+        wgs = [["AAAA02000001-AAAA02050231"], [], ["ABCD01000001-ABCD01000042"]]
+        data = gb_io.dumps([gb_io.Record(b"ATGC", wgs=lines) for lines in wgs])
+        records = list(gb_io.iter(io.BytesIO(data.encode()), prefetch=2))
+        self.assertEqual([r.wgs for r in records], wgs)
+
+    def test_reuse(self):
+        names = [record.name for record in gb_io.load(self.path)]
+        reader = gb_io.iter(self.path, reuse=True, prefetch=2)
+        records = set()
+        for record in reader:
+            records.add(id(record))
+            names.remove(record.name)
+        self.assertEqual(names, [])
+        self.assertEqual(len(records), 1)
+
+    def test_partial(self):
+        reader = gb_io.iter(self.path, prefetch=1)
+        self.assertEqual(next(reader).name, gb_io.load(self.path)[0].name)
+        del reader
+
+    @unittest.skipUnless(os.path.exists("/proc/self/task"), "requires procfs")
+    def test_join_worker(self):
+        def workers():
+            count = 0
+            for task in os.listdir("/proc/self/task"):
+                with open(os.path.join("/proc/self/task", task, "comm")) as f:
+                    count += f.read().strip() == "gb-io-prefetch"
+            return count
+
+        reader = gb_io.iter(self.path, prefetch=1)
+        next(reader)
+        self.assertEqual(workers(), 1)
+        del reader
+        self.assertEqual(workers(), 0)
+
+    def test_empty(self):
+        self.assertEqual(list(gb_io.iter(io.BytesIO(b""), prefetch=4)), [])
+
+    def test_with_text(self):
+        self.assertRaises(ValueError, gb_io.iter, self.path, with_text=True, prefetch=1)
+        self.assertEqual(len(list(gb_io.iter(self.path, with_text=True, prefetch=0))), 6)
+
+    def test_error(self):
+        with open(self.path, "rb") as f:
+            data = f.read()
+        data = data.replace(b"ORIGIN", b"ORIGIX", 2).replace(b"ORIGIX", b"ORIGIN", 1)
+        reader = gb_io.iter(io.BytesIO(data), prefetch=4)
+        self.assertEqual(next(reader).name, "ATCOR66M")
+        self.assertRaises(ValueError, next, reader)
+
+    def test_handle_error(self):
+        with open(self.path, "rb") as f:
+            data = f.read(100)
+
+        class BrokenReader(io.RawIOBase):
+            def __init__(self):
+                self.data = data
+            def readable(self):
+                return True
+            def readinto(self, b):
+                if not self.data:
+                    raise ValueError("broken")
+                n = min(len(b), len(self.data))
+                b[:n] = self.data[:n]
+                self.data = self.data[n:]
+                return n
+
+        reader = gb_io.iter(BrokenReader(), prefetch=2)
+        with self.assertRaises(ValueError) as ctx:
+            next(reader)
+        self.assertEqual(str(ctx.exception), "broken")
+
+    def test_handle_os_error(self):
+        with open(self.path, "rb") as f:
+            data = f.read(100)
+
+        class BrokenReader(io.RawIOBase):
+            def __init__(self):
+                self.data = data
+            def readable(self):
+                return True
+            def readinto(self, b):
+                if not self.data:
+                    raise OSError(5, "broken")
+                n = min(len(b), len(self.data))
+                b[:n] = self.data[:n]
+                self.data = self.data[n:]
+                return n
+
+        for prefetch in (0, 2):
+            reader = gb_io.iter(BrokenReader(), prefetch=prefetch)
+            with self.assertRaises(OSError) as ctx:
+                next(reader)
+            self.assertEqual(ctx.exception.errno, 5)
+
+
+class TestIterTell(unittest.TestCase):
+
+    path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+
+    @classmethod
+    def setUpClass(cls):
+        with open(cls.path, "rb") as f:
+            cls.data = f.read()
+        cls.offsets = []
+        offset = 0
+        for line in cls.data.splitlines(keepends=True):
+            if line.startswith(b"LOCUS"):
+                cls.offsets.append(offset)
+            offset += len(line)
+
+    def test_path(self):
+        reader = gb_io.iter(self.path)
+        self.assertIs(reader.tell(), None)
+        offsets = [reader.tell() for record in reader]
+        self.assertEqual(offsets, self.offsets)
+        self.assertEqual(reader.tell(), self.offsets[-1])
+
+    def test_seek(self):
+        names = [record.name for record in gb_io.load(self.path)]
+        index = {record.name: offset for record, offset in zip(gb_io.iter(self.path), self.offsets)}
+        with open(self.path, "rb") as f:
+            for name in reversed(names):
+                f.seek(index[name])
+                reader = gb_io.iter(f)
+                self.assertEqual(next(reader).name, name)
+                self.assertEqual(reader.tell(), index[name])
+
+    def test_handle_position(self):
+        with open(self.path, "rb") as f:
+            f.seek(self.offsets[1] - 10)
+            reader = gb_io.iter(f)
+            offsets = [reader.tell() for record in reader]
+        self.assertEqual(offsets, self.offsets[1:])
+
+    def test_text_handle(self):
+        with open(self.path, "r") as f:
+            reader = gb_io.iter(f)
+            offsets = [reader.tell() for record in reader]
+        self.assertEqual(offsets, self.offsets)
+
+    def test_with_text(self):
+        reader = gb_io.iter(self.path, with_text=True)
+        for (text, record), offset in zip(reader, self.offsets):
+            self.assertEqual(reader.tell(), offset)
+            self.assertEqual(self.data[offset:offset + len(text)], text)
+
+    def test_prefetch(self):
+        reader = gb_io.iter(self.path, prefetch=3)
+        offsets = [reader.tell() for record in reader]
+        self.assertEqual(offsets, self.offsets)
+
+    def test_compressed(self):
+        reader = gb_io.iter(io.BytesIO(gzip.compress(self.data)))
+        offsets = [reader.tell() for record in reader]
+        self.assertEqual(offsets, self.offsets)
+
+    def test_compressed_handle_position(self):
+        handle = io.BytesIO(b"header" + gzip.compress(self.data))
+        handle.seek(6)
+        reader = gb_io.iter(handle)
+        offsets = [reader.tell() for record in reader]
+        self.assertEqual(offsets, self.offsets)
+
+
+class TestIndex(unittest.TestCase):
+
+    path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+
+    @classmethod
+    def setUpClass(cls):
+        cls.records = gb_io.load(cls.path)
+
+    def test_keys(self):
+        index = gb_io.index(self.path)
+        accessions = [record.accession.split()[0] for record in self.records]
+        self.assertEqual(index.keys(), accessions)
+        self.assertEqual(list(index), accessions)
+        self.assertEqual(len(index), len(self.records))
+
+    def test_keys_name(self):
+        index = gb_io.index(self.path, key="name")
+        self.assertEqual(index.keys(), [record.name for record in self.records])
+
+    def test_getitem(self):
+        index = gb_io.index(self.path)
+        for expected in reversed(self.records):
+            record = index[expected.accession.split()[0]]
+            self.assertEqual(record, expected)
+
+    def test_getitem_name(self):
+        index = gb_io.index(self.path, key="name")
+        for expected in self.records:
+            self.assertEqual(index[expected.name], expected)
+
+    def test_missing(self):
+        index = gb_io.index(self.path)
+        self.assertRaises(KeyError, index.__getitem__, "XXXXXX")
+        self.assertRaises(KeyError, index.offset, "XXXXXX")
+        self.assertIs(index.get("XXXXXX"), None)
+        self.assertEqual(index.get("XXXXXX", 1), 1)
+        self.assertNotIn("XXXXXX", index)
+        self.assertNotIn(1, index)
+        self.assertIn(self.records[0].accession.split()[0], index)
+
+    def test_offset(self):
+        index = gb_io.index(self.path)
+        reader = gb_io.iter(self.path)
+        for record in reader:
+            self.assertEqual(index.offset(record.accession.split()[0]), reader.tell())
+
+    def test_duplicate(self):
+        with open(self.path, "rb") as f:
+            data = f.read()
+        with tempfile.NamedTemporaryFile(suffix=".gb", delete=False) as f:
+            f.write(data * 2)
+        try:
+            self.assertRaises(ValueError, gb_io.index, f.name)
+        finally:
+            os.remove(f.name)
+
+    def test_compressed(self):
+        with open(self.path, "rb") as f:
+            data = gzip.compress(f.read())
+        with tempfile.NamedTemporaryFile(suffix=".gb.gz", delete=False) as f:
+            f.write(data)
+        try:
+            self.assertRaises(ValueError, gb_io.index, f.name)
+        finally:
+            os.remove(f.name)
+
+    def test_invalid_key(self):
+        self.assertRaises(ValueError, gb_io.index, self.path, key="definition")
+
+    def test_missing_file(self):
+        self.assertRaises(FileNotFoundError, gb_io.index, os.path.join(DATA_FOLDER, "missing.gb"))
+
+
+class TestIterBatches(unittest.TestCase):
+
+    path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+
+    def test_batch_sizes(self):
+        names = [record.name for record in gb_io.load(self.path)]
+        for batch_size, sizes in [(1, [1] * 6), (4, [4, 2]), (6, [6]), (10, [6])]:
+            batches = list(gb_io.iter_batches(self.path, batch_size))
+            self.assertEqual([len(batch) for batch in batches], sizes)
+            self.assertEqual([r.name for batch in batches for r in batch], names)
+
+    def test_handle(self):
+        expected = gb_io.load(self.path)
+        with open(self.path, "rb") as f:
+            batches = list(gb_io.iter_batches(f, 5))
+        self.assertEqual([len(batch) for batch in batches], [5, 1])
+        records = [record for batch in batches for record in batch]
+        for record, other in zip(records, expected):
+            self.assertEqual(record.name, other.name)
+            self.assertEqual(record.sequence, other.sequence)
+            self.assertEqual(len(record.features), len(other.features))
+
+    def test_hold_gil(self):
+        batches = list(gb_io.iter_batches(self.path, 4, release_gil=False))
+        self.assertEqual([len(batch) for batch in batches], [4, 2])
+
+    def test_normalize_kinds(self):
+        for batch in gb_io.iter_batches(self.path, normalize_kinds="lower"):
+            for record in batch:
+                for feature in record.features:
+                    self.assertEqual(feature.kind, feature.kind.lower())
+
+    def test_empty(self):
+        self.assertEqual(list(gb_io.iter_batches(io.BytesIO(b""))), [])
+
+    def test_invalid_batch_size(self):
+        self.assertRaises(ValueError, gb_io.iter_batches, self.path, 0)
+        self.assertRaises(OverflowError, gb_io.iter_batches, self.path, -1)
+
+    def test_error(self):
+        with open(self.path, "rb") as f:
+            data = f.read().replace(b"ORIGIN", b"ORIGIX", 1)
+        batches = gb_io.iter_batches(io.BytesIO(data))
+        self.assertRaises(ValueError, next, batches)
+
+    def test_handle_error(self):
+        with open(self.path, "rb") as f:
+            data = f.read(100)
+
+        class BrokenReader(io.RawIOBase):
+            def __init__(self):
+                self.data = data
+            def readable(self):
+                return True
+            def readinto(self, b):
+                if not self.data:
+                    raise ValueError("broken")
+                n = min(len(b), len(self.data))
+                b[:n] = self.data[:n]
+                self.data = self.data[n:]
+                return n
+
+        batches = gb_io.iter_batches(BrokenReader())
+        self.assertRaises(ValueError, next, batches)
+
+
+class TestGrep(unittest.TestCase):
+
+    path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+
+    def test_grep_definition(self):
+        matches = list(gb_io.grep(self.path, r"kin\d", fields=["definition"]))
+        self.assertEqual([(r.name, f) for r, f in matches], [("ATKIN2", "definition"), ("BNAKINI", "definition")])
+        self.assertIsInstance(matches[0][0], gb_io.Record)
+
+    def test_grep_features(self):
+        matches = list(gb_io.grep(self.path, "^csp14$", fields="features"))
+        self.assertEqual([(r.name, f) for r, f in matches], [("ARU237582", "features")])
+
+    def test_grep_organism(self):
+        matches = list(gb_io.grep(self.path, "thaliana", fields=["organism"]))
+        self.assertEqual([r.name for r, _ in matches], ["ATCOR66M", "ATKIN2"])
+
+    def test_grep_field_order(self):
+        # the first matching field is reported, in the given order
+        matches = list(gb_io.grep(self.path, "kin2", fields=["features", "definition"]))
+        self.assertEqual([(r.name, f) for r, f in matches], [("ATKIN2", "features")])
+        matches = list(gb_io.grep(self.path, "kin2"))
+        self.assertEqual([(r.name, f) for r, f in matches], [("ATKIN2", "definition")])
+
+    def test_grep_no_match(self):
+        self.assertEqual(list(gb_io.grep(self.path, "nothing to see here")), [])
+        self.assertEqual(list(gb_io.grep(self.path, "thaliana", fields=[])), [])
+
+    def test_grep_handle(self):
+        expected = {r.name: r for r in gb_io.load(self.path)}
+        with open(self.path, "rb") as f:
+            for record, field in gb_io.grep(f, "Brassica", fields=["organism"]):
+                other = expected[record.name]
+                self.assertEqual(record.sequence, other.sequence)
+                self.assertEqual(record.origin_note, other.origin_note)
+                self.assertEqual(len(record.features), len(other.features))
+
+    def test_grep_errors(self):
+        self.assertRaises(ValueError, gb_io.grep, self.path, "(unclosed")
+        self.assertRaises(ValueError, gb_io.grep, self.path, "kin", fields=["sequence"])
+        self.assertRaises(TypeError, gb_io.grep, self.path, "kin", fields=[1])
+
+
+class TestNormalizeKinds(unittest.TestCase):
+
+    @classmethod
+    def setUpClass(cls):
+        cls.path = os.path.join(DATA_FOLDER, "AY048670.1.gb")
+        cls.kinds = [f.kind for f in gb_io.load(cls.path)[0].features]
+
+    def test_default(self):
+        record = gb_io.load(self.path, normalize_kinds=None)[0]
+        self.assertEqual([f.kind for f in record.features], self.kinds)
+
+    def test_lower(self):
+        record = gb_io.load(self.path, normalize_kinds="lower")[0]
+        kinds = [f.kind for f in record.features]
+        self.assertEqual(kinds, [k.lower() for k in self.kinds])
+        self.assertIs(record.features[1].kind, record.features[2].kind)
+        self.assertIs(sys.intern(record.features[1].kind), record.features[1].kind)
+
+    def test_upper(self):
+        records = gb_io.iter(self.path, normalize_kinds="upper")
+        kinds = [f.kind for f in next(records).features]
+        self.assertEqual(kinds, [k.upper() for k in self.kinds])
+
+    def test_mapping(self):
+        with open(self.path, "rb") as f:
+            records = gb_io.iter(f, with_text=True, normalize_kinds={"CDS": "cds"})
+            _, record = next(records)
+        kinds = [f.kind for f in record.features]
+        self.assertEqual(kinds, ["cds" if k == "CDS" else k for k in self.kinds])
+
+    def test_invalid(self):
+        self.assertRaises(ValueError, gb_io.load, self.path, normalize_kinds="title")
+        self.assertRaises(TypeError, gb_io.load, self.path, normalize_kinds=1)
+        self.assertRaises(ValueError, gb_io.iter, self.path, normalize_kinds="title")
+
+
+class TestDropQualifiers(unittest.TestCase):
+
+    @classmethod
+    def setUpClass(cls):
+        cls.path = os.path.join(DATA_FOLDER, "biopython", "NC_005816.gb")
+        cls.keys = [[q.key for q in f.qualifiers] for f in gb_io.load(cls.path)[0].features]
+
+    def assertDropped(self, record, dropped):
+        keys = [[q.key for q in f.qualifiers] for f in record.features]
+        self.assertEqual(keys, [[k for k in fk if k not in dropped] for fk in self.keys])
+
+    def test_default(self):
+        record = gb_io.load(self.path, drop_qualifiers=None)[0]
+        self.assertDropped(record, set())
+
+    def test_load(self):
+        self.assertTrue(any("translation" in keys for keys in self.keys))
+        record = gb_io.load(self.path, drop_qualifiers={"translation", "note"})[0]
+        self.assertDropped(record, {"translation", "note"})
+
+    def test_loads(self):
+        with open(self.path, "rb") as f:
+            records = gb_io.loads(f.read(), drop_qualifiers=["translation"])
+        self.assertDropped(records[0], {"translation"})
+
+    def test_iter(self):
+        record = next(gb_io.iter(self.path, drop_qualifiers="translation"))
+        self.assertDropped(record, {"translation"})
+        _, record = next(gb_io.iter(self.path, with_text=True, drop_qualifiers={"db_xref"}))
+        self.assertDropped(record, {"db_xref"})
+        record = next(gb_io.iter(self.path, reuse=True, drop_qualifiers={"db_xref"}))
+        self.assertDropped(record, {"db_xref"})
+
+    def test_invalid(self):
+        self.assertRaises(TypeError, gb_io.load, self.path, drop_qualifiers=1)
+        self.assertRaises(TypeError, gb_io.load, self.path, drop_qualifiers=[1])
+
+
+class TestRequireQualifiers(unittest.TestCase):
+
+    path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+
+    def test_satisfied(self):
+        spec = {"CDS": ["gene", "protein_id"], "gene": "gene", "misc_feature": ["note"]}
+        records = list(gb_io.iter(self.path, require_qualifiers=spec))
+        self.assertEqual(len(records), 6)
+
+    def test_raise(self):
+        reader = gb_io.iter(self.path, require_qualifiers={"CDS": ["product", "gene"]})
+        with self.assertRaises(ValueError) as ctx:
+            next(reader)
+        self.assertIn("feature 2 (CDS)", str(ctx.exception))
+        self.assertIn("record X55053", str(ctx.exception))
+        self.assertIn("product", str(ctx.exception))
+        self.assertNotIn("gene", str(ctx.exception))
+
+    def test_skip(self):
+        spec = {"CDS": ["product"], "source": ["organism"]}
+        with warnings.catch_warnings():
+            warnings.simplefilter("error")
+            records = list(gb_io.iter(self.path, require_qualifiers=spec, errors="skip"))
+        self.assertEqual([record.name for record in records], ["ARU237582", "AF297471"])
+
+    def test_warn(self):
+        spec = {"CDS": ["product"], "source": ["organism"]}
+        with warnings.catch_warnings(record=True) as caught:
+            warnings.simplefilter("always")
+            records = list(gb_io.iter(self.path, require_qualifiers=spec, errors="warn"))
+        self.assertEqual(len(records), 6)
+        messages = [str(w.message) for w in caught]
+        self.assertEqual(len(messages), 4)
+        self.assertTrue(all(issubclass(w.category, UserWarning) for w in caught))
+        self.assertIn("feature 6 (CDS) of record X62281", messages[1])
+
+    def test_warn_as_error(self):
+        reader = gb_io.iter(self.path, require_qualifiers={"CDS": "product"}, errors="warn")
+        with warnings.catch_warnings():
+            warnings.simplefilter("error")
+            self.assertRaises(UserWarning, next, reader)
+
+    def test_normalize_kinds(self):
+        spec = {"cds": ["product"]}
+        self.assertEqual(len(list(gb_io.iter(self.path, require_qualifiers=spec))), 6)
+        reader = gb_io.iter(self.path, require_qualifiers=spec, normalize_kinds="lower")
+        self.assertRaises(ValueError, next, reader)
+
+    def test_prefetch(self):
+        reader = gb_io.iter(self.path, require_qualifiers={"CDS": "product"}, prefetch=2)
+        self.assertRaises(ValueError, next, reader)
+
+    def test_invalid(self):
+        self.assertRaises(ValueError, gb_io.iter, self.path, require_qualifiers={}, errors="ignore")
+        self.assertRaises(TypeError, gb_io.iter, self.path, require_qualifiers={"CDS": [1]})
+        self.assertRaises(TypeError, gb_io.iter, self.path, require_qualifiers=["CDS"])
+
+
+class TestOriginNote(unittest.TestCase):
+
+    @classmethod
+    def setUpClass(cls):
+        path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+        with open(path, "rb") as f:
+            data = f.read()
+        # annotate the ORIGIN line of the first and third records only
+        cls.notes = ["Chromosome 2 region", None, "Unreported sequence", None, None, None]
+        parts = data.split(b"\nORIGIN      \n")
+        cls.data = parts[0]
+        for note, part in zip(cls.notes, parts[1:]):
+            cls.data += b"\nORIGIN      %s\n" % (note or "").encode() + part
+
+    def test_load(self):
+        records = gb_io.load(io.BytesIO(self.data))
+        self.assertEqual([r.origin_note for r in records], self.notes)
+
+    def test_load_chunked(self):
+        reader = TestLoadReadinto.ChunkedReader(self.data, 7)
+        records = gb_io.load(reader)
+        self.assertEqual([r.origin_note for r in records], self.notes)
+
+    def test_iter(self):
+        records = gb_io.iter(io.BytesIO(self.data))
+        self.assertEqual([r.origin_note for r in records], self.notes)
+        records = gb_io.iter(io.BytesIO(self.data), with_text=True)
+        self.assertEqual([r.origin_note for _, r in records], self.notes)
+        records = gb_io.iter(io.BytesIO(self.data), reuse=True)
+        self.assertEqual([r.origin_note for r in records], self.notes)
+
+    def test_roundtrip(self):
+        records = gb_io.load(io.BytesIO(self.data))
+        buffer = gb_io.dump(records)
+        self.assertIn(b"\nORIGIN      Chromosome 2 region\n        1 ", buffer.getvalue())
+        self.assertEqual(buffer.getvalue().count(b"\nORIGIN      \n"), 4)
+        loaded = gb_io.load(buffer)
+        self.assertEqual([r.origin_note for r in loaded], self.notes)
+
+    def test_copy(self):
+        record = gb_io.load(io.BytesIO(self.data))[0]
+        self.assertEqual(copy.copy(record).origin_note, self.notes[0])
+        self.assertEqual(copy.deepcopy(record).origin_note, self.notes[0])
+        self.assertEqual(pickle.loads(pickle.dumps(record)).origin_note, self.notes[0])
+
+    def test_setter(self):
+        record = gb_io.Record(b"ATGC", origin_note="Some note")
+        self.assertEqual(record.origin_note, "Some note")
+        record.origin_note = None
+        self.assertNotIn(b"Some note", gb_io.dump(record).getvalue())
+        record.origin_note = "Other note"
+        self.assertEqual(gb_io.load(gb_io.dump(record))[0].origin_note, "Other note")
+
+
+class TestWGS(unittest.TestCase):
+
+    @classmethod
+    def setUpClass(cls):
+        path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+        with open(path, "rb") as f:
+            data = f.read()
+        # add WGS lines to the first and second records only
+        cls.wgs = [["AAAA02000001-AAAA02050231"], ["ABCD01000001-ABCD01000042"], [], [], [], []]
+        cls.wgs_scafld = [["CM000126-CM000137", "CH398081-CH401163"], [], [], [], [], []]
+        parts = data.split(b"\nORIGIN")
+        cls.data = parts[0]
+        for wgs, scafld, part in zip(cls.wgs, cls.wgs_scafld, parts[1:]):
+            for line in wgs:
+                cls.data += b"\nWGS         %s" % line.encode()
+            for line in scafld:
+                cls.data += b"\nWGS_SCAFLD  %s" % line.encode()
+            cls.data += b"\nORIGIN" + part
+
+    def assertWGS(self, records):
+        self.assertEqual([r.wgs for r in records], self.wgs)
+        self.assertEqual([r.wgs_scafld for r in records], self.wgs_scafld)
+
+    def test_load(self):
+        records = gb_io.load(io.BytesIO(self.data))
+        self.assertWGS(records)
+        self.assertEqual(len(records[0].sequence), 513)
+
+    def test_load_chunked(self):
+        reader = TestLoadReadinto.ChunkedReader(self.data, 7)
+        self.assertWGS(gb_io.load(reader))
+
+    def test_loads(self):
+        self.assertWGS(gb_io.loads(self.data))
+
+    def test_iter(self):
+        self.assertWGS(list(gb_io.iter(io.BytesIO(self.data))))
+        records = gb_io.iter(io.BytesIO(self.data), with_text=True)
+        self.assertWGS([r for _, r in records])
+
+    def test_roundtrip(self):
+        records = gb_io.load(io.BytesIO(self.data))
+        text = gb_io.dumps(records)
+        self.assertIn("\nWGS         AAAA02000001-AAAA02050231\n", text)
+        self.assertIn("\nWGS_SCAFLD  CH398081-CH401163\nORIGIN", text)
+        self.assertWGS(gb_io.loads(text))
+
+    def test_copy(self):
+        record = gb_io.load(io.BytesIO(self.data))[0]
+        for other in (copy.copy(record), copy.deepcopy(record), pickle.loads(pickle.dumps(record))):
+            self.assertEqual(other.wgs, self.wgs[0])
+            self.assertEqual(other.wgs_scafld, self.wgs_scafld[0])
+
+    def test_setter(self):
+        record = gb_io.Record(b"ATGC", wgs=["AAAA02000001-AAAA02050231"])
+        self.assertEqual(record.wgs, ["AAAA02000001-AAAA02050231"])
+        self.assertEqual(record.wgs_scafld, [])
+        record.wgs_scafld = ["CM000126-CM000137"]
+        loaded = gb_io.loads(gb_io.dumps(record))[0]
+        self.assertEqual(loaded.wgs, ["AAAA02000001-AAAA02050231"])
+        self.assertEqual(loaded.wgs_scafld, ["CM000126-CM000137"])
+
+
+class TestPrimary(unittest.TestCase):
+
+    primary = [
+        ("1-426", "AC035185.1", "14-439", False),
+        ("427-1135", "BI345677.1", "1-709", True),
+    ]
+
+    @classmethod
+    def setUpClass(cls):
+        path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+        with open(path, "rb") as f:
+            data = f.read()
+        # add a PRIMARY table to the first record only
+        head, tail = data.split(b"\nFEATURES", 1)
+        cls.data = head + (
+            b"\nPRIMARY     TPA_SPAN            PRIMARY_IDENTIFIER PRIMARY_SPAN        COMP"
+            b"\n            1-426               AC035185.1         14-439"
+            b"\n            427-1135            BI345677.1         1-709               c"
+            b"\nFEATURES"
+        ) + tail
+
+    def test_load(self):
+        records = gb_io.load(io.BytesIO(self.data))
+        self.assertEqual(records[0].primary, self.primary)
+        self.assertEqual(records[1].primary, [])
+        self.assertEqual(len(records[0].features), 3)
+
+    def test_load_chunked(self):
+        reader = TestLoadReadinto.ChunkedReader(self.data, 7)
+        self.assertEqual(gb_io.load(reader)[0].primary, self.primary)
+
+    def test_roundtrip(self):
+        records = gb_io.load(io.BytesIO(self.data))
+        text = gb_io.dumps(records)
+        self.assertIn("\n            427-1135            BI345677.1         1-709               c\n", text)
+        self.assertIn("\n            1-426               AC035185.1         14-439\n", text)
+        self.assertEqual(text.count("\nPRIMARY "), 1)
+        self.assertEqual([r.primary for r in gb_io.loads(text)], [r.primary for r in records])
+
+    def test_copy(self):
+        record = gb_io.load(io.BytesIO(self.data))[0]
+        for other in (copy.copy(record), copy.deepcopy(record), pickle.loads(pickle.dumps(record))):
+            self.assertEqual(other.primary, self.primary)
+
+    def test_setter(self):
+        record = gb_io.Record(b"ATGC", primary=self.primary[:1])
+        self.assertEqual(record.primary, self.primary[:1])
+        record.primary = self.primary
+        loaded = gb_io.loads(gb_io.dumps(record))[0]
+        self.assertEqual(loaded.primary, self.primary)
+
+
+class TestLoadCompressed(unittest.TestCase):
+
+    @classmethod
+    def setUpClass(cls):
+        cls.path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+        cls.names = [record.name for record in gb_io.load(cls.path)]
+
+    def test_load_gzip_file(self):
+        with open(self.path, "rb") as f:
+            data = gzip.compress(f.read())
+        records = gb_io.load(io.BytesIO(data))
+        self.assertEqual([record.name for record in records], self.names)
+
+    def test_iter_gzip_path(self):
+        with tempfile.TemporaryDirectory() as dst:
+            path = os.path.join(dst, "records.gb.gz")
+            gb_io.dump(gb_io.load(self.path), path)
+            records = gb_io.iter(path)
+            self.assertEqual([record.name for record in records], self.names)
+
+    def test_load_no_decompress(self):
+        records = gb_io.load(self.path, decompress=False)
+        self.assertEqual([record.name for record in records], self.names)
+        with open(self.path, "rb") as f:
+            data = gzip.compress(f.read())
+        self.assertRaises(ValueError, gb_io.load, io.BytesIO(data), decompress=False)
+
+    def test_iter_no_decompress(self):
+        records = gb_io.iter(self.path, decompress=False)
+        self.assertEqual([record.name for record in records], self.names)
+        with tempfile.TemporaryDirectory() as dst:
+            path = os.path.join(dst, "records.gb.gz")
+            gb_io.dump(gb_io.load(self.path), path)
+            reader = gb_io.iter(path, decompress=False)
+            self.assertRaises(ValueError, next, reader)
+            reader = gb_io.iter(path, with_text=True, decompress=False)
+            self.assertRaises(StopIteration, next, reader)
+
+    def test_zstd_no_decompress(self):
+        data = b"\x28\xb5\x2f\xfd" + b"\x00" * 16
+        self.assertRaises(ValueError, gb_io.load, io.BytesIO(data), decompress=False)
+
+    @unittest.skipUnless("ZSTD" in gb_io.__build__["features"], "zstd feature disabled")
+    def test_zstd_roundtrip(self):
+        with tempfile.TemporaryDirectory() as dst:
+            path = os.path.join(dst, "records.gb.zst")
+            gb_io.dump(gb_io.load(self.path), path)
+            with open(path, "rb") as f:
+                self.assertEqual(f.read(4), b"\x28\xb5\x2f\xfd")
+            records = gb_io.load(path)
+            self.assertEqual([record.name for record in records], self.names)
+            records = gb_io.iter(path, with_text=True)
+            self.assertEqual([record.name for _, record in records], self.names)
+
+    @unittest.skipIf("ZSTD" in gb_io.__build__["features"], "zstd feature enabled")
+    def test_zstd_unsupported(self):
+        data = b"\x28\xb5\x2f\xfd" + b"\x00" * 16
+        self.assertRaises(OSError, gb_io.load, io.BytesIO(data))
+
+
+class TestLoadURL(unittest.TestCase):
+
+    @classmethod
+    def setUpClass(cls):
+        cls.path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+        cls.names = [record.name for record in gb_io.load(cls.path)]
+
+    def test_unsupported_scheme(self):
+        self.assertRaises(ValueError, gb_io.load, "ftp://example.com/records.gb")
+        self.assertRaises(ValueError, gb_io.iter, "ftp://example.com/records.gb")
+
+    @unittest.skipIf("HTTP" in gb_io.__build__["features"], "http feature enabled")
+    def test_http_unsupported(self):
+        self.assertRaises(ValueError, gb_io.load, "https://example.com/records.gb")
+        self.assertRaises(ValueError, gb_io.iter, "https://example.com/records.gb")
+
+    @unittest.skipUnless("HTTP" in gb_io.__build__["features"], "http feature disabled")
+    def test_http(self):
+        import functools
+        import http.server
+        import threading
+
+        class Handler(http.server.SimpleHTTPRequestHandler):
+            def log_message(self, *args):
+                pass
+
+        handler = functools.partial(Handler, directory=os.path.dirname(self.path))
+        with http.server.ThreadingHTTPServer(("127.0.0.1", 0), handler) as server:
+            thread = threading.Thread(target=server.serve_forever)
+            thread.start()
+            try:
+                url = "http://127.0.0.1:{}/".format(server.server_address[1])
+                records = gb_io.load(url + "cor6_6.gb")
+                self.assertEqual([record.name for record in records], self.names)
+                records = gb_io.iter(url + "cor6_6.gb")
+                self.assertEqual([record.name for record in records], self.names)
+                self.assertRaises(OSError, gb_io.load, url + "missing.gb")
+            finally:
+                server.shutdown()
+                thread.join()
+
+
+class TestLoadReadinto(unittest.TestCase):
+
+    class ChunkedReader(io.RawIOBase):
+        """A binary reader returning at most `chunk` bytes per call."""
+
+        def __init__(self, data, chunk):
+            self.handle = io.BytesIO(data)
+            self.chunk = chunk
+            self.calls = 0
+
+        def readable(self):
+            return True
+
+        def readinto(self, b):
+            self.calls += 1
+            n = min(len(b), self.chunk)
+            return self.handle.readinto(memoryview(b)[:n])
+
+    class ReadOnly(object):
+        """A binary reader without a `readinto` method."""
+
+        def __init__(self, data):
+            self.handle = io.BytesIO(data)
+
+        def read(self, n=-1):
+            return self.handle.read(n)
+
+    @classmethod
+    def setUpClass(cls):
+        path = os.path.join(DATA_FOLDER, "biopython", "cor6_6.gb")
+        with open(path, "rb") as f:
+            cls.data = f.read()
+        cls.names = [record.name for record in gb_io.load(path)]
+
+    def test_readinto_and_read_agree(self):
+        records = gb_io.load(io.BytesIO(self.data))
+        self.assertEqual([record.name for record in records], self.names)
+        records = gb_io.load(self.ReadOnly(self.data))
+        self.assertEqual([record.name for record in records], self.names)
+
+    def test_readinto_used(self):
+        handle = self.ChunkedReader(self.data, len(self.data))
+        records = gb_io.load(handle)
+        self.assertEqual([record.name for record in records], self.names)
+        self.assertGreater(handle.calls, 0)
+
+    def test_readinto_short_reads(self):
+        handle = self.ChunkedReader(self.data, 7)
+        records = list(gb_io.iter(handle))
+        self.assertEqual([record.name for record in records], self.names)
+        self.assertGreaterEqual(handle.calls, len(self.data) // 7)
+
+    def test_readinto_short_reads_gzip(self):
+        handle = self.ChunkedReader(gzip.compress(self.data), 5)
+        records = gb_io.load(handle)
+        self.assertEqual([record.name for record in records], self.names)
+
+    def test_readinto_eof(self):
+        handle = self.ChunkedReader(b"", 16)
+        self.assertEqual(gb_io.load(handle), [])
+        self.assertEqual(list(gb_io.iter(self.ChunkedReader(b"", 16))), [])
+
+    def test_readinto_too_many_bytes(self):
+        class Liar(self.ChunkedReader):
+            def readinto(self, b):
+                return len(b) + 1
+        self.assertRaises(ValueError, gb_io.load, Liar(self.data, 16))
+
+    def test_readinto_invalid_type(self):
+        class Invalid(self.ChunkedReader):
+            def readinto(self, b):
+                return "abc"
+        self.assertRaises(TypeError, gb_io.load, Invalid(self.data, 16))
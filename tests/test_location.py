@@ -1,5 +1,6 @@
 import unittest
 import os
+import warnings
 
 import gb_io
 
@@ -11,4 +12,424 @@ class TestLocation(unittest.TestCase):
         self.assertEqual(location.strand, "+")
 
         location = gb_io.Complement(location)
-        self.assertEqual(location.strand, "-")
\ No newline at end of file
+        self.assertEqual(location.strand, "-")
+
+    def test_strand_compound(self):
+        a = gb_io.Range(1, 2)
+        b = gb_io.Range(5, 8)
+        for cls in (gb_io.Join, gb_io.Order, gb_io.Bond, gb_io.OneOf):
+            self.assertEqual(cls([a, b]).strand, "+")
+            self.assertEqual(cls([gb_io.Complement(a), gb_io.Complement(b)]).strand, "-")
+            self.assertIs(cls([a, gb_io.Complement(b)]).strand, None)
+            self.assertIs(cls([]).strand, None)
+            self.assertIs(cls([a, gb_io.External("J00194.1")]).strand, None)
+        self.assertEqual(gb_io.Complement(gb_io.Join([a, b])).strand, "-")
+        self.assertIs(gb_io.Complement(gb_io.Join([a, gb_io.Complement(b)])).strand, None)
+
+    def test_complement_coordinates(self):
+        location = gb_io.parse_location("complement(join(1..10,20..30))")
+        self.assertEqual(location.start, 30)
+        self.assertEqual(location.end, 0)
+        location = gb_io.Complement(
+            gb_io.Join([gb_io.Complement(gb_io.Range(0, 10)), gb_io.Range(19, 30)])
+        )
+        self.assertEqual(location.start, 30)
+        self.assertEqual(location.end, 0)
+        location = gb_io.Complement(gb_io.Order([gb_io.Range(5, 10), gb_io.Between(19, 20)]))
+        self.assertEqual(location.start, 20)
+        self.assertEqual(location.end, 5)
+        location = gb_io.Complement(gb_io.External("J00194.1"))
+        self.assertRaises(ValueError, getattr, location, "start")
+        self.assertRaises(ValueError, getattr, location, "end")
+
+    def test_empty_range(self):
+        location = gb_io.Range(5, 5)
+        record = gb_io.Record(b"ATGCATGCAT")
+        self.assertEqual(len(location), 0)
+        self.assertTrue(location)
+        self.assertEqual((location.start, location.end), (5, 5))
+        self.assertEqual(gb_io.Feature("misc_feature", location).extract(record), b"")
+        feature = gb_io.Feature("misc_feature", gb_io.Complement(location))
+        self.assertEqual(feature.extract(record), b"")
+
+    def test_empty_join(self):
+        location = gb_io.Join([])
+        record = gb_io.Record(b"ATGCATGCAT")
+        self.assertEqual(len(location), 0)
+        self.assertTrue(location)
+        self.assertRaises(ValueError, getattr, location, "start")
+        self.assertRaises(ValueError, getattr, location, "end")
+        self.assertEqual(gb_io.Feature("misc_feature", location).extract(record), b"")
+        record.features.append(gb_io.Feature("misc_feature", location))
+        self.assertRaises(ValueError, gb_io.dump, record)
+
+    def test_join_large_coordinates(self):
+        location = gb_io.Join([gb_io.Range(2**31, 2**31 + 10), gb_io.Range(2**32, 2**32 + 10)])
+        self.assertEqual((location.start, location.end), (2**31, 2**32 + 10))
+        complement = gb_io.Complement(location)
+        self.assertEqual((complement.start, complement.end), (2**32 + 10, 2**31))
+        nested = gb_io.Join([gb_io.Range(0, 10), complement])
+        self.assertEqual((nested.start, nested.end), (0, 2**32 + 10))
+
+    def test_empty_join_strict(self):
+        self.assertRaises(ValueError, gb_io.Join, [], strict=True)
+        self.assertRaises(ValueError, gb_io.Join, iter(()), strict=True)
+        location = gb_io.Join([gb_io.Range(0, 1)], strict=True)
+        self.assertEqual(len(location), 1)
+
+    def test_intersects_range(self):
+        location = gb_io.Range(10, 20)
+        self.assertTrue(location.intersects_range(15, 30))
+        self.assertTrue(location.intersects_range(0, 11))
+        self.assertFalse(location.intersects_range(20, 30))
+        self.assertFalse(location.intersects_range(0, 10))
+
+    def test_intersects_range_join(self):
+        location = gb_io.Join([gb_io.Range(0, 10), gb_io.Range(20, 30)])
+        self.assertTrue(location.intersects_range(5, 6))
+        self.assertTrue(location.intersects_range(25, 40))
+        self.assertFalse(location.intersects_range(10, 20))
+        self.assertTrue(gb_io.Complement(location).intersects_range(5, 6))
+
+    def test_intersects_range_external(self):
+        location = gb_io.External("J00194.1", gb_io.Range(10, 20))
+        self.assertFalse(location.intersects_range(0, 100))
+
+    def test_overlaps(self):
+        a = gb_io.Range(0, 10)
+        self.assertTrue(a.overlaps(gb_io.Range(5, 15)))
+        self.assertFalse(a.overlaps(gb_io.Range(10, 15)))
+        j = gb_io.Join([gb_io.Range(0, 10), gb_io.Range(20, 30)])
+        self.assertFalse(j.overlaps(gb_io.Range(12, 18)))
+        self.assertTrue(j.overlaps(gb_io.Range(12, 22)))
+        e = gb_io.External("J00194.1", gb_io.Range(0, 10))
+        self.assertFalse(a.overlaps(e))
+
+    def test_overlaps_same_strand(self):
+        a = gb_io.Range(0, 10)
+        c = gb_io.Complement(gb_io.Range(5, 15))
+        self.assertTrue(a.overlaps(c))
+        self.assertFalse(a.overlaps(c, same_strand=True))
+        self.assertTrue(gb_io.Complement(a).overlaps(c, same_strand=True))
+        mixed = gb_io.Join([gb_io.Range(0, 5), gb_io.Complement(gb_io.Range(8, 12))])
+        self.assertTrue(mixed.overlaps(a))
+        self.assertFalse(mixed.overlaps(a, same_strand=True))
+        self.assertFalse(mixed.overlaps(mixed, same_strand=True))
+
+    def test_contains(self):
+        a = gb_io.Range(0, 100)
+        self.assertTrue(a.contains(gb_io.Range(10, 20)))
+        self.assertTrue(a.contains(a))
+        self.assertFalse(a.contains(gb_io.Range(90, 110)))
+        j = gb_io.Join([gb_io.Range(0, 10), gb_io.Range(20, 30)])
+        self.assertTrue(a.contains(j))
+        self.assertFalse(j.contains(gb_io.Range(5, 25)))
+        self.assertTrue(j.contains(gb_io.Range(22, 25)))
+        c = gb_io.Complement(gb_io.Range(10, 20))
+        self.assertTrue(a.contains(c))
+        self.assertFalse(a.contains(c, same_strand=True))
+
+    def test_parse_location(self):
+        location = gb_io.parse_location("complement(1..100)")
+        self.assertIsInstance(location, gb_io.Complement)
+        self.assertEqual(location.location.start, 0)
+        self.assertEqual(location.location.end, 100)
+        self.assertRaises(ValueError, gb_io.parse_location, "(1..100")
+
+    def test_parse_location_external(self):
+        location = gb_io.parse_location("J00194.1:100..202")
+        self.assertIsInstance(location, gb_io.External)
+        self.assertEqual(location.accession, "J00194.1")
+        self.assertIsInstance(location.location, gb_io.Range)
+        self.assertEqual(location.location.start, 99)
+        self.assertEqual(location.location.end, 202)
+        self.assertRaises(ValueError, gb_io.parse_location, "J00194.x:100..202")
+
+    def test_parse_location_site(self):
+        location = gb_io.parse_location("467")
+        self.assertIsInstance(location, gb_io.Site)
+        self.assertIsInstance(location, gb_io.Range)
+        self.assertEqual(location.position, 466)
+        location = gb_io.parse_location("complement(467)")
+        self.assertIsInstance(location.location, gb_io.Site)
+        location = gb_io.parse_location("<467..467")
+        self.assertIsInstance(location, gb_io.Range)
+
+    def test_site(self):
+        site = gb_io.Site(466)
+        self.assertEqual(repr(site), "Site(466)")
+        self.assertEqual(str(site), "467")
+        self.assertEqual(site.start, 466)
+        self.assertEqual(site.end, 467)
+        self.assertEqual(site.strand, "+")
+        self.assertEqual(len(site), 1)
+        self.assertEqual(site, gb_io.Range(466, 467))
+        self.assertEqual(hash(site), hash(gb_io.Range(466, 467)))
+        self.assertNotEqual(site, gb_io.Site(467))
+        self.assertEqual(site.shift(10), gb_io.Site(476))
+        site.position = 10
+        self.assertEqual((site.start, site.end), (10, 11))
+        self.assertRaises(OverflowError, gb_io.Site, 2**63 - 1)
+
+    def test_site_readonly(self):
+        site = gb_io.Site(4)
+        with self.assertRaises(AttributeError):
+            site.end = 20
+        with self.assertRaises(AttributeError):
+            site.start = 2
+        with self.assertRaises(AttributeError):
+            site.before = True
+        self.assertEqual(repr(site), "Site(4)")
+        self.assertEqual(str(site), "5")
+        self.assertEqual(len(site), 1)
+
+    def test_single_position_range(self):
+        self.assertIsInstance(gb_io.Range(8, 9).shift(0), gb_io.Site)
+        self.assertEqual(gb_io.Range(8, 9).shift(0), gb_io.Range(8, 9))
+        self.assertIsInstance(gb_io.Join([gb_io.Range(8, 9)]).simplify(), gb_io.Site)
+        self.assertIs(type(gb_io.Range(8, 9, before=True).shift(0)), gb_io.Range)
+
+    def test_external_string_location(self):
+        location = gb_io.External("J00194.1", "100..202")
+        self.assertIsInstance(location.location, gb_io.Range)
+        self.assertEqual(location.location.start, 99)
+        self.assertEqual(location.location.end, 202)
+        self.assertRaises(ValueError, gb_io.External, "J00194.1", "(100..202")
+
+    def test_external_invalid_accession(self):
+        with warnings.catch_warnings(record=True) as caught:
+            warnings.simplefilter("always")
+            location = gb_io.External("12 34", "100..202")
+        self.assertEqual(location.accession, "12 34")
+        self.assertEqual(len(caught), 1)
+        self.assertTrue(issubclass(caught[0].category, DeprecationWarning))
+
+    def test_str(self):
+        self.assertEqual(str(gb_io.Range(99, 202)), "100..202")
+        self.assertEqual(str(gb_io.Range(466, 467)), "467")
+        self.assertEqual(str(gb_io.Range(466, 467, before=True)), "<467..467")
+        self.assertEqual(str(gb_io.Between(466, 467)), "467^468")
+        location = gb_io.Join([gb_io.Range(0, 10), gb_io.Complement(gb_io.Range(19, 20))])
+        self.assertEqual(str(location), "join(1..10,complement(20))")
+        self.assertEqual(gb_io.parse_location(str(location)), location)
+        self.assertEqual(gb_io.parse_location("467"), gb_io.Range(466, 467))
+
+    def test_ordering(self):
+        a = gb_io.Range(0, 10)
+        b = gb_io.Range(0, 20)
+        c = gb_io.Complement(gb_io.Range(0, 10))
+        d = gb_io.Join([gb_io.Range(5, 10), gb_io.Range(20, 30)])
+        e = gb_io.External("J00194.1", gb_io.Range(0, 10))
+        self.assertLess(a, b)
+        self.assertLess(a, c)
+        self.assertLess(c, b)
+        self.assertGreater(d, b)
+        self.assertEqual(sorted([e, d, c, b, a]), [a, c, b, d, e])
+
+    def test_equality(self):
+        self.assertEqual(gb_io.Range(0, 10), gb_io.Range(0, 10))
+        self.assertNotEqual(gb_io.Range(0, 10), gb_io.Range(0, 10, before=True))
+        self.assertNotEqual(gb_io.Range(0, 10), gb_io.Complement(gb_io.Range(0, 10)))
+        self.assertNotEqual(gb_io.Range(0, 10), (0, 10))
+        self.assertLessEqual(gb_io.Range(0, 10), gb_io.Range(0, 10))
+        self.assertEqual(hash(gb_io.Range(0, 10)), hash(gb_io.Range(0, 10)))
+        self.assertRaises(TypeError, lambda: gb_io.Range(0, 10) < 1)
+
+    def test_flip_range(self):
+        loc = gb_io.Range(0, 10, after=True)
+        self.assertEqual(loc.flip(100), gb_io.Complement(gb_io.Range(90, 100, before=True)))
+        self.assertEqual(loc.flip(100).flip(100), loc)
+        self.assertRaises(ValueError, loc.flip, 5)
+
+    def test_flip_between(self):
+        loc = gb_io.Between(0, 1)
+        self.assertEqual(loc.flip(10), gb_io.Complement(gb_io.Between(8, 9)))
+
+    def test_flip_complement(self):
+        loc = gb_io.Complement(gb_io.Range(2, 5))
+        self.assertEqual(loc.flip(10), gb_io.Range(5, 8))
+
+    def test_flip_join(self):
+        loc = gb_io.Join([gb_io.Range(0, 2), gb_io.Range(3, 5)])
+        flipped = loc.flip(10)
+        self.assertEqual(
+            flipped,
+            gb_io.Complement(gb_io.Join([gb_io.Range(5, 7), gb_io.Range(8, 10)])),
+        )
+        self.assertEqual(flipped.flip(10), loc)
+
+    def test_flip_order(self):
+        loc = gb_io.Order([gb_io.Range(0, 2), gb_io.Range(3, 5)])
+        self.assertEqual(
+            loc.flip(10),
+            gb_io.Complement(gb_io.Order([gb_io.Range(5, 7), gb_io.Range(8, 10)])),
+        )
+
+    def test_flip_bond(self):
+        loc = gb_io.Bond([gb_io.Range(0, 1), gb_io.Range(4, 5)])
+        self.assertEqual(
+            loc.flip(10),
+            gb_io.Complement(gb_io.Bond([gb_io.Range(5, 6), gb_io.Range(9, 10)])),
+        )
+
+    def test_flip_one_of(self):
+        loc = gb_io.OneOf([gb_io.Range(0, 2), gb_io.Range(1, 3)])
+        self.assertEqual(
+            loc.flip(10),
+            gb_io.Complement(gb_io.OneOf([gb_io.Range(7, 9), gb_io.Range(8, 10)])),
+        )
+
+    def test_flip_external(self):
+        loc = gb_io.External("J00194.1", gb_io.Range(100, 202))
+        self.assertEqual(loc.flip(10), loc)
+
+    def test_shift_range(self):
+        loc = gb_io.Range(0, 10, before=True)
+        shifted = loc.shift(5)
+        self.assertEqual(shifted, gb_io.Range(5, 15, before=True))
+        self.assertIsNot(shifted, loc)
+        self.assertEqual(loc, gb_io.Range(0, 10, before=True))
+        self.assertEqual(shifted.shift(-5), loc)
+        self.assertRaises(ValueError, loc.shift, -1)
+
+    def test_shift_between(self):
+        self.assertEqual(gb_io.Between(4, 5).shift(10), gb_io.Between(14, 15))
+
+    def test_shift_compound(self):
+        parts = [gb_io.Range(0, 2), gb_io.Complement(gb_io.Range(3, 5))]
+        shifted = [gb_io.Range(10, 12), gb_io.Complement(gb_io.Range(13, 15))]
+        for cls in (gb_io.Join, gb_io.Order, gb_io.Bond, gb_io.OneOf):
+            loc = cls(parts)
+            self.assertEqual(loc.shift(10), cls(shifted))
+            self.assertIsNot(loc.shift(10).locations[0], parts[0])
+        self.assertEqual(
+            gb_io.Complement(gb_io.Join(parts)).shift(10),
+            gb_io.Complement(gb_io.Join(shifted)),
+        )
+
+    def test_shift_overflow(self):
+        loc = gb_io.Join([gb_io.Range(0, 10), gb_io.Between(20, 21)])
+        self.assertRaises(OverflowError, loc.shift, 2**63 - 15)
+        self.assertRaises(OverflowError, loc.shift, 2**63)
+        self.assertEqual(loc, gb_io.Join([gb_io.Range(0, 10), gb_io.Between(20, 21)]))
+
+    def test_shift_external(self):
+        loc = gb_io.External("J00194.1", gb_io.Range(100, 202))
+        self.assertEqual(loc.shift(10), loc)
+        join = gb_io.Join([gb_io.Range(0, 10), loc])
+        self.assertEqual(join.shift(10), gb_io.Join([gb_io.Range(10, 20), loc]))
+
+    def test_len(self):
+        self.assertEqual(len(gb_io.Range(0, 100)), 100)
+        self.assertEqual(len(gb_io.Between(9, 10)), 0)
+        self.assertEqual(len(gb_io.Complement(gb_io.Range(10, 20))), 10)
+        join = gb_io.Join([gb_io.Range(0, 10), gb_io.Range(20, 35)])
+        self.assertEqual(len(join), 25)
+        self.assertEqual(len(gb_io.Complement(join)), 25)
+        self.assertEqual(len(gb_io.Order([gb_io.Range(0, 10), gb_io.Range(5, 8)])), 13)
+        self.assertRaises(ValueError, len, gb_io.OneOf([gb_io.Range(0, 10)]))
+        self.assertRaises(ValueError, len, gb_io.External("AB000001.1", gb_io.Range(0, 10)))
+
+    def test_bool(self):
+        self.assertTrue(gb_io.Between(9, 10))
+        self.assertTrue(gb_io.OneOf([gb_io.Range(0, 10)]))
+        self.assertTrue(gb_io.Feature("misc_feature", gb_io.Between(9, 10)))
+
+    def test_to_tuple(self):
+        self.assertEqual(gb_io.Range(0, 10).to_tuple(), ("range", 0, 10, False, False))
+        self.assertEqual(gb_io.Range(0, 10, True, True).to_tuple(), ("range", 0, 10, True, True))
+        self.assertEqual(gb_io.Between(4, 5).to_tuple(), ("between", 4, 5))
+        self.assertEqual(
+            gb_io.Complement(gb_io.Between(4, 5)).to_tuple(),
+            ("complement", ("between", 4, 5)),
+        )
+        parts = [gb_io.Range(0, 10), gb_io.Range(20, 30)]
+        for cls, kind in [
+            (gb_io.Join, "join"),
+            (gb_io.Order, "order"),
+            (gb_io.Bond, "bond"),
+            (gb_io.OneOf, "one_of"),
+        ]:
+            self.assertEqual(
+                cls(parts).to_tuple(),
+                (kind, [("range", 0, 10, False, False), ("range", 20, 30, False, False)]),
+            )
+        self.assertEqual(
+            gb_io.External("J00194.1", gb_io.Range(10, 20)).to_tuple(),
+            ("external", "J00194.1", ("range", 10, 20, False, False)),
+        )
+        self.assertEqual(gb_io.External("J00194.1").to_tuple(), ("external", "J00194.1", None))
+
+    def test_from_tuple_roundtrip(self):
+        for text in [
+            "1..10",
+            "<1..>10",
+            "5^6",
+            "complement(join(1..10,21..30))",
+            "order(1..10,complement(21..30))",
+            "bond(1,10)",
+            "one-of(1..10,2..10)",
+            "J00194.1:100..202",
+            "join(1..10,J00194.1:100..202)",
+        ]:
+            loc = gb_io.parse_location(text)
+            data = loc.to_tuple()
+            self.assertEqual(gb_io.Location.from_tuple(data), loc, text)
+            self.assertEqual(gb_io.Location.from_tuple(data).to_tuple(), data, text)
+        self.assertIsInstance(gb_io.Location.from_tuple(("range", 0, 10, False, False)), gb_io.Range)
+        self.assertEqual(
+            gb_io.Location.from_tuple(("join", (("between", 1, 2), ("range", 3, 4, False, False)))),
+            gb_io.Join([gb_io.Between(1, 2), gb_io.Range(3, 4)]),
+        )
+
+    def test_from_tuple_error(self):
+        self.assertRaises(ValueError, gb_io.Location.from_tuple, ())
+        self.assertRaises(ValueError, gb_io.Location.from_tuple, ("circle", 0, 10))
+        self.assertRaises(ValueError, gb_io.Location.from_tuple, ("range", 0, 10))
+        self.assertRaises(ValueError, gb_io.Location.from_tuple, ("external", "not an accession", None))
+        self.assertRaises(TypeError, gb_io.Location.from_tuple, ["range", 0, 10, False, False])
+        self.assertRaises(TypeError, gb_io.Location.from_tuple, ("range", "0", 10, False, False))
+        self.assertRaises(TypeError, gb_io.Location.from_tuple, ("join", [gb_io.Range(0, 10)]))
+
+    def test_simplify_single(self):
+        r = gb_io.Range(0, 100)
+        for cls in (gb_io.Join, gb_io.Order, gb_io.Bond, gb_io.OneOf):
+            loc = cls([r])
+            self.assertEqual(loc.simplify(), r)
+            self.assertEqual(loc, cls([r]))
+        self.assertEqual(gb_io.Join([gb_io.Join([r])]).simplify(), r)
+        self.assertEqual(str(gb_io.Join([r]).simplify()), "1..100")
+
+    def test_simplify_nested(self):
+        a, b, c = gb_io.Range(0, 2), gb_io.Range(4, 6), gb_io.Range(8, 9)
+        self.assertEqual(
+            gb_io.Join([gb_io.Join([a, b]), c]).simplify(),
+            gb_io.Join([a, b, c]),
+        )
+        self.assertEqual(
+            gb_io.Order([a, gb_io.Order([b, c])]).simplify(),
+            gb_io.Order([a, b, c]),
+        )
+        # nested locations of a different kind are kept
+        self.assertEqual(
+            gb_io.Join([gb_io.Order([a, b]), c]).simplify(),
+            gb_io.Join([gb_io.Order([a, b]), c]),
+        )
+        self.assertEqual(
+            gb_io.Join([gb_io.Complement(gb_io.Join([a])), c]).simplify(),
+            gb_io.Join([gb_io.Complement(a), c]),
+        )
+
+    def test_simplify_complement(self):
+        r = gb_io.Range(0, 100)
+        self.assertEqual(gb_io.Complement(gb_io.Complement(r)).simplify(), r)
+        self.assertEqual(
+            gb_io.Complement(gb_io.Join([gb_io.Complement(r)])).simplify(), r
+        )
+        self.assertEqual(gb_io.Complement(r).simplify(), gb_io.Complement(r))
+
+    def test_simplify_external(self):
+        loc = gb_io.External("J00194.1", gb_io.Join([gb_io.Range(100, 202)]))
+        self.assertEqual(loc.simplify(), gb_io.External("J00194.1", gb_io.Range(100, 202)))
+        loc = gb_io.External("J00194.1")
+        self.assertEqual(loc.simplify(), loc)
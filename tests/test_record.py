@@ -0,0 +1,1039 @@
+import copy
+import gc
+import io
+import os
+import sys
+import tempfile
+import unittest
+import zlib
+
+import gb_io
+
+DATA_FOLDER = os.path.realpath(os.path.join(__file__, os.path.pardir, "data"))
+
+
+class TestRecord(unittest.TestCase):
+
+    @classmethod
+    def setUpClass(cls):
+        cls.path = os.path.join(DATA_FOLDER, "AY048670.1.gb")
+
+    def test_deepcopy(self):
+        record = gb_io.load(self.path)[0]
+        copied = copy.deepcopy(record)
+        self.assertIsNot(copied.features, record.features)
+        self.assertIsNot(copied.features[1], record.features[1])
+        self.assertEqual(copied.features[1].kind, "CDS")
+        copied.features[1].kind = "gene"
+        self.assertEqual(copied.features[1].kind, "gene")
+        self.assertEqual(record.features[1].kind, "CDS")
+        self.assertIs(record.features[1].kind, record.features[2].kind)
+        self.assertIs(sys.intern(record.features[1].kind), record.features[1].kind)
+        self.assertIs(copied.features[2].kind, record.features[2].kind)
+
+    def test_deepcopy_sequence(self):
+        record = gb_io.load(self.path)[0]
+        copied = copy.deepcopy(record)
+        copied.sequence[0:4] = b"nnnn"
+        self.assertEqual(copied.sequence[:4], b"nnnn")
+        self.assertNotEqual(record.sequence[:4], b"nnnn")
+
+    def test_copy(self):
+        record = gb_io.load(self.path)[0]
+        features = record.features
+        copied = copy.copy(record)
+        self.assertIs(copied.features, features)
+
+    def test_features_in_range(self):
+        record = gb_io.load(self.path)[0]
+        features = record.features_in_range(0, 10)
+        self.assertTrue(features)
+        for feature in features:
+            self.assertTrue(feature.location.intersects_range(0, 10))
+        self.assertIs(features[0], record.features[0])
+        cds = record.features_in_range(0, len(record.sequence), kind="CDS")
+        self.assertEqual(cds, [f for f in record.features if f.kind == "CDS"])
+        self.assertEqual(record.features_in_range(0, 0), [])
+
+    def test_overlapping_features(self):
+        record = gb_io.load(self.path)[0]
+        pairs = record.overlapping_features(kind="CDS")
+        expected = [
+            (f1, f2)
+            for i, f1 in enumerate(record.features)
+            for f2 in record.features[i+1:]
+            if f1.kind == f2.kind == "CDS" and f1.location.overlaps(f2.location)
+        ]
+        self.assertTrue(pairs)
+        self.assertEqual(len(pairs), len(expected))
+        for (a, b), (x, y) in zip(pairs, expected):
+            self.assertIs(a, x)
+            self.assertIs(b, y)
+
+    def test_overlapping_features_same_strand(self):
+        features = [
+            gb_io.Feature("gene", gb_io.Range(0, 100)),
+            gb_io.Feature("gene", gb_io.Complement(gb_io.Range(50, 150))),
+            gb_io.Feature("gene", gb_io.Range(90, 200)),
+            gb_io.Feature("gene", gb_io.Range(200, 300)),
+        ]
+        record = gb_io.Record(b"a" * 300, features=features)
+        pairs = record.overlapping_features()
+        self.assertEqual(
+            [(features.index(a), features.index(b)) for a, b in pairs],
+            [(0, 1), (0, 2), (1, 2)],
+        )
+        pairs = record.overlapping_features(same_strand=True)
+        self.assertEqual(
+            [(features.index(a), features.index(b)) for a, b in pairs],
+            [(0, 2)],
+        )
+
+    def test_overlapping_features_join(self):
+        features = [
+            gb_io.Feature("CDS", gb_io.Join([gb_io.Range(0, 10), gb_io.Range(50, 60)])),
+            gb_io.Feature("CDS", gb_io.Range(20, 40)),
+            gb_io.Feature("CDS", gb_io.Range(55, 70)),
+            gb_io.Feature("misc_feature", gb_io.Range(0, 100)),
+        ]
+        record = gb_io.Record(b"a" * 100, circular=True, features=features)
+        pairs = record.overlapping_features(kind="CDS")
+        self.assertEqual(len(pairs), 1)
+        self.assertIs(pairs[0][0], record.features[0])
+        self.assertIs(pairs[0][1], record.features[2])
+        self.assertEqual(len(record.overlapping_features()), 4)
+
+    def test_build_hierarchy(self):
+        def feature(kind, location, **qualifiers):
+            qualifiers = [gb_io.Qualifier(k, v) for k, v in qualifiers.items()]
+            return gb_io.Feature(kind, location, qualifiers)
+
+        features = [
+            feature("gene", gb_io.Range(0, 100), locus_tag="A", gene="abc"),
+            feature("mRNA", gb_io.Join([gb_io.Range(0, 20), gb_io.Range(40, 100)]), locus_tag="A"),
+            feature("mRNA", gb_io.Join([gb_io.Range(0, 30), gb_io.Range(60, 100)]), locus_tag="A"),
+            feature("CDS", gb_io.Join([gb_io.Range(10, 20), gb_io.Range(40, 90)]), locus_tag="A"),
+            feature("CDS", gb_io.Join([gb_io.Range(10, 30), gb_io.Range(60, 90)]), locus_tag="A"),
+            feature("exon", gb_io.Range(0, 20), gene="abc"),
+            feature("gene", gb_io.Complement(gb_io.Range(120, 180)), gene="def"),
+            feature("CDS", gb_io.Complement(gb_io.Range(120, 180)), gene="def"),
+            feature("CDS", gb_io.Range(120, 180), gene="def"),
+            feature("misc_feature", gb_io.Range(0, 200), gene="abc"),
+        ]
+        record = gb_io.Record(b"a" * 200, features=features)
+        hierarchy = record.build_hierarchy()
+
+        def shape(nodes):
+            return [(features.index(f), shape(children)) for f, children in nodes]
+
+        self.assertEqual(
+            shape(hierarchy),
+            [
+                (0, [(1, [(3, [])]), (2, [(4, [])]), (5, [])]),
+                (6, [(7, [])]),
+                (8, []),
+                (9, []),
+            ],
+        )
+        self.assertIs(hierarchy[0][0], record.features[0])
+
+    def test_build_hierarchy_identifiers(self):
+        features = [
+            gb_io.Feature("gene", gb_io.Range(0, 100), [gb_io.Qualifier("locus_tag", "A"), gb_io.Qualifier("gene", "abc")]),
+            gb_io.Feature("CDS", gb_io.Range(0, 100), [gb_io.Qualifier("locus_tag", "B"), gb_io.Qualifier("gene", "abc")]),
+            gb_io.Feature("CDS", gb_io.Range(0, 100), [gb_io.Qualifier("gene", "abc")]),
+            gb_io.Feature("CDS", gb_io.Range(0, 100)),
+            gb_io.Feature("gene", gb_io.Range(200, 300), [gb_io.Qualifier("gene", "abc")]),
+        ]
+        record = gb_io.Record(b"a" * 300, features=features)
+        roots = [(record.features.index(f), len(c)) for f, c in record.build_hierarchy()]
+        self.assertEqual(roots, [(0, 1), (1, 0), (3, 0), (4, 0)])
+
+    def test_build_hierarchy_parsed(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "NC_005816.gb")
+        record = gb_io.load(path)[0]
+        hierarchy = record.build_hierarchy()
+        nodes = []
+        stack = list(hierarchy)
+        while stack:
+            feature, children = stack.pop()
+            nodes.append(feature)
+            stack.extend(children)
+        self.assertEqual(len(nodes), len(record.features))
+        for gene, children in hierarchy:
+            if gene.kind == "gene":
+                self.assertEqual([c.kind for c, _ in children], ["CDS"])
+                tag = lambda f: next(q.value for q in f.qualifiers if q.key == "locus_tag")
+                self.assertEqual(tag(children[0][0]), tag(gene))
+        self.assertEqual(gb_io.Record(b"").build_hierarchy(), [])
+
+    def test_gaps(self):
+        record = gb_io.Record(b"NNATGNNNCnGTNNNN")
+        self.assertEqual(record.gaps(), [(0, 2), (5, 8), (9, 10), (12, 16)])
+        self.assertEqual(record.gaps(min_length=3), [(5, 8), (12, 16)])
+        self.assertEqual(record.ungapped_length(), 6)
+        record.sequence[9:10] = b"a"
+        self.assertEqual(record.gaps(min_length=1), [(0, 2), (5, 8), (12, 16)])
+        self.assertEqual(record.ungapped_length(), 7)
+
+    def test_gaps_empty(self):
+        record = gb_io.Record(b"")
+        self.assertEqual(record.gaps(), [])
+        self.assertEqual(record.ungapped_length(), 0)
+        record = gb_io.load(self.path)[0]
+        self.assertEqual(record.gaps(), [])
+        self.assertEqual(record.ungapped_length(), len(record.sequence))
+
+    def test_validate_sequence(self):
+        record = gb_io.Record(b"ATGCNxTG-A-u")
+        self.assertEqual(record.validate_sequence(), {"x": [5], "-": [8, 10], "u": [11]})
+        self.assertEqual(record.validate_sequence("rna"), {"T": [1, 6], "x": [5], "-": [8, 10]})
+        self.assertEqual(record.validate_sequence("iupac"), {"x": [5], "-": [8, 10]})
+        record.sequence[5:6] = b"X"
+        self.assertEqual(record.validate_sequence("protein"), {"-": [8, 10]})
+        record = gb_io.load(self.path)[0]
+        self.assertEqual(record.validate_sequence(), {"s": [42504]})
+        self.assertEqual(record.validate_sequence("iupac", strict=True), {})
+        self.assertEqual(gb_io.Record(b"").validate_sequence(strict=True), {})
+
+    def test_validate_sequence_strict(self):
+        record = gb_io.Record(b"ATGCNxTG-A-")
+        with self.assertRaisesRegex(ValueError, "'x' at position 5"):
+            record.validate_sequence(strict=True)
+        record.sequence[5:6] = b"r"
+        with self.assertRaisesRegex(ValueError, "'-' at position 8"):
+            record.validate_sequence("iupac", strict=True)
+
+    def test_validate_sequence_error(self):
+        record = gb_io.Record(b"ATGC")
+        self.assertRaises(ValueError, record.validate_sequence, "DNA")
+        self.assertRaises(TypeError, record.validate_sequence, 1)
+
+    def test_count_features(self):
+        record = gb_io.load(self.path)[0]
+        self.assertEqual(record.count_features(), 67)
+        self.assertEqual(record.count_features("CDS"), 66)
+        self.assertEqual(record.count_features("tRNA"), 0)
+        self.assertTrue(record.has_feature("CDS"))
+        self.assertFalse(record.has_feature("tRNA"))
+        record.features[1].kind = "tRNA"
+        self.assertEqual(record.count_features(), 67)
+        self.assertEqual(record.count_features("CDS"), 65)
+        self.assertEqual(record.count_features("tRNA"), 1)
+        self.assertTrue(record.has_feature("tRNA"))
+
+    def test_features_of_kind(self):
+        record = gb_io.load(self.path)[0]
+        features = record.features_of_kind("CDS")
+        self.assertEqual(len(features), 66)
+        self.assertIs(features[0], record.features[1])
+        self.assertEqual(record.features_of_kind("tRNA"), [])
+        record.features[1].kind = "tRNA"
+        self.assertEqual(record.features_of_kind("tRNA"), [record.features[1]])
+        self.assertEqual(len(record.features_of_kind("CDS")), 65)
+        record = gb_io.Record(b"ATGC")
+        self.assertEqual(record.features_of_kind("CDS"), [])
+
+    def test_iter_features(self):
+        record = gb_io.load(self.path)[0]
+        it = record.iter_features(kind="CDS")
+        self.assertIsInstance(it, gb_io.FeatureIterator)
+        self.assertIs(iter(it), it)
+        for feature in it:
+            break
+        self.assertEqual(feature, record.features[1])
+        self.assertIs(next(it), record.features[2])
+
+    def test_iter_features_range(self):
+        record = gb_io.load(self.path)[0]
+        features = list(record.iter_features(in_range=(900, 1000)))
+        self.assertEqual(features, record.features_in_range(900, 1000))
+        features = list(record.iter_features(kind="CDS", in_range=(900, 1000)))
+        self.assertEqual(features, [record.features[2]])
+
+    def test_iter_features_snapshot(self):
+        record = gb_io.load(self.path)[0]
+        it = record.iter_features(kind="CDS")
+        self.assertEqual(next(it), record.features[1])
+        feature = gb_io.Feature("CDS", gb_io.Range(0, 3))
+        record.features.append(feature)
+        features = list(it)
+        self.assertEqual(len(features), 65)
+        self.assertIsNot(features[-1], feature)
+        it = record.iter_features()
+        record.features.clear()
+        self.assertRaises(StopIteration, next, it)
+
+    def test_iter_features_owned(self):
+        record = gb_io.load(self.path)[0]
+        feature = next(record.iter_features(kind="CDS"))
+        feature.kind = "misc_feature"
+        self.assertEqual(record.features[1].kind, "CDS")
+        feature = next(record.iter_features(kind="CDS"))
+        self.assertIs(feature, record.features[1])
+
+    def test_set_sequence_from_path(self):
+        record = gb_io.Record(b"")
+        with tempfile.TemporaryDirectory() as dst:
+            path = os.path.join(dst, "seq.fna")
+            with open(path, "w") as f:
+                f.write(">seq1 test sequence\nATGC ATGC\nNNNN\n\n")
+            record.set_sequence_from_path(path)
+        self.assertEqual(record.sequence, b"ATGCATGCNNNN")
+
+    def test_set_sequence_from_path_error(self):
+        record = gb_io.Record(b"")
+        with tempfile.TemporaryDirectory() as dst:
+            path = os.path.join(dst, "seq.fna")
+            self.assertRaises(OSError, record.set_sequence_from_path, path)
+            with open(path, "w") as f:
+                f.write(">seq1\nATGC\n>seq2\nATGC\n")
+            self.assertRaises(ValueError, record.set_sequence_from_path, path)
+            with open(path, "w") as f:
+                f.write("ATGC1234\n")
+            self.assertRaises(ValueError, record.set_sequence_from_path, path)
+
+    def test_comments(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "NC_005816.gb")
+        record = gb_io.load(path)[0]
+        self.assertEqual(len(record.comments), 1)
+        self.assertTrue(record.comments[0].startswith("PROVISIONAL REFSEQ:"))
+        self.assertTrue(record.comments[0].endswith("\nCOMPLETENESS: full length."))
+        copied = gb_io.loads(gb_io.dumps(record))[0]
+        self.assertEqual(copied.comments, record.comments)
+
+    def test_comments_setter(self):
+        record = gb_io.Record(b"ATGC", name="TEST")
+        self.assertEqual(record.comments, [])
+        self.assertNotIn("COMMENT", gb_io.dumps(record))
+        record.comments = ("first block", "second block\nwith two lines")
+        self.assertEqual(record.comments, ["first block", "second block\nwith two lines"])
+        text = gb_io.dumps(record)
+        self.assertIn("COMMENT     first block\n", text)
+        self.assertIn("COMMENT     second block\n            with two lines\n", text)
+        self.assertEqual(gb_io.loads(text)[0].comments, record.comments)
+        record.comments = []
+        self.assertNotIn("COMMENT", gb_io.dumps(record))
+        self.assertRaises(TypeError, setattr, record, "comments", "comment")
+
+    def test_structured_comments(self):
+        record = gb_io.Record(b"ATGC", name="TEST")
+        self.assertEqual(record.structured_comments, {})
+        record.comments = [
+            "Assembled from reads.\n"
+            "\n"
+            "##Assembly-Data-START##\n"
+            "Assembly Method       :: Trinity r2013-02-16\n"
+            "Sequencing Technology :: Illumina; Oxford\n"
+            "    Nanopore\n"
+            "##Assembly-Data-END##\n"
+            "##Genome-Annotation-Data-START##\n"
+            "Annotation Provider :: NCBI\n"
+            "##Genome-Annotation-Data-END##",
+        ]
+        self.assertEqual(
+            record.structured_comments,
+            {
+                "Assembly-Data": {
+                    "Assembly Method": "Trinity r2013-02-16",
+                    "Sequencing Technology": "Illumina; Oxford Nanopore",
+                },
+                "Genome-Annotation-Data": {"Annotation Provider": "NCBI"},
+            },
+        )
+        loaded = gb_io.loads(gb_io.dumps(record))[0]
+        self.assertEqual(loaded.structured_comments, record.structured_comments)
+
+    def test_structured_comments_setter(self):
+        record = gb_io.Record(b"ATGC", name="TEST")
+        record.structured_comments = {"Assembly-Data": {"Assembly Method": "SPAdes"}}
+        self.assertEqual(
+            record.comments,
+            ["##Assembly-Data-START##\nAssembly Method :: SPAdes\n##Assembly-Data-END##"],
+        )
+        record.comments = [record.comments[0] + "\nSome notes.", "Last comment."]
+        record.structured_comments = {
+            "Assembly-Data": {"Assembly Method": "SPAdes", "Coverage": "100x"},
+            "Genome-Annotation-Data": {"Annotation Provider": "NCBI"},
+        }
+        self.assertEqual(record.comments, [
+            "##Assembly-Data-START##\n"
+            "Assembly Method :: SPAdes\n"
+            "Coverage        :: 100x\n"
+            "##Assembly-Data-END##\n"
+            "Some notes.",
+            "Last comment.\n\n"
+            "##Genome-Annotation-Data-START##\n"
+            "Annotation Provider :: NCBI\n"
+            "##Genome-Annotation-Data-END##",
+        ])
+        text = gb_io.dumps(record)
+        self.assertIn("            Coverage        :: 100x\n", text)
+        self.assertEqual(gb_io.loads(text)[0].structured_comments, record.structured_comments)
+        record.structured_comments = {}
+        self.assertEqual(record.comments, ["Some notes.", "Last comment."])
+        invalid = {"Assembly-Data": {"Assembly Method": "two\nlines"}}
+        self.assertRaises(ValueError, setattr, record, "structured_comments", invalid)
+        self.assertRaises(ValueError, setattr, record, "structured_comments", {"": {}})
+        self.assertRaises(TypeError, setattr, record, "structured_comments", {"a": 1})
+
+    def test_contig(self):
+        record = gb_io.load(self.path)[0]
+        self.assertIs(record.contig, None)
+        contig = gb_io.Join([
+            gb_io.External("AL391218.9", gb_io.Range(105172, 108462)),
+            gb_io.Complement(gb_io.External("AL512330.12", gb_io.Range(0, 182490))),
+        ])
+        record.contig = contig
+        self.assertIs(record.contig, contig)
+        loaded = gb_io.loads(gb_io.dumps(record))[0]
+        self.assertEqual(loaded.contig, contig)
+        loaded.contig = None
+        self.assertNotIn("CONTIG", gb_io.dumps(loaded))
+
+    def test_contig_gaps(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "NT_019265.gb")
+        record = gb_io.load(path)[0]
+        self.assertRaises(NotImplementedError, getattr, record, "contig")
+        self.assertRaises(NotImplementedError, getattr, record, "contig")
+        self.assertIn("CONTIG      join(AL391218.9:105173..108462,gap(100),", gb_io.dumps(record))
+
+    def test_kind(self):
+        record = gb_io.load(self.path)[0]
+        self.assertEqual(record.kind, "nucleotide")
+        path = os.path.join(DATA_FOLDER, "biopython", "1MRR_A.gp")
+        record = gb_io.load(path)[0]
+        self.assertEqual(record.kind, "protein")
+        record = gb_io.Record(b"MEFLQ")
+        self.assertEqual(record.kind, "protein")
+        record = gb_io.Record(b"ATGC", molecule_type="mRNA")
+        self.assertEqual(record.kind, "nucleotide")
+        self.assertIs(gb_io.Record(b"").kind, None)
+
+    def test_accession_type(self):
+        record = gb_io.load(self.path)[0]
+        self.assertEqual(record.accession_type, "insdc")
+        path = os.path.join(DATA_FOLDER, "biopython", "NC_005816.gb")
+        record = gb_io.load(path)[0]
+        self.assertEqual(record.accession_type, "refseq")
+        for accession, expected in [
+            ("NZ_AAAA01000001.1", "refseq"),
+            ("XP_123456", "refseq"),
+            ("U49845", "insdc"),
+            ("AAA12345", "insdc"),
+            ("AB12345678", "insdc"),
+            ("AAAA01000001", "wgs"),
+            ("AAAAAA010000001", "wgs"),
+            ("QQ_123", "unknown"),
+            ("12345", "unknown"),
+        ]:
+            record = gb_io.Record(b"", accession=accession)
+            self.assertEqual(record.accession_type, expected, accession)
+        self.assertIs(gb_io.Record(b"").accession_type, None)
+
+    def test_contains_position(self):
+        record = gb_io.Record(b"ATGCATGC")
+        self.assertIn(0, record)
+        self.assertIn(7, record)
+        self.assertNotIn(8, record)
+        self.assertNotIn(-1, record)
+        record.circular = True
+        self.assertIn(8, record)
+        self.assertIn(-1, record)
+        self.assertNotIn(0, gb_io.Record(b"", circular=True))
+        self.assertRaises(TypeError, record.__contains__, True)
+        self.assertRaises(TypeError, record.__contains__, False)
+
+    def test_contains_feature(self):
+        record = gb_io.load(self.path)[0]
+        self.assertIn(record.features[1], record)
+        self.assertNotIn(gb_io.Feature("CDS", gb_io.Range(0, 10)), record)
+        copied = copy.copy(record.features[1])
+        self.assertEqual(copied, record.features[1])
+        self.assertNotIn(copied, record)
+        self.assertIn("CDS", record)
+        self.assertNotIn("tRNA", record)
+        self.assertRaises(TypeError, record.__contains__, 1.0)
+
+    def test_len(self):
+        record = gb_io.load(self.path)[0]
+        self.assertEqual(len(record), len(record.sequence))
+        self.assertEqual(len(record), record.length)
+        record.sequence.extend(b"ATGC")
+        self.assertEqual(len(record), record.length + 4)
+        self.assertEqual(len(gb_io.Record(b"ATGC", length=10)), 4)
+        self.assertEqual(len(gb_io.Record(b"", length=10)), 10)
+        self.assertEqual(len(gb_io.Record(b"")), 0)
+        self.assertTrue(gb_io.Record(b""))
+
+    def test_split_at(self):
+        record = gb_io.Record(
+            b"ATGCATGCATGCATGCATGC",
+            name="test",
+            features=[
+                gb_io.Feature("gene", gb_io.Range(2, 8)),
+                gb_io.Feature("CDS", gb_io.Complement(gb_io.Range(4, 16))),
+                gb_io.Feature("misc_feature", gb_io.Range(11, 14)),
+            ],
+        )
+        fragments = record.split_at([5, 10])
+        self.assertEqual(len(fragments), 3)
+        self.assertEqual(
+            [bytes(fragment.sequence) for fragment in fragments],
+            [b"ATGCA", b"TGCAT", b"GCATGCATGC"],
+        )
+        self.assertEqual([f.name for f in fragments], ["test"] * 3)
+
+        self.assertEqual([f.kind for f in fragments[0].features], ["gene", "CDS"])
+        self.assertEqual(fragments[0].features[0].location, gb_io.Range(2, 5, after=True))
+        self.assertEqual(
+            fragments[0].features[1].location,
+            gb_io.Complement(gb_io.Range(4, 5, after=True)),
+        )
+        self.assertEqual([f.kind for f in fragments[1].features], ["gene", "CDS"])
+        self.assertEqual(fragments[1].features[0].location, gb_io.Range(0, 3, before=True))
+        self.assertEqual(
+            fragments[1].features[1].location,
+            gb_io.Complement(gb_io.Range(0, 5, before=True, after=True)),
+        )
+        self.assertEqual([f.kind for f in fragments[2].features], ["CDS", "misc_feature"])
+        self.assertEqual(fragments[2].features[1].location, gb_io.Range(1, 4))
+
+    def test_split_at_external(self):
+        record = gb_io.Record(
+            b"ATGCATGCAT",
+            features=[
+                gb_io.Feature("misc_feature", gb_io.External("J00194.1", gb_io.Range(0, 5))),
+                gb_io.Feature(
+                    "CDS",
+                    gb_io.Join([gb_io.Range(2, 4), gb_io.External("J00194.1", gb_io.Range(0, 5))]),
+                ),
+            ],
+        )
+        fragments = record.split_at([5])
+        self.assertEqual([f.kind for f in fragments[0].features], ["CDS"])
+        self.assertEqual(fragments[0].features[0].location, gb_io.Range(2, 4))
+        self.assertEqual(fragments[1].features, [])
+
+    def test_split_at_invalid(self):
+        record = gb_io.Record(b"ATGCATGCAT")
+        self.assertEqual(len(record.split_at([])), 1)
+        self.assertRaises(ValueError, record.split_at, [5, 3])
+        self.assertRaises(ValueError, record.split_at, [0])
+        self.assertRaises(ValueError, record.split_at, [10])
+
+    def test_reverse_complement(self):
+        record = gb_io.Record(
+            b"ATGCRYKMBVDHNacgt",
+            name="TEST",
+            circular=True,
+            features=[
+                gb_io.Feature("CDS", gb_io.Range(0, 3, before=True)),
+                gb_io.Feature("gene", gb_io.Complement(gb_io.Range(4, 8))),
+                gb_io.Feature("mRNA", gb_io.Join([gb_io.Range(0, 2), gb_io.Range(5, 9)])),
+            ],
+        )
+        rc = record.reverse_complement()
+        self.assertEqual(rc.sequence, bytearray(b"acgtNDHBVKMRYGCAT"))
+        self.assertEqual(rc.name, "TEST")
+        self.assertTrue(rc.circular)
+        self.assertEqual(
+            [feature.location for feature in rc.features],
+            [
+                gb_io.Complement(gb_io.Range(14, 17, after=True)),
+                gb_io.Range(9, 13),
+                gb_io.Complement(gb_io.Join([gb_io.Range(8, 12), gb_io.Range(15, 17)])),
+            ],
+        )
+        # the original record is not modified
+        self.assertEqual(record.sequence, bytearray(b"ATGCRYKMBVDHNacgt"))
+        self.assertEqual(record.features[0].location, gb_io.Range(0, 3, before=True))
+        # the reverse complement of the reverse complement is the record
+        self.assertEqual(rc.reverse_complement(), record)
+
+    def test_reverse_complement_extract(self):
+        record = gb_io.load(self.path)[0]
+        rc = record.reverse_complement()
+        self.assertEqual(len(rc.features), len(record.features))
+        for f1, f2 in zip(record.features, rc.features):
+            self.assertEqual(f1.kind, f2.kind)
+            if f1.kind == "CDS":
+                self.assertEqual(f1.extract(record), f2.extract(rc))
+
+    def test_reverse_complement_empty(self):
+        self.assertRaises(ValueError, gb_io.Record(b"").reverse_complement)
+
+    def test_set_origin(self):
+        cds = gb_io.Feature("CDS", gb_io.Range(6, 12))
+        gene = gb_io.Feature("gene", gb_io.Complement(gb_io.Range(1, 4)))
+        source = gb_io.Feature("source", gb_io.Range(0, 12))
+        record = gb_io.Record(b"CCCTAAATGAAA", circular=True, features=[source, gene, cds])
+        sequences = [f.extract(record) for f in record.features]
+        record.set_origin(9)
+        self.assertEqual(record.sequence, bytearray(b"AAACCCTAAATG"))
+        self.assertEqual(record.features[0].location, gb_io.Range(0, 12))
+        self.assertEqual(record.features[1].location, gb_io.Complement(gb_io.Range(4, 7)))
+        self.assertEqual(
+            record.features[2].location,
+            gb_io.Join([gb_io.Range(9, 12), gb_io.Range(0, 3)]),
+        )
+        self.assertEqual(record.features[0].extract(record), bytes(record.sequence))
+        self.assertEqual([f.extract(record) for f in record.features[1:]], sequences[1:])
+
+    def test_set_origin_join(self):
+        location = gb_io.Join([gb_io.Range(0, 2), gb_io.Range(4, 8)])
+        record = gb_io.Record(b"ATGCATGCAT", circular=True, features=[gb_io.Feature("CDS", location)])
+        expected = record.features[0].extract(record)
+        record.set_origin(6)
+        self.assertEqual(
+            record.features[0].location,
+            gb_io.Join([
+                gb_io.Range(4, 6),
+                gb_io.Range(8, 10),
+                gb_io.Range(0, 2),
+            ]),
+        )
+        self.assertEqual(record.features[0].extract(record), expected)
+
+    def test_set_origin_shared(self):
+        record = gb_io.load(self.path)[0]
+        record.circular = True
+        sequence = record.sequence
+        features = record.features
+        expected = [f.extract(record) for f in features if f.kind == "CDS"]
+        record.set_origin(100)
+        self.assertEqual(len(sequence), len(record.sequence))
+        self.assertIs(record.sequence, sequence)
+        self.assertEqual([f.extract(record) for f in features if f.kind == "CDS"], expected)
+
+    def test_set_origin_duplicate(self):
+        cds = gb_io.Feature("CDS", gb_io.Range(6, 9))
+        record = gb_io.Record(b"CCCTAAATGAAA", circular=True, features=[cds, cds])
+        record.set_origin(3)
+        self.assertEqual(cds.location, gb_io.Range(3, 6))
+        self.assertEqual(cds.extract(record), b"ATG")
+
+    def test_set_origin_atomic(self):
+        cds = gb_io.Feature("CDS", gb_io.Range(6, 9))
+        record = gb_io.Record(b"CCCTAAATGAAA", circular=True, features=[cds])
+        record.features.append("not a feature")
+        self.assertRaises(TypeError, record.set_origin, 3)
+        self.assertEqual(record.sequence, bytearray(b"CCCTAAATGAAA"))
+        self.assertEqual(cds.location, gb_io.Range(6, 9))
+
+    def test_set_origin_error(self):
+        record = gb_io.Record(b"ATGAAACCCTAA")
+        self.assertRaises(ValueError, record.set_origin, 3)
+        record.circular = True
+        self.assertRaises(ValueError, record.set_origin, 12)
+        self.assertRaises(ValueError, record.set_origin, -1)
+        self.assertEqual(record.sequence, bytearray(b"ATGAAACCCTAA"))
+
+    def test_extract(self):
+        record = gb_io.Record(b"ATGAAACCCTAA")
+        self.assertEqual(record.extract(gb_io.Range(3, 9)), b"AAACCC")
+        self.assertEqual(record.extract(gb_io.Complement(gb_io.Range(0, 6))), b"TTTCAT")
+        join = gb_io.Join([gb_io.Range(9, 12), gb_io.Range(0, 3)])
+        self.assertEqual(record.extract(join), b"TAAATG")
+        self.assertEqual(record.extract(gb_io.Range(5, 5)), b"")
+        record = gb_io.load(self.path)[0]
+        for feature in record.features:
+            if feature.kind == "CDS":
+                self.assertEqual(record.extract(feature.location), feature.extract(record))
+
+    def test_extract_circular(self):
+        record = gb_io.Record(b"ATGAAACCCTAA", circular=True)
+        self.assertEqual(record.extract(gb_io.Range(9, 15)), b"TAAATG")
+        self.assertEqual(record.extract(gb_io.Range(21, 27)), b"TAAATG")
+        self.assertEqual(record.extract(gb_io.Range(-3, 3)), b"TAAATG")
+        self.assertEqual(record.extract(gb_io.Complement(gb_io.Range(9, 15))), b"CATTTA")
+        self.assertEqual(record.extract(gb_io.Range(6, 18)), b"CCCTAAATGAAA")
+        self.assertRaises(IndexError, record.extract, gb_io.Range(6, 19))
+
+    def test_extract_error(self):
+        record = gb_io.Record(b"ATGAAACCCTAA")
+        self.assertRaises(IndexError, record.extract, gb_io.Range(9, 15))
+        self.assertRaises(IndexError, record.extract, gb_io.Range(-3, 3))
+        self.assertRaises(IndexError, record.extract, gb_io.Join([gb_io.Range(0, 3), gb_io.Range(10, 13)]))
+        self.assertRaises(ValueError, record.extract, gb_io.External("J00194.1", gb_io.Range(0, 6)))
+        self.assertRaises(TypeError, record.extract, (0, 6))
+
+    def _make_genes(self):
+        return gb_io.Record(b"ATGAAATAGATGCCCTAA", circular=True, features=[
+            gb_io.Feature("gene", gb_io.Range(0, 9), [gb_io.Qualifier("locus_tag", "a")]),
+            gb_io.Feature("CDS", gb_io.Range(0, 9), [gb_io.Qualifier("locus_tag", "a")]),
+            gb_io.Feature("CDS", gb_io.Complement(gb_io.Range(9, 18)), [gb_io.Qualifier("gene", "x")]),
+            gb_io.Feature("CDS", gb_io.Range(15, 24), [gb_io.Qualifier("locus_tag", "a")]),
+        ])
+
+    def test_gene_sequences(self):
+        record = self._make_genes()
+        self.assertEqual(record.gene_sequences(), {"a": b"ATGAAATAG", "a_2": b"TAAATGAAA"})
+        self.assertEqual(record.gene_sequences(key="gene"), {"x": b"TTAGGGCAT"})
+        self.assertEqual(record.gene_sequences("gene"), {"a": b"ATGAAATAG"})
+        self.assertEqual(
+            record.gene_sequences(None),
+            {"a": b"ATGAAATAG", "a_2": b"ATGAAATAG", "a_3": b"TAAATGAAA"},
+        )
+
+    def test_gene_sequences_list(self):
+        record = self._make_genes()
+        self.assertEqual(
+            record.gene_sequences(duplicates="list"),
+            {"a": [b"ATGAAATAG", b"TAAATGAAA"]},
+        )
+
+    def test_gene_sequences_parsed(self):
+        record = gb_io.load(self.path)[0]
+        sequences = record.gene_sequences(key="protein_id", duplicates="list")
+        expected = {}
+        for feature in record.features:
+            qualifiers = feature.qualifiers_dict()
+            if feature.kind == "CDS" and "protein_id" in qualifiers:
+                key = qualifiers["protein_id"][0]
+                expected.setdefault(key, []).append(feature.extract(record))
+        self.assertTrue(expected)
+        self.assertEqual(sequences, expected)
+        # features are converted to Python objects once accessed
+        self.assertTrue(record.features)
+        self.assertEqual(record.gene_sequences(key="protein_id", duplicates="list"), sequences)
+        self.assertEqual(record.gene_sequences(key="not_a_qualifier"), {})
+
+    def test_gene_sequences_error(self):
+        record = self._make_genes()
+        with self.assertRaises(ValueError) as ctx:
+            record.gene_sequences(strict=True)
+        self.assertIn("feature 2", str(ctx.exception))
+        self.assertRaises(ValueError, record.gene_sequences, duplicates="error")
+        record.circular = False
+        self.assertRaises(IndexError, record.gene_sequences)
+
+    def test_rename_features_kind(self):
+        record = gb_io.load(self.path)[0]
+        cds = record.count_features("CDS")
+        self.assertEqual(record.rename_features_kind("CDS", "coding"), cds)
+        self.assertEqual(record.count_features("CDS"), 0)
+        self.assertEqual(record.count_features("coding"), cds)
+        self.assertEqual(record.rename_features_kind("CDS", "coding"), 0)
+        self.assertIs(record.features[1].kind, sys.intern("coding"))
+
+    def test_rename_features_kind_shared(self):
+        record = gb_io.load(self.path)[0]
+        features = record.features
+        cds = record.count_features("CDS")
+        self.assertEqual(record.rename_features_kind("CDS", "coding"), cds)
+        self.assertEqual(sum(f.kind == "coding" for f in features), cds)
+        buffer = io.BytesIO()
+        gb_io.dump(record, buffer)
+        buffer.seek(0)
+        loaded = gb_io.load(buffer)[0]
+        self.assertEqual(loaded.count_features("coding"), cds)
+        self.assertEqual(loaded.count_features("CDS"), 0)
+
+    def test_identity(self):
+        record = gb_io.load(self.path)[0]
+        key = record.identity()
+        self.assertTrue(key.startswith("AY048670.1:crc64:"))
+        self.assertEqual(key, copy.deepcopy(record).identity())
+        record.features.clear()
+        record.date = None
+        self.assertEqual(record.identity(), key)
+        record.sequence[0:1] = b"N"
+        self.assertNotEqual(record.identity(), key)
+
+    def test_identity_checksum(self):
+        record = gb_io.Record(b"acgtacgtacgt", accession="X00001")
+        self.assertEqual(record.identity(), "X00001:crc64:C4FBB762C4A87EBD")
+        self.assertEqual(
+            record.identity("crc32"),
+            "X00001:crc32:{:08X}".format(zlib.crc32(b"ACGTACGTACGT")),
+        )
+        self.assertRaises(ValueError, record.identity, "md4")
+
+    def test_identity_unnamed(self):
+        record = gb_io.Record(b"acgtacgtacgt")
+        self.assertRaises(ValueError, record.identity)
+        record.accession = ""
+        self.assertRaises(ValueError, record.identity)
+        record.name = "TEST"
+        self.assertTrue(record.identity().startswith("TEST:crc64:"))
+
+    def test_identity_to(self):
+        record = gb_io.load(self.path)[0]
+        self.assertEqual(record.identity_to(record), (1.0, len(record.sequence)))
+        other = gb_io.Record(record.sequence.lower()[:100])
+        self.assertEqual(record.identity_to(other), (1.0, 100))
+        self.assertEqual(other.identity_to(record), (1.0, 100))
+        other.sequence[:25] = b"N" * 25
+        self.assertEqual(record.identity_to(other), (0.75, 100))
+        self.assertEqual(record.identity_to(gb_io.Record(b"")), (0.0, 0))
+        self.assertRaises(TypeError, record.identity_to, record.sequence)
+
+    def test_equals(self):
+        record = gb_io.load(self.path)[0]
+        other = gb_io.load(self.path)[0]
+        self.assertTrue(record.equals(other))
+        self.assertTrue(record.equals(copy.deepcopy(record)))
+        other.name = "other"
+        self.assertFalse(record.equals(other))
+        self.assertTrue(record.equals(other, ignore={"name"}))
+        self.assertRaises(ValueError, record.equals, other, ignore=["unknown"])
+
+    def test_eq(self):
+        record = gb_io.load(self.path)[0]
+        other = gb_io.load(self.path)[0]
+        self.assertEqual(record, record)
+        self.assertEqual(record, other)
+        self.assertEqual(record, copy.deepcopy(record))
+        self.assertEqual(record, gb_io.loads(gb_io.dumps([record]))[0])
+        self.assertNotEqual(record, "record")
+        self.assertRaises(TypeError, hash, record)
+        other.name = "other"
+        self.assertNotEqual(record, other)
+        other = copy.deepcopy(record)
+        other.origin_note = "Some note"
+        self.assertNotEqual(record, other)
+        other = copy.deepcopy(record)
+        other.features[0].qualifiers[0].value = "other"
+        self.assertFalse(record == other)
+        self.assertTrue(record != other)
+
+    def test_equals_ignore_order(self):
+        record = gb_io.load(self.path)[0]
+        other = gb_io.load(self.path)[0]
+        other.features = other.features[::-1]
+        qualifiers = other.features[0].qualifiers
+        other.features[0].qualifiers = qualifiers[::-1]
+        self.assertFalse(record.equals(other))
+        self.assertTrue(record.equals(other, ignore_order=True))
+        del other.features[0]
+        self.assertFalse(record.equals(other, ignore_order=True))
+        self.assertTrue(record.equals(other, ignore_order=True, ignore={"features"}))
+
+    def test_gc_content(self):
+        self.assertEqual(gb_io.Record(b"ATGC").gc_content(), 0.5)
+        self.assertEqual(gb_io.Record(b"gggc").gc_content(), 1.0)
+        self.assertEqual(gb_io.Record(b"ATNN").gc_content(), 0.0)
+        self.assertEqual(gb_io.Record(b"GCSN").gc_content(), 0.5)
+        self.assertEqual(gb_io.Record(b"").gc_content(), 0.0)
+
+    def test_gc_content_parsed(self):
+        record = gb_io.load(self.path)[0]
+        sequence = record.sequence.upper()
+        expected = (sequence.count(b"G") + sequence.count(b"C")) / len(sequence)
+        self.assertAlmostEqual(record.gc_content(), expected)
+
+    def test_base_counts(self):
+        record = gb_io.Record(b"ACgtnNrA-")
+        counts = record.base_counts()
+        self.assertEqual(counts, {"A": 2, "C": 1, "G": 1, "T": 1, "N": 2, "R": 1, "-": 1})
+        self.assertEqual(list(counts)[:4], ["A", "C", "G", "T"])
+        self.assertEqual(gb_io.Record(b"").base_counts(), {"A": 0, "C": 0, "G": 0, "T": 0})
+
+    def test_base_counts_shared_sequence(self):
+        record = gb_io.Record(b"AAAA")
+        record.sequence[:2] = b"cc"
+        self.assertEqual(record.base_counts(), {"A": 2, "C": 2, "G": 0, "T": 0})
+        self.assertEqual(record.gc_content(), 0.5)
+
+    def test_to_fasta(self):
+        record = gb_io.load(self.path)[0]
+        fasta = record.to_fasta()
+        lines = fasta.splitlines()
+        definition = " ".join(record.definition.split())
+        self.assertEqual(lines[0], ">{} {}".format(record.accession.split()[0], definition))
+        self.assertTrue(all(len(line) <= 70 for line in lines[1:]))
+        self.assertEqual(len(lines[1]), 70)
+        self.assertEqual("".join(lines[1:]), record.sequence.decode().upper())
+        self.assertTrue(fasta.endswith("\n"))
+
+    def test_to_fasta_line_width(self):
+        record = gb_io.Record(b"acgt" * 10, name="test")
+        self.assertEqual(record.to_fasta(line_width=16), ">test\n" + "ACGT" * 4 + "\n" + "ACGT" * 4 + "\n" + "ACGT" * 2 + "\n")
+        self.assertEqual(record.to_fasta(line_width=0), ">test\n" + "ACGT" * 10 + "\n")
+        self.assertRaises(OverflowError, record.to_fasta, line_width=-1)
+
+    def test_to_fasta_header(self):
+        record = gb_io.Record(b"ATGC", name="test")
+        self.assertEqual(record.to_fasta().splitlines()[0], ">test")
+        record.accession = "X56734 S46826"
+        record.definition = "Trifolium repens mRNA\nfor beta-glucosidase."
+        self.assertEqual(record.to_fasta().splitlines()[0], ">X56734 Trifolium repens mRNA for beta-glucosidase.")
+        record.version = "X56734.1"
+        self.assertEqual(record.to_fasta().splitlines()[0], ">X56734 Trifolium repens mRNA for beta-glucosidase.")
+        record.accession = None
+        self.assertEqual(record.to_fasta().splitlines()[0], ">test Trifolium repens mRNA for beta-glucosidase.")
+
+    def test_to_fasta_empty(self):
+        record = gb_io.Record(b"", name="empty")
+        self.assertEqual(record.to_fasta(), ">empty\n")
+
+    def test_to_fasta_shared_sequence(self):
+        record = gb_io.Record(b"acgt", name="test")
+        record.sequence[0:1] = b"t"
+        self.assertEqual(record.to_fasta(), ">test\nTCGT\n")
+
+
+class TestSource(unittest.TestCase):
+
+    def test_lifted_qualifiers(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "NC_005816.gb")
+        record = gb_io.load(path)[0]
+        self.assertEqual(record.source.name, "Yersinia pestis biovar Microtus str. 91001")
+        self.assertEqual(record.source.plasmid, "pPCP1")
+        self.assertEqual(record.source.mol_type, "genomic DNA")
+        self.assertIs(record.source.organelle, None)
+        self.assertIs(record.source.chromosome, None)
+        self.assertIs(record.source.segment, None)
+
+    def test_chromosome(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "iro.gb")
+        record = gb_io.load(path)[0]
+        self.assertEqual(record.source.chromosome, "21")
+        self.assertIs(record.source.plasmid, None)
+
+    def test_roundtrip(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "NC_005816.gb")
+        record = gb_io.load(path)[0]
+        record.source.plasmid = "pTest"
+        record.source.organelle = "plastid"
+        buffer = io.BytesIO()
+        gb_io.dump(record, buffer)
+        buffer.seek(0)
+        copied = gb_io.load(buffer)[0]
+        self.assertEqual(copied.source.plasmid, "pTest")
+        self.assertEqual(copied.source.organelle, "plastid")
+        self.assertEqual(copied.source.mol_type, "genomic DNA")
+        source = copied.features[0]
+        self.assertEqual(source.kind, "source")
+        keys = [qualifier.key for qualifier in source.qualifiers]
+        self.assertEqual(keys.count("plasmid"), 1)
+        self.assertEqual(keys[-1], "organelle")
+
+    def test_view_qualifiers(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "NC_005816.gb")
+        record = gb_io.load(path)[0]
+        source = record.source
+        qualifier = next(q for q in record.features[0].qualifiers if q.key == "plasmid")
+        qualifier.value = "pEdited"
+        self.assertEqual(source.plasmid, "pEdited")
+        copied = gb_io.loads(gb_io.dumps(record))[0]
+        self.assertEqual(copied.source.plasmid, "pEdited")
+
+    def test_view_shared_features(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "NC_005816.gb")
+        record = gb_io.load(path)[0]
+        feature = record.features[0]
+        self.assertEqual(record.source.mol_type, "genomic DNA")
+        record.source.mol_type = "other DNA"
+        values = [q.value for q in feature.qualifiers if q.key == "mol_type"]
+        self.assertEqual(values, ["other DNA"])
+
+    def test_remove_qualifier(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "NC_005816.gb")
+        for shared in (False, True):
+            record = gb_io.load(path)[0]
+            if shared:
+                record.features[0].qualifiers
+            record.source.plasmid = None
+            self.assertIs(record.source.plasmid, None)
+            keys = [q.key for q in record.features[0].qualifiers]
+            self.assertNotIn("plasmid", keys)
+            copied = gb_io.loads(gb_io.dumps(record))[0]
+            self.assertIs(copied.source.plasmid, None)
+
+    def test_set_source_view(self):
+        record = gb_io.Record(b"ATGC", molecule_type="DNA")
+        source = gb_io.Source("Test", mol_type="other DNA")
+        record.source = source
+        self.assertEqual(record.features[0].kind, "source")
+        self.assertEqual(record.features[0].qualifiers[0].value, "other DNA")
+        record.features[0].qualifiers[0].value = "genomic DNA"
+        self.assertEqual(source.mol_type, "genomic DNA")
+        other = gb_io.Record(b"ATGC", molecule_type="DNA")
+        other.source = source
+        self.assertIsNot(other.source, source)
+        self.assertEqual(other.source.mol_type, "genomic DNA")
+        other.source.mol_type = "other DNA"
+        self.assertEqual(source.mol_type, "genomic DNA")
+
+    def test_shared_source(self):
+        source = gb_io.Source("Test", mol_type="genomic DNA")
+        a = gb_io.Record(b"ACGT", source=source)
+        b = gb_io.Record(b"ACGT", source=source)
+        self.assertIs(a.source, source)
+        self.assertIsNot(b.source, source)
+        for record in (a, b):
+            self.assertEqual(len(record.features), 1)
+            self.assertEqual(record.features[0].kind, "source")
+            self.assertEqual(record.features[0].qualifiers[0].value, "genomic DNA")
+            self.assertIn("/mol_type=\"genomic DNA\"", gb_io.dumps(record))
+        b.source.mol_type = "other DNA"
+        self.assertEqual(source.mol_type, "genomic DNA")
+        self.assertEqual(a.features[0].qualifiers[0].value, "genomic DNA")
+        self.assertEqual(b.features[0].qualifiers[0].value, "other DNA")
+
+    def test_replace_source(self):
+        source = gb_io.Source("Test", mol_type="genomic DNA")
+        record = gb_io.Record(b"ACGT", source=source)
+        record.source = None
+        self.assertEqual(source.mol_type, "genomic DNA")
+        source.mol_type = "other DNA"
+        self.assertEqual(record.features[0].qualifiers[0].value, "genomic DNA")
+        other = gb_io.Record(b"ACGT", source=source)
+        self.assertIs(other.source, source)
+        self.assertEqual(other.features[0].qualifiers[0].value, "other DNA")
+
+    def test_collect_record(self):
+        source = gb_io.Source("Test", mol_type="other DNA")
+        record = gb_io.Record(b"ATGC", name="COLLECTED", source=source)
+        self.assertEqual(record.source.mol_type, "other DNA")
+        del record, source
+        gc.collect()
+        for obj in gc.get_objects():
+            if isinstance(obj, gb_io.Record):
+                self.assertNotEqual(obj.name, "COLLECTED")
+
+    def test_set_organism(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "NC_005816.gb")
+        record = gb_io.load(path)[0]
+        record.source.organism = "Yersinia pestis"
+        copied = gb_io.loads(gb_io.dumps(record))[0]
+        self.assertEqual(copied.source.organism, "Yersinia pestis")
+        self.assertEqual(copied.source.name, record.source.name)
+
+    def test_set_source(self):
+        path = os.path.join(DATA_FOLDER, "biopython", "NC_005816.gb")
+        record = gb_io.load(path)[0]
+        source = gb_io.Source("Test", "Test organism")
+        record.source = source
+        self.assertIs(record.source, source)
+        copied = gb_io.loads(gb_io.dumps(record))[0]
+        self.assertEqual(copied.source.name, "Test")
+        self.assertEqual(copied.source.organism, "Test organism")
+        record.source = None
+        self.assertIs(record.source, None)
+        self.assertNotIn("SOURCE", gb_io.dumps(record))
+        self.assertRaises(TypeError, setattr, record, "source", "Test")
+
+    def test_create_source_feature(self):
+        record = gb_io.Record(
+            b"ATGC",
+            molecule_type="DNA",
+            source=gb_io.Source("Test", segment="S"),
+        )
+        buffer = io.BytesIO()
+        gb_io.dump(record, buffer)
+        buffer.seek(0)
+        copied = gb_io.load(buffer)[0]
+        self.assertEqual(copied.source.segment, "S")
+        self.assertEqual(copied.features[0].kind, "source")
+        self.assertEqual(copied.features[0].location.start, 0)
+        self.assertEqual(copied.features[0].location.end, 4)
+
+    def test_repr(self):
+        source = gb_io.Source("Test", "Test organism", plasmid="pX")
+        self.assertEqual(
+            repr(source), "Source('Test', 'Test organism', plasmid='pX')"
+        )
+        self.assertEqual(repr(gb_io.Source("Test")), "Source('Test')")
@@ -79,3 +79,7 @@ unittest!(test_biopython);
 unittest!(test_load);
 unittest!(test_dump);
 unittest!(test_location);
+unittest!(test_record);
+unittest!(test_feature);
+unittest!(test_keys);
+unittest!(test_copy);